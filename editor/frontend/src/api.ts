@@ -1,4 +1,4 @@
-import { EditorManifest, EditorSnapshot, EditorTimeline } from "./types";
+import { EditorAction, EditorManifest, EditorSnapshot, EditorTimeline } from "./types";
 
 const API_BASE_STORAGE_KEY = "rollout.editor.apiBase";
 
@@ -92,6 +92,10 @@ export function fetchManifest(): Promise<EditorManifest> {
   return request("/api/manifest");
 }
 
+export function fetchActions(): Promise<EditorAction[]> {
+  return request("/api/actions");
+}
+
 export function fetchGameStatus(): Promise<{ running: boolean; detail: string }> {
   return request("/api/game/status");
 }
@@ -115,6 +119,13 @@ export function step(actionId: string): Promise<EditorSnapshot> {
   });
 }
 
+export function stepMany(actionIds: string[]): Promise<EditorSnapshot> {
+  return request("/api/agent/step-many", {
+    method: "POST",
+    body: JSON.stringify(actionIds),
+  });
+}
+
 export function rewind(frames: number): Promise<EditorSnapshot> {
   return request("/api/agent/rewind", {
     method: "POST",
@@ -139,3 +150,17 @@ export function seek(frame: number): Promise<EditorSnapshot> {
 export function reset(): Promise<EditorSnapshot> {
   return request("/api/agent/reset", { method: "POST" });
 }
+
+export function loadRecording(path: string): Promise<EditorSnapshot> {
+  return request("/api/agent/load-recording", {
+    method: "POST",
+    body: JSON.stringify({ path }),
+  });
+}
+
+export function setFrameState(frame: number, snapshot: unknown): Promise<EditorSnapshot> {
+  return request("/api/agent/set-state", {
+    method: "POST",
+    body: JSON.stringify({ frame, snapshot }),
+  });
+}
@@ -4,10 +4,12 @@ use std::error::Error;
 use std::fs;
 use std::hash::Hash;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use pixels::{Pixels, PixelsBuilder, SurfaceTexture};
+use serde::{Deserialize, Serialize};
 use winit::dpi::PhysicalSize;
 use winit::event::{
     ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode, WindowEvent,
@@ -16,7 +18,10 @@ use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::{Window, WindowBuilder};
 
 use crate::graphics::Renderer2d;
+use crate::localization::{Localizer, TableLocalizer};
+pub use crate::logical_clock::FixedTimestep;
 use crate::pixels_renderer::PixelsRenderer2d;
+use crate::profiling::{FrameTimings, Profiler};
 use crate::surface::SurfaceSize;
 use crate::ui_tree::UiInput;
 use crate::view_tree::{ViewTree, hit_test_actions};
@@ -28,6 +33,117 @@ pub struct AppConfig {
     pub clamp_to_monitor: bool,
     pub vsync: Option<bool>,
     pub present_mode: Option<pixels::wgpu::PresentMode>,
+    /// Upper bound for the `dt` handed to `GameApp::update_state`.
+    ///
+    /// A single slow frame (window drag, debugger pause, shader compile) would otherwise deliver
+    /// a huge `dt` and fast-forward dt-driven accumulators such as gravity. `None` disables the
+    /// clamp. Replay stepping is driven by its own fixed cadence and is not affected.
+    pub max_frame_dt: Option<Duration>,
+    /// Redraw cap for the window loops; `0` leaves them uncapped (`ControlFlow::Poll`).
+    ///
+    /// Otherwise the loop sleeps with `ControlFlow::WaitUntil` between frames. This stacks with
+    /// `vsync`/`present_mode`: a `Fifo` (vsync) present already blocks on the display refresh, so
+    /// a target above the refresh rate has no effect and one below it caps further. With an
+    /// uncapped target and a non-blocking present mode (`Immediate`/`Mailbox`) the loop spins.
+    pub target_fps: u32,
+    /// Skip `update_state`/`fixed_update` while `InputFrame::window_focused` is false so
+    /// dt-driven timers do not drain in the background. The frame focus is lost on still runs,
+    /// with a zero `dt`, so the game can release held input. Rendering continues. Profile and
+    /// replay loops ignore it.
+    pub pause_on_unfocus: bool,
+    /// UI label table exposed as `AppContext::localizer`. `None` uses an empty table, which
+    /// renders every label key as-is.
+    pub localizer: Option<Arc<dyn Localizer>>,
+}
+
+pub const DEFAULT_MAX_FRAME_DT: Duration = Duration::from_millis(100);
+
+pub const DEFAULT_TARGET_FPS: u32 = 60;
+
+/// Time between redraws at `fps`, or `None` when uncapped (`0`).
+pub fn frame_interval_for_fps(fps: u32) -> Option<Duration> {
+    (fps > 0).then(|| Duration::from_secs_f64(1.0 / fps as f64))
+}
+
+/// Paces window redraws to [`AppConfig::target_fps`].
+#[derive(Debug, Clone, Copy)]
+pub struct FramePacer {
+    interval: Option<Duration>,
+    next_frame: Instant,
+}
+
+impl FramePacer {
+    pub fn new(target_fps: u32) -> Self {
+        Self {
+            interval: frame_interval_for_fps(target_fps),
+            next_frame: Instant::now(),
+        }
+    }
+
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// `WaitUntil` the next due frame, or `Poll` when uncapped.
+    pub fn control_flow(&self) -> ControlFlow {
+        match self.interval {
+            Some(_) => ControlFlow::WaitUntil(self.next_frame),
+            None => ControlFlow::Poll,
+        }
+    }
+
+    /// Whether a redraw at `now` is due. A due frame schedules the next one an interval later;
+    /// early redraws (resizes, spurious wakeups) return `false` and should be skipped.
+    pub fn begin_frame(&mut self, now: Instant) -> bool {
+        let Some(interval) = self.interval else {
+            return true;
+        };
+        if now < self.next_frame {
+            return false;
+        }
+        self.next_frame = now + interval;
+        true
+    }
+}
+
+/// Whether `config.pause_on_unfocus` holds logic updates for a frame with `input`.
+pub fn logic_paused(config: &AppConfig, input: &InputFrame) -> bool {
+    config.pause_on_unfocus && !input.window_focused
+}
+
+/// Applies `pause_on_unfocus` across frames: logic is held while unfocused, except the first
+/// unfocused frame, which runs with a zero `dt` so `update_state` sees the focus change.
+#[derive(Debug, Default)]
+struct UnfocusGate {
+    held: bool,
+}
+
+impl UnfocusGate {
+    /// `dt` to step logic with this frame, or `None` while held.
+    fn frame_dt(
+        &mut self,
+        config: &AppConfig,
+        input: &InputFrame,
+        dt: Duration,
+    ) -> Option<Duration> {
+        if !logic_paused(config, input) {
+            self.held = false;
+            return Some(dt);
+        }
+        if self.held {
+            return None;
+        }
+        self.held = true;
+        Some(Duration::ZERO)
+    }
+}
+
+/// Caps a measured frame delta to `max` (if set).
+pub fn clamp_frame_dt(dt: Duration, max: Option<Duration>) -> Duration {
+    match max {
+        Some(max) => dt.min(max),
+        None => dt,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -38,12 +154,52 @@ pub struct RecordingConfig {
 #[derive(Debug, Clone)]
 pub struct ReplayConfig {
     pub path: PathBuf,
+    /// Recorded frames played per second at 1x.
     pub fps: u32,
+    /// Playback multiplier on `fps`, clamped to `MIN_REPLAY_SPEED..=MAX_REPLAY_SPEED`.
+    pub speed: f32,
+}
+
+pub const MIN_REPLAY_SPEED: f32 = 0.25;
+pub const MAX_REPLAY_SPEED: f32 = 8.0;
+
+/// Clamps `speed` to the supported replay range; non-finite values fall back to 1x.
+pub fn clamp_replay_speed(speed: f32) -> f32 {
+    if speed.is_finite() {
+        speed.clamp(MIN_REPLAY_SPEED, MAX_REPLAY_SPEED)
+    } else {
+        1.0
+    }
+}
+
+/// Time between replay frames at `fps` scaled by `speed`.
+pub fn replay_step_interval(fps: u32, speed: f32) -> Duration {
+    Duration::from_secs_f64(1.0 / (f64::from(fps.max(1)) * f64::from(clamp_replay_speed(speed))))
+}
+
+/// Frame a normalized scrub `position` (0 = first, 1 = last, clamped) lands on in a replay of
+/// `len` frames.
+pub fn replay_frame_for_position(len: usize, position: f32) -> usize {
+    let last = len.saturating_sub(1);
+    let position = if position.is_nan() {
+        0.0
+    } else {
+        position.clamp(0.0, 1.0)
+    };
+    ((last as f64) * f64::from(position)).round() as usize
+}
+
+/// Seeks `state` to the frame at normalized `position`; see [`replay_frame_for_position`].
+pub fn replay_scrub<S: ReplayableState>(state: &mut S, position: f32) {
+    let frame = replay_frame_for_position(state.replay_len(), position);
+    state.replay_seek(frame);
 }
 
 #[derive(Debug, Clone)]
 pub struct ProfileConfig {
     pub target_frames: usize,
+    /// Also write per-frame samples as CSV next to the Chrome trace JSON.
+    pub write_csv: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -73,6 +229,19 @@ pub fn default_recording_path(app_tag: &str) -> PathBuf {
         .join(format!("{app_tag}_{nanos}.json"))
 }
 
+/// `target/screenshots/<app_tag>_<nanos>.png`, next to the default recordings directory.
+pub fn default_screenshot_path(app_tag: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("target")
+        .join("screenshots")
+        .join(format!("{app_tag}_{nanos}.png"))
+}
+
 pub fn parse_capture_cli_with_default_path(
     default_recording_path: impl Fn() -> PathBuf,
 ) -> io::Result<CaptureCli> {
@@ -122,12 +291,46 @@ pub fn parse_capture_cli_with_default_path(
 }
 
 pub struct AppContext {
-    pub window: Window,
-    pub renderer: PixelsRenderer2d,
+    /// `None` when the app is driven headlessly by [`simulate`].
+    pub window: Option<Window>,
+    /// `None` when the app is driven headlessly by [`simulate`]; use `surface_size` for layout.
+    pub renderer: Option<PixelsRenderer2d>,
     pub surface_size: SurfaceSize,
+    run_mode: RunMode,
+    localizer: Arc<dyn Localizer>,
 }
 
-#[derive(Debug, Clone)]
+impl AppContext {
+    /// Mode the app loop is running in. Set by the `run_game*` entry point before `init_state`
+    /// and fixed for the whole run, so `build_view`/`update_state` can branch on it (e.g. a
+    /// "REPLAY" badge) without tracking it themselves.
+    pub fn run_mode(&self) -> RunMode {
+        self.run_mode
+    }
+
+    /// Localizer injected via `AppConfig::localizer`; shared so apps can keep a handle for
+    /// drawing outside callbacks that receive the context.
+    pub fn localizer(&self) -> Arc<dyn Localizer> {
+        Arc::clone(&self.localizer)
+    }
+
+    /// Asks the window for another frame; a no-op without a window.
+    pub fn request_redraw(&self) {
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+
+    /// Renderer of a windowed run. The `run_game*` loops always create one.
+    fn windowed_renderer(&mut self) -> &mut PixelsRenderer2d {
+        self.renderer
+            .as_mut()
+            .expect("windowed app context has a renderer")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InputFrame {
     pub mouse_pos: Option<(u32, u32)>,
     pub mouse_down: bool,
@@ -141,6 +344,9 @@ pub struct InputFrame {
     pub scroll_x: f32,
     pub scroll_y: f32,
     pub window_focused: bool,
+    /// Characters typed this frame (winit `ReceivedCharacter`), including control characters such
+    /// as backspace; feed them to `UiInput::text_events`.
+    pub text_events: Vec<char>,
 }
 
 impl Default for InputFrame {
@@ -159,6 +365,7 @@ impl Default for InputFrame {
             scroll_y: 0.0,
             // Avoid gating controls before the OS sends an initial focus event.
             window_focused: true,
+            text_events: Vec::new(),
         }
     }
 }
@@ -217,6 +424,7 @@ impl InputFrame {
             self.mouse_buttons_released.clear();
             self.mouse_down = false;
             self.mouse_up = false;
+            self.text_events.clear();
         }
     }
 
@@ -229,6 +437,7 @@ impl InputFrame {
         self.keys_released.clear();
         self.scroll_x = 0.0;
         self.scroll_y = 0.0;
+        self.text_events.clear();
     }
 }
 
@@ -283,6 +492,9 @@ fn apply_window_event_to_input(input: &mut InputFrame, event: &WindowEvent) {
         WindowEvent::MouseWheel { delta, .. } => {
             input.apply_scroll_delta(delta);
         }
+        WindowEvent::ReceivedCharacter(c) => {
+            input.text_events.push(*c);
+        }
         WindowEvent::Focused(focused) => {
             input.set_window_focus(*focused);
         }
@@ -308,10 +520,23 @@ pub trait GameApp {
         _ctx: &mut AppContext,
     ) -> Vec<Self::Effect>;
 
-    fn render(&mut self, view: &ViewTree<Self::Action>, renderer: &mut dyn Renderer2d);
+    /// Draws the frame. `alpha` (0..1) is how far the `fixed_update` accumulator has run toward
+    /// the next step, for blending previous and current fixed-step positions; it is 1.0 when
+    /// the hook is disabled or in replay.
+    fn render(&mut self, view: &ViewTree<Self::Action>, renderer: &mut dyn Renderer2d, alpha: f32);
 
     fn handle_effects(&mut self, _effects: Vec<Self::Effect>, _ctx: &mut AppContext) {}
 
+    /// Interval for `fixed_update`; `None` (the default) disables the hook.
+    fn fixed_update_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Runs once per whole `fixed_update_interval` elapsed, after `update_state` in the same
+    /// frame. Every run loop (and `simulate`) shares one accumulator, so interactive, recording
+    /// and profiling modes step the same number of times for the same frame times.
+    fn fixed_update(&mut self, _state: &mut Self::State, _step: Duration, _ctx: &mut AppContext) {}
+
     fn on_run_mode(&mut self, _mode: RunMode, _state: &mut Self::State, _ctx: &mut AppContext) {}
 
     fn handle_event(
@@ -328,6 +553,53 @@ pub trait GameApp {
     }
 }
 
+/// Feeds `dt` to `timestep` and calls `GameApp::fixed_update` once per due step.
+fn run_fixed_updates<G: GameApp>(
+    game: &mut G,
+    state: &mut G::State,
+    timestep: &mut FixedTimestep,
+    dt: Duration,
+    ctx: &mut AppContext,
+) {
+    let Some(interval) = game.fixed_update_interval() else {
+        return;
+    };
+    for _ in 0..timestep.advance(dt, interval) {
+        game.fixed_update(state, interval, ctx);
+    }
+}
+
+/// Interpolation alpha handed to `GameApp::render` for the shared fixed-update accumulator.
+fn render_alpha<G: GameApp>(game: &G, timestep: &FixedTimestep) -> f32 {
+    game.fixed_update_interval()
+        .map_or(1.0, |interval| timestep.alpha(interval))
+}
+
+/// One logic frame as every run loop and `simulate_with_dt` drive it: hit-test `input` against
+/// the current view, `update_state`, then any due `fixed_update` steps.
+fn step_game_frame<G: GameApp>(
+    game: &mut G,
+    state: &mut G::State,
+    input: InputFrame,
+    dt: Duration,
+    timestep: &mut FixedTimestep,
+    ctx: &mut AppContext,
+) -> Vec<G::Effect> {
+    let view = game.build_view(state, ctx);
+    let actions = hit_test_actions(
+        &view,
+        UiInput {
+            mouse_pos: input.mouse_pos,
+            mouse_down: input.mouse_down,
+            mouse_up: input.mouse_up,
+            ..UiInput::default()
+        },
+    );
+    let effects = game.update_state(state, input, dt, &actions, ctx);
+    run_fixed_updates(game, state, timestep, dt, ctx);
+    effects
+}
+
 pub trait AppHandler {
     fn init(&mut self, _ctx: &mut AppContext) -> Result<(), Box<dyn Error>> {
         Ok(())
@@ -398,9 +670,14 @@ fn create_app_context(
 
     let renderer = PixelsRenderer2d::new_auto(pixels, surface_size)?;
     Ok(AppContext {
-        window,
-        renderer,
+        window: Some(window),
+        renderer: Some(renderer),
         surface_size,
+        run_mode: RunMode::Normal,
+        localizer: config
+            .localizer
+            .clone()
+            .unwrap_or_else(|| Arc::new(TableLocalizer::new())),
     })
 }
 
@@ -420,9 +697,86 @@ pub fn run_app<H: AppHandler + 'static>(
     Ok(())
 }
 
-pub fn run_game<G: GameApp + 'static>(
+pub fn run_game<G: GameApp + 'static>(config: AppConfig, game: G) -> Result<(), Box<dyn Error>> {
+    run_windowed_game(config, game, FrameSource::Live)
+}
+
+/// Where the windowed loop gets each logic frame's input and `dt`.
+enum FrameSource {
+    /// Live window input with the wall-clock frame `dt`.
+    Live,
+    /// Live input, also logged and written to `path` when the loop exits.
+    Recording {
+        path: PathBuf,
+        recording: InputRecording,
+    },
+    /// Recorded frames with their recorded `dt`; live input is not fed to the game.
+    Replay {
+        frames: std::vec::IntoIter<RecordedInputFrame>,
+        total: usize,
+        finished: bool,
+    },
+}
+
+impl FrameSource {
+    /// Input and `dt` for this redraw's logic step, or `None` when logic does not run.
+    fn next_frame(
+        &mut self,
+        gate: &mut UnfocusGate,
+        config: &AppConfig,
+        input: &InputFrame,
+        dt: Duration,
+    ) -> Option<(InputFrame, Duration)> {
+        match self {
+            FrameSource::Live => gate
+                .frame_dt(config, input, dt)
+                .map(|dt| (input.clone(), dt)),
+            FrameSource::Recording { recording, .. } => {
+                let dt = gate.frame_dt(config, input, dt)?;
+                recording.frames.push(RecordedInputFrame {
+                    dt,
+                    input: input.clone(),
+                });
+                Some((input.clone(), dt))
+            }
+            FrameSource::Replay {
+                frames,
+                total,
+                finished,
+            } => match frames.next() {
+                Some(frame) => Some((frame.input, frame.dt)),
+                None => {
+                    if !*finished {
+                        println!("input replay finished ({total} frames)");
+                        *finished = true;
+                    }
+                    None
+                }
+            },
+        }
+    }
+
+    fn save(&self) {
+        let FrameSource::Recording { path, recording } = self else {
+            return;
+        };
+        match save_input_recording(path, recording) {
+            Ok(()) => println!(
+                "input recording saved: {} ({} frames)",
+                path.display(),
+                recording.frames.len()
+            ),
+            Err(err) => eprintln!("failed saving input recording to {}: {err}", path.display()),
+        }
+    }
+}
+
+/// The event loop behind `run_game`, `run_game_with_input_recording` and
+/// `run_game_with_input_replay`; only where frames come from differs.
+fn run_windowed_game<G: GameApp + 'static>(
     config: AppConfig,
     mut game: G,
+    mut source: FrameSource,
 ) -> Result<(), Box<dyn Error>> {
     let event_loop = EventLoop::new();
     let mut ctx = create_app_context(&config, &event_loop)?;
@@ -430,14 +784,23 @@ pub fn run_game<G: GameApp + 'static>(
     game.on_run_mode(RunMode::Normal, &mut state, &mut ctx);
     let mut input = InputFrame::default();
     let mut last_frame = Instant::now();
+    let mut fixed_timestep = FixedTimestep::new();
+    let mut unfocus_gate = UnfocusGate::default();
+
+    let mut pacer = FramePacer::new(config.target_fps);
 
     event_loop.run(move |event, _, control_flow| {
-        *control_flow = ControlFlow::Poll;
+        *control_flow = pacer.control_flow();
 
         if let Event::WindowEvent { event, .. } = &event {
             apply_window_event_to_input(&mut input, event);
         }
 
+        // Saved before `handle_event`, which may consume lifecycle events.
+        if let Event::LoopDestroyed = &event {
+            source.save();
+        }
+
         if game.handle_event(&event, &mut state, &mut input, &mut ctx, control_flow) {
             return;
         }
@@ -446,42 +809,49 @@ pub fn run_game<G: GameApp + 'static>(
             Event::WindowEvent { event, .. } => match event {
                 WindowEvent::CloseRequested => {
                     *control_flow = ControlFlow::Exit;
-                    return;
                 }
                 WindowEvent::Resized(size) => {
                     ctx.surface_size = SurfaceSize::new(size.width, size.height);
-                    if let Err(err) = ctx.renderer.resize(ctx.surface_size) {
+                    let surface_size = ctx.surface_size;
+                    if let Err(err) = ctx.windowed_renderer().resize(surface_size) {
                         eprintln!("resize failed: {err}");
                     }
-                    ctx.window.request_redraw();
+                    ctx.request_redraw();
                 }
                 _ => {}
             },
             Event::RedrawRequested(_) => {
+                if !pacer.begin_frame(Instant::now()) {
+                    return;
+                }
                 let now = Instant::now();
-                let dt = now.saturating_duration_since(last_frame);
+                let dt = clamp_frame_dt(
+                    now.saturating_duration_since(last_frame),
+                    config.max_frame_dt,
+                );
                 last_frame = now;
 
-                let frame_input = input.clone();
-                let view_for_input = game.build_view(&state, &ctx);
-                let actions = hit_test_actions(
-                    &view_for_input,
-                    UiInput {
-                        mouse_pos: frame_input.mouse_pos,
-                        mouse_down: frame_input.mouse_down,
-                        mouse_up: frame_input.mouse_up,
-                    },
-                );
-                let effects = game.update_state(&mut state, frame_input, dt, &actions, &mut ctx);
+                let effects = match source.next_frame(&mut unfocus_gate, &config, &input, dt) {
+                    Some((frame_input, dt)) => step_game_frame(
+                        &mut game,
+                        &mut state,
+                        frame_input,
+                        dt,
+                        &mut fixed_timestep,
+                        &mut ctx,
+                    ),
+                    None => Vec::new(),
+                };
 
                 let view_for_render = game.build_view(&state, &ctx);
-                let draw_res = ctx.renderer.draw_frame(|gfx| {
-                    game.render(&view_for_render, gfx);
+                let alpha = render_alpha(&game, &fixed_timestep);
+                let draw_res = ctx.windowed_renderer().draw_frame(|gfx| {
+                    game.render(&view_for_render, gfx, alpha);
                 });
                 if let Err(err) = draw_res {
                     eprintln!("draw failed: {err}");
                 }
-                if let Err(err) = ctx.renderer.present() {
+                if let Err(err) = ctx.windowed_renderer().present() {
                     eprintln!("present failed: {err}");
                 }
 
@@ -489,7 +859,7 @@ pub fn run_game<G: GameApp + 'static>(
                 input.clear_frame_transients();
             }
             Event::MainEventsCleared => {
-                ctx.window.request_redraw();
+                ctx.request_redraw();
             }
             _ => {}
         }
@@ -499,6 +869,59 @@ pub fn run_game<G: GameApp + 'static>(
     Ok(())
 }
 
+/// Frame time `simulate` hands to `GameApp::update_state` (60 fps).
+pub const SIMULATED_FRAME_DT: Duration = Duration::from_nanos(16_666_667);
+
+/// Runs `game` headlessly over `inputs`, one frame each at [`SIMULATED_FRAME_DT`], and returns
+/// the final state. See [`simulate_with_dt`].
+pub fn simulate<G: GameApp>(config: AppConfig, game: &mut G, inputs: Vec<InputFrame>) -> G::State {
+    simulate_with_dt(
+        config,
+        game,
+        inputs.into_iter().map(|input| (input, SIMULATED_FRAME_DT)),
+    )
+}
+
+/// Drives the same per-frame path as `run_game` without a window or renderer: `build_view`, view
+/// hit-testing into `GameApp::Action`s, `update_state` with the frame's `dt` (clamped by
+/// `config.max_frame_dt`), then `handle_effects`. `render` is never called.
+///
+/// The context has no window or renderer; `surface_size` is `config.desired_size` and the run
+/// mode is `RunMode::Normal`. Each `InputFrame` is used as given, so include the `keys_pressed`/
+/// `mouse_up` transients for the frame they belong to. Frames held by `config.pause_on_unfocus`
+/// are skipped; the first unfocused frame of a run of them steps with a zero `dt`.
+pub fn simulate_with_dt<G: GameApp>(
+    config: AppConfig,
+    game: &mut G,
+    frames: impl IntoIterator<Item = (InputFrame, Duration)>,
+) -> G::State {
+    let mut ctx = AppContext {
+        window: None,
+        renderer: None,
+        surface_size: SurfaceSize::new(config.desired_size.width, config.desired_size.height),
+        run_mode: RunMode::Normal,
+        localizer: config
+            .localizer
+            .clone()
+            .unwrap_or_else(|| Arc::new(TableLocalizer::new())),
+    };
+    let mut state = game.init_state(&mut ctx);
+    game.on_run_mode(RunMode::Normal, &mut state, &mut ctx);
+    let mut fixed_timestep = FixedTimestep::new();
+    let mut unfocus_gate = UnfocusGate::default();
+
+    for (input, dt) in frames {
+        let dt = clamp_frame_dt(dt, config.max_frame_dt);
+        let Some(dt) = unfocus_gate.frame_dt(&config, &input, dt) else {
+            continue;
+        };
+        let effects = step_game_frame(game, &mut state, input, dt, &mut fixed_timestep, &mut ctx);
+        game.handle_effects(effects, &mut ctx);
+    }
+
+    state
+}
+
 pub fn run_game_with_recording<G>(
     config: AppConfig,
     mut game: G,
@@ -510,14 +933,19 @@ where
 {
     let event_loop = EventLoop::new();
     let mut ctx = create_app_context(&config, &event_loop)?;
+    ctx.run_mode = RunMode::Recording;
     let mut state = game.init_state(&mut ctx);
     game.on_run_mode(RunMode::Recording, &mut state, &mut ctx);
     let mut input = InputFrame::default();
     let mut last_frame = Instant::now();
+    let mut fixed_timestep = FixedTimestep::new();
+    let mut unfocus_gate = UnfocusGate::default();
     let mut recording_saved = false;
 
+    let mut pacer = FramePacer::new(config.target_fps);
+
     event_loop.run(move |event, _, control_flow| {
-        *control_flow = ControlFlow::Poll;
+        *control_flow = pacer.control_flow();
 
         if let Event::WindowEvent { event, .. } = &event {
             apply_window_event_to_input(&mut input, event);
@@ -544,29 +972,36 @@ where
                 }
                 WindowEvent::Resized(size) => {
                     ctx.surface_size = SurfaceSize::new(size.width, size.height);
-                    if let Err(err) = ctx.renderer.resize(ctx.surface_size) {
+                    let surface_size = ctx.surface_size;
+                    if let Err(err) = ctx.windowed_renderer().resize(surface_size) {
                         eprintln!("resize failed: {err}");
                     }
-                    ctx.window.request_redraw();
+                    ctx.request_redraw();
                 }
                 _ => {}
             },
             Event::RedrawRequested(_) => {
+                if !pacer.begin_frame(Instant::now()) {
+                    return;
+                }
                 let now = Instant::now();
-                let dt = now.saturating_duration_since(last_frame);
+                let dt = clamp_frame_dt(
+                    now.saturating_duration_since(last_frame),
+                    config.max_frame_dt,
+                );
                 last_frame = now;
 
-                let frame_input = input.clone();
-                let view_for_input = game.build_view(&state, &ctx);
-                let actions = hit_test_actions(
-                    &view_for_input,
-                    UiInput {
-                        mouse_pos: frame_input.mouse_pos,
-                        mouse_down: frame_input.mouse_down,
-                        mouse_up: frame_input.mouse_up,
-                    },
-                );
-                let effects = game.update_state(&mut state, frame_input, dt, &actions, &mut ctx);
+                let effects = match unfocus_gate.frame_dt(&config, &input, dt) {
+                    Some(dt) => step_game_frame(
+                        &mut game,
+                        &mut state,
+                        input.clone(),
+                        dt,
+                        &mut fixed_timestep,
+                        &mut ctx,
+                    ),
+                    None => Vec::new(),
+                };
 
                 if !recording_saved && state.recording_frame() > 0 {
                     if let Err(err) = state.save_recording(&recording.path) {
@@ -581,13 +1016,14 @@ where
                 }
 
                 let view_for_render = game.build_view(&state, &ctx);
-                let draw_res = ctx.renderer.draw_frame(|gfx| {
-                    game.render(&view_for_render, gfx);
+                let alpha = render_alpha(&game, &fixed_timestep);
+                let draw_res = ctx.windowed_renderer().draw_frame(|gfx| {
+                    game.render(&view_for_render, gfx, alpha);
                 });
                 if let Err(err) = draw_res {
                     eprintln!("draw failed: {err}");
                 }
-                if let Err(err) = ctx.renderer.present() {
+                if let Err(err) = ctx.windowed_renderer().present() {
                     eprintln!("present failed: {err}");
                 }
 
@@ -595,7 +1031,7 @@ where
                 input.clear_frame_transients();
             }
             Event::MainEventsCleared => {
-                ctx.window.request_redraw();
+                ctx.request_redraw();
             }
             Event::LoopDestroyed => {
                 if !recording_saved {
@@ -618,6 +1054,100 @@ where
     Ok(())
 }
 
+/// One logic frame of an input recording: the input `update_state` saw and the (already clamped)
+/// `dt` it ran with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedInputFrame {
+    pub dt: Duration,
+    pub input: InputFrame,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InputRecording {
+    pub frames: Vec<RecordedInputFrame>,
+}
+
+impl InputRecording {
+    /// `(input, dt)` pairs in the shape `simulate_with_dt` takes.
+    pub fn into_simulation_frames(self) -> impl Iterator<Item = (InputFrame, Duration)> {
+        self.frames.into_iter().map(|frame| (frame.input, frame.dt))
+    }
+}
+
+pub fn save_input_recording(path: &Path, recording: &InputRecording) -> io::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let file = fs::File::create(path)?;
+    let mut writer = io::BufWriter::new(file);
+    serde_json::to_writer(&mut writer, recording).map_err(io::Error::other)?;
+    writer.flush()
+}
+
+pub fn load_input_recording(path: &Path) -> io::Result<InputRecording> {
+    let file = fs::File::open(path)?;
+    serde_json::from_reader(io::BufReader::new(file))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Like `run_game`, but logs every logic frame's `InputFrame` and `dt` and writes them to `path`
+/// when the loop exits. Much smaller than a state recording; with deterministic logic,
+/// `run_game_with_input_replay` (or `simulate_with_dt`) reproduces the session, including
+/// dt-driven timers such as real-time gravity. Games must derive any timing from the `dt` they
+/// are handed rather than the wall clock for a replay to match.
+pub fn run_game_with_input_recording<G: GameApp + 'static>(
+    config: AppConfig,
+    game: G,
+    path: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    run_windowed_game(
+        config,
+        game,
+        FrameSource::Recording {
+            path,
+            recording: InputRecording::default(),
+        },
+    )
+}
+
+/// Plays an input recording from `path` through `update_state`, one recorded frame per redraw
+/// with its recorded `dt`. Live keyboard/mouse input is not fed to the game; once the recording
+/// runs out the final state stays on screen.
+pub fn run_game_with_input_replay<G: GameApp + 'static>(
+    config: AppConfig,
+    game: G,
+    path: PathBuf,
+) -> Result<(), Box<dyn Error>> {
+    let recording = load_input_recording(&path)?;
+    run_windowed_game(
+        config,
+        game,
+        FrameSource::Replay {
+            total: recording.frames.len(),
+            frames: recording.frames.into_iter(),
+            finished: false,
+        },
+    )
+}
+
+fn scrub_tenth_for_key(key: VirtualKeyCode) -> Option<u8> {
+    Some(match key {
+        VirtualKeyCode::Key0 => 0,
+        VirtualKeyCode::Key1 => 1,
+        VirtualKeyCode::Key2 => 2,
+        VirtualKeyCode::Key3 => 3,
+        VirtualKeyCode::Key4 => 4,
+        VirtualKeyCode::Key5 => 5,
+        VirtualKeyCode::Key6 => 6,
+        VirtualKeyCode::Key7 => 7,
+        VirtualKeyCode::Key8 => 8,
+        VirtualKeyCode::Key9 => 9,
+        _ => return None,
+    })
+}
+
 pub fn run_game_with_replay<G>(
     config: AppConfig,
     mut game: G,
@@ -629,17 +1159,23 @@ where
 {
     let event_loop = EventLoop::new();
     let mut ctx = create_app_context(&config, &event_loop)?;
+    ctx.run_mode = RunMode::Replay;
     let initial_state = game.init_state(&mut ctx);
     let mut state = initial_state
         .replay_load(&replay.path)
         .map_err(|err| -> Box<dyn Error> { err.into() })?;
     game.on_run_mode(RunMode::Replay, &mut state, &mut ctx);
     let mut replay_playing = true;
-    let mut replay_fps = replay.fps.max(1);
-    let mut replay_next_step = Instant::now();
+    let replay_fps = replay.fps.max(1);
+    let mut replay_speed = clamp_replay_speed(replay.speed);
+    let mut replay_clock = FixedTimestep::new();
+    let mut replay_last_tick = Instant::now();
+    let mut cursor_x: Option<f64> = None;
+
+    let mut pacer = FramePacer::new(config.target_fps);
 
     event_loop.run(move |event, _, control_flow| {
-        *control_flow = ControlFlow::Poll;
+        *control_flow = pacer.control_flow();
 
         if let Event::WindowEvent {
             event:
@@ -655,7 +1191,6 @@ where
             ..
         } = &event
         {
-            let now = Instant::now();
             match *key {
                 VirtualKeyCode::Escape => {
                     *control_flow = ControlFlow::Exit;
@@ -663,7 +1198,7 @@ where
                 }
                 VirtualKeyCode::Space => {
                     replay_playing = !replay_playing;
-                    replay_next_step = now;
+                    replay_clock.reset();
                     return;
                 }
                 VirtualKeyCode::Left => {
@@ -688,17 +1223,44 @@ where
                     return;
                 }
                 VirtualKeyCode::Up => {
-                    replay_fps = replay_fps.saturating_mul(2).min(240).max(1);
-                    replay_next_step = now;
+                    replay_speed = clamp_replay_speed(replay_speed * 2.0);
+                    println!("replay speed: {replay_speed}x");
                     return;
                 }
                 VirtualKeyCode::Down => {
-                    replay_fps = (replay_fps / 2).max(1);
-                    replay_next_step = now;
+                    replay_speed = clamp_replay_speed(replay_speed / 2.0);
+                    println!("replay speed: {replay_speed}x");
                     return;
                 }
                 _ => {}
             }
+            // Number keys scrub to tenths of the recording: 1 = 10% ... 9 = 90%, 0 = start.
+            if let Some(tenth) = scrub_tenth_for_key(*key) {
+                replay_scrub(&mut state, tenth as f32 / 10.0);
+                replay_playing = false;
+                return;
+            }
+        }
+
+        // Clicking anywhere scrubs to the pointer's horizontal position.
+        if let Event::WindowEvent { event, .. } = &event {
+            match event {
+                WindowEvent::CursorMoved { position, .. } => {
+                    cursor_x = Some(position.x);
+                }
+                WindowEvent::MouseInput {
+                    state: ElementState::Pressed,
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    if let Some(x) = cursor_x {
+                        let width = f64::from(ctx.surface_size.width.max(2) - 1);
+                        replay_scrub(&mut state, (x / width) as f32);
+                        replay_playing = false;
+                    }
+                }
+                _ => {}
+            }
         }
 
         match &event {
@@ -709,39 +1271,49 @@ where
                 }
                 WindowEvent::Resized(size) => {
                     ctx.surface_size = SurfaceSize::new(size.width, size.height);
-                    if let Err(err) = ctx.renderer.resize(ctx.surface_size) {
+                    let surface_size = ctx.surface_size;
+                    if let Err(err) = ctx.windowed_renderer().resize(surface_size) {
                         eprintln!("resize failed: {err}");
                     }
-                    ctx.window.request_redraw();
+                    ctx.request_redraw();
                 }
                 _ => {}
             },
             Event::RedrawRequested(_) => {
+                if !pacer.begin_frame(Instant::now()) {
+                    return;
+                }
                 let now = Instant::now();
-                if replay_playing && now >= replay_next_step {
+                let elapsed = now.saturating_duration_since(replay_last_tick);
+                replay_last_tick = now;
+                if replay_playing {
+                    // Several frames may be due per redraw at high speeds; none are skipped.
+                    let interval = replay_step_interval(replay_fps, replay_speed);
+                    let steps = replay_clock.advance(elapsed, interval) as usize;
                     let max_frame = state.replay_len().saturating_sub(1);
-                    if state.replay_frame() < max_frame {
-                        state.replay_forward(1);
-                    } else {
+                    let remaining = max_frame.saturating_sub(state.replay_frame());
+                    if steps > 0 && remaining > 0 {
+                        state.replay_forward(steps.min(remaining));
+                    }
+                    if state.replay_frame() >= max_frame {
                         replay_playing = false;
                     }
-                    let interval = Duration::from_secs_f64(1.0 / (replay_fps.max(1) as f64));
-                    replay_next_step = now + interval;
                 }
 
                 let view_for_render = game.build_view(&state, &ctx);
-                let draw_res = ctx.renderer.draw_frame(|gfx| {
-                    game.render(&view_for_render, gfx);
+                let alpha = 1.0;
+                let draw_res = ctx.windowed_renderer().draw_frame(|gfx| {
+                    game.render(&view_for_render, gfx, alpha);
                 });
                 if let Err(err) = draw_res {
                     eprintln!("draw failed: {err}");
                 }
-                if let Err(err) = ctx.renderer.present() {
+                if let Err(err) = ctx.windowed_renderer().present() {
                     eprintln!("present failed: {err}");
                 }
             }
             Event::MainEventsCleared => {
-                ctx.window.request_redraw();
+                ctx.request_redraw();
             }
             _ => {}
         }
@@ -761,14 +1333,18 @@ where
 {
     let event_loop = EventLoop::new();
     let mut ctx = create_app_context(&config, &event_loop)?;
+    ctx.run_mode = RunMode::Profile;
     let mut state = game.init_state(&mut ctx);
     game.on_run_mode(RunMode::Profile, &mut state, &mut ctx);
     let mut input = InputFrame::default();
     let mut last_frame = Instant::now();
+    let mut fixed_timestep = FixedTimestep::new();
     let mut trace = TraceCapture::new(profile.target_frames);
 
+    let mut pacer = FramePacer::new(config.target_fps);
+
     event_loop.run(move |event, _, control_flow| {
-        *control_flow = ControlFlow::Poll;
+        *control_flow = pacer.control_flow();
 
         if let Event::WindowEvent { event, .. } = &event {
             apply_window_event_to_input(&mut input, event);
@@ -786,38 +1362,43 @@ where
                 }
                 WindowEvent::Resized(size) => {
                     ctx.surface_size = SurfaceSize::new(size.width, size.height);
-                    if let Err(err) = ctx.renderer.resize(ctx.surface_size) {
+                    let surface_size = ctx.surface_size;
+                    if let Err(err) = ctx.windowed_renderer().resize(surface_size) {
                         eprintln!("resize failed: {err}");
                     }
-                    ctx.window.request_redraw();
+                    ctx.request_redraw();
                 }
                 _ => {}
             },
             Event::RedrawRequested(_) => {
+                if !pacer.begin_frame(Instant::now()) {
+                    return;
+                }
                 let now = Instant::now();
-                let dt = now.saturating_duration_since(last_frame);
+                let dt = clamp_frame_dt(
+                    now.saturating_duration_since(last_frame),
+                    config.max_frame_dt,
+                );
                 last_frame = now;
 
                 let frame_start = Instant::now();
                 let update_start = Instant::now();
-                let frame_input = input.clone();
-                let view_for_input = game.build_view(&state, &ctx);
-                let actions = hit_test_actions(
-                    &view_for_input,
-                    UiInput {
-                        mouse_pos: frame_input.mouse_pos,
-                        mouse_down: frame_input.mouse_down,
-                        mouse_up: frame_input.mouse_up,
-                    },
+                let effects = step_game_frame(
+                    &mut game,
+                    &mut state,
+                    input.clone(),
+                    dt,
+                    &mut fixed_timestep,
+                    &mut ctx,
                 );
-                let effects = game.update_state(&mut state, frame_input, dt, &actions, &mut ctx);
                 let update_dt = update_start.elapsed();
                 trace.record("engine.update", update_start, update_dt);
 
                 let draw_start = Instant::now();
                 let view_for_render = game.build_view(&state, &ctx);
-                let draw_res = ctx.renderer.draw_frame(|gfx| {
-                    game.render(&view_for_render, gfx);
+                let alpha = render_alpha(&game, &fixed_timestep);
+                let draw_res = ctx.windowed_renderer().draw_frame(|gfx| {
+                    game.render(&view_for_render, gfx, alpha);
                 });
                 let draw_dt = draw_start.elapsed();
                 trace.record("render.draw", draw_start, draw_dt);
@@ -826,7 +1407,7 @@ where
                 }
 
                 let present_start = Instant::now();
-                if let Err(err) = ctx.renderer.present() {
+                if let Err(err) = ctx.windowed_renderer().present() {
                     eprintln!("present failed: {err}");
                 }
                 let present_dt = present_start.elapsed();
@@ -834,16 +1415,30 @@ where
 
                 let frame_total_dt = frame_start.elapsed();
                 trace.record("frame.total", frame_start, frame_total_dt);
-                trace.record_frame_samples(update_dt, draw_dt, present_dt, frame_total_dt);
+                trace.on_frame(FrameTimings {
+                    frame_dt: dt,
+                    update: Some(update_dt),
+                    draw: Some(draw_dt),
+                    present: Some(present_dt),
+                    total: frame_total_dt,
+                    ..FrameTimings::default()
+                });
 
                 game.handle_effects(effects, &mut ctx);
                 input.clear_frame_transients();
 
                 if trace.captured_frames >= trace.target_frames {
-                    let size = ctx.renderer.size();
+                    let size = ctx.windowed_renderer().size();
                     match trace.write(size) {
                         Ok(path) => {
                             println!("trace written: {}", path.display());
+                            if profile.write_csv {
+                                let csv_path = path.with_extension("csv");
+                                match trace.write_csv(&csv_path) {
+                                    Ok(()) => println!("csv written: {}", csv_path.display()),
+                                    Err(err) => eprintln!("failed writing csv: {err}"),
+                                }
+                            }
                             trace.print_summary();
                         }
                         Err(err) => eprintln!("failed writing trace: {err}"),
@@ -852,7 +1447,7 @@ where
                 }
             }
             Event::MainEventsCleared => {
-                ctx.window.request_redraw();
+                ctx.request_redraw();
             }
             _ => {}
         }
@@ -897,12 +1492,23 @@ struct TraceEvent {
     dur_us: u64,
 }
 
+/// One captured frame's phase timings, kept for the CSV export.
+#[derive(Debug, Clone, Copy)]
+struct FrameSample {
+    update: Duration,
+    draw: Duration,
+    overlay: Duration,
+    present: Duration,
+    frame_total: Duration,
+}
+
 #[derive(Debug)]
 struct TraceCapture {
     target_frames: usize,
     captured_frames: usize,
     start: Instant,
     events: Vec<TraceEvent>,
+    frames: Vec<FrameSample>,
 
     update: DurationAgg,
     draw: DurationAgg,
@@ -918,6 +1524,7 @@ impl TraceCapture {
             captured_frames: 0,
             start: Instant::now(),
             events: Vec::with_capacity(target_frames.saturating_mul(6)),
+            frames: Vec::with_capacity(target_frames),
             update: DurationAgg::default(),
             draw: DurationAgg::default(),
             present: DurationAgg::default(),
@@ -937,20 +1544,6 @@ impl TraceCapture {
         });
     }
 
-    fn record_frame_samples(
-        &mut self,
-        update: Duration,
-        draw: Duration,
-        present: Duration,
-        frame: Duration,
-    ) {
-        self.update.push(update);
-        self.draw.push(draw);
-        self.present.push(present);
-        self.frame_total.push(frame);
-        self.captured_frames = self.captured_frames.saturating_add(1);
-    }
-
     fn default_trace_dir() -> PathBuf {
         // `CARGO_MANIFEST_DIR` is `.../rollout_engine/engine`; the workspace `target/` lives at `..`.
         PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -989,6 +1582,33 @@ impl TraceCapture {
         Ok(path)
     }
 
+    /// Writes one CSV row per captured frame from the same samples `print_summary` aggregates.
+    ///
+    /// The app loop doesn't time overlays separately (they are drawn inside `render`), so
+    /// `overlay_us` is 0 for its frames and overlay cost is included in `draw_us`.
+    fn write_csv(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut f = io::BufWriter::new(fs::File::create(path)?);
+        writeln!(
+            f,
+            "frame,engine_us,draw_us,overlay_us,present_us,frame_total_us"
+        )?;
+        for (frame, sample) in self.frames.iter().enumerate() {
+            writeln!(
+                f,
+                "{frame},{},{},{},{},{}",
+                sample.update.as_micros(),
+                sample.draw.as_micros(),
+                sample.overlay.as_micros(),
+                sample.present.as_micros(),
+                sample.frame_total.as_micros()
+            )?;
+        }
+        f.flush()
+    }
+
     fn print_summary(&self) {
         println!("profile summary (ms; lower is better)");
         println!(
@@ -1014,10 +1634,68 @@ impl TraceCapture {
     }
 }
 
+impl Profiler for TraceCapture {
+    fn on_frame(&mut self, timings: FrameTimings) {
+        let sample = FrameSample {
+            update: timings.update.unwrap_or_default(),
+            draw: timings.draw.unwrap_or_default(),
+            overlay: timings.overlay.unwrap_or_default(),
+            present: timings.present.unwrap_or_default(),
+            frame_total: timings.total,
+        };
+        self.update.push(sample.update);
+        self.draw.push(sample.draw);
+        self.present.push(sample.present);
+        self.frame_total.push(sample.frame_total);
+        self.frames.push(sample);
+        self.captured_frames = self.captured_frames.saturating_add(1);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn trace_csv_has_one_row_per_frame_matching_summary_aggregates() {
+        let mut trace = TraceCapture::new(3);
+        for ms in [2u64, 4, 6] {
+            trace.on_frame(FrameTimings {
+                update: Some(Duration::from_millis(ms)),
+                draw: Some(Duration::from_millis(ms * 2)),
+                present: Some(Duration::from_millis(1)),
+                total: Duration::from_millis(ms * 3 + 1),
+                ..FrameTimings::default()
+            });
+        }
+        let path = std::env::temp_dir().join(format!(
+            "rollout_engine_trace_csv_{}.csv",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        ));
+        trace.write_csv(&path).expect("write csv");
+        let text = fs::read_to_string(&path).expect("read csv");
+        let _ = fs::remove_file(&path);
+
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next(),
+            Some("frame,engine_us,draw_us,overlay_us,present_us,frame_total_us")
+        );
+        let rows: Vec<Vec<u64>> = lines
+            .map(|l| l.split(',').map(|v| v.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1], vec![1, 4000, 8000, 0, 1000, 13000]);
+
+        let avg_update_ms = rows.iter().map(|r| r[1]).sum::<u64>() as f64 / 3.0 / 1000.0;
+        assert!((avg_update_ms - trace.update.avg_ms()).abs() < 1e-9);
+        let max_frame_ms = rows.iter().map(|r| r[5]).max().unwrap() as f64 / 1000.0;
+        assert!((max_frame_ms - trace.frame_total.max_ms()).abs() < 1e-9);
+    }
+
     #[test]
     fn key_transitions_are_frame_based() {
         let mut input = InputFrame::default();
@@ -1071,6 +1749,20 @@ mod tests {
         assert!((input.scroll_y - 0.0).abs() < 0.0001);
     }
 
+    #[test]
+    fn frame_dt_is_capped_only_when_configured() {
+        let spike = Duration::from_millis(750);
+        assert_eq!(
+            clamp_frame_dt(spike, Some(DEFAULT_MAX_FRAME_DT)),
+            DEFAULT_MAX_FRAME_DT
+        );
+        assert_eq!(
+            clamp_frame_dt(Duration::from_millis(16), Some(DEFAULT_MAX_FRAME_DT)),
+            Duration::from_millis(16)
+        );
+        assert_eq!(clamp_frame_dt(spike, None), spike);
+    }
+
     #[test]
     fn focus_loss_clears_held_inputs() {
         let mut input = InputFrame::default();
@@ -1085,4 +1777,257 @@ mod tests {
         assert!(!input.mouse_down);
         assert!(!input.mouse_up);
     }
+
+    /// Counts button clicks and total `dt`; panics if asked to render.
+    struct ClickCounter;
+
+    #[derive(Default)]
+    struct Counted {
+        clicks: u32,
+        elapsed: Duration,
+        frames: u32,
+        fixed_steps: u32,
+    }
+
+    impl GameApp for ClickCounter {
+        type State = Counted;
+        type Action = ();
+        type Effect = ();
+
+        fn init_state(&mut self, _ctx: &mut AppContext) -> Self::State {
+            Counted::default()
+        }
+
+        fn build_view(&self, _state: &Self::State, ctx: &AppContext) -> ViewTree<()> {
+            assert!(ctx.renderer.is_none());
+            let mut view = ViewTree::new();
+            view.push(crate::view_tree::ViewNode::Button(
+                crate::view_tree::ButtonNode {
+                    id: 1,
+                    rect: crate::ui::Rect::new(0, 0, ctx.surface_size.width / 2, 10),
+                    label: "+1".to_string(),
+                    action: (),
+                    enabled: true,
+                },
+            ));
+            view
+        }
+
+        fn update_state(
+            &mut self,
+            state: &mut Self::State,
+            _input: InputFrame,
+            dt: Duration,
+            actions: &[()],
+            _ctx: &mut AppContext,
+        ) -> Vec<()> {
+            state.clicks += actions.len() as u32;
+            state.elapsed += dt;
+            state.frames += 1;
+            Vec::new()
+        }
+
+        fn render(&mut self, _view: &ViewTree<()>, _renderer: &mut dyn Renderer2d, _alpha: f32) {
+            panic!("simulate must not render");
+        }
+
+        fn fixed_update_interval(&self) -> Option<Duration> {
+            Some(Duration::from_millis(30))
+        }
+
+        fn fixed_update(&mut self, state: &mut Counted, _step: Duration, _ctx: &mut AppContext) {
+            state.fixed_steps += 1;
+        }
+    }
+
+    #[test]
+    fn simulate_runs_view_hit_testing_and_updates_without_rendering() {
+        let config = AppConfig {
+            title: "sim".to_string(),
+            desired_size: PhysicalSize::new(100, 50),
+            clamp_to_monitor: false,
+            vsync: None,
+            present_mode: None,
+            max_frame_dt: Some(Duration::from_millis(50)),
+            target_fps: DEFAULT_TARGET_FPS,
+            pause_on_unfocus: false,
+            localizer: None,
+        };
+        let click = |x| InputFrame {
+            mouse_pos: Some((x, 5)),
+            mouse_up: true,
+            ..InputFrame::default()
+        };
+        let frames = vec![
+            (click(10), Duration::from_millis(10)),
+            (click(80), Duration::from_millis(20)),
+            (InputFrame::default(), Duration::from_secs(1)),
+        ];
+
+        let state = simulate_with_dt(config, &mut ClickCounter, frames);
+        assert_eq!(state.frames, 3);
+        assert_eq!(
+            state.clicks, 1,
+            "only the click inside the half-width button counts"
+        );
+        assert_eq!(
+            state.elapsed,
+            Duration::from_millis(80),
+            "dt is clamped per frame"
+        );
+        assert_eq!(
+            state.fixed_steps, 2,
+            "80ms of clamped frames is two 30ms steps"
+        );
+    }
+
+    #[test]
+    fn pause_on_unfocus_skips_logic_for_unfocused_frames() {
+        let config = |pause_on_unfocus| AppConfig {
+            title: "sim".to_string(),
+            desired_size: PhysicalSize::new(100, 50),
+            clamp_to_monitor: false,
+            vsync: None,
+            present_mode: None,
+            max_frame_dt: None,
+            target_fps: 0,
+            pause_on_unfocus,
+            localizer: None,
+        };
+        let frames = || {
+            let unfocused = InputFrame {
+                window_focused: false,
+                ..InputFrame::default()
+            };
+            vec![
+                (InputFrame::default(), Duration::from_millis(10)),
+                (unfocused, Duration::from_secs(5)),
+                (InputFrame::default(), Duration::from_millis(10)),
+            ]
+        };
+
+        let paused = simulate_with_dt(config(true), &mut ClickCounter, frames());
+        // The focus-loss frame still reaches `update_state`, but without its 5s of dt.
+        assert_eq!(paused.frames, 3);
+        assert_eq!(paused.elapsed, Duration::from_millis(20));
+
+        let unfocused = InputFrame {
+            window_focused: false,
+            ..InputFrame::default()
+        };
+        let mut long = frames();
+        long.insert(2, (unfocused, Duration::from_secs(5)));
+        let held = simulate_with_dt(config(true), &mut ClickCounter, long);
+        assert_eq!(held.frames, 3);
+        assert_eq!(held.elapsed, Duration::from_millis(20));
+
+        let running = simulate_with_dt(config(false), &mut ClickCounter, frames());
+        assert_eq!(running.frames, 3);
+        assert!(logic_paused(&config(true), &frames()[1].0));
+    }
+
+    #[test]
+    fn input_recording_round_trips_and_replays_deterministically() {
+        let config = || AppConfig {
+            title: "sim".to_string(),
+            desired_size: PhysicalSize::new(100, 50),
+            clamp_to_monitor: false,
+            vsync: None,
+            present_mode: None,
+            max_frame_dt: Some(Duration::from_millis(50)),
+            target_fps: 0,
+            pause_on_unfocus: false,
+            localizer: None,
+        };
+        let mut click = InputFrame {
+            mouse_pos: Some((10, 5)),
+            mouse_up: true,
+            ..InputFrame::default()
+        };
+        click.keys_pressed.insert(VirtualKeyCode::Space);
+        let recording = InputRecording {
+            frames: vec![
+                RecordedInputFrame {
+                    dt: Duration::from_millis(17),
+                    input: click,
+                },
+                RecordedInputFrame {
+                    dt: Duration::from_millis(43),
+                    input: InputFrame::default(),
+                },
+            ],
+        };
+
+        let path = std::env::temp_dir().join(format!(
+            "rollout_engine_input_recording_{}.json",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        ));
+        save_input_recording(&path, &recording).expect("save input recording");
+        let loaded = load_input_recording(&path).expect("load input recording");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.frames.len(), 2);
+        assert_eq!(loaded.frames[0].dt, Duration::from_millis(17));
+        assert!(
+            loaded.frames[0]
+                .input
+                .keys_pressed
+                .contains(&VirtualKeyCode::Space)
+        );
+
+        let replayed =
+            simulate_with_dt(config(), &mut ClickCounter, loaded.into_simulation_frames());
+        let original = simulate_with_dt(
+            config(),
+            &mut ClickCounter,
+            recording.into_simulation_frames(),
+        );
+        assert_eq!(replayed.clicks, 1);
+        assert_eq!(replayed.elapsed, Duration::from_millis(60));
+        assert_eq!(replayed.fixed_steps, original.fixed_steps);
+    }
+
+    #[test]
+    fn replay_speed_and_scrub_positions_are_clamped() {
+        assert_eq!(clamp_replay_speed(0.1), MIN_REPLAY_SPEED);
+        assert_eq!(clamp_replay_speed(100.0), MAX_REPLAY_SPEED);
+        assert_eq!(clamp_replay_speed(f32::NAN), 1.0);
+        assert_eq!(
+            replay_step_interval(15, 0.5),
+            Duration::from_secs_f64(2.0 / 15.0)
+        );
+        assert_eq!(replay_step_interval(10, 4.0), Duration::from_millis(25));
+
+        assert_eq!(replay_frame_for_position(101, 0.0), 0);
+        assert_eq!(replay_frame_for_position(101, 0.25), 25);
+        assert_eq!(replay_frame_for_position(101, 1.0), 100);
+        assert_eq!(replay_frame_for_position(101, 2.0), 100);
+        assert_eq!(replay_frame_for_position(101, -1.0), 0);
+        assert_eq!(replay_frame_for_position(0, 0.5), 0);
+        assert_eq!(scrub_tenth_for_key(VirtualKeyCode::Key7), Some(7));
+        assert_eq!(scrub_tenth_for_key(VirtualKeyCode::A), None);
+    }
+
+    #[test]
+    fn frame_pacer_skips_early_redraws_and_polls_when_uncapped() {
+        let mut uncapped = FramePacer::new(0);
+        assert_eq!(uncapped.interval(), None);
+        assert_eq!(uncapped.control_flow(), ControlFlow::Poll);
+        assert!(uncapped.begin_frame(Instant::now()));
+        assert!(uncapped.begin_frame(Instant::now()));
+
+        let mut capped = FramePacer::new(50);
+        assert_eq!(capped.interval(), Some(Duration::from_millis(20)));
+        let start = Instant::now();
+        assert!(capped.begin_frame(start));
+        assert_eq!(
+            capped.control_flow(),
+            ControlFlow::WaitUntil(start + Duration::from_millis(20))
+        );
+        assert!(!capped.begin_frame(start + Duration::from_millis(5)));
+        assert!(capped.begin_frame(start + Duration::from_millis(20)));
+    }
 }
@@ -1,4 +1,5 @@
 use std::f32::consts::TAU;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Transport {
@@ -370,10 +371,147 @@ impl MusicRuntime {
     }
 }
 
+/// Side-chain ducking for background music.
+///
+/// Call `trigger` whenever an SFX starts and `update` once per frame, then scale the music volume
+/// by the returned gain. The gain ramps down over `attack`, holds for `hold`, and ramps back up
+/// over `recovery`. Triggers that land while already ducked extend the hold (or reverse a
+/// recovery from the current level) instead of restarting the ramp, so rapid SFX don't stutter.
+/// An `amount` of zero disables ducking entirely.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MusicDucker {
+    amount: f32,
+    attack: Duration,
+    hold: Duration,
+    recovery: Duration,
+    gain: f32,
+    attacking: bool,
+    hold_left: Duration,
+}
+
+impl Default for MusicDucker {
+    fn default() -> Self {
+        Self::new(0.0, Duration::from_millis(250))
+    }
+}
+
+impl MusicDucker {
+    /// `amount` is the fraction of music volume removed at full duck (0.0..=1.0).
+    pub fn new(amount: f32, recovery: Duration) -> Self {
+        Self {
+            amount: amount.clamp(0.0, 1.0),
+            attack: Duration::from_millis(20),
+            hold: Duration::from_millis(60),
+            recovery,
+            gain: 1.0,
+            attacking: false,
+            hold_left: Duration::ZERO,
+        }
+    }
+
+    pub fn with_attack(mut self, attack: Duration) -> Self {
+        self.attack = attack;
+        self
+    }
+
+    pub fn with_hold(mut self, hold: Duration) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    /// Retunes the duck depth and recovery time without resetting an in-flight duck.
+    pub fn configure(&mut self, amount: f32, recovery: Duration) {
+        self.amount = amount.clamp(0.0, 1.0);
+        self.recovery = recovery;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.amount > 0.0
+    }
+
+    /// Current music gain multiplier (1.0 = not ducked).
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    pub fn trigger(&mut self) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.attacking = true;
+        self.hold_left = self.hold;
+    }
+
+    /// Advances the envelope by `dt` and returns the new gain.
+    pub fn update(&mut self, dt: Duration) -> f32 {
+        let floor = 1.0 - self.amount;
+        if self.attacking {
+            self.gain = (self.gain - ramp_step(self.amount, dt, self.attack)).max(floor);
+            if self.gain <= floor {
+                self.attacking = false;
+            }
+        } else if !self.hold_left.is_zero() {
+            self.hold_left = self.hold_left.saturating_sub(dt);
+        } else {
+            self.gain = (self.gain + ramp_step(self.amount, dt, self.recovery)).min(1.0);
+        }
+        // Settings may have shrunk the duck depth mid-flight.
+        self.gain = self.gain.clamp(floor, 1.0);
+        self.gain
+    }
+}
+
+/// Linear ramp covering `span` over `duration`; a zero duration jumps straight to the target.
+fn ramp_step(span: f32, dt: Duration, duration: Duration) -> f32 {
+    if duration.is_zero() {
+        return 1.0;
+    }
+    span * (dt.as_secs_f32() / duration.as_secs_f32())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn ducker_is_a_no_op_by_default() {
+        let mut ducker = MusicDucker::default();
+        ducker.trigger();
+        assert_eq!(ducker.update(Duration::from_millis(16)), 1.0);
+    }
+
+    #[test]
+    fn ducker_ramps_down_holds_and_recovers_without_jumps() {
+        let frame = Duration::from_millis(10);
+        let mut ducker = MusicDucker::new(0.5, Duration::from_millis(100))
+            .with_attack(Duration::from_millis(20))
+            .with_hold(Duration::from_millis(30));
+        ducker.trigger();
+        assert!((ducker.update(frame) - 0.75).abs() < 1e-4);
+        assert!((ducker.update(frame) - 0.5).abs() < 1e-4);
+
+        // Rapid re-triggers while ducked only extend the hold.
+        for _ in 0..10 {
+            ducker.trigger();
+            assert!((ducker.update(frame) - 0.5).abs() < 1e-4);
+        }
+
+        let mut last = ducker.gain();
+        let mut frames = 0;
+        while ducker.update(frame) < 1.0 {
+            assert!(ducker.gain() >= last);
+            assert!(ducker.gain() - last <= 0.05 + 1e-4);
+            last = ducker.gain();
+            frames += 1;
+            assert!(frames < 100);
+        }
+
+        // A trigger mid-recovery reverses from the current level.
+        ducker.trigger();
+        ducker.update(Duration::from_millis(5));
+        assert!(ducker.gain() > 0.8 && ducker.gain() < 1.0);
+    }
+
     #[test]
     fn transport_quantizes_to_next_bar() {
         let mut transport = Transport::new(48_000, 120.0).with_signature(4);
@@ -81,3 +81,18 @@ pub struct FramesRequest {
 pub struct SeekRequest {
     pub frame: usize,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadRecordingRequest {
+    /// Recording JSON on the game's filesystem.
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFrameStateRequest {
+    pub frame: usize,
+    /// Game state in the same shape as `EditorSnapshot::state`.
+    pub snapshot: serde_json::Value,
+}
@@ -1,3 +1,5 @@
+use std::{fs, io, path::Path};
+
 use crate::{surface::SurfaceSize, ui::Rect};
 use pixels::{PixelsContext, wgpu};
 use wgpu::util::DeviceExt;
@@ -17,6 +19,126 @@ fn line_advance_y(scale: u32) -> u32 {
     (GLYPH_H + 1) * scale.max(1)
 }
 
+/// Integer text scale for a surface `height` pixels tall: `DEFAULT_TEXT_SCALE` up to 1080p, then
+/// one more step per 540px so HiDPI surfaces keep text at a readable physical size.
+pub fn text_scale_for_height(height: u32) -> u32 {
+    (height / 540).clamp(DEFAULT_TEXT_SCALE, 8)
+}
+
+/// Pixel `(width, height)` of `text` drawn with the built-in block font at `scale`.
+///
+/// Matches `draw_text_scaled`: the box spans from the first glyph's left edge to the last glyph's
+/// right edge (no trailing spacing), and `\n` starts a new line. Empty text measures `(0, 0)`.
+pub fn measure_text_scaled(text: &str, scale: u32) -> (u32, u32) {
+    if text.is_empty() {
+        return (0, 0);
+    }
+    let scale = scale.max(1);
+    let mut max_chars = 0u32;
+    let mut lines = 0u32;
+    for line in text.split('\n') {
+        lines = lines.saturating_add(1);
+        max_chars = max_chars.max(line.chars().count().min(u32::MAX as usize) as u32);
+    }
+    let w = max_chars
+        .saturating_mul(glyph_advance_x(scale))
+        .saturating_sub(scale);
+    let h = lines
+        .saturating_mul(line_advance_y(scale))
+        .saturating_sub(scale);
+    (if max_chars == 0 { 0 } else { w }, h)
+}
+
+/// Straight-alpha RGBA8 image for sprite blitting (`rgba.len() == width * height * 4`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Texture {
+    width: u32,
+    height: u32,
+    rgba: Vec<u8>,
+}
+
+impl Texture {
+    /// Wraps row-major RGBA bytes; fails if the length doesn't match the dimensions.
+    pub fn from_rgba(width: u32, height: u32, rgba: Vec<u8>) -> io::Result<Self> {
+        let expected = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|v| v.checked_mul(4));
+        if expected != Some(rgba.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "texture is {width}x{height} but got {} bytes of RGBA",
+                    rgba.len()
+                ),
+            ));
+        }
+        Ok(Self {
+            width,
+            height,
+            rgba,
+        })
+    }
+
+    /// Decodes an in-memory PNG (any bit depth/color type) to RGBA8.
+    pub fn from_png_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let mut decoder = png::Decoder::new(io::Cursor::new(bytes));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(io::Error::other)?;
+        let buf_len = reader.output_buffer_size().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "png is too large to decode")
+        })?;
+        let mut buf = vec![0u8; buf_len];
+        let info = reader.next_frame(&mut buf).map_err(io::Error::other)?;
+        buf.truncate(info.buffer_size());
+
+        let rgba = match info.color_type {
+            png::ColorType::Rgba => buf,
+            png::ColorType::Rgb => buf
+                .chunks_exact(3)
+                .flat_map(|px| [px[0], px[1], px[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buf
+                .chunks_exact(2)
+                .flat_map(|px| [px[0], px[0], px[0], px[1]])
+                .collect(),
+            png::ColorType::Grayscale => buf.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+            png::ColorType::Indexed => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "indexed png was not expanded",
+                ));
+            }
+        };
+        Self::from_rgba(info.width, info.height, rgba)
+    }
+
+    pub fn load_png(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_png_bytes(&fs::read(path)?)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    /// Texel at `(x, y)`, or `None` if out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let idx = (y as usize * self.width as usize + x as usize) * 4;
+        let px = self.rgba.get(idx..idx + 4)?;
+        Some([px[0], px[1], px[2], px[3]])
+    }
+}
+
 /// Unified 2D rendering interface.
 ///
 /// Game code should only talk to this trait — it must not care whether the underlying renderer is
@@ -34,38 +156,504 @@ pub trait Renderer2d {
     fn rect_outline(&mut self, rect: Rect, color: Color);
     fn draw_text_scaled(&mut self, x: u32, y: u32, text: &str, color: Color, scale: u32);
 
+    /// Restrict subsequent draws to `rect`, intersected with any clip already pushed.
+    ///
+    /// Every `push_clip` must be paired with a `pop_clip`; `begin_frame` resets the stack.
+    fn push_clip(&mut self, rect: Rect);
+
+    /// Undo the most recent `push_clip`. Popping an empty stack is a no-op.
+    fn pop_clip(&mut self);
+
+    /// Effective clip rect (the intersection of the stack), or `None` when nothing is pushed.
+    fn clip_rect(&self) -> Option<Rect>;
+
     fn draw_text(&mut self, x: u32, y: u32, text: &str, color: Color) {
         self.draw_text_scaled(x, y, text, color, DEFAULT_TEXT_SCALE);
     }
 
+    /// Pixel `(width, height)` that `draw_text_scaled` covers for `text` at `scale`.
+    fn measure_text_scaled(&self, text: &str, scale: u32) -> (u32, u32) {
+        measure_text_scaled(text, scale)
+    }
+
+    /// Pixel `(width, height)` that `draw_text` covers for `text`.
+    fn measure_text(&self, text: &str) -> (u32, u32) {
+        self.measure_text_scaled(text, DEFAULT_TEXT_SCALE)
+    }
+
     fn clear(&mut self, color: Color) {
         let s = self.size();
         self.fill_rect(Rect::from_size(s.width, s.height), color);
     }
+
+    /// One-pixel line from `(x0, y0)` to `(x1, y1)` inclusive, clipped to the surface.
+    ///
+    /// `color[3]` is the blend alpha: 255 draws opaque like `fill_rect`, anything lower blends like
+    /// `blend_rect`.
+    fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: Color) {
+        let s = self.size();
+        let Some((x0, y0, x1, y1)) = clip_line_to_surface(x0, y0, x1, y1, s) else {
+            return;
+        };
+
+        // Bresenham over the clipped segment.
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        let (mut x, mut y) = (x0, y0);
+        loop {
+            plot_span(self, x, y, 1, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// One-pixel circle outline centered on `(cx, cy)`, clipped to the surface.
+    ///
+    /// Alpha follows the same rule as `draw_line`; no pixel is drawn twice, so translucent
+    /// outlines blend evenly.
+    fn draw_circle_outline(&mut self, cx: i32, cy: i32, r: u32, color: Color) {
+        let r = r.min(i32::MAX as u32) as i32;
+        if r == 0 {
+            plot_span(self, cx, cy, 1, color);
+            return;
+        }
+
+        // Midpoint circle; each octant point is mirrored, skipping duplicates on the diagonals
+        // and axes.
+        let mut x = r;
+        let mut y = 0i32;
+        let mut err = 1 - r;
+        while x >= y {
+            let mut points = [
+                (cx + x, cy + y),
+                (cx + y, cy + x),
+                (cx - y, cy + x),
+                (cx - x, cy + y),
+                (cx - x, cy - y),
+                (cx - y, cy - x),
+                (cx + y, cy - x),
+                (cx + x, cy - y),
+            ];
+            points.sort_unstable();
+            let mut prev = None;
+            for p in points {
+                if prev != Some(p) {
+                    plot_span(self, p.0, p.1, 1, color);
+                    prev = Some(p);
+                }
+            }
+
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Filled circle of radius `r` centered on `(cx, cy)`, drawn as clipped horizontal spans.
+    ///
+    /// Alpha follows the same rule as `draw_line`.
+    fn fill_circle(&mut self, cx: i32, cy: i32, r: u32, color: Color) {
+        let r = r.min(i32::MAX as u32) as i64;
+        let r2 = r * r;
+        for dy in -r..=r {
+            // Widest |dx| with dx^2 + dy^2 <= r^2.
+            let mut half = ((r2 - dy * dy) as f64).sqrt() as i64;
+            while half * half > r2 - dy * dy {
+                half -= 1;
+            }
+            while (half + 1) * (half + 1) <= r2 - dy * dy {
+                half += 1;
+            }
+            let y = cy as i64 + dy;
+            let x = cx as i64 - half;
+            if y < i32::MIN as i64 || y > i32::MAX as i64 {
+                continue;
+            }
+            let x = x.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+            plot_span(self, x, y as i32, (2 * half + 1) as u32, color);
+        }
+    }
+
+    /// Blit `texture` with its top-left corner at `(x, y)`.
+    ///
+    /// Each texel's alpha composites like `blend_rect` (255 overwrites, 0 is skipped), and the
+    /// blit is clipped to the surface and the active clip rect.
+    fn draw_texture(&mut self, x: i32, y: i32, texture: &Texture) {
+        self.draw_texture_scaled(x, y, texture, 1);
+    }
+
+    /// Nearest-neighbour blit with every texel drawn as a `scale` x `scale` block.
+    fn draw_texture_scaled(&mut self, x: i32, y: i32, texture: &Texture, scale: u32) {
+        let scale = scale.max(1);
+        let s = self.size();
+        let step = scale as i64;
+        for ty in 0..texture.height() {
+            let py = y as i64 + ty as i64 * step;
+            if py + step <= 0 {
+                continue;
+            }
+            if py >= s.height as i64 {
+                break;
+            }
+            // Merge horizontal runs of identical texels so flat artwork stays cheap on the GPU path.
+            let mut tx = 0;
+            while tx < texture.width() {
+                let color = texture.pixel(tx, ty).unwrap_or([0, 0, 0, 0]);
+                let run_start = tx;
+                while tx < texture.width() && texture.pixel(tx, ty) == Some(color) {
+                    tx += 1;
+                }
+                let px = x as i64 + run_start as i64 * step;
+                let run_w = (tx - run_start) as i64 * step;
+                plot_block(self, px, py, run_w, step, color);
+            }
+        }
+    }
+
+    /// Color of the composited frame at `(x, y)` after all draws so far, or `None` if out of bounds.
+    ///
+    /// Intended for debug tooling (e.g. a color picker under the cursor); cheap enough to call once
+    /// per frame.
+    fn read_pixel(&self, x: u32, y: u32) -> Option<Color>;
+}
+
+/// Draw a horizontal run of `len` pixels starting at `(x, y)`, clipped to the surface.
+///
+/// Opaque colors go through `fill_rect`; translucent ones through `blend_rect`, so the shape
+/// primitives share the rect helpers' blend semantics on every backend.
+fn plot_span<R: Renderer2d + ?Sized>(gfx: &mut R, x: i32, y: i32, len: u32, color: Color) {
+    plot_block(gfx, x as i64, y as i64, len as i64, 1, color);
+}
+
+/// `plot_span` for a `w` x `h` block; coordinates are `i64` so scaled blits can't overflow.
+fn plot_block<R: Renderer2d + ?Sized>(gfx: &mut R, x: i64, y: i64, w: i64, h: i64, color: Color) {
+    let s = gfx.size();
+    let x0 = x.max(0);
+    let y0 = y.max(0);
+    let x1 = x.saturating_add(w).min(s.width as i64);
+    let y1 = y.saturating_add(h).min(s.height as i64);
+    if x0 >= x1 || y0 >= y1 {
+        return;
+    }
+    let rect = Rect::new(x0 as u32, y0 as u32, (x1 - x0) as u32, (y1 - y0) as u32);
+    match color[3] {
+        0 => {}
+        255 => gfx.fill_rect(rect, color),
+        alpha => gfx.blend_rect(rect, color, alpha),
+    }
+}
+
+/// Cohen–Sutherland clip of a segment to `[0, width) x [0, height)`.
+///
+/// Returns the clipped integer endpoints, or `None` when the segment misses the surface.
+fn clip_line_to_surface(
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+    size: SurfaceSize,
+) -> Option<(i32, i32, i32, i32)> {
+    if size.width == 0 || size.height == 0 {
+        return None;
+    }
+    const LEFT: u8 = 1;
+    const RIGHT: u8 = 2;
+    const BOTTOM: u8 = 4;
+    const TOP: u8 = 8;
+
+    let xmax = size.width.min(i32::MAX as u32) as i64 - 1;
+    let ymax = size.height.min(i32::MAX as u32) as i64 - 1;
+    let outcode = |x: i64, y: i64| {
+        let mut code = 0;
+        if x < 0 {
+            code |= LEFT;
+        } else if x > xmax {
+            code |= RIGHT;
+        }
+        if y < 0 {
+            code |= TOP;
+        } else if y > ymax {
+            code |= BOTTOM;
+        }
+        code
+    };
+
+    let (mut ax, mut ay, mut bx, mut by) = (x0 as i64, y0 as i64, x1 as i64, y1 as i64);
+    let mut code_a = outcode(ax, ay);
+    let mut code_b = outcode(bx, by);
+    // Each pass clips one edge; rounding can re-violate an edge at most once more.
+    for _ in 0..8 {
+        if code_a | code_b == 0 {
+            return Some((ax as i32, ay as i32, bx as i32, by as i32));
+        }
+        if code_a & code_b != 0 {
+            return None;
+        }
+        let out = if code_a != 0 { code_a } else { code_b };
+        // Intersect with the violated edge, rounding to the nearest pixel on the line.
+        let (x, y) = if out & TOP != 0 {
+            (ax + div_round(bx - ax, -ay, by - ay), 0)
+        } else if out & BOTTOM != 0 {
+            (ax + div_round(bx - ax, ymax - ay, by - ay), ymax)
+        } else if out & RIGHT != 0 {
+            (xmax, ay + div_round(by - ay, xmax - ax, bx - ax))
+        } else {
+            (0, ay + div_round(by - ay, -ax, bx - ax))
+        };
+        if out == code_a {
+            (ax, ay) = (x, y);
+            code_a = outcode(ax, ay);
+        } else {
+            (bx, by) = (x, y);
+            code_b = outcode(bx, by);
+        }
+    }
+    None
+}
+
+/// `a * b / d` rounded to nearest, in i128 so large coordinates can't overflow.
+fn div_round(a: i64, b: i64, d: i64) -> i64 {
+    let n = a as i128 * b as i128;
+    let d = d as i128;
+    let q = if (n < 0) == (d < 0) {
+        (n + d / 2) / d
+    } else {
+        (n - d / 2) / d
+    };
+    q as i64
+}
+
+/// Clip stack shared by the renderers; each entry is already intersected with the one below.
+#[derive(Debug, Default, Clone)]
+struct ClipStack {
+    rects: Vec<Rect>,
+}
+
+impl ClipStack {
+    fn push(&mut self, rect: Rect) {
+        let next = match self.top() {
+            // An empty intersection is kept as a zero-size rect so everything stays clipped.
+            Some(top) => crate::render::clip_rect_to_viewport(rect, top)
+                .unwrap_or(Rect::new(rect.x, rect.y, 0, 0)),
+            None => rect,
+        };
+        self.rects.push(next);
+    }
+
+    fn pop(&mut self) {
+        self.rects.pop();
+    }
+
+    fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    fn top(&self) -> Option<Rect> {
+        self.rects.last().copied()
+    }
+
+    /// `rect` clipped to the current top, or `None` if nothing of it is visible.
+    fn apply(&self, rect: Rect) -> Option<Rect> {
+        match self.top() {
+            Some(top) => crate::render::clip_rect_to_viewport(rect, top),
+            None => Some(rect),
+        }
+    }
+}
+
+/// Wraps another renderer and records the screen regions each draw call touches.
+///
+/// `begin_frame` is deferred until the first draw, so a frame that draws nothing leaves the inner
+/// renderer's previous output intact. Clips pushed before that are replayed onto it then.
+pub struct DirtyTracker<'a> {
+    inner: &'a mut dyn Renderer2d,
+    size: SurfaceSize,
+    started: bool,
+    pending_clip: ClipStack,
+    dirty: Vec<Rect>,
+}
+
+impl<'a> DirtyTracker<'a> {
+    pub fn new(inner: &'a mut dyn Renderer2d, size: SurfaceSize) -> Self {
+        Self {
+            inner,
+            size,
+            started: false,
+            pending_clip: ClipStack::default(),
+            dirty: Vec::new(),
+        }
+    }
+
+    /// Visible regions drawn so far this frame, in draw order (they may overlap).
+    pub fn dirty_rects(&self) -> &[Rect] {
+        &self.dirty
+    }
+
+    pub fn into_dirty_rects(self) -> Vec<Rect> {
+        self.dirty
+    }
+
+    fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        self.inner.begin_frame(self.size);
+        for rect in self.pending_clip.rects.drain(..) {
+            self.inner.push_clip(rect);
+        }
+    }
+
+    fn mark(&mut self, rect: Rect) {
+        self.start();
+        let surface = Rect::from_size(self.size.width, self.size.height);
+        let visible =
+            crate::render::clip_rect_to_viewport(rect, surface).and_then(|rect| {
+                match self.inner.clip_rect() {
+                    Some(clip) => crate::render::clip_rect_to_viewport(rect, clip),
+                    None => Some(rect),
+                }
+            });
+        if let Some(rect) = visible.filter(|rect| rect.w > 0 && rect.h > 0) {
+            self.dirty.push(rect);
+        }
+    }
+}
+
+impl Renderer2d for DirtyTracker<'_> {
+    fn begin_frame(&mut self, size: SurfaceSize) {
+        self.size = size;
+        self.started = false;
+        self.pending_clip.clear();
+        self.dirty.clear();
+    }
+
+    fn size(&self) -> SurfaceSize {
+        self.size
+    }
+
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.mark(rect);
+        self.inner.fill_rect(rect, color);
+    }
+
+    fn blend_rect(&mut self, rect: Rect, color: Color, alpha: u8) {
+        self.mark(rect);
+        self.inner.blend_rect(rect, color, alpha);
+    }
+
+    fn rect_outline(&mut self, rect: Rect, color: Color) {
+        self.mark(rect);
+        self.inner.rect_outline(rect, color);
+    }
+
+    fn draw_text_scaled(&mut self, x: u32, y: u32, text: &str, color: Color, scale: u32) {
+        let (w, h) = measure_text_scaled(text, scale);
+        self.mark(Rect::new(x, y, w, h));
+        self.inner.draw_text_scaled(x, y, text, color, scale);
+    }
+
+    fn push_clip(&mut self, rect: Rect) {
+        if self.started {
+            self.inner.push_clip(rect);
+        } else {
+            self.pending_clip.push(rect);
+        }
+    }
+
+    fn pop_clip(&mut self) {
+        if self.started {
+            self.inner.pop_clip();
+        } else {
+            self.pending_clip.pop();
+        }
+    }
+
+    fn clip_rect(&self) -> Option<Rect> {
+        if self.started {
+            self.inner.clip_rect()
+        } else {
+            self.pending_clip.top()
+        }
+    }
+
+    fn read_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        self.inner.read_pixel(x, y)
+    }
 }
 
 /// CPU renderer that draws into an RGBA frame buffer.
 pub struct CpuRenderer<'a> {
     frame: &'a mut [u8],
     size: SurfaceSize,
+    clip: ClipStack,
 }
 
 impl<'a> CpuRenderer<'a> {
     pub fn new(frame: &'a mut [u8], size: SurfaceSize) -> Self {
-        Self { frame, size }
+        Self {
+            frame,
+            size,
+            clip: ClipStack::default(),
+        }
     }
 }
 
 impl Renderer2d for CpuRenderer<'_> {
     fn begin_frame(&mut self, size: SurfaceSize) {
         self.size = size;
+        self.clip.clear();
+    }
+
+    fn push_clip(&mut self, rect: Rect) {
+        self.clip.push(rect);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clip.pop();
+    }
+
+    fn clip_rect(&self) -> Option<Rect> {
+        self.clip.top()
     }
 
     fn size(&self) -> SurfaceSize {
         self.size
     }
 
+    fn read_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.size.width || y >= self.size.height {
+            return None;
+        }
+        let idx = (y as usize)
+            .checked_mul(self.size.width as usize)?
+            .checked_add(x as usize)?
+            .checked_mul(4)?;
+        let px = self.frame.get(idx..idx + 4)?;
+        Some([px[0], px[1], px[2], px[3]])
+    }
+
     fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let Some(rect) = self.clip.apply(rect) else {
+            return;
+        };
         let width = self.size.width;
         let height = self.size.height;
 
@@ -119,6 +707,9 @@ impl Renderer2d for CpuRenderer<'_> {
             self.fill_rect(rect, color);
             return;
         }
+        let Some(rect) = self.clip.apply(rect) else {
+            return;
+        };
 
         let width = self.size.width;
         let height = self.size.height;
@@ -203,6 +794,10 @@ impl Renderer2d for CpuRenderer<'_> {
     fn draw_text_scaled(&mut self, x: u32, y: u32, text: &str, color: Color, scale: u32) {
         let width = self.size.width;
         let height = self.size.height;
+        let bounds = self
+            .clip
+            .apply(Rect::from_size(width, height))
+            .unwrap_or(Rect::new(0, 0, 0, 0));
         let scale = scale.max(1);
         let adv_x = glyph_advance_x(scale);
         let adv_y = line_advance_y(scale);
@@ -231,7 +826,7 @@ impl Renderer2d for CpuRenderer<'_> {
             }
 
             draw_char_cpu(
-                self.frame, width, height, cursor_x, cursor_y, ch, color, scale,
+                self.frame, width, bounds, cursor_x, cursor_y, ch, color, scale,
             );
             cursor_x = cursor_x.saturating_add(adv_x);
             if cursor_x >= width {
@@ -244,7 +839,7 @@ impl Renderer2d for CpuRenderer<'_> {
 fn draw_char_cpu(
     frame: &mut [u8],
     width: u32,
-    height: u32,
+    bounds: Rect,
     x: u32,
     y: u32,
     ch: char,
@@ -262,15 +857,16 @@ fn draw_char_cpu(
             let px0 = x.saturating_add(col.saturating_mul(scale));
             for dy in 0..scale {
                 for dx in 0..scale {
-                    set_pixel_cpu(frame, width, height, px0 + dx, py0 + dy, color);
+                    set_pixel_cpu(frame, width, bounds, px0 + dx, py0 + dy, color);
                 }
             }
         }
     }
 }
 
-fn set_pixel_cpu(frame: &mut [u8], width: u32, height: u32, x: u32, y: u32, color: Color) {
-    if x >= width || y >= height {
+/// `bounds` is the surface (or active clip) rect and must lie within the `width`-wide frame.
+fn set_pixel_cpu(frame: &mut [u8], width: u32, bounds: Rect, x: u32, y: u32, color: Color) {
+    if !bounds.contains(x, y) {
         return;
     }
     let idx = ((y * width + x) * 4) as usize;
@@ -431,6 +1027,7 @@ pub struct GpuRenderer2d {
     instance_buf: wgpu::Buffer,
     instance_capacity: usize,
     instances: Vec<Instance>,
+    clip: ClipStack,
 }
 
 impl GpuRenderer2d {
@@ -558,6 +1155,7 @@ impl GpuRenderer2d {
             instance_buf,
             instance_capacity,
             instances: Vec::with_capacity(8192),
+            clip: ClipStack::default(),
         }
     }
 
@@ -636,6 +1234,9 @@ impl GpuRenderer2d {
     }
 
     fn push_rect_alpha(&mut self, rect: Rect, color: Color, alpha: f32) {
+        let Some(rect) = self.clip.apply(rect) else {
+            return;
+        };
         if rect.w == 0 || rect.h == 0 {
             return;
         }
@@ -646,6 +1247,69 @@ impl GpuRenderer2d {
         });
     }
 
+    /// Replays the queued instances over the clear color for one pixel, mirroring the
+    /// `ALPHA_BLENDING` pipeline so no GPU read-back is needed.
+    fn composite_pixel(&self, x: u32, y: u32) -> Color {
+        let cx = x as f32 + 0.5;
+        let cy = y as f32 + 0.5;
+        let mut rgb = [
+            DEFAULT_CLEAR_COLOR[0] as f32 / 255.0,
+            DEFAULT_CLEAR_COLOR[1] as f32 / 255.0,
+            DEFAULT_CLEAR_COLOR[2] as f32 / 255.0,
+        ];
+        for inst in &self.instances {
+            let [px, py] = inst.pos;
+            let [w, h] = inst.size;
+            if cx < px || cy < py || cx >= px + w || cy >= py + h {
+                continue;
+            }
+            let a = inst.color[3];
+            for (dst, src) in rgb.iter_mut().zip(inst.color) {
+                *dst = src * a + *dst * (1.0 - a);
+            }
+        }
+        let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [to_u8(rgb[0]), to_u8(rgb[1]), to_u8(rgb[2]), 255]
+    }
+
+    /// Rasterizes the queued instances into an RGBA buffer (`size.width * size.height * 4`).
+    ///
+    /// Uses the same blend math as `composite_pixel`, but walks each instance's covered pixels
+    /// once instead of replaying every instance per pixel, so a full-frame capture stays cheap.
+    pub fn capture_rgba(&self) -> Vec<u8> {
+        let w = self.size.width as usize;
+        let h = self.size.height as usize;
+        let clear = [
+            DEFAULT_CLEAR_COLOR[0] as f32 / 255.0,
+            DEFAULT_CLEAR_COLOR[1] as f32 / 255.0,
+            DEFAULT_CLEAR_COLOR[2] as f32 / 255.0,
+        ];
+        let mut rgb = vec![clear; w.saturating_mul(h)];
+        for inst in &self.instances {
+            let [px, py] = inst.pos;
+            let [iw, ih] = inst.size;
+            // Pixel centers inside `[px, px + iw) x [py, py + ih)`.
+            let x0 = ((px - 0.5).ceil().max(0.0) as usize).min(w);
+            let x1 = ((px + iw - 0.5).ceil().max(0.0) as usize).min(w);
+            let y0 = ((py - 0.5).ceil().max(0.0) as usize).min(h);
+            let y1 = ((py + ih - 0.5).ceil().max(0.0) as usize).min(h);
+            let a = inst.color[3];
+            for y in y0..y1 {
+                for dst in &mut rgb[y * w + x0..y * w + x1] {
+                    for (d, src) in dst.iter_mut().zip(inst.color) {
+                        *d = src * a + *d * (1.0 - a);
+                    }
+                }
+            }
+        }
+        let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let mut out = Vec::with_capacity(rgb.len() * 4);
+        for [r, g, b] in rgb {
+            out.extend_from_slice(&[to_u8(r), to_u8(g), to_u8(b), 255]);
+        }
+        out
+    }
+
     fn push_char(&mut self, x: u32, y: u32, ch: char, color: Color, scale: u32) {
         let rows = glyph_rows(ch);
         for (row, bits) in rows.into_iter().enumerate() {
@@ -666,12 +1330,32 @@ impl Renderer2d for GpuRenderer2d {
     fn begin_frame(&mut self, size: SurfaceSize) {
         self.size = size;
         self.instances.clear();
+        self.clip.clear();
+    }
+
+    fn push_clip(&mut self, rect: Rect) {
+        self.clip.push(rect);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clip.pop();
+    }
+
+    fn clip_rect(&self) -> Option<Rect> {
+        self.clip.top()
     }
 
     fn size(&self) -> SurfaceSize {
         self.size
     }
 
+    fn read_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.size.width || y >= self.size.height {
+            return None;
+        }
+        Some(self.composite_pixel(x, y))
+    }
+
     fn fill_rect(&mut self, rect: Rect, color: Color) {
         // Match CPU semantics: opaque fill.
         self.push_rect_alpha(rect, color, 1.0);
@@ -3,11 +3,14 @@ pub mod audio;
 pub mod app;
 pub mod editor;
 pub mod graphics;
+pub mod localization;
+pub mod logical_clock;
 pub mod pixels_renderer;
 pub mod profiling;
 pub mod recording;
 pub mod regression;
 pub mod render;
+pub mod replay_stream;
 pub mod slider;
 pub mod surface;
 pub mod ui;
@@ -15,27 +18,101 @@ pub mod ui_tree;
 pub mod view_tree;
 
 use std::{
+    collections::{BTreeMap, HashMap},
     fs,
-    io::{self, BufReader, BufWriter, Write},
+    io::{self, BufReader, BufWriter, Read, Write},
     path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
+/// Newest `RecordingMeta::format_version` this crate can load.
+pub const RECORDING_FORMAT_VERSION: u32 = 1;
+
+/// Provenance header saved with every recording. Files written before the header existed load
+/// with `format_version` 0 and empty fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingMeta {
+    pub format_version: u32,
+    pub game_id: String,
+    pub created_unix_ms: u64,
+    pub engine_version: String,
+    /// Free-form `key -> value` labels (scenario, player, build flavor, ...).
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+}
+
+impl RecordingMeta {
+    /// Header for a recording started now by this engine build.
+    pub fn new(game_id: impl Into<String>) -> Self {
+        let created_unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis().min(u64::MAX as u128) as u64)
+            .unwrap_or(0);
+        Self {
+            format_version: RECORDING_FORMAT_VERSION,
+            game_id: game_id.into(),
+            created_unix_ms,
+            engine_version: env!("CARGO_PKG_VERSION").to_string(),
+            tags: BTreeMap::new(),
+        }
+    }
+}
+
+/// A tag both recordings set to different values; the appended-to recording's value is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagConflict {
+    pub key: String,
+    pub kept: String,
+    pub dropped: String,
+}
+
+/// What [`TimeMachine::append`] (or [`concat`], across every seam) had to resolve while merging
+/// metadata.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetaMergeReport {
+    pub tag_conflicts: Vec<TagConflict>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TimeMachine<State> {
+    #[serde(default)]
+    meta: RecordingMeta,
     states: Vec<State>,
     frame: usize,
     #[serde(default = "default_record_every_n_frames")]
     record_every_n_frames: usize,
+    #[serde(default)]
+    bookmarks: HashMap<String, usize>,
+    /// Wall-clock elapsed time per recorded state. Either empty (untimed) or parallel to `states`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    timestamps: Vec<Duration>,
+    /// Optional input log: entry `i` holds the inputs applied on absolute step `i + 1`, encoded
+    /// as JSON so the log doesn't tie the recording to one input type.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    inputs: Vec<Vec<serde_json::Value>>,
 }
 
 impl<State> TimeMachine<State> {
     pub fn new(initial_state: State) -> Self {
+        Self::with_capacity(initial_state, 1)
+    }
+
+    /// Like `new`, but reserves room for `capacity` recorded states up front so long runs of a
+    /// known length don't reallocate mid-run. The history still starts with one state.
+    pub fn with_capacity(initial_state: State, capacity: usize) -> Self {
+        let mut states = Vec::with_capacity(capacity.max(1));
+        states.push(initial_state);
         Self {
-            states: vec![initial_state],
+            meta: RecordingMeta::new(""),
+            states,
             frame: 0,
             record_every_n_frames: default_record_every_n_frames(),
+            bookmarks: HashMap::new(),
+            timestamps: Vec::new(),
+            inputs: Vec::new(),
         }
     }
 
@@ -43,6 +120,14 @@ impl<State> TimeMachine<State> {
         self.frame
     }
 
+    pub fn meta(&self) -> &RecordingMeta {
+        &self.meta
+    }
+
+    pub fn set_meta(&mut self, meta: RecordingMeta) {
+        self.meta = meta;
+    }
+
     pub fn record_every_n_frames(&self) -> usize {
         self.record_every_n_frames.max(1)
     }
@@ -55,6 +140,11 @@ impl<State> TimeMachine<State> {
         self.states.len()
     }
 
+    /// Number of states the history can hold before reallocating.
+    pub fn capacity(&self) -> usize {
+        self.states.capacity()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.states.is_empty()
     }
@@ -71,6 +161,39 @@ impl<State> TimeMachine<State> {
         &self.states
     }
 
+    /// Walks the recorded states as `(frame_index, &state)` without cloning the history.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &State)> + '_ {
+        self.states.iter().enumerate()
+    }
+
+    /// Per-frame elapsed times; empty when the recording was made without timing.
+    pub fn timestamps(&self) -> &[Duration] {
+        &self.timestamps
+    }
+
+    pub fn timestamp_at(&self, frame: usize) -> Option<Duration> {
+        self.timestamps.get(frame).copied()
+    }
+
+    /// Raw input log, one entry per absolute step; empty when inputs weren't logged.
+    pub fn inputs(&self) -> &[Vec<serde_json::Value>] {
+        &self.inputs
+    }
+
+    /// Decodes the input log into per-step input lists, e.g. for `HeadlessRunner::replay_with`.
+    pub fn logged_inputs<Input: DeserializeOwned>(&self) -> io::Result<Vec<Vec<Input>>> {
+        self.inputs
+            .iter()
+            .map(|frame| {
+                frame
+                    .iter()
+                    .map(|value| Input::deserialize(value))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
     pub fn can_rewind(&self) -> bool {
         self.frame > 0
     }
@@ -100,32 +223,196 @@ impl<State> TimeMachine<State> {
         self.frame
     }
 
-    pub fn record(&mut self, state: State) -> usize {
+    /// Drops every recorded state after the cursor (and the timestamps/bookmarks pointing there).
+    pub fn truncate_future(&mut self) {
         if self.frame + 1 < self.states.len() {
             self.states.truncate(self.frame + 1);
+            self.timestamps.truncate(self.frame + 1);
+            self.prune_bookmarks();
         }
+    }
+
+    pub fn record(&mut self, state: State) -> usize {
+        self.truncate_future();
         self.states.push(state);
+        if !self.timestamps.is_empty() {
+            // Keep timed recordings parallel; untimed frames inherit the previous timestamp.
+            let last = self.timestamps.last().copied().unwrap_or_default();
+            self.timestamps.push(last);
+        }
         self.frame += 1;
         self.frame
     }
 
+    /// Records `state` along with the wall-clock time elapsed since the recording started.
+    ///
+    /// The first timed record turns timing on; earlier frames are backfilled with the most recent
+    /// known timestamp (zero for the initial state).
+    pub fn record_timed(&mut self, state: State, elapsed: Duration) -> usize {
+        if self.timestamps.len() < self.states.len() {
+            let fill = self.timestamps.last().copied().unwrap_or_default();
+            self.timestamps.resize(self.states.len(), fill);
+        }
+        let frame = self.record(state);
+        if let Some(last) = self.timestamps.last_mut() {
+            *last = elapsed;
+        }
+        frame
+    }
+
+    /// Names `frame` so it can be jumped back to later (e.g. "before_game_over").
+    ///
+    /// Frames past the end of the history are clamped to the last recorded frame. Re-using a
+    /// name moves the bookmark.
+    pub fn set_bookmark(&mut self, name: &str, frame: usize) {
+        let frame = frame.min(self.states.len().saturating_sub(1));
+        self.bookmarks.insert(name.to_string(), frame);
+    }
+
+    pub fn remove_bookmark(&mut self, name: &str) -> Option<usize> {
+        self.bookmarks.remove(name)
+    }
+
+    pub fn bookmark(&self, name: &str) -> Option<usize> {
+        self.bookmarks.get(name).copied()
+    }
+
+    pub fn bookmarks(&self) -> &HashMap<String, usize> {
+        &self.bookmarks
+    }
+
+    /// Seeks to a named bookmark, returning the new frame (or `None` if the name is unknown).
+    pub fn seek_bookmark(&mut self, name: &str) -> Option<usize> {
+        let frame = self.bookmark(name)?;
+        Some(self.seek(frame))
+    }
+
+    /// Drops bookmarks that no longer point into the recorded history.
+    fn prune_bookmarks(&mut self) {
+        let len = self.states.len();
+        self.bookmarks.retain(|_, frame| *frame < len);
+    }
+
     pub fn save_json_file(&self, path: impl AsRef<Path>) -> io::Result<()>
     where
         State: Serialize,
     {
-        let path = path.as_ref();
-        if let Some(parent) = path.parent() {
-            if !parent.as_os_str().is_empty() {
-                fs::create_dir_all(parent)?;
-            }
+        write_json_file(path.as_ref(), self)
+    }
+
+    /// Writes the recording as pretty JSON to any sink (an in-memory buffer, a socket, an HTTP
+    /// response body). Output is buffered and flushed before returning, exactly like
+    /// `save_json_file`.
+    pub fn save_json_writer(&self, writer: &mut impl Write) -> io::Result<()>
+    where
+        State: Serialize,
+    {
+        write_json(writer, self)
+    }
+
+    /// Saves the recording with every frame before the first state matching `keep_from` dropped.
+    ///
+    /// Useful for skipping idle menu frames before gameplay starts. The predicate sees each state
+    /// in order until it returns `true`; the live machine is left untouched. Returns an
+    /// `InvalidInput` error (and writes nothing) if no state matches.
+    pub fn save_json_file_trimmed<P>(&self, path: impl AsRef<Path>, keep_from: P) -> io::Result<()>
+    where
+        State: Serialize,
+        P: FnMut(&State) -> bool,
+    {
+        let Some(start) = self.states.iter().position(keep_from) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "trim predicate matched no frames; refusing to save an empty recording",
+            ));
+        };
+        let end = self.states.len().saturating_sub(1);
+        write_json_file(path.as_ref(), &self.range_view(start, end)?)
+    }
+
+    /// Saves only the inclusive frame range `[start, end]`.
+    ///
+    /// `end` is clamped to the last recorded frame. The saved `frame` and bookmarks are rebased so
+    /// the file loads as a standalone recording.
+    pub fn save_json_file_range(
+        &self,
+        path: impl AsRef<Path>,
+        start: usize,
+        end: usize,
+    ) -> io::Result<()>
+    where
+        State: Serialize,
+    {
+        write_json_file(path.as_ref(), &self.range_view(start, end)?)
+    }
+
+    /// Copies the inclusive frame range `[start, end]` into a standalone recording.
+    ///
+    /// Indices clamp to the recorded history (an inverted range yields just frame `end`), so the
+    /// result always has at least one state. The cursor is reset to frame 0; bookmarks inside the
+    /// range are rebased and `record_every_n_frames` is preserved.
+    pub fn slice(&self, start: usize, end: usize) -> TimeMachine<State>
+    where
+        State: Clone,
+    {
+        let end = end.min(self.states.len().saturating_sub(1));
+        let start = start.min(end);
+        let bookmarks = self
+            .bookmarks
+            .iter()
+            .filter(|(_, frame)| (start..=end).contains(*frame))
+            .map(|(name, frame)| (name.clone(), frame - start))
+            .collect();
+        TimeMachine {
+            meta: self.meta.clone(),
+            states: self.states[start..=end].to_vec(),
+            frame: 0,
+            record_every_n_frames: self.record_every_n_frames,
+            bookmarks,
+            timestamps: self
+                .timestamps
+                .get(start..=end)
+                .map(<[Duration]>::to_vec)
+                .unwrap_or_default(),
+            inputs: self.input_range(start, end).to_vec(),
         }
+    }
 
-        let file = fs::File::create(path)?;
-        let mut writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(&mut writer, self)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-        writer.flush()?;
-        Ok(())
+    /// Input-log entries for the steps between recorded frames `start` and `end`.
+    fn input_range(&self, start: usize, end: usize) -> &[Vec<serde_json::Value>] {
+        let n = self.record_every_n_frames();
+        let from = start.saturating_mul(n).min(self.inputs.len());
+        let to = end.saturating_mul(n).clamp(from, self.inputs.len());
+        &self.inputs[from..to]
+    }
+
+    fn range_view(&self, start: usize, end: usize) -> io::Result<TimeMachineView<'_, State>> {
+        let end = end.min(self.states.len().saturating_sub(1));
+        if self.states.is_empty() || start > end {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "frame range [{start}, {end}] is empty (len {})",
+                    self.states.len()
+                ),
+            ));
+        }
+
+        let bookmarks = self
+            .bookmarks
+            .iter()
+            .filter(|(_, frame)| (start..=end).contains(*frame))
+            .map(|(name, frame)| (name.clone(), frame - start))
+            .collect();
+        Ok(TimeMachineView {
+            meta: &self.meta,
+            states: &self.states[start..=end],
+            frame: self.frame.clamp(start, end) - start,
+            record_every_n_frames: self.record_every_n_frames,
+            bookmarks,
+            timestamps: self.timestamps.get(start..=end).unwrap_or_default(),
+            inputs: self.input_range(start, end),
+        })
     }
 
     pub fn load_json_file(path: impl AsRef<Path>) -> io::Result<Self>
@@ -134,45 +421,263 @@ impl<State> TimeMachine<State> {
     {
         let path = path.as_ref();
         let file = fs::File::open(path)?;
-        let reader = BufReader::new(file);
+        Self::read_json(file, &path.display().to_string())
+    }
+
+    /// Loads a recording from any reader, with the same version check, bookmark pruning and
+    /// validation as `load_json_file`.
+    pub fn load_json_reader(reader: impl Read) -> io::Result<Self>
+    where
+        State: DeserializeOwned,
+    {
+        Self::read_json(reader, "from reader")
+    }
+
+    /// Shared loader; `source` names the input in version errors.
+    fn read_json(reader: impl Read, source: &str) -> io::Result<Self>
+    where
+        State: DeserializeOwned,
+    {
+        let reader = BufReader::new(reader);
         let mut tm: Self =
             serde_json::from_reader(reader).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
-        if tm.states.is_empty() {
+        if tm.meta.format_version > RECORDING_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "recording {} uses format version {} (engine {}), but this build only understands up to version {}",
+                    source,
+                    tm.meta.format_version,
+                    tm.meta.engine_version,
+                    RECORDING_FORMAT_VERSION
+                ),
+            ));
+        }
+
+        if tm.record_every_n_frames == 0 {
+            tm.record_every_n_frames = default_record_every_n_frames();
+        }
+        tm.prune_bookmarks();
+        tm.validate()?;
+
+        Ok(tm)
+    }
+
+    /// Checks the invariants `load_json_file` enforces on a freshly loaded recording.
+    pub fn validate(&self) -> io::Result<()> {
+        if self.states.is_empty() {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 "timemachine recording has no states",
             ));
         }
-        if tm.frame >= tm.states.len() {
+        if self.frame >= self.states.len() {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!(
                     "timemachine recording frame {} out of bounds (len {})",
-                    tm.frame,
-                    tm.states.len()
+                    self.frame,
+                    self.states.len()
+                ),
+            ));
+        }
+        if self.record_every_n_frames == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "timemachine recording has record_every_n_frames 0",
+            ));
+        }
+        if !self.timestamps.is_empty() && self.timestamps.len() != self.states.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "timemachine recording has {} timestamps for {} states",
+                    self.timestamps.len(),
+                    self.states.len()
                 ),
             ));
         }
+        if let Some((name, frame)) = self
+            .bookmarks
+            .iter()
+            .find(|(_, frame)| **frame >= self.states.len())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "timemachine bookmark {name:?} points at frame {frame} (len {})",
+                    self.states.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
 
-        if tm.record_every_n_frames == 0 {
-            tm.record_every_n_frames = default_record_every_n_frames();
+impl<State: PartialEq> TimeMachine<State> {
+    /// Appends `other`'s history after this recording and moves the cursor to the new end.
+    ///
+    /// If `other` starts with the state this recording ends on, that duplicate is skipped. Bookmarks
+    /// and timestamps from `other` are shifted onto the combined timeline (bookmarks from `other`
+    /// win on name clashes). Both recordings must use the same `record_every_n_frames`.
+    ///
+    /// Input logs are joined only when both recordings have one and they meet at a shared state;
+    /// otherwise the steps across the seam are unknown and the combined log is dropped.
+    ///
+    /// Metadata merges as follows: `format_version` must match (anything else is an error and
+    /// leaves `self` untouched), tags are unioned with this recording's value winning on conflicts
+    /// (reported in the returned [`MetaMergeReport`]), and the earliest non-zero creation time is
+    /// kept.
+    pub fn append(&mut self, other: TimeMachine<State>) -> io::Result<MetaMergeReport> {
+        if other.meta.format_version != self.meta.format_version {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot append recording with format version {} to one with version {}",
+                    other.meta.format_version, self.meta.format_version
+                ),
+            ));
+        }
+        if other.record_every_n_frames != self.record_every_n_frames {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot append recording with record_every_n_frames {} to one with {}",
+                    other.record_every_n_frames, self.record_every_n_frames
+                ),
+            ));
         }
 
-        Ok(tm)
+        let skip =
+            usize::from(self.states.last().is_some() && self.states.last() == other.states.first());
+        // Frame in `other` at index `skip` lands at `base` in the combined history.
+        let base = self.states.len();
+        let time_offset = self.timestamps.last().copied().unwrap_or_default();
+
+        if !self.timestamps.is_empty() || !other.timestamps.is_empty() {
+            self.timestamps.resize(self.states.len(), time_offset);
+            let other_len = other.states.len();
+            self.timestamps.extend((skip..other_len).map(|i| {
+                time_offset.saturating_add(other.timestamps.get(i).copied().unwrap_or_default())
+            }));
+        }
+        if skip == 1 && !self.inputs.is_empty() && !other.inputs.is_empty() {
+            self.inputs.extend(other.inputs);
+        } else {
+            self.inputs.clear();
+        }
+        for (name, frame) in other.bookmarks {
+            // A bookmark on a skipped shared first state lands on our copy of it at `base - 1`.
+            self.bookmarks.insert(name, base + frame - skip);
+        }
+        self.states.extend(other.states.into_iter().skip(skip));
+        self.frame = self.states.len().saturating_sub(1);
+
+        let mut report = MetaMergeReport::default();
+        for (key, value) in other.meta.tags {
+            match self.meta.tags.get(&key) {
+                Some(kept) if *kept != value => report.tag_conflicts.push(TagConflict {
+                    key,
+                    kept: kept.clone(),
+                    dropped: value,
+                }),
+                Some(_) => {}
+                None => {
+                    self.meta.tags.insert(key, value);
+                }
+            }
+        }
+        if other.meta.created_unix_ms != 0
+            && (self.meta.created_unix_ms == 0
+                || other.meta.created_unix_ms < self.meta.created_unix_ms)
+        {
+            self.meta.created_unix_ms = other.meta.created_unix_ms;
+        }
+        Ok(report)
     }
 }
 
+/// Stitches recordings together in order via [`TimeMachine::append`].
+///
+/// Returns an `InvalidInput` error if `recordings` is empty or their format versions or
+/// `record_every_n_frames` differ. Tag conflicts from every seam are collected, in order, into the
+/// returned [`MetaMergeReport`] for the caller to report.
+pub fn concat<State: PartialEq>(
+    recordings: Vec<TimeMachine<State>>,
+) -> io::Result<(TimeMachine<State>, MetaMergeReport)> {
+    let mut iter = recordings.into_iter();
+    let Some(mut merged) = iter.next() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot concatenate an empty list of recordings",
+        ));
+    };
+    let mut report = MetaMergeReport::default();
+    for tm in iter {
+        report
+            .tag_conflicts
+            .extend(merged.append(tm)?.tag_conflicts);
+    }
+    Ok((merged, report))
+}
+
 fn default_record_every_n_frames() -> usize {
     1
 }
 
+/// Borrowed, serialize-only form of a `TimeMachine` sub-range.
+///
+/// Field names must stay in sync with `TimeMachine` so the output loads via `load_json_file`.
+#[derive(Serialize)]
+struct TimeMachineView<'a, State> {
+    meta: &'a RecordingMeta,
+    states: &'a [State],
+    frame: usize,
+    record_every_n_frames: usize,
+    bookmarks: HashMap<String, usize>,
+    #[serde(skip_serializing_if = "<[Duration]>::is_empty")]
+    timestamps: &'a [Duration],
+    #[serde(skip_serializing_if = "<[Vec<serde_json::Value>]>::is_empty")]
+    inputs: &'a [Vec<serde_json::Value>],
+}
+
+fn write_json_file(path: &Path, value: &impl Serialize) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    let mut file = fs::File::create(path)?;
+    write_json(&mut file, value)
+}
+
+fn write_json(sink: &mut impl Write, value: &impl Serialize) -> io::Result<()> {
+    let mut writer = BufWriter::new(sink);
+    serde_json::to_writer_pretty(&mut writer, value)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    writer.flush()?;
+    Ok(())
+}
+
 pub trait GameLogic {
     type State: Clone;
     type Input;
 
     fn initial_state(&self) -> Self::State;
     fn step(&self, state: &Self::State, input: Self::Input) -> Self::State;
+
+    /// Identifier written into `RecordingMeta::game_id` for recordings of this game.
+    fn game_id(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Resets any RNG seed held by the logic itself (used by `initial_state` and future runs).
+    fn reseed(&mut self, _seed: u64) {}
+
+    /// Resets RNG carried inside `state` so steps after this point depend only on `seed`.
+    fn reseed_state(&self, _state: &mut Self::State, _seed: u64) {}
 }
 
 pub trait RecordableState {
@@ -189,35 +694,106 @@ pub trait ReplayableState: Sized {
     fn replay_load(&self, path: &Path) -> io::Result<Self>;
 }
 
+/// Result of [`HeadlessRunner::run_until`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunUntil {
+    /// Timemachine frame reached.
+    pub frame: usize,
+    /// Number of steps taken.
+    pub steps: usize,
+    /// `true` if the stop predicate fired, `false` if `max_steps` was hit first.
+    pub stopped: bool,
+}
+
 #[derive(Debug)]
 pub struct HeadlessRunner<G: GameLogic> {
     game: G,
     timemachine: TimeMachine<G::State>,
     state: G::State,
     absolute_frame: usize,
+    clock_base: Duration,
+    /// Set only by `with_timestamps`; untimed runners never read the clock.
+    clock_start: Option<Instant>,
+    queued_inputs: Vec<G::Input>,
+    input_encoder: Option<fn(&G::Input) -> serde_json::Value>,
 }
 
 impl<G: GameLogic> HeadlessRunner<G> {
     pub fn new(game: G) -> Self {
+        Self::with_capacity(game, 1)
+    }
+
+    /// Creates a runner whose timemachine preallocates `capacity` recorded states.
+    pub fn with_capacity(game: G, capacity: usize) -> Self {
         let initial_state = game.initial_state();
+        let mut timemachine = TimeMachine::with_capacity(initial_state.clone(), capacity);
+        timemachine.set_meta(RecordingMeta::new(game.game_id()));
         Self {
             game,
-            timemachine: TimeMachine::new(initial_state.clone()),
+            timemachine,
             state: initial_state,
             absolute_frame: 0,
+            clock_base: Duration::ZERO,
+            clock_start: None,
+            queued_inputs: Vec::new(),
+            input_encoder: None,
         }
     }
 
+    /// Re-simulates a logged input stream (see `TimeMachine::logged_inputs`) under `game`, one
+    /// `step_frame` per entry. Use it to check an old session against a patched `GameLogic`.
+    pub fn replay_with<F>(game: G, frames: F) -> Self
+    where
+        F: IntoIterator,
+        F::Item: IntoIterator<Item = G::Input>,
+    {
+        let mut runner = Self::new(game);
+        for inputs in frames {
+            runner.step_frame(inputs);
+        }
+        runner
+    }
+
+    /// Also log every stepped input into the recording so it can be replayed with
+    /// `replay_with`. Enable before the first step; earlier steps are logged as empty. Stepping
+    /// panics if an input fails to serialize rather than logging a placeholder.
+    pub fn with_input_log(mut self) -> Self
+    where
+        G::Input: Serialize,
+    {
+        self.input_encoder = Some(|input| {
+            serde_json::to_value(input).expect("input must serialize for the input log")
+        });
+        self
+    }
+
+    /// Stamp every recorded state with the wall-clock time since the runner started (see
+    /// `TimeMachine::timestamps`). Off by default: timestamps differ run to run, so untimed
+    /// recordings of the same inputs are byte-identical.
+    pub fn with_timestamps(mut self) -> Self {
+        self.clock_start = Some(Instant::now());
+        self
+    }
+
     pub fn from_timemachine(game: G, timemachine: TimeMachine<G::State>) -> Self {
         let state = timemachine.state().clone();
         let absolute_frame = timemachine
             .frame()
             .saturating_mul(timemachine.record_every_n_frames());
+        let clock_base = timemachine
+            .timestamp_at(timemachine.frame())
+            .unwrap_or_default();
+        // A timed recording keeps getting timestamps when it is continued.
+        let clock_start = (!timemachine.timestamps().is_empty()).then(Instant::now);
         Self {
             game,
             timemachine,
             state,
             absolute_frame,
+            clock_base,
+            clock_start,
+            queued_inputs: Vec::new(),
+            input_encoder: None,
         }
     }
 
@@ -253,15 +829,77 @@ impl<G: GameLogic> HeadlessRunner<G> {
         &self.timemachine
     }
 
+    /// Applies one input as one whole frame. Same path as `step_profiled` with a
+    /// `NoopProfiler`, so no timing overhead: unless `with_timestamps` was set, no clock is read.
     pub fn step(&mut self, input: G::Input) -> usize {
-        let next_state = self.game.step(&self.state, input);
+        self.step_profiled(input, &mut profiling::NoopProfiler)
+    }
+
+    /// Applies `inputs` in order within a single frame: each input sees the state left by the
+    /// previous one, `absolute_frame` advances once, and at most one state is recorded. An empty
+    /// iterator leaves the runner untouched.
+    ///
+    /// Replaying the same per-frame input lists through `step_frame` reproduces the recording
+    /// exactly, including the ordering of actions that landed on the same frame.
+    pub fn step_frame<I>(&mut self, inputs: I) -> usize
+    where
+        I: IntoIterator<Item = G::Input>,
+    {
+        let inputs: Vec<G::Input> = inputs.into_iter().collect();
+        if inputs.is_empty() {
+            return self.frame();
+        }
+        let logged = self.encode_inputs(&inputs);
+        let mut next_state = self.state.clone();
+        for input in inputs {
+            next_state = self.game.step(&next_state, input);
+        }
+        self.commit_frame(next_state, logged)
+    }
+
+    /// Queues `input` for the next `flush_step`, after any inputs already queued this frame.
+    pub fn queue_input(&mut self, input: G::Input) {
+        self.queued_inputs.push(input);
+    }
+
+    /// Inputs waiting for the next `flush_step`, in the order they will be applied.
+    pub fn queued_inputs(&self) -> &[G::Input] {
+        &self.queued_inputs
+    }
+
+    /// Applies every queued input as one frame via `step_frame` and clears the queue.
+    pub fn flush_step(&mut self) -> usize {
+        let inputs = std::mem::take(&mut self.queued_inputs);
+        self.step_frame(inputs)
+    }
+
+    fn encode_inputs(&self, inputs: &[G::Input]) -> Option<Vec<serde_json::Value>> {
+        let encode = self.input_encoder?;
+        Some(inputs.iter().map(encode).collect())
+    }
+
+    fn commit_frame(
+        &mut self,
+        next_state: G::State,
+        logged: Option<Vec<serde_json::Value>>,
+    ) -> usize {
+        // Stepping after a rewind branches the log just like the state history, whether or not
+        // this runner logs inputs itself.
+        let log = &mut self.timemachine.inputs;
+        log.truncate(self.absolute_frame);
+        if let Some(logged) = logged {
+            log.resize(self.absolute_frame, Vec::new());
+            log.push(logged);
+        }
         self.state = next_state.clone();
         self.absolute_frame = self.absolute_frame.saturating_add(1);
 
-        if self.absolute_frame % self.timemachine.record_every_n_frames() == 0 {
-            self.timemachine.record(next_state)
-        } else {
+        if self.absolute_frame % self.timemachine.record_every_n_frames() != 0 {
             self.timemachine.frame()
+        } else if let Some(elapsed) = self.clock_elapsed() {
+            self.timemachine.record_timed(next_state, elapsed)
+        } else {
+            self.timemachine.record(next_state)
         }
     }
 
@@ -270,26 +908,25 @@ impl<G: GameLogic> HeadlessRunner<G> {
         input: G::Input,
         profiler: &mut P,
     ) -> usize {
-        use std::time::Instant;
+        if !profiler.is_enabled() {
+            let logged = self.encode_inputs(std::slice::from_ref(&input));
+            let next_state = self.game.step(&self.state, input);
+            return self.commit_frame(next_state, logged);
+        }
 
         let total_start = Instant::now();
 
+        let logged = self.encode_inputs(std::slice::from_ref(&input));
         let step_start = Instant::now();
         let next_state = self.game.step(&self.state, input);
         let step_dt = step_start.elapsed();
 
         let record_start = Instant::now();
-        self.state = next_state.clone();
-        self.absolute_frame = self.absolute_frame.saturating_add(1);
-        let frame = if self.absolute_frame % self.timemachine.record_every_n_frames() == 0 {
-            self.timemachine.record(next_state)
-        } else {
-            self.timemachine.frame()
-        };
+        let frame = self.commit_frame(next_state, logged);
         let record_dt = if self.absolute_frame % self.timemachine.record_every_n_frames() == 0 {
             record_start.elapsed()
         } else {
-            std::time::Duration::ZERO
+            Duration::ZERO
         };
 
         let total_dt = total_start.elapsed();
@@ -305,6 +942,15 @@ impl<G: GameLogic> HeadlessRunner<G> {
         frame
     }
 
+    /// Reseeds the logic and the live state's RNG.
+    ///
+    /// Call after `rewind`/`seek` and before stepping a new branch so the branch is reproducible
+    /// regardless of which RNG position the rewound state carried.
+    pub fn reseed(&mut self, seed: u64) {
+        self.game.reseed(seed);
+        self.game.reseed_state(&mut self.state, seed);
+    }
+
     pub fn run<I>(&mut self, inputs: I) -> usize
     where
         I: IntoIterator<Item = G::Input>,
@@ -316,25 +962,124 @@ impl<G: GameLogic> HeadlessRunner<G> {
         last_frame
     }
 
+    /// Steps with inputs from `input_fn` until `stop` holds for the live state or `max_steps`
+    /// steps have run. `stop` is checked before the first step, so a satisfied predicate runs
+    /// nothing. Recording follows `record_every_n_frames` exactly as `step` does.
+    pub fn run_until<I, F>(&mut self, mut input_fn: I, stop: F, max_steps: usize) -> RunUntil
+    where
+        I: FnMut(&G::State) -> G::Input,
+        F: Fn(&G::State) -> bool,
+    {
+        let mut steps = 0;
+        while !stop(&self.state) {
+            if steps == max_steps {
+                return RunUntil {
+                    frame: self.frame(),
+                    steps,
+                    stopped: false,
+                };
+            }
+            let input = input_fn(&self.state);
+            self.step(input);
+            steps += 1;
+        }
+        RunUntil {
+            frame: self.frame(),
+            steps,
+            stopped: true,
+        }
+    }
+
+    /// Rolls back to recorded frame `from_frame`, discards everything after it, and steps
+    /// `inputs` in its place. Returns the new head frame.
+    ///
+    /// Unlike `rewind` followed by `step`, the old future is dropped even if `inputs` is too
+    /// short to record a new state. Every input is stepped (not just the recorded ones), so with
+    /// `record_every_n_frames > 1` the result matches a straight-line run of the same inputs.
+    pub fn resimulate(&mut self, from_frame: usize, inputs: &[G::Input]) -> usize
+    where
+        G::Input: Clone,
+    {
+        self.seek(from_frame);
+        self.timemachine.truncate_future();
+        self.timemachine.inputs.truncate(self.absolute_frame);
+        for input in inputs {
+            self.step(input.clone());
+        }
+        self.frame()
+    }
+
+    /// Replaces the recorded state at `frame` and makes it the live state, dropping every later
+    /// frame (and its logged inputs) like stepping after a rewind. Errors if `frame` is past the
+    /// recorded history.
+    pub fn overwrite_frame(&mut self, frame: usize, state: G::State) -> io::Result<usize> {
+        let len = self.timemachine.len();
+        if frame >= len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("frame {frame} is out of range (history len {len})"),
+            ));
+        }
+        self.timemachine.seek(frame);
+        self.timemachine.truncate_future();
+        self.timemachine.states[frame] = state;
+        self.sync_to_timemachine_cursor();
+        self.timemachine.inputs.truncate(self.absolute_frame);
+        Ok(frame)
+    }
+
     pub fn rewind(&mut self, frames: usize) -> usize {
         let frame = self.timemachine.rewind(frames);
-        self.state = self.timemachine.state().clone();
-        self.absolute_frame = frame.saturating_mul(self.timemachine.record_every_n_frames());
+        self.sync_to_timemachine_cursor();
         frame
     }
 
     pub fn forward(&mut self, frames: usize) -> usize {
         let frame = self.timemachine.forward(frames);
-        self.state = self.timemachine.state().clone();
-        self.absolute_frame = frame.saturating_mul(self.timemachine.record_every_n_frames());
+        self.sync_to_timemachine_cursor();
         frame
     }
 
     pub fn seek(&mut self, frame: usize) -> usize {
         let frame = self.timemachine.seek(frame);
+        self.sync_to_timemachine_cursor();
+        frame
+    }
+
+    /// Re-derives the live state, absolute frame, and record clock from the timemachine cursor.
+    fn sync_to_timemachine_cursor(&mut self) {
+        let frame = self.timemachine.frame();
         self.state = self.timemachine.state().clone();
         self.absolute_frame = frame.saturating_mul(self.timemachine.record_every_n_frames());
-        frame
+        self.clock_base = self.timemachine.timestamp_at(frame).unwrap_or_default();
+        if self.clock_start.is_some() {
+            self.clock_start = Some(Instant::now());
+        }
+    }
+
+    /// Record-clock reading for timed runners; `None` (without touching the clock) otherwise.
+    fn clock_elapsed(&self) -> Option<Duration> {
+        let start = self.clock_start?;
+        Some(self.clock_base.saturating_add(start.elapsed()))
+    }
+
+    pub fn set_bookmark(&mut self, name: &str, frame: usize) {
+        self.timemachine.set_bookmark(name, frame);
+    }
+
+    /// Bookmarks the current recorded frame.
+    pub fn bookmark_current(&mut self, name: &str) {
+        let frame = self.timemachine.frame();
+        self.timemachine.set_bookmark(name, frame);
+    }
+
+    pub fn bookmark(&self, name: &str) -> Option<usize> {
+        self.timemachine.bookmark(name)
+    }
+
+    pub fn seek_bookmark(&mut self, name: &str) -> Option<usize> {
+        let frame = self.timemachine.bookmark(name)?;
+        Some(self.seek(frame))
     }
 }
 
@@ -403,6 +1148,68 @@ mod tests {
         assert_eq!(tm.frame(), 2);
     }
 
+    #[test]
+    fn timemachine_bookmarks_seek_and_prune_on_branch() {
+        let mut tm = TimeMachine::new(0);
+        tm.record(1);
+        tm.record(2);
+        tm.record(3);
+        tm.set_bookmark("start", 0);
+        tm.set_bookmark("late", 3);
+        tm.set_bookmark("clamped", 99);
+        assert_eq!(tm.bookmark("clamped"), Some(3));
+
+        assert_eq!(tm.seek_bookmark("start"), Some(0));
+        assert_eq!(tm.state(), &0);
+        assert_eq!(tm.seek_bookmark("missing"), None);
+        assert_eq!(tm.frame(), 0);
+
+        // Branching from frame 1 discards frames 2..=3 and the bookmarks pointing at them.
+        tm.seek(1);
+        tm.record(42);
+        assert_eq!(tm.bookmark("start"), Some(0));
+        assert_eq!(tm.bookmark("late"), None);
+        assert_eq!(tm.bookmark("clamped"), None);
+    }
+
+    #[test]
+    fn timemachine_iter_and_timestamps_stay_parallel_to_states() {
+        let mut tm = TimeMachine::new(10);
+        tm.record(11);
+        assert!(tm.timestamps().is_empty());
+
+        tm.record_timed(12, Duration::from_millis(30));
+        assert_eq!(
+            tm.timestamps(),
+            &[Duration::ZERO, Duration::ZERO, Duration::from_millis(30)]
+        );
+        tm.record(13);
+        assert_eq!(tm.timestamp_at(3), Some(Duration::from_millis(30)));
+
+        tm.seek(1);
+        tm.record_timed(99, Duration::from_millis(50));
+        assert_eq!(tm.timestamps().len(), tm.len());
+        assert_eq!(
+            tm.iter().collect::<Vec<_>>(),
+            vec![(0, &10), (1, &11), (2, &99)]
+        );
+        assert_eq!(tm.timestamp_at(2), Some(Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn with_capacity_preallocates_without_changing_length() {
+        let mut tm = TimeMachine::with_capacity(0, 64);
+        assert_eq!(tm.len(), 1);
+        assert!(tm.capacity() >= 64);
+
+        let before = tm.capacity();
+        for v in 1..64 {
+            tm.record(v);
+        }
+        assert_eq!(tm.len(), 64);
+        assert_eq!(tm.capacity(), before);
+    }
+
     #[test]
     fn runner_steps_and_seeks() {
         struct Additive;
@@ -421,6 +1228,11 @@ mod tests {
         }
 
         let mut runner = HeadlessRunner::new(Additive);
+        assert!(runner.timemachine().meta().game_id.ends_with("Additive"));
+        assert_eq!(
+            runner.timemachine().meta().format_version,
+            RECORDING_FORMAT_VERSION
+        );
         runner.run([1, 2, 3]);
         assert_eq!(runner.frame(), 3);
         assert_eq!(runner.state(), &6);
@@ -430,6 +1242,168 @@ mod tests {
 
         runner.forward(1);
         assert_eq!(runner.state(), &3);
+        assert!(runner.timemachine().timestamps().is_empty());
+
+        let mut timed = HeadlessRunner::new(Additive).with_timestamps();
+        timed.run([1, 2]);
+        assert_eq!(
+            timed.timemachine().timestamps().len(),
+            timed.timemachine().len()
+        );
+    }
+
+    #[test]
+    fn runner_run_until_reports_predicate_or_step_cap() {
+        struct Additive;
+
+        impl GameLogic for Additive {
+            type State = i32;
+            type Input = i32;
+
+            fn initial_state(&self) -> Self::State {
+                0
+            }
+
+            fn step(&self, state: &Self::State, input: Self::Input) -> Self::State {
+                *state + input
+            }
+        }
+
+        let mut runner = HeadlessRunner::new(Additive);
+        runner.set_record_every_n_frames(2);
+        let out = runner.run_until(|_| 3, |s| *s >= 10, 100);
+        assert_eq!(
+            out,
+            RunUntil {
+                frame: 2,
+                steps: 4,
+                stopped: true
+            }
+        );
+        assert_eq!(runner.state(), &12);
+
+        let out = runner.run_until(|_| 0, |s| *s > 100, 5);
+        assert!(!out.stopped);
+        assert_eq!(out.steps, 5);
+
+        let out = runner.run_until(|_| 1, |_| true, 5);
+        assert_eq!(out.steps, 0);
+        assert!(out.stopped);
+    }
+
+    #[test]
+    fn runner_resimulate_matches_straight_line_run() {
+        struct Mix;
+
+        impl GameLogic for Mix {
+            type State = i64;
+            type Input = i64;
+
+            fn initial_state(&self) -> Self::State {
+                1
+            }
+
+            fn step(&self, state: &Self::State, input: Self::Input) -> Self::State {
+                state.wrapping_mul(31).wrapping_add(input)
+            }
+        }
+
+        let inputs: Vec<i64> = (1..=10).collect();
+        let mut straight = HeadlessRunner::new(Mix);
+        straight.set_record_every_n_frames(3);
+        straight.run(inputs.iter().copied());
+
+        let mut rolled = HeadlessRunner::new(Mix);
+        rolled.set_record_every_n_frames(3);
+        rolled.run([1, 2, 3, 4, 5, 6, 70, 80, 90, 100, 110, 120, 130]);
+        assert_eq!(rolled.history().len(), 5);
+
+        // Recorded frame 1 is absolute frame 3; the remaining 7 inputs record frames 2 and 3.
+        let head = rolled.resimulate(1, &inputs[3..]);
+        assert_eq!(head, 3);
+        assert_eq!(rolled.absolute_frame(), 10);
+        assert_eq!(rolled.state(), straight.state());
+        assert_eq!(rolled.history(), straight.history());
+
+        // Too few inputs to record still drops the old future.
+        rolled.resimulate(1, &inputs[3..4]);
+        assert_eq!(rolled.history().len(), 2);
+        assert_eq!(rolled.absolute_frame(), 4);
+    }
+
+    #[test]
+    fn runner_overwrite_frame_branches_from_the_new_state() {
+        struct Add;
+
+        impl GameLogic for Add {
+            type State = i64;
+            type Input = i64;
+
+            fn initial_state(&self) -> Self::State {
+                0
+            }
+
+            fn step(&self, state: &Self::State, input: Self::Input) -> Self::State {
+                state + input
+            }
+        }
+
+        let mut runner = HeadlessRunner::new(Add);
+        runner.run([1, 2, 3]);
+        assert_eq!(runner.history(), &[0, 1, 3, 6]);
+
+        assert!(runner.overwrite_frame(4, 100).is_err());
+        assert_eq!(runner.history(), &[0, 1, 3, 6]);
+
+        assert_eq!(runner.overwrite_frame(1, 100).unwrap(), 1);
+        assert_eq!(runner.history(), &[0, 100]);
+        assert_eq!(runner.state(), &100);
+        assert_eq!(runner.absolute_frame(), 1);
+
+        runner.step(5);
+        assert_eq!(runner.history(), &[0, 100, 105]);
+    }
+
+    #[test]
+    fn runner_flush_step_applies_queued_inputs_in_order_as_one_frame() {
+        struct Mix;
+
+        impl GameLogic for Mix {
+            type State = i64;
+            type Input = i64;
+
+            fn initial_state(&self) -> Self::State {
+                1
+            }
+
+            fn step(&self, state: &Self::State, input: Self::Input) -> Self::State {
+                state.wrapping_mul(31).wrapping_add(input)
+            }
+        }
+
+        let frames: Vec<Vec<i64>> = vec![vec![1, 2], vec![], vec![3], vec![5, 4, 6]];
+        let mut live = HeadlessRunner::new(Mix);
+        for inputs in &frames {
+            for &input in inputs {
+                live.queue_input(input);
+            }
+            assert_eq!(live.queued_inputs(), inputs.as_slice());
+            live.flush_step();
+            assert!(live.queued_inputs().is_empty());
+        }
+        assert_eq!(live.frame(), 3);
+        assert_eq!(live.absolute_frame(), 3);
+        assert_eq!(live.history()[1], (31 + 1) * 31 + 2);
+
+        let mut replay = HeadlessRunner::new(Mix);
+        for inputs in frames.iter().cloned() {
+            replay.step_frame(inputs);
+        }
+        assert_eq!(replay.history(), live.history());
+
+        let mut swapped = HeadlessRunner::new(Mix);
+        swapped.step_frame([2, 1]);
+        assert_ne!(swapped.state(), &live.history()[1]);
     }
 
     #[test]
@@ -473,6 +1447,11 @@ mod tests {
         let t = capture.timings[0];
         assert!(t.total >= t.step);
         assert!(t.total >= t.record);
+
+        let frame = runner.step_profiled(2, &mut crate::profiling::NoopProfiler);
+        assert_eq!(frame, 2);
+        assert_eq!(runner.state(), &3);
+        assert_eq!(capture.frames, vec![1]);
     }
 
     #[test]
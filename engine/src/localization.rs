@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+/// Maps UI label keys (e.g. `"menu.start"`) to display text.
+///
+/// Draw code asks for keys instead of hardcoding English, so a UI can be translated by swapping
+/// the localizer injected through `AppConfig::localizer`.
+pub trait Localizer: Send + Sync {
+    /// Text for `key`, or `None` if this localizer has no entry for it.
+    fn lookup(&self, key: &str) -> Option<String>;
+
+    /// Text for `key`, falling back to the key itself so a missing translation shows up on screen
+    /// instead of failing.
+    fn label(&self, key: &str) -> String {
+        self.lookup(key).unwrap_or_else(|| key.to_string())
+    }
+}
+
+/// Table-backed localizer; an empty table renders every key as-is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableLocalizer {
+    labels: BTreeMap<String, String>,
+}
+
+impl TableLocalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_pairs<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self {
+            labels: pairs
+                .into_iter()
+                .map(|(key, text)| (key.into(), text.into()))
+                .collect(),
+        }
+    }
+
+    /// Adds or replaces the text for `key`.
+    pub fn with_label(mut self, key: impl Into<String>, text: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), text.into());
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+impl Localizer for TableLocalizer {
+    fn lookup(&self, key: &str) -> Option<String> {
+        self.labels.get(key).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_keys_fall_back_to_the_key() {
+        let labels = TableLocalizer::from_pairs([("menu.start", "START")])
+            .with_label("menu.quit", "QUITTER");
+
+        assert_eq!(labels.label("menu.start"), "START");
+        assert_eq!(labels.label("menu.quit"), "QUITTER");
+        assert_eq!(labels.lookup("menu.missing"), None);
+        assert_eq!(labels.label("menu.missing"), "menu.missing");
+        assert_eq!(TableLocalizer::new().label("hud.level"), "hud.level");
+    }
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Fixed-step logical time for `GameLogic` that needs elapsed time but must stay deterministic.
+///
+/// Logic never reads the wall clock. The driver turns wall time into whole steps (see
+/// [`StepAccumulator`]) and passes the step count in its `Input`; the logic advances a
+/// `LogicalClock` stored in its state. Replaying the same inputs reproduces the same times
+/// frame-exactly, and the clock round-trips through recordings with the rest of the state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogicalClock {
+    step: Duration,
+    ticks: u64,
+}
+
+impl LogicalClock {
+    /// A clock at tick 0 advancing by `step` per tick. A zero step is bumped to 1ns.
+    pub fn new(step: Duration) -> Self {
+        Self {
+            step: step.max(Duration::from_nanos(1)),
+            ticks: 0,
+        }
+    }
+
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Whole steps advanced so far.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Logical time since tick 0 (`ticks * step`, saturating).
+    pub fn elapsed(&self) -> Duration {
+        let nanos = self.step.as_nanos().saturating_mul(u128::from(self.ticks));
+        Duration::from_nanos(nanos.min(u128::from(u64::MAX)) as u64)
+    }
+
+    /// Advances by `steps` ticks and returns the logical time that passed.
+    pub fn advance(&mut self, steps: u32) -> Duration {
+        self.ticks = self.ticks.saturating_add(u64::from(steps));
+        self.step.saturating_mul(steps)
+    }
+}
+
+/// Turns variable frame `dt`s into whole fixed-interval logic steps, carrying the leftover time
+/// into the next frame so nothing is lost or double counted.
+///
+/// The interval is passed per call, so it may change between frames (e.g. a gravity curve).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixedTimestep {
+    remainder: Duration,
+}
+
+impl FixedTimestep {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resumes from a remainder stored elsewhere, such as serialized game state.
+    pub fn from_remainder(remainder: Duration) -> Self {
+        Self { remainder }
+    }
+
+    /// Adds `dt` and returns how many `interval`s are now due. A zero interval counts as 1ns.
+    pub fn advance(&mut self, dt: Duration, interval: Duration) -> u32 {
+        let interval = interval.max(Duration::from_nanos(1));
+        self.remainder = self.remainder.saturating_add(dt);
+        let steps = (self.remainder.as_nanos() / interval.as_nanos()).min(u128::from(u32::MAX));
+        let steps = steps as u32;
+        self.remainder = self
+            .remainder
+            .saturating_sub(interval.saturating_mul(steps));
+        steps
+    }
+
+    /// Time accumulated but not yet turned into a whole step.
+    pub fn remainder(&self) -> Duration {
+        self.remainder
+    }
+
+    /// How far (0..1) the remainder is toward the next `interval` step; the blend factor for
+    /// rendering between the previous and current fixed-step state.
+    pub fn alpha(&self, interval: Duration) -> f32 {
+        let interval = interval.max(Duration::from_nanos(1));
+        (self.remainder.as_secs_f64() / interval.as_secs_f64()).clamp(0.0, 1.0) as f32
+    }
+
+    pub fn reset(&mut self) {
+        self.remainder = Duration::ZERO;
+    }
+}
+
+/// Maps variable wall-clock frame times onto whole logical steps, carrying the remainder into the
+/// next frame so no time is lost or double counted.
+///
+/// This lives in the real-time driver, not in game state: only the resulting step counts feed the
+/// logic, so replays never see wall time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepAccumulator {
+    step: Duration,
+    timestep: FixedTimestep,
+}
+
+impl StepAccumulator {
+    pub fn new(step: Duration) -> Self {
+        Self {
+            step: step.max(Duration::from_nanos(1)),
+            timestep: FixedTimestep::new(),
+        }
+    }
+
+    /// Adds `wall_dt` and returns how many whole steps are now due.
+    pub fn accumulate(&mut self, wall_dt: Duration) -> u32 {
+        self.timestep.advance(wall_dt, self.step)
+    }
+
+    /// Wall time accumulated but not yet converted into a step.
+    pub fn pending(&self) -> Duration {
+        self.timestep.remainder()
+    }
+
+    pub fn reset(&mut self) {
+        self.timestep.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_timestep_carries_the_remainder_across_interval_changes() {
+        let mut timestep = FixedTimestep::new();
+        assert_eq!(
+            timestep.advance(Duration::from_millis(16), Duration::from_millis(10)),
+            1
+        );
+        assert_eq!(timestep.remainder(), Duration::from_millis(6));
+        assert!((timestep.alpha(Duration::from_millis(10)) - 0.6).abs() < 1e-6);
+        assert_eq!(timestep.alpha(Duration::from_millis(4)), 1.0);
+        assert_eq!(
+            timestep.advance(Duration::from_millis(16), Duration::from_millis(5)),
+            4
+        );
+        assert_eq!(timestep.remainder(), Duration::from_millis(2));
+
+        let mut resumed = FixedTimestep::from_remainder(Duration::from_millis(9));
+        assert_eq!(
+            resumed.advance(Duration::from_millis(1), Duration::from_millis(10)),
+            1
+        );
+        assert_eq!(resumed.remainder(), Duration::ZERO);
+        assert_eq!(
+            resumed.advance(Duration::from_millis(3), Duration::ZERO),
+            3_000_000
+        );
+    }
+
+    #[test]
+    fn accumulator_carries_partial_steps_between_frames() {
+        let mut acc = StepAccumulator::new(Duration::from_millis(10));
+        assert_eq!(acc.accumulate(Duration::from_millis(16)), 1);
+        assert_eq!(acc.pending(), Duration::from_millis(6));
+        assert_eq!(acc.accumulate(Duration::from_millis(16)), 2);
+        assert_eq!(acc.pending(), Duration::from_millis(2));
+        assert_eq!(acc.accumulate(Duration::ZERO), 0);
+        acc.reset();
+        assert_eq!(acc.pending(), Duration::ZERO);
+    }
+
+    #[test]
+    fn clock_advances_in_whole_steps_and_round_trips() {
+        let mut clock = LogicalClock::new(Duration::from_millis(10));
+        assert_eq!(clock.advance(3), Duration::from_millis(30));
+        assert_eq!(clock.advance(0), Duration::ZERO);
+        assert_eq!(clock.ticks(), 3);
+        assert_eq!(clock.elapsed(), Duration::from_millis(30));
+
+        let json = serde_json::to_string(&clock).expect("serialize clock");
+        let back: LogicalClock = serde_json::from_str(&json).expect("deserialize clock");
+        assert_eq!(back, clock);
+    }
+}
@@ -1,5 +1,8 @@
-use crate::graphics::{CpuRenderer, GpuRenderer2d, Renderer2d};
+use std::{fs, io, path::Path};
+
+use crate::graphics::{Color, CpuRenderer, DirtyTracker, GpuRenderer2d, Renderer2d};
 use crate::surface::SurfaceSize;
+use crate::ui::Rect;
 
 use pixels::Pixels;
 
@@ -28,6 +31,10 @@ pub struct PixelsRenderer2d {
     size: SurfaceSize,
     backend: RenderBackend2d,
     gpu: Option<GpuRenderer2d>,
+    dirty_tracking: bool,
+    dirty_rects: Vec<Rect>,
+    /// Set when the surface must be presented even without new draws (resize, tracking toggled).
+    force_present: bool,
 }
 
 impl PixelsRenderer2d {
@@ -35,6 +42,7 @@ impl PixelsRenderer2d {
     ///
     /// - `ROLLOUT_HEADFUL_GPU=0` forces CPU rendering.
     /// - Any other value (or unset) defaults to GPU rendering.
+    /// - `ROLLOUT_HEADFUL_DIRTY_RECTS=1` turns on `enable_dirty_tracking`.
     pub fn new_auto(pixels: Pixels, size: SurfaceSize) -> Result<Self, pixels::Error> {
         let gpu_enabled = env_bool("ROLLOUT_HEADFUL_GPU").unwrap_or(true);
         let backend = if gpu_enabled {
@@ -42,7 +50,9 @@ impl PixelsRenderer2d {
         } else {
             RenderBackend2d::Cpu
         };
-        Self::new(pixels, size, backend)
+        let mut renderer = Self::new(pixels, size, backend)?;
+        renderer.enable_dirty_tracking(env_bool("ROLLOUT_HEADFUL_DIRTY_RECTS").unwrap_or(false));
+        Ok(renderer)
     }
 
     pub fn new(
@@ -68,9 +78,32 @@ impl PixelsRenderer2d {
             size,
             backend,
             gpu,
+            dirty_tracking: false,
+            dirty_rects: Vec::new(),
+            force_present: true,
         })
     }
 
+    /// Record the regions each `draw_frame` touches and skip `present` after a frame that drew
+    /// nothing, leaving the previous frame on screen.
+    ///
+    /// `pixels` always uploads the whole buffer, so a dirty frame still presents in full; the win
+    /// is for frames that don't redraw at all (static menus, paused states).
+    pub fn enable_dirty_tracking(&mut self, enabled: bool) {
+        self.dirty_tracking = enabled;
+        self.dirty_rects.clear();
+        self.force_present = true;
+    }
+
+    pub fn dirty_tracking_enabled(&self) -> bool {
+        self.dirty_tracking
+    }
+
+    /// Regions drawn by the last `draw_frame` while dirty tracking is on (empty otherwise).
+    pub fn dirty_rects(&self) -> &[Rect] {
+        &self.dirty_rects
+    }
+
     pub fn size(&self) -> SurfaceSize {
         self.size
     }
@@ -85,6 +118,7 @@ impl PixelsRenderer2d {
 
     pub fn resize(&mut self, size: SurfaceSize) -> Result<(), pixels::Error> {
         self.size = size;
+        self.force_present = true;
         self.pixels.resize_surface(size.width, size.height)?;
 
         match self.backend {
@@ -104,24 +138,68 @@ impl PixelsRenderer2d {
     where
         F: FnOnce(&mut dyn Renderer2d) -> R,
     {
+        let size = self.size;
+        let dirty = self.dirty_tracking.then_some(&mut self.dirty_rects);
         match self.backend {
             RenderBackend2d::Cpu => {
-                let mut cpu = CpuRenderer::new(self.pixels.frame_mut(), self.size);
-                cpu.begin_frame(self.size);
-                Ok(f(&mut cpu))
+                let mut cpu = CpuRenderer::new(self.pixels.frame_mut(), size);
+                Ok(draw_tracked(&mut cpu, size, dirty, f))
             }
             RenderBackend2d::Gpu => {
                 let gpu = self
                     .gpu
                     .as_mut()
                     .expect("RenderBackend2d::Gpu requires gpu renderer to be initialized");
-                gpu.begin_frame(self.size);
-                Ok(f(gpu))
+                Ok(draw_tracked(gpu, size, dirty, f))
+            }
+        }
+    }
+
+    /// Color at `(x, y)` of the most recently drawn frame, or `None` if out of bounds.
+    pub fn read_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        match self.backend {
+            RenderBackend2d::Cpu => {
+                let size = self.size;
+                if x >= size.width || y >= size.height {
+                    return None;
+                }
+                let idx = ((y as usize) * (size.width as usize) + (x as usize)) * 4;
+                let px = self.pixels.frame().get(idx..idx + 4)?;
+                Some([px[0], px[1], px[2], px[3]])
             }
+            RenderBackend2d::Gpu => self.gpu.as_ref()?.read_pixel(x, y),
         }
     }
 
+    /// RGBA8 copy of the most recently drawn frame, for screenshots and golden captures.
+    ///
+    /// Call after `draw_frame`. Works on either backend: the CPU path copies the pixel buffer, the
+    /// GPU path rasterizes the queued instances on the CPU (no GPU read-back).
+    pub fn capture_rgba(&self) -> (Vec<u8>, SurfaceSize) {
+        let rgba = match self.backend {
+            RenderBackend2d::Cpu => self.pixels.frame().to_vec(),
+            RenderBackend2d::Gpu => self
+                .gpu
+                .as_ref()
+                .map(GpuRenderer2d::capture_rgba)
+                .unwrap_or_default(),
+        };
+        (rgba, self.size)
+    }
+
+    /// Writes `capture_rgba` to `path` as a PNG, creating parent directories as needed.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let (rgba, size) = self.capture_rgba();
+        save_rgba_png(path, size, &rgba)
+    }
+
+    /// Presents the last drawn frame. With dirty tracking on, a frame with no dirty rects is
+    /// skipped entirely.
     pub fn present(&mut self) -> Result<(), pixels::Error> {
+        if self.dirty_tracking && self.dirty_rects.is_empty() && !self.force_present {
+            return Ok(());
+        }
+        self.force_present = false;
         match self.backend {
             RenderBackend2d::Cpu => self.pixels.render(),
             RenderBackend2d::Gpu => {
@@ -139,3 +217,39 @@ impl PixelsRenderer2d {
         }
     }
 }
+
+/// Runs `f` on `renderer`, through a `DirtyTracker` when `dirty` is given (its rects replace
+/// `dirty`'s contents).
+fn draw_tracked<F, R>(
+    renderer: &mut dyn Renderer2d,
+    size: SurfaceSize,
+    dirty: Option<&mut Vec<Rect>>,
+    f: F,
+) -> R
+where
+    F: FnOnce(&mut dyn Renderer2d) -> R,
+{
+    let Some(dirty) = dirty else {
+        renderer.begin_frame(size);
+        return f(renderer);
+    };
+    let mut tracker = DirtyTracker::new(renderer, size);
+    let out = f(&mut tracker);
+    *dirty = tracker.into_dirty_rects();
+    out
+}
+
+/// Encodes a tightly packed RGBA8 buffer of `size` as a PNG file at `path`.
+pub fn save_rgba_png(path: impl AsRef<Path>, size: SurfaceSize, rgba: &[u8]) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(io::BufWriter::new(file), size.width, size.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(rgba).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
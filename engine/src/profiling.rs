@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -7,10 +8,243 @@ pub struct StepTimings {
     pub total: Duration,
 }
 
-/// Optional hook interface for capturing engine step timings.
+/// Whole-frame breakdown handed to `Profiler::on_frame`.
+///
+/// Loops fill in the phases they measure and leave the rest `None`, so a single profiler sink
+/// can aggregate frames from the app loop, the headful render pipeline, or a headless driver.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameTimings {
+    /// Wall time since the previous frame began.
+    pub frame_dt: Duration,
+    /// `update_state` plus fixed updates.
+    pub update: Option<Duration>,
+    /// Input handling inside `update`, including the steps it triggered.
+    pub input: Option<Duration>,
+    /// Gravity/tick steps inside `update`.
+    pub gravity: Option<Duration>,
+    /// Board/world drawing inside `draw`.
+    pub board: Option<Duration>,
+    pub draw: Option<Duration>,
+    /// Debug overlays and tooltips drawn after the scene.
+    pub overlay: Option<Duration>,
+    pub present: Option<Duration>,
+    pub total: Duration,
+}
+
+/// Optional hook interface for capturing engine step and frame timings.
 ///
 /// This is intentionally generic: it avoids depending on game-specific State/Input types
 /// so it can be used across headful, headless, and editor integrations.
 pub trait Profiler {
     fn on_step(&mut self, _frame: usize, _timings: StepTimings) {}
+
+    /// Called once per rendered frame with whatever phases the caller measured.
+    fn on_frame(&mut self, _timings: FrameTimings) {}
+
+    /// Whether `on_step` wants timings at all. Returning `false` lets
+    /// `HeadlessRunner::step_profiled` skip every `Instant::now()` call and never invoke
+    /// `on_step`.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+/// Profiler that records nothing; `HeadlessRunner::step` runs through it.
+///
+/// With timing skipped, a step costs only `GameLogic::step` plus recording. For trivial logic
+/// the four `Instant::now()` calls of a timed step are most of the cost (tens of nanoseconds
+/// each on typical desktop clocks), so prefer this over an empty custom profiler.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopProfiler;
+
+impl Profiler for NoopProfiler {
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+/// Fixed-size window over the most recent `Duration` samples.
+///
+/// Percentiles use the nearest-rank method over the current window, so they are stable across
+/// runs instead of tracking a single worst sample.
+#[derive(Debug, Clone)]
+pub struct RollingStats {
+    window: usize,
+    samples: VecDeque<Duration>,
+    sum: Duration,
+}
+
+impl RollingStats {
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            samples: VecDeque::with_capacity(window),
+            sum: Duration::ZERO,
+        }
+    }
+
+    pub fn push(&mut self, sample: Duration) {
+        if self.samples.len() == self.window {
+            let old = self.samples.pop_front().unwrap_or_default();
+            self.sum = self.sum.saturating_sub(old);
+        }
+        self.samples.push_back(sample);
+        self.sum = self.sum.saturating_add(sample);
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.sum = Duration::ZERO;
+    }
+
+    pub fn last(&self) -> Duration {
+        self.samples.back().copied().unwrap_or_default()
+    }
+
+    pub fn avg(&self) -> Duration {
+        match u32::try_from(self.samples.len()) {
+            Ok(0) | Err(_) => Duration::ZERO,
+            Ok(n) => self.sum / n,
+        }
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.iter().copied().max().unwrap_or_default()
+    }
+
+    pub fn p50(&self) -> Duration {
+        self.percentile(50.0)
+    }
+
+    pub fn p95(&self) -> Duration {
+        self.percentile(95.0)
+    }
+
+    pub fn p99(&self) -> Duration {
+        self.percentile(99.0)
+    }
+
+    /// Nearest-rank percentile for `pct` in `0.0..=100.0`; zero when the window is empty.
+    pub fn percentile(&self, pct: f64) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = ((pct.clamp(0.0, 100.0) / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+}
+
+/// `Profiler` that keeps a `RollingStats` window per `StepTimings` field.
+#[derive(Debug, Clone)]
+pub struct StatsProfiler {
+    pub step: RollingStats,
+    pub record: RollingStats,
+    pub total: RollingStats,
+}
+
+impl StatsProfiler {
+    pub fn new(window: usize) -> Self {
+        Self {
+            step: RollingStats::new(window),
+            record: RollingStats::new(window),
+            total: RollingStats::new(window),
+        }
+    }
+}
+
+impl Profiler for StatsProfiler {
+    fn on_step(&mut self, _frame: usize, timings: StepTimings) {
+        self.step.push(timings.step);
+        self.record.push(timings.record);
+        self.total.push(timings.total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn rolling_stats_percentiles_use_nearest_rank() {
+        let mut stats = RollingStats::new(100);
+        assert_eq!(stats.p50(), Duration::ZERO);
+        for v in 1..=100 {
+            stats.push(ms(v));
+        }
+        assert_eq!(stats.p50(), ms(50));
+        assert_eq!(stats.p95(), ms(95));
+        assert_eq!(stats.p99(), ms(99));
+        assert_eq!(stats.max(), ms(100));
+        assert_eq!(stats.avg(), Duration::from_micros(50_500));
+    }
+
+    #[test]
+    fn rolling_stats_evicts_oldest_samples() {
+        let mut stats = RollingStats::new(3);
+        for v in [100, 1, 2, 3] {
+            stats.push(ms(v));
+        }
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats.max(), ms(3));
+        assert_eq!(stats.avg(), ms(2));
+        assert_eq!(stats.last(), ms(3));
+    }
+
+    #[test]
+    fn noop_profiler_opts_out_of_timing() {
+        assert!(!NoopProfiler.is_enabled());
+        assert!(StatsProfiler::new(1).is_enabled());
+    }
+
+    #[test]
+    fn frame_timings_default_to_unmeasured_phases() {
+        let timings = FrameTimings {
+            draw: Some(ms(2)),
+            total: ms(3),
+            ..FrameTimings::default()
+        };
+        assert_eq!(timings.update, None);
+        assert_eq!(timings.present, None);
+        assert_eq!(timings.draw, Some(ms(2)));
+
+        // The default hook is a no-op, so step-only profilers need no changes.
+        let mut profiler = StatsProfiler::new(1);
+        profiler.on_frame(timings);
+        assert!(profiler.total.is_empty());
+    }
+
+    #[test]
+    fn stats_profiler_tracks_each_timing_separately() {
+        let mut profiler = StatsProfiler::new(8);
+        profiler.on_step(
+            1,
+            StepTimings {
+                step: ms(1),
+                record: ms(2),
+                total: ms(3),
+            },
+        );
+        assert_eq!(profiler.step.p50(), ms(1));
+        assert_eq!(profiler.record.p50(), ms(2));
+        assert_eq!(profiler.total.p50(), ms(3));
+    }
 }
@@ -6,6 +6,8 @@ use std::{
     process::{Child, ChildStdin, Command, Stdio},
 };
 
+use crate::{TimeMachine, pixels_renderer::save_rgba_png, surface::SurfaceSize};
+
 fn ffmpeg_bin() -> OsString {
     std::env::var_os("ROLLOUT_FFMPEG_BIN").unwrap_or_else(|| OsString::from("ffmpeg"))
 }
@@ -139,3 +141,102 @@ impl Drop for Mp4Recorder {
         let _ = self.finish();
     }
 }
+
+/// Size and pacing for `export_png_sequence` / `export_gif`.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameExportConfig {
+    pub width: u32,
+    pub height: u32,
+    /// Simulation frames per second; a recorded state spans `record_every_n_frames` of them.
+    pub fps: u32,
+}
+
+impl FrameExportConfig {
+    pub fn rgba_frame_len(&self) -> usize {
+        (self.width as usize)
+            .saturating_mul(self.height as usize)
+            .saturating_mul(4)
+    }
+
+    fn surface_size(&self) -> SurfaceSize {
+        SurfaceSize::new(self.width, self.height)
+    }
+}
+
+/// File name for the recorded state at simulation frame `frame` (`frame_00042.png`).
+pub fn export_frame_file_name(frame: usize) -> String {
+    format!("frame_{frame:05}.png")
+}
+
+/// Renders every recorded state of `tm` into `dir` as `frame_%05d.png` and returns the paths.
+///
+/// Files are numbered by simulation frame, so a recording made with `record_every_n_frames = 4`
+/// yields `frame_00000.png`, `frame_00004.png`, ... `render` receives a zeroed RGBA buffer of
+/// `config.rgba_frame_len()` bytes per state.
+pub fn export_png_sequence<State, Render>(
+    tm: &TimeMachine<State>,
+    dir: impl AsRef<Path>,
+    config: FrameExportConfig,
+    mut render: Render,
+) -> io::Result<Vec<PathBuf>>
+where
+    Render: FnMut(&State, &mut [u8]),
+{
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let stride = tm.record_every_n_frames();
+    let mut rgba = vec![0u8; config.rgba_frame_len()];
+    let mut written = Vec::with_capacity(tm.len());
+    for (index, state) in tm.iter() {
+        rgba.fill(0);
+        render(state, &mut rgba);
+        let path = dir.join(export_frame_file_name(index.saturating_mul(stride)));
+        save_rgba_png(&path, config.surface_size(), &rgba)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Renders every recorded state of `tm` into a looping animated GIF at `output`.
+///
+/// Each state is shown for `record_every_n_frames` simulation frames at `config.fps`, so playback
+/// runs at the recorded speed whatever the stride.
+pub fn export_gif<State, Render>(
+    tm: &TimeMachine<State>,
+    output: impl AsRef<Path>,
+    config: FrameExportConfig,
+    mut render: Render,
+) -> io::Result<()>
+where
+    Render: FnMut(&State, &mut [u8]),
+{
+    use image::codecs::gif::{GifEncoder, Repeat};
+    use image::{Delay, Frame, RgbaImage};
+
+    let output = output.as_ref();
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let fps = config.fps.max(1);
+    let frame_ms = u32::try_from(tm.record_every_n_frames())
+        .unwrap_or(u32::MAX)
+        .saturating_mul(1000);
+    let delay = Delay::from_numer_denom_ms(frame_ms, fps);
+
+    let file = fs::File::create(output)?;
+    let mut encoder = GifEncoder::new(io::BufWriter::new(file));
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for (_, state) in tm.iter() {
+        let mut rgba = vec![0u8; config.rgba_frame_len()];
+        render(state, &mut rgba);
+        let image = RgbaImage::from_raw(config.width, config.height, rgba).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "rgba buffer size changed")
+        })?;
+        encoder
+            .encode_frame(Frame::from_parts(image, 0, 0, delay))
+            .map_err(io::Error::other)?;
+    }
+    Ok(())
+}
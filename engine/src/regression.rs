@@ -21,7 +21,10 @@ use sha2::{Digest, Sha256};
 
 use crate::{
     GameLogic, HeadlessRunner, TimeMachine,
+    graphics::Texture,
+    pixels_renderer::save_rgba_png,
     recording::{Mp4Config, Mp4Recorder},
+    surface::SurfaceSize,
 };
 
 /// Environment flag helper: accepts `1/true/yes/on` (case-insensitive).
@@ -314,6 +317,126 @@ fn write_ppm_rgb_diff(
     Ok(())
 }
 
+/// Result of `compare_images`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageDiff {
+    pub width: u32,
+    pub height: u32,
+    /// Pixels where at least one RGBA channel differs by more than the tolerance.
+    pub differing_pixels: usize,
+}
+
+impl ImageDiff {
+    pub fn total_pixels(&self) -> usize {
+        (self.width as usize).saturating_mul(self.height as usize)
+    }
+
+    /// `differing_pixels` as a fraction of the image, in `0.0..=1.0`.
+    pub fn differing_fraction(&self) -> f64 {
+        match self.total_pixels() {
+            0 => 0.0,
+            total => self.differing_pixels as f64 / total as f64,
+        }
+    }
+
+    pub fn is_match(&self) -> bool {
+        self.differing_pixels == 0
+    }
+}
+
+/// `target/image-diffs/`, where image comparisons can write diffs without touching the checked-in
+/// references.
+pub fn default_image_diff_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("target")
+        .join("image-diffs")
+}
+
+/// Path of the diff image `compare_images` writes for `reference_path` into `diff_dir`
+/// (`menu.png` -> `<diff_dir>/menu.diff.png`).
+pub fn image_diff_path(reference_path: impl AsRef<Path>, diff_dir: impl AsRef<Path>) -> PathBuf {
+    let stem = reference_path
+        .as_ref()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    diff_dir.as_ref().join(format!("{stem}.diff.png"))
+}
+
+/// Compares a rendered frame against the reference PNG at `reference_path`.
+///
+/// A pixel only counts as different when some channel is off by more than `tolerance`, which
+/// absorbs small anti-aliasing changes. On any difference, an image marking the differing pixels
+/// in red over a dimmed copy of `actual` is written to `image_diff_path(reference_path, diff_dir)`
+/// (usually [`default_image_diff_dir`], so the source tree stays clean); a stale one is removed
+/// on a match. Mismatched dimensions are an error rather than a diff.
+pub fn compare_images(
+    actual: &Texture,
+    reference_path: impl AsRef<Path>,
+    tolerance: u8,
+    diff_dir: impl AsRef<Path>,
+) -> io::Result<ImageDiff> {
+    let reference_path = reference_path.as_ref();
+    let reference = Texture::load_png(reference_path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "failed loading reference image {}: {e}",
+                reference_path.display()
+            ),
+        )
+    })?;
+    if (actual.width(), actual.height()) != (reference.width(), reference.height()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "image size mismatch against {}: expected {}x{}, got {}x{}",
+                reference_path.display(),
+                reference.width(),
+                reference.height(),
+                actual.width(),
+                actual.height()
+            ),
+        ));
+    }
+
+    let mut diff_rgba = Vec::with_capacity(actual.rgba().len());
+    let mut differing_pixels = 0;
+    for (a, b) in actual
+        .rgba()
+        .chunks_exact(4)
+        .zip(reference.rgba().chunks_exact(4))
+    {
+        if a.iter().zip(b).any(|(x, y)| x.abs_diff(*y) > tolerance) {
+            differing_pixels += 1;
+            diff_rgba.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let luma = ((a[0] as u32 + a[1] as u32 + a[2] as u32) / 3 / 4) as u8;
+            diff_rgba.extend_from_slice(&[luma, luma, luma, 255]);
+        }
+    }
+
+    let diff = ImageDiff {
+        width: actual.width(),
+        height: actual.height(),
+        differing_pixels,
+    };
+    let diff_path = image_diff_path(reference_path, diff_dir);
+    if diff.is_match() {
+        if diff_path.exists() {
+            fs::remove_file(&diff_path)?;
+        }
+    } else {
+        save_rgba_png(
+            &diff_path,
+            SurfaceSize::new(diff.width, diff.height),
+            &diff_rgba,
+        )?;
+    }
+    Ok(diff)
+}
+
 pub fn render_frame_hashes_for_timemachine<State, Render>(
     tm: &TimeMachine<State>,
     width: u32,
@@ -678,3 +801,79 @@ where
         replay_hashes,
     })
 }
+
+/// First frame of a recording that broke a gameplay invariant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvariantViolation {
+    pub frame: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invariant violated at frame {}: {}",
+            self.frame, self.message
+        )
+    }
+}
+
+impl std::error::Error for InvariantViolation {}
+
+/// Runs `invariant` against every recorded state in order and stops at the first `Err`.
+///
+/// Complements structural checks: the recording loads fine, but does the gameplay it captured
+/// make sense? States are borrowed in place, so long recordings are never cloned. `invariant`
+/// may carry state between frames (e.g. "score never decreases").
+pub fn validate_recording<State, F>(
+    recording: &TimeMachine<State>,
+    mut invariant: F,
+) -> Result<(), InvariantViolation>
+where
+    F: FnMut(usize, &State) -> Result<(), String>,
+{
+    for (frame, state) in recording.iter() {
+        invariant(frame, state).map_err(|message| InvariantViolation { frame, message })?;
+    }
+    Ok(())
+}
+
+/// Re-simulates `inputs` (one per step, as passed to `HeadlessRunner::step`) under `logic` and
+/// returns the first recorded frame whose state differs from `recorded`.
+///
+/// Only steps that land on a recorded frame are compared, so `record_every_n_frames` strides are
+/// honoured. Frame 0 is checked against `logic.initial_state()`. If every compared frame matches
+/// but the inputs run out before the recording does, the first frame they never reached is
+/// reported; extra inputs past the end of the recording are ignored.
+pub fn find_divergence<G>(
+    logic: G,
+    recorded: &TimeMachine<G::State>,
+    inputs: &[G::Input],
+) -> Option<usize>
+where
+    G: GameLogic,
+    G::State: PartialEq,
+    G::Input: Clone,
+{
+    let stride = recorded.record_every_n_frames().max(1);
+    let mut state = logic.initial_state();
+    if recorded.state_at(0) != Some(&state) {
+        return Some(0);
+    }
+
+    let mut frame = 0;
+    for (step, input) in inputs.iter().enumerate() {
+        if frame + 1 >= recorded.len() {
+            return None;
+        }
+        state = logic.step(&state, input.clone());
+        if (step + 1) % stride == 0 {
+            frame += 1;
+            if recorded.state_at(frame) != Some(&state) {
+                return Some(frame);
+            }
+        }
+    }
+    (frame + 1 < recorded.len()).then_some(frame + 1)
+}
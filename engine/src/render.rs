@@ -3,6 +3,9 @@ pub const CELL_SIZE: u32 = 24;
 const COLOR_BACKGROUND: [u8; 4] = [0, 0, 0, 255];
 const COLOR_BOARD_OUTLINE: [u8; 4] = [28, 28, 38, 255];
 const COLOR_GRID_DOT: [u8; 4] = [18, 18, 24, 255];
+const COLOR_GRID_DOT_HIGH_CONTRAST: [u8; 4] = [88, 88, 112, 255];
+const COLOR_PATTERN_DARK: [u8; 4] = [20, 20, 24, 255];
+const COLOR_PATTERN_LIGHT: [u8; 4] = [240, 240, 245, 255];
 const FILLED_EDGE_STROKE_PX: u32 = 3;
 const INTERNAL_SEAM_STROKE_PX: u32 = 3;
 const FILLED_EDGE_STROKE_NUM: u16 = 11;
@@ -25,26 +28,104 @@ const COLOR_MOSS: [u8; 4] = [72, 128, 64, 255];
 const COLOR_MOSS_SEED: [u8; 4] = [118, 188, 98, 255];
 const COLOR_SAND: [u8; 4] = [206, 180, 109, 255];
 
-pub fn color_for_cell(cell: u8) -> [u8; 4] {
-    match cell {
-        0 => COLOR_BACKGROUND,
-        1 => COLOR_WOOD_I4,
-        2 => COLOR_STONE_O,
-        3 => COLOR_GLASS_I3,
-        4 => COLOR_DIRT_I2,
-        5 => COLOR_Z,
-        6 => COLOR_J,
-        7 => COLOR_L,
-        8 => COLOR_GARBAGE,
-        9 => COLOR_STONE,
-        10 => COLOR_ORE,
-        11 => COLOR_COIN,
-        12 => COLOR_BOTTOMWELL_GRASS,
-        13 => COLOR_MOSS,
-        14 => COLOR_MOSS_SEED,
-        15 => COLOR_SAND,
-        _ => [255, 255, 255, 255],
+/// Number of cell values a `CellPalette` colors; larger values draw as `COLOR_UNKNOWN_CELL`.
+pub const CELL_PALETTE_LEN: usize = 16;
+const COLOR_UNKNOWN_CELL: [u8; 4] = [255, 255, 255, 255];
+
+/// Fill colors for board cell values, indexed by cell value (0 is the empty background).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellPalette {
+    pub colors: [[u8; 4]; CELL_PALETTE_LEN],
+    /// Dot drawn in the middle of each empty cell to reveal the grid.
+    pub grid: [u8; 4],
+    /// Draw a per-value marker inside filled piece cells (values 1-7) so pieces differ by shape
+    /// as well as color.
+    pub patterns: bool,
+}
+
+impl CellPalette {
+    pub const CLASSIC: CellPalette = CellPalette {
+        colors: [
+            COLOR_BACKGROUND,
+            COLOR_WOOD_I4,
+            COLOR_STONE_O,
+            COLOR_GLASS_I3,
+            COLOR_DIRT_I2,
+            COLOR_Z,
+            COLOR_J,
+            COLOR_L,
+            COLOR_GARBAGE,
+            COLOR_STONE,
+            COLOR_ORE,
+            COLOR_COIN,
+            COLOR_BOTTOMWELL_GRASS,
+            COLOR_MOSS,
+            COLOR_MOSS_SEED,
+            COLOR_SAND,
+        ],
+        grid: COLOR_GRID_DOT,
+        patterns: false,
+    };
+
+    /// Piece colors from the Okabe-Ito set, spread across luminance so neighbours stay apart
+    /// under common color-vision deficiencies, plus pattern markers. Earth cells match `CLASSIC`.
+    pub const COLORBLIND: CellPalette = CellPalette {
+        colors: [
+            COLOR_BACKGROUND,
+            [150, 210, 240, 255],
+            [240, 228, 66, 255],
+            [0, 158, 115, 255],
+            [70, 45, 25, 255],
+            [230, 110, 20, 255],
+            [0, 100, 170, 255],
+            [204, 121, 167, 255],
+            COLOR_GARBAGE,
+            COLOR_STONE,
+            COLOR_ORE,
+            COLOR_COIN,
+            COLOR_BOTTOMWELL_GRASS,
+            COLOR_MOSS,
+            COLOR_MOSS_SEED,
+            COLOR_SAND,
+        ],
+        grid: COLOR_GRID_DOT,
+        patterns: true,
+    };
+
+    /// The same palette with a brighter empty-cell grid.
+    pub const fn with_high_contrast_grid(self) -> CellPalette {
+        CellPalette {
+            grid: COLOR_GRID_DOT_HIGH_CONTRAST,
+            ..self
+        }
     }
+
+    /// The same palette with every color mapped to its luma (alpha kept).
+    pub const fn grayscale(self) -> CellPalette {
+        let mut colors = self.colors;
+        let mut i = 0;
+        while i < CELL_PALETTE_LEN {
+            let [r, g, b, a] = colors[i];
+            let luma = ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8;
+            colors[i] = [luma, luma, luma, a];
+            i += 1;
+        }
+        CellPalette { colors, ..self }
+    }
+}
+
+impl Default for CellPalette {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+pub fn color_for_cell(palette: &CellPalette, cell: u8) -> [u8; 4] {
+    palette
+        .colors
+        .get(cell as usize)
+        .copied()
+        .unwrap_or(COLOR_UNKNOWN_CELL)
 }
 
 /// Clip a screen-space rect to a viewport, returning the visible intersection.
@@ -144,6 +225,16 @@ pub fn draw_board_cells_in_rect(
     gfx: &mut dyn crate::graphics::Renderer2d,
     board: &[Vec<u8>],
     board_rect: crate::ui::Rect,
+) {
+    draw_board_cells_in_rect_with_palette(gfx, board, board_rect, &CellPalette::CLASSIC);
+}
+
+/// `draw_board_cells_in_rect` with cell fills and edge strokes taken from `palette`.
+pub fn draw_board_cells_in_rect_with_palette(
+    gfx: &mut dyn crate::graphics::Renderer2d,
+    board: &[Vec<u8>],
+    board_rect: crate::ui::Rect,
+    palette: &CellPalette,
 ) {
     if board.is_empty() {
         return;
@@ -160,7 +251,6 @@ pub fn draw_board_cells_in_rect(
     let board_pixel_height = board_height.saturating_mul(CELL_SIZE);
     let offset_x = board_rect.x;
     let offset_y = board_rect.y;
-    let screen_rect = crate::ui::Rect::from_size(width, height);
 
     draw_board_outline(
         gfx,
@@ -190,20 +280,21 @@ pub fn draw_board_cells_in_rect(
                 let dot_y = pixel_y + (CELL_SIZE / 2).saturating_sub(dot_size / 2);
                 gfx.fill_rect(
                     crate::ui::Rect::new(dot_x, dot_y, dot_size, dot_size),
-                    COLOR_GRID_DOT,
+                    palette.grid,
                 );
             } else {
-                let color = color_for_cell(cell);
-                gfx.fill_rect(
-                    crate::ui::Rect::new(pixel_x, pixel_y, CELL_SIZE, CELL_SIZE),
-                    color,
-                );
-                draw_exposed_cell_edges(gfx, board, None, x, y, pixel_x, pixel_y, screen_rect);
+                let cell_rect = crate::ui::Rect::new(pixel_x, pixel_y, CELL_SIZE, CELL_SIZE);
+                let fill = color_for_cell(palette, cell);
+                gfx.fill_rect(cell_rect, fill);
+                if palette.patterns {
+                    draw_cell_pattern(gfx, cell, cell_rect, fill);
+                }
+                draw_exposed_cell_edges(gfx, palette, board, None, x, y, cell_rect);
             }
         }
     }
 
-    draw_inside_corner_bridges(gfx, board, offset_x, offset_y, screen_rect);
+    draw_inside_corner_bridges(gfx, palette, board, offset_x, offset_y);
 }
 
 /// Draw board content translated to `board_rect`, clipped to `clip_rect`.
@@ -223,13 +314,34 @@ pub fn draw_board_cells_in_rect_clipped(
 /// per-cell piece ownership for interior seam rendering.
 ///
 /// When `board_owners` is provided, interior seams are drawn between adjacent non-empty cells
-/// that belong to different piece IDs.
+/// that belong to different piece IDs. Clipping goes through the renderer's clip stack, so an
+/// enclosing `push_clip` (e.g. a panel) narrows it further.
 pub fn draw_board_cells_in_rect_clipped_with_owners(
     gfx: &mut dyn crate::graphics::Renderer2d,
     board: &[Vec<u8>],
     board_owners: Option<&[Vec<Option<u32>>]>,
     board_rect: crate::ui::Rect,
     clip_rect: crate::ui::Rect,
+) {
+    draw_board_cells_in_rect_clipped_with_owners_and_palette(
+        gfx,
+        board,
+        board_owners,
+        board_rect,
+        clip_rect,
+        &CellPalette::CLASSIC,
+    );
+}
+
+/// `draw_board_cells_in_rect_clipped_with_owners` with cell fills and edge strokes taken from
+/// `palette`.
+pub fn draw_board_cells_in_rect_clipped_with_owners_and_palette(
+    gfx: &mut dyn crate::graphics::Renderer2d,
+    board: &[Vec<u8>],
+    board_owners: Option<&[Vec<Option<u32>>]>,
+    board_rect: crate::ui::Rect,
+    clip_rect: crate::ui::Rect,
+    palette: &CellPalette,
 ) {
     if board.is_empty() || clip_rect.w == 0 || clip_rect.h == 0 {
         return;
@@ -248,6 +360,7 @@ pub fn draw_board_cells_in_rect_clipped_with_owners(
         draw_board_outline(gfx, clip_rect.x, clip_rect.y, outline_w, outline_h);
     }
 
+    gfx.push_clip(clip_rect);
     for (y, row) in board.iter().enumerate() {
         for (x, &cell) in row.iter().enumerate() {
             let pixel_x = offset_x + x as u32 * CELL_SIZE;
@@ -260,35 +373,88 @@ pub fn draw_board_cells_in_rect_clipped_with_owners(
                 let dot_size = 2u32;
                 let dot_x = pixel_x + (CELL_SIZE / 2).saturating_sub(dot_size / 2);
                 let dot_y = pixel_y + (CELL_SIZE / 2).saturating_sub(dot_size / 2);
-                let dot_rect = crate::ui::Rect::new(dot_x, dot_y, dot_size, dot_size);
-                if let Some(clipped_dot) = clip_rect_to_viewport(dot_rect, clip_rect) {
-                    gfx.fill_rect(clipped_dot, COLOR_GRID_DOT);
-                }
+                gfx.fill_rect(
+                    crate::ui::Rect::new(dot_x, dot_y, dot_size, dot_size),
+                    palette.grid,
+                );
             } else {
                 let cell_rect = crate::ui::Rect::new(pixel_x, pixel_y, CELL_SIZE, CELL_SIZE);
-                let Some(clipped_cell_rect) = clip_rect_to_viewport(cell_rect, clip_rect) else {
-                    continue;
-                };
-                let color = color_for_cell(cell);
-                gfx.fill_rect(clipped_cell_rect, color);
-                draw_exposed_cell_edges(gfx, board, board_owners, x, y, pixel_x, pixel_y, clip_rect);
+                let fill = color_for_cell(palette, cell);
+                gfx.fill_rect(cell_rect, fill);
+                if palette.patterns {
+                    draw_cell_pattern(gfx, cell, cell_rect, fill);
+                }
+                draw_exposed_cell_edges(gfx, palette, board, board_owners, x, y, cell_rect);
             }
         }
     }
 
-    draw_interior_seam_corner_bridges(gfx, board, board_owners, offset_x, offset_y, clip_rect);
-    draw_inside_corner_bridges(gfx, board, offset_x, offset_y, clip_rect);
+    draw_interior_seam_corner_bridges(gfx, palette, board, board_owners, offset_x, offset_y);
+    draw_inside_corner_bridges(gfx, palette, board, offset_x, offset_y);
+    gfx.pop_clip();
+}
+
+/// Marker for `CellPalette::patterns`: a small shape per piece value in a dark or light tone
+/// chosen against `fill`. Other values draw plain.
+fn draw_cell_pattern(
+    gfx: &mut dyn crate::graphics::Renderer2d,
+    cell: u8,
+    cell_rect: crate::ui::Rect,
+    fill: [u8; 4],
+) {
+    use crate::ui::Rect;
+
+    let [r, g, b, _] = fill;
+    let luma = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+    let color = if luma >= 128 {
+        COLOR_PATTERN_DARK
+    } else {
+        COLOR_PATTERN_LIGHT
+    };
+    let (x, y, size) = (cell_rect.x, cell_rect.y, cell_rect.w.min(cell_rect.h));
+    let mid = size / 2;
+    let inset = size / 4;
+    let bar = (size / 6).max(2);
+    let dot = (size / 6).max(2);
+    let h_bar = Rect::new(x + inset, y + mid - bar / 2, size - inset * 2, bar);
+    let v_bar = Rect::new(x + mid - bar / 2, y + inset, bar, size - inset * 2);
+    let dot_at = |dx: u32, dy: u32| Rect::new(x + dx - dot / 2, y + dy - dot / 2, dot, dot);
+    let near = inset;
+    let far = size - inset;
+
+    match cell {
+        1 => gfx.fill_rect(h_bar, color),
+        2 => gfx.fill_rect(dot_at(mid, mid), color),
+        3 => gfx.fill_rect(v_bar, color),
+        4 => {
+            gfx.fill_rect(dot_at(near, near), color);
+            gfx.fill_rect(dot_at(far, far), color);
+        }
+        5 => {
+            gfx.fill_rect(h_bar, color);
+            gfx.fill_rect(v_bar, color);
+        }
+        6 => {
+            for (dx, dy) in [(near, near), (far, near), (near, far), (far, far)] {
+                gfx.fill_rect(dot_at(dx, dy), color);
+            }
+        }
+        7 => gfx.rect_outline(
+            Rect::new(x + inset, y + inset, size - inset * 2, size - inset * 2),
+            color,
+        ),
+        _ => {}
+    }
 }
 
 fn draw_exposed_cell_edges(
     gfx: &mut dyn crate::graphics::Renderer2d,
+    palette: &CellPalette,
     board: &[Vec<u8>],
     board_owners: Option<&[Vec<Option<u32>>]>,
     x: usize,
     y: usize,
-    pixel_x: u32,
-    pixel_y: u32,
-    clip_rect: crate::ui::Rect,
+    cell_rect: crate::ui::Rect,
 ) {
     if board.is_empty() {
         return;
@@ -301,7 +467,8 @@ fn draw_exposed_cell_edges(
 
     let board_h = board.len();
     let board_w = board[0].len();
-    let stroke_color = edge_stroke_color_for_cell(cell);
+    let stroke_color = edge_stroke_color_for_cell(palette, cell);
+    let (pixel_x, pixel_y) = (cell_rect.x, cell_rect.y);
     let owner_here = owner_at(board_owners, x, y);
 
     let left_kind = if x == 0 {
@@ -327,9 +494,7 @@ fn draw_exposed_cell_edges(
 
     if let Some(stroke_px) = stroke_px_for_edge(left_kind, EdgeDir::Left) {
         let edge_rect = crate::ui::Rect::new(pixel_x, pixel_y, stroke_px, CELL_SIZE);
-        if let Some(clipped) = clip_rect_to_viewport(edge_rect, clip_rect) {
-            gfx.fill_rect(clipped, stroke_color);
-        }
+        gfx.fill_rect(edge_rect, stroke_color);
     }
     if let Some(stroke_px) = stroke_px_for_edge(right_kind, EdgeDir::Right) {
         let edge_rect = crate::ui::Rect::new(
@@ -338,15 +503,11 @@ fn draw_exposed_cell_edges(
             stroke_px,
             CELL_SIZE,
         );
-        if let Some(clipped) = clip_rect_to_viewport(edge_rect, clip_rect) {
-            gfx.fill_rect(clipped, stroke_color);
-        }
+        gfx.fill_rect(edge_rect, stroke_color);
     }
     if let Some(stroke_px) = stroke_px_for_edge(up_kind, EdgeDir::Up) {
         let edge_rect = crate::ui::Rect::new(pixel_x, pixel_y, CELL_SIZE, stroke_px);
-        if let Some(clipped) = clip_rect_to_viewport(edge_rect, clip_rect) {
-            gfx.fill_rect(clipped, stroke_color);
-        }
+        gfx.fill_rect(edge_rect, stroke_color);
     }
     if let Some(stroke_px) = stroke_px_for_edge(down_kind, EdgeDir::Down) {
         let edge_rect = crate::ui::Rect::new(
@@ -355,9 +516,7 @@ fn draw_exposed_cell_edges(
             CELL_SIZE,
             stroke_px,
         );
-        if let Some(clipped) = clip_rect_to_viewport(edge_rect, clip_rect) {
-            gfx.fill_rect(clipped, stroke_color);
-        }
+        gfx.fill_rect(edge_rect, stroke_color);
     }
 }
 
@@ -435,11 +594,11 @@ fn owner_at(board_owners: Option<&[Vec<Option<u32>>]>, x: usize, y: usize) -> Op
 
 fn draw_interior_seam_corner_bridges(
     gfx: &mut dyn crate::graphics::Renderer2d,
+    palette: &CellPalette,
     board: &[Vec<u8>],
     board_owners: Option<&[Vec<Option<u32>>]>,
     offset_x: u32,
     offset_y: u32,
-    clip_rect: crate::ui::Rect,
 ) {
     let Some(_owners) = board_owners else {
         return;
@@ -476,7 +635,7 @@ fn draw_interior_seam_corner_bridges(
             let px = pixel_x.min(i32::MAX as u32) as i32;
             let py = pixel_y.min(i32::MAX as u32) as i32;
             let cell_size_i32 = CELL_SIZE.min(i32::MAX as u32) as i32;
-            let stroke_color = edge_stroke_color_for_cell(cell);
+            let stroke_color = edge_stroke_color_for_cell(palette, cell);
 
             if right_kind == EdgeKind::Interior && up_kind == EdgeKind::Interior {
                 fill_rect_i32_clipped(
@@ -485,7 +644,6 @@ fn draw_interior_seam_corner_bridges(
                     py.saturating_sub(seam_i32),
                     seam_px,
                     seam_px,
-                    clip_rect,
                     stroke_color,
                 );
             }
@@ -495,10 +653,10 @@ fn draw_interior_seam_corner_bridges(
 
 fn draw_inside_corner_bridges(
     gfx: &mut dyn crate::graphics::Renderer2d,
+    palette: &CellPalette,
     board: &[Vec<u8>],
     offset_x: u32,
     offset_y: u32,
-    clip_rect: crate::ui::Rect,
 ) {
     if board.is_empty() {
         return;
@@ -529,14 +687,13 @@ fn draw_inside_corner_bridges(
                 if left != 0 && up != 0 {
                     let owner = board[y + 1][x - 1];
                     if owner != 0 {
-                        let corner = edge_stroke_color_for_cell(owner);
+                        let corner = edge_stroke_color_for_cell(palette, owner);
                         fill_rect_i32_clipped(
                             gfx,
                             px.saturating_sub(stroke_i32),
                             py.saturating_sub(stroke_i32),
                             stroke_px,
                             stroke_px,
-                            clip_rect,
                             corner,
                         );
                     }
@@ -550,14 +707,13 @@ fn draw_inside_corner_bridges(
                 if right != 0 && up != 0 {
                     let owner = board[y + 1][x + 1];
                     if owner != 0 {
-                        let corner = edge_stroke_color_for_cell(owner);
+                        let corner = edge_stroke_color_for_cell(palette, owner);
                         fill_rect_i32_clipped(
                             gfx,
                             px.saturating_add(cell_size_i32),
                             py.saturating_sub(stroke_i32),
                             stroke_px,
                             stroke_px,
-                            clip_rect,
                             corner,
                         );
                     }
@@ -571,14 +727,13 @@ fn draw_inside_corner_bridges(
                 if left != 0 && down != 0 {
                     let owner = board[y - 1][x - 1];
                     if owner != 0 {
-                        let corner = edge_stroke_color_for_cell(owner);
+                        let corner = edge_stroke_color_for_cell(palette, owner);
                         fill_rect_i32_clipped(
                             gfx,
                             px.saturating_sub(stroke_i32),
                             py.saturating_add(cell_size_i32),
                             stroke_px,
                             stroke_px,
-                            clip_rect,
                             corner,
                         );
                     }
@@ -592,14 +747,13 @@ fn draw_inside_corner_bridges(
                 if right != 0 && down != 0 {
                     let owner = board[y - 1][x + 1];
                     if owner != 0 {
-                        let corner = edge_stroke_color_for_cell(owner);
+                        let corner = edge_stroke_color_for_cell(palette, owner);
                         fill_rect_i32_clipped(
                             gfx,
                             px.saturating_add(cell_size_i32),
                             py.saturating_add(cell_size_i32),
                             stroke_px,
                             stroke_px,
-                            clip_rect,
                             corner,
                         );
                     }
@@ -609,22 +763,26 @@ fn draw_inside_corner_bridges(
     }
 }
 
+/// Fill an `i32`-positioned rect, clipped to the active clip rect (or the surface).
 fn fill_rect_i32_clipped(
     gfx: &mut dyn crate::graphics::Renderer2d,
     x: i32,
     y: i32,
     w: u32,
     h: u32,
-    clip_rect: crate::ui::Rect,
     color: [u8; 4],
 ) {
-    if let Some(clipped) = clip_rect_i32_to_viewport(x, y, w, h, clip_rect) {
+    let size = gfx.size();
+    let viewport = gfx
+        .clip_rect()
+        .unwrap_or(crate::ui::Rect::from_size(size.width, size.height));
+    if let Some(clipped) = clip_rect_i32_to_viewport(x, y, w, h, viewport) {
         gfx.fill_rect(clipped, color);
     }
 }
 
-fn edge_stroke_color_for_cell(cell: u8) -> [u8; 4] {
-    let base = color_for_cell(cell);
+fn edge_stroke_color_for_cell(palette: &CellPalette, cell: u8) -> [u8; 4] {
+    let base = color_for_cell(palette, cell);
     [
         ((base[0] as u16 * FILLED_EDGE_STROKE_NUM) / FILLED_EDGE_STROKE_DEN) as u8,
         ((base[1] as u16 * FILLED_EDGE_STROKE_NUM) / FILLED_EDGE_STROKE_DEN) as u8,
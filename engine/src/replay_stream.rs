@@ -0,0 +1,284 @@
+//! Streaming replay for recordings too large to hold in memory.
+//!
+//! `TimeMachine::save_ndjson_file` writes a header line followed by one state per line, plus a
+//! sidecar index of byte offsets (see [`frame_index_path`]). [`StreamingReplay`] keeps only the
+//! index and the current frame in memory and seeks straight to a frame's line on demand.
+
+use std::{
+    ffi::OsString,
+    fs,
+    io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{RECORDING_FORMAT_VERSION, RecordingMeta, ReplayableState, TimeMachine};
+
+/// First line of an NDJSON recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NdjsonHeader {
+    #[serde(default)]
+    meta: RecordingMeta,
+    frames: usize,
+    record_every_n_frames: usize,
+}
+
+/// Sidecar index path for an NDJSON recording: `<path>.idx`.
+pub fn frame_index_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut name = OsString::from(path.as_ref().as_os_str());
+    name.push(".idx");
+    PathBuf::from(name)
+}
+
+impl<State> TimeMachine<State> {
+    /// Saves the history as NDJSON (header line, then one state per line) and writes the
+    /// little-endian `u64` byte offset of every state line to [`frame_index_path`].
+    pub fn save_ndjson_file(&self, path: impl AsRef<Path>) -> io::Result<()>
+    where
+        State: Serialize,
+    {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let header = NdjsonHeader {
+            meta: self.meta.clone(),
+            frames: self.states.len(),
+            record_every_n_frames: self.record_every_n_frames(),
+        };
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        let mut offset = write_json_line(&mut writer, &header)?;
+        let mut offsets = Vec::with_capacity(self.states.len());
+        for state in &self.states {
+            offsets.push(offset);
+            offset += write_json_line(&mut writer, state)?;
+        }
+        writer.flush()?;
+
+        let mut index = BufWriter::new(fs::File::create(frame_index_path(path))?);
+        for offset in offsets {
+            index.write_all(&offset.to_le_bytes())?;
+        }
+        index.flush()
+    }
+}
+
+fn write_json_line(writer: &mut impl Write, value: &impl Serialize) -> io::Result<u64> {
+    let mut line = serde_json::to_vec(value).map_err(io::Error::other)?;
+    line.push(b'\n');
+    writer.write_all(&line)?;
+    Ok(line.len() as u64)
+}
+
+enum FrameSource<State> {
+    /// Frames stay on disk; `offsets[i]` is the byte offset of frame `i`'s line.
+    Indexed {
+        reader: BufReader<fs::File>,
+        offsets: Vec<u64>,
+    },
+    /// Fallback when the index is missing: every frame loaded up front.
+    Loaded(Vec<State>),
+}
+
+/// Replay cursor over an NDJSON recording that reads frames lazily via the frame index.
+pub struct StreamingReplay<State> {
+    path: PathBuf,
+    meta: RecordingMeta,
+    record_every_n_frames: usize,
+    source: FrameSource<State>,
+    frame: usize,
+    state: State,
+}
+
+impl<State: DeserializeOwned + Clone> StreamingReplay<State> {
+    /// Opens `path` at frame 0. Without a usable index the whole recording is loaded into
+    /// memory instead, with a warning on stderr.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut reader = BufReader::new(fs::File::open(&path)?);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let header: NdjsonHeader = parse_line(&line)?;
+        if header.meta.format_version > RECORDING_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "recording {} uses format version {}, but this build only understands up to version {}",
+                    path.display(),
+                    header.meta.format_version,
+                    RECORDING_FORMAT_VERSION
+                ),
+            ));
+        }
+        if header.frames == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "ndjson recording has no states",
+            ));
+        }
+
+        let mut source = match read_index(&frame_index_path(&path), header.frames) {
+            Ok(offsets) => FrameSource::Indexed { reader, offsets },
+            Err(err) => {
+                eprintln!(
+                    "warning: frame index for {} unusable ({err}); loading all {} frames into memory",
+                    path.display(),
+                    header.frames
+                );
+                let mut states: Vec<State> = Vec::with_capacity(header.frames);
+                for line in reader.lines() {
+                    let line = line?;
+                    if !line.is_empty() {
+                        states.push(parse_line(&line)?);
+                    }
+                }
+                if states.len() != header.frames {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "ndjson recording header promises {} frames but has {}",
+                            header.frames,
+                            states.len()
+                        ),
+                    ));
+                }
+                FrameSource::Loaded(states)
+            }
+        };
+
+        let state = match &mut source {
+            FrameSource::Indexed { reader, offsets } => read_frame_at(reader, 0, offsets)?,
+            FrameSource::Loaded(states) => states[0].clone(),
+        };
+        Ok(Self {
+            path,
+            meta: header.meta,
+            record_every_n_frames: header.record_every_n_frames.max(1),
+            source,
+            frame: 0,
+            state,
+        })
+    }
+
+    /// Moves to `frame` (clamped to the last frame) and returns the frame reached.
+    pub fn seek(&mut self, frame: usize) -> io::Result<usize> {
+        let frame = frame.min(self.len() - 1);
+        self.state = match &mut self.source {
+            FrameSource::Indexed { reader, offsets } => read_frame_at(reader, frame, offsets)?,
+            FrameSource::Loaded(states) => states[frame].clone(),
+        };
+        self.frame = frame;
+        Ok(frame)
+    }
+
+    pub fn forward(&mut self, frames: usize) -> io::Result<usize> {
+        self.seek(self.frame.saturating_add(frames))
+    }
+
+    pub fn rewind(&mut self, frames: usize) -> io::Result<usize> {
+        self.seek(self.frame.saturating_sub(frames))
+    }
+}
+
+impl<State> StreamingReplay<State> {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn meta(&self) -> &RecordingMeta {
+        &self.meta
+    }
+
+    pub fn record_every_n_frames(&self) -> usize {
+        self.record_every_n_frames
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.source {
+            FrameSource::Indexed { offsets, .. } => offsets.len(),
+            FrameSource::Loaded(states) => states.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `true` when frames are read from disk through the index rather than held in memory.
+    pub fn is_indexed(&self) -> bool {
+        matches!(self.source, FrameSource::Indexed { .. })
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+}
+
+fn read_index(path: &Path, frames: usize) -> io::Result<Vec<u64>> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)?.read_to_end(&mut bytes)?;
+    if bytes.len() != frames * 8 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("index has {} bytes, expected {}", bytes.len(), frames * 8),
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().expect("chunk is 8 bytes")))
+        .collect())
+}
+
+fn read_frame_at<State: DeserializeOwned>(
+    reader: &mut BufReader<fs::File>,
+    frame: usize,
+    offsets: &[u64],
+) -> io::Result<State> {
+    reader.seek(SeekFrom::Start(offsets[frame]))?;
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    parse_line(&line)
+}
+
+fn parse_line<T: DeserializeOwned>(line: &str) -> io::Result<T> {
+    serde_json::from_str(line.trim_end()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+impl<State> ReplayableState for StreamingReplay<State>
+where
+    State: DeserializeOwned + Clone,
+{
+    fn replay_frame(&self) -> usize {
+        self.frame
+    }
+
+    fn replay_len(&self) -> usize {
+        self.len()
+    }
+
+    fn replay_seek(&mut self, frame: usize) {
+        if let Err(err) = self.seek(frame) {
+            eprintln!("warning: failed to read replay frame {frame}: {err}");
+        }
+    }
+
+    fn replay_forward(&mut self, frames: usize) {
+        let target = self.frame.saturating_add(frames);
+        self.replay_seek(target);
+    }
+
+    fn replay_rewind(&mut self, frames: usize) {
+        let target = self.frame.saturating_sub(frames);
+        self.replay_seek(target);
+    }
+
+    fn replay_load(&self, path: &Path) -> io::Result<Self> {
+        Self::open(path)
+    }
+}
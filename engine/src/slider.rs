@@ -10,6 +10,8 @@ pub struct Slider {
     pub min: f32,
     pub max: f32,
     pub value: f32,
+    /// Values snap to `min + k * step` when set.
+    pub step: Option<f32>,
 }
 
 impl Slider {
@@ -21,9 +23,18 @@ impl Slider {
             min,
             max,
             value,
+            step: None,
         }
     }
 
+    /// Snaps the value (now and on every later set) to multiples of `step` above `min`.
+    /// Non-positive or non-finite steps disable snapping.
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = (step.is_finite() && step > 0.0).then_some(step);
+        self.value = self.snap(self.value);
+        self
+    }
+
     pub fn normalized_value(&self) -> f32 {
         if self.track.w == 0 || (self.max - self.min).abs() <= f32::EPSILON {
             0.0
@@ -33,12 +44,41 @@ impl Slider {
     }
 
     pub fn set_value(&mut self, value: f32) {
-        self.value = clamp_f32(value, self.min, self.max);
+        self.value = self.snap(value);
     }
 
     pub fn value_from_normalized(&self, t: f32) -> f32 {
         let t = t.clamp(0.0, 1.0);
-        self.min + (self.max - self.min) * t
+        self.snap(self.min + (self.max - self.min) * t)
+    }
+
+    /// Clamps `value` into range and applies step snapping.
+    pub fn snap(&self, value: f32) -> f32 {
+        let value = match self.step {
+            Some(step) => self.min + ((value - self.min) / step).round() * step,
+            None => value,
+        };
+        clamp_f32(value, self.min, self.max)
+    }
+
+    /// Value with as many decimals as the step needs (two without a step), e.g. `"0.25"`.
+    pub fn value_label(&self) -> String {
+        let decimals = match self.step {
+            Some(step) => {
+                let mut decimals = 0;
+                while decimals < 4 && (step * 10f32.powi(decimals)).fract().abs() > 1e-3 {
+                    decimals += 1;
+                }
+                decimals as usize
+            }
+            None => 2,
+        };
+        format!("{:.*}", decimals, self.value)
+    }
+
+    /// Position in the range as a right-aligned percentage, e.g. `" 40%"`.
+    pub fn percent_label(&self) -> String {
+        format!("{:>3}%", (self.normalized_value() * 100.0).round() as i32)
     }
 
     pub fn value_from_x(&self, x: u32) -> f32 {
@@ -118,6 +158,21 @@ mod tests {
         assert!((mid - 0.5).abs() < 0.01, "expected ~0.5, got {mid}");
     }
 
+    #[test]
+    fn step_snaps_values_and_sets_label_precision() {
+        let mut slider = Slider::new(Rect::new(0, 0, 101, 8), 0.0, 1.0, 0.33).with_step(0.25);
+        assert_eq!(slider.value, 0.25);
+        assert_eq!(slider.value_label(), "0.25");
+        assert_eq!(slider.value_from_x(62), 0.5);
+        slider.set_value(0.9);
+        assert_eq!(slider.value, 1.0);
+        assert_eq!(slider.percent_label(), "100%");
+
+        let shake = Slider::new(Rect::new(0, 0, 100, 8), 0.0, 100.0, 40.4).with_step(1.0);
+        assert_eq!(shake.value_label(), "40");
+        assert_eq!(shake.percent_label(), " 40%");
+    }
+
     #[test]
     fn thumb_rect_tracks_value() {
         let mut slider = Slider::new(Rect::new(0, 0, 100, 6), 0.0, 1.0, 0.0);
@@ -1,7 +1,16 @@
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::time::Duration;
 
+use crate::slider::Slider;
 use crate::ui::Rect;
 
+/// Pixels a scroll container moves per wheel line (`UiInput::scroll_y` unit).
+pub const SCROLL_LINE_PX: f32 = 24.0;
+
+/// How long the pointer must rest on a tooltip target before `UiEvent::HoverStart`.
+pub const DEFAULT_TOOLTIP_DELAY: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct UiId(pub u32);
 
@@ -12,6 +21,8 @@ pub struct UiAction(pub u32);
 pub struct UiState {
     pub hovered: Option<UiId>,
     pub pressed: Option<UiId>,
+    /// Text input receiving `UiInput::text_events`.
+    pub focused: Option<UiId>,
 }
 
 impl UiState {
@@ -22,19 +33,37 @@ impl UiState {
     pub fn is_pressed(&self, id: UiId) -> bool {
         self.pressed == Some(id)
     }
+
+    pub fn is_focused(&self, id: UiId) -> bool {
+        self.focused == Some(id)
+    }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct UiInput {
     pub mouse_pos: Option<(u32, u32)>,
     pub mouse_down: bool,
     pub mouse_up: bool,
+    /// Characters typed this frame, in order. Backspace arrives as `'\u{8}'` (or DEL) like
+    /// winit's `ReceivedCharacter`; other control characters are ignored.
+    pub text_events: Vec<char>,
+    /// Wheel lines this frame (positive scrolls content up, like `InputFrame::scroll_y`).
+    pub scroll_y: f32,
+    /// Time since the previous `process_input` call; advances the tooltip dwell timer.
+    pub dt: Duration,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum UiEvent {
     Click { id: UiId, action: Option<UiAction> },
     Hover { id: UiId, entered: bool },
+    TextChanged { id: UiId, value: String },
+    Scrolled { id: UiId, offset: u32 },
+    HoverStart { id: UiId },
+    HoverEnd { id: UiId },
+    Toggled { id: UiId, checked: bool },
+    RadioSelected { group: UiId, id: UiId },
+    ValueChanged { id: UiId, value: f32 },
 }
 
 #[derive(Debug, Clone)]
@@ -42,6 +71,17 @@ pub struct UiTree {
     nodes: HashMap<UiId, UiNode>,
     roots: Vec<UiId>,
     state: UiState,
+    tooltips: HashMap<UiId, Tooltip>,
+    tooltip_delay: Duration,
+    /// How long the pointer has rested on the current hover target.
+    hover_dwell: Duration,
+    shown_tooltip: Option<UiId>,
+}
+
+#[derive(Debug, Clone)]
+struct Tooltip {
+    target: UiId,
+    text: String,
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +92,8 @@ struct UiNode {
     children: Vec<UiId>,
     visible: bool,
     enabled: bool,
+    /// Layer relative to the parent; effective z is the sum along the path from the root.
+    z_index: i32,
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +101,11 @@ enum UiNodeKind {
     Canvas,
     Container,
     Button { action: Option<UiAction> },
+    TextInput { value: String },
+    ScrollContainer { content_height: u32, offset: u32 },
+    Checkbox { checked: bool },
+    Radio { group: UiId, selected: bool },
+    Slider { slider: Slider },
 }
 
 impl UiTree {
@@ -67,9 +114,18 @@ impl UiTree {
             nodes: HashMap::new(),
             roots: Vec::new(),
             state: UiState::default(),
+            tooltips: HashMap::new(),
+            tooltip_delay: DEFAULT_TOOLTIP_DELAY,
+            hover_dwell: Duration::ZERO,
+            shown_tooltip: None,
         }
     }
 
+    pub fn with_tooltip_delay(mut self, delay: Duration) -> Self {
+        self.tooltip_delay = delay;
+        self
+    }
+
     pub fn begin_frame(&mut self) {
         self.roots.clear();
         for node in self.nodes.values_mut() {
@@ -89,6 +145,15 @@ impl UiTree {
         self.state.is_pressed(id)
     }
 
+    pub fn is_focused(&self, id: UiId) -> bool {
+        self.state.is_focused(id)
+    }
+
+    /// Focuses a text input (or clears focus with `None`) without a click.
+    pub fn set_focus(&mut self, id: Option<UiId>) {
+        self.state.focused = id;
+    }
+
     pub fn ensure_canvas(&mut self, id: UiId, rect: Rect) {
         self.ensure_node(id, UiNodeKind::Canvas, rect);
     }
@@ -101,6 +166,137 @@ impl UiTree {
         self.ensure_node(id, UiNodeKind::Button { action }, rect);
     }
 
+    /// Container whose children live in a `content_height`-tall column viewed through `rect`.
+    ///
+    /// Children keep their unscrolled rects; hit-testing shifts the pointer by the scroll offset
+    /// and ignores anything outside `rect`. Draw children `scroll_offset(id)` pixels higher,
+    /// clipped to `rect`. The offset survives across frames and is re-clamped when the content
+    /// height or viewport changes.
+    pub fn ensure_scroll_container(&mut self, id: UiId, rect: Rect, content_height: u32) {
+        let max_offset = content_height.saturating_sub(rect.h);
+        let offset = self.scroll_offset(id).min(max_offset);
+        self.ensure_node(
+            id,
+            UiNodeKind::ScrollContainer {
+                content_height,
+                offset,
+            },
+            rect,
+        );
+    }
+
+    /// Current scroll offset in pixels (0 for unknown or non-scrolling nodes).
+    pub fn scroll_offset(&self, id: UiId) -> u32 {
+        match self.nodes.get(&id).map(|node| &node.kind) {
+            Some(UiNodeKind::ScrollContainer { offset, .. }) => *offset,
+            _ => 0,
+        }
+    }
+
+    /// Single-line text field showing `buffer`. Clicking focuses it; while focused, typed
+    /// characters append at the end and backspace removes the last one, each change emitting
+    /// `UiEvent::TextChanged` with the full new value. The caller owns the text and passes it back
+    /// in every frame.
+    pub fn ensure_text_input(&mut self, id: UiId, rect: Rect, buffer: &str) {
+        self.ensure_node(
+            id,
+            UiNodeKind::TextInput {
+                value: buffer.to_string(),
+            },
+            rect,
+        );
+    }
+
+    /// Tooltip `id` showing `text` once the pointer has hovered `target_id` for the tooltip delay.
+    ///
+    /// The tree emits `UiEvent::HoverStart { id }` when the dwell time (summed from `UiInput::dt`)
+    /// is reached and `UiEvent::HoverEnd { id }` when the pointer leaves the target. Tooltips are
+    /// not laid out by the tree; draw `shown_tooltip()` near the cursor while it is `Some`.
+    pub fn ensure_tooltip(&mut self, id: UiId, target_id: UiId, text: &str) {
+        self.tooltips.insert(
+            id,
+            Tooltip {
+                target: target_id,
+                text: text.to_string(),
+            },
+        );
+    }
+
+    /// The tooltip currently shown and its text.
+    pub fn shown_tooltip(&self) -> Option<(UiId, &str)> {
+        let id = self.shown_tooltip?;
+        self.tooltips
+            .get(&id)
+            .map(|tooltip| (id, tooltip.text.as_str()))
+    }
+
+    /// Two-state toggle. Clicking it flips the stored state and emits `UiEvent::Toggled`; the
+    /// caller owns the setting and passes `checked` back in every frame.
+    pub fn ensure_checkbox(&mut self, id: UiId, rect: Rect, checked: bool) {
+        self.ensure_node(id, UiNodeKind::Checkbox { checked }, rect);
+    }
+
+    /// One option of the radio group `group`. Clicking an unselected option selects it, clears
+    /// every other option in the same group and emits `UiEvent::RadioSelected`. Clicking the
+    /// selected option does nothing.
+    pub fn ensure_radio(&mut self, id: UiId, rect: Rect, group: UiId, selected: bool) {
+        self.ensure_node(id, UiNodeKind::Radio { group, selected }, rect);
+    }
+
+    /// Stored state of a checkbox or radio option (`false` for other nodes).
+    pub fn is_checked(&self, id: UiId) -> bool {
+        matches!(
+            self.nodes.get(&id).map(|node| &node.kind),
+            Some(UiNodeKind::Checkbox { checked: true } | UiNodeKind::Radio { selected: true, .. })
+        )
+    }
+
+    /// The selected option of a radio group, if any.
+    pub fn selected_radio(&self, group: UiId) -> Option<UiId> {
+        self.nodes.values().find_map(|node| match node.kind {
+            UiNodeKind::Radio {
+                group: g,
+                selected: true,
+            } if g == group => Some(node.id),
+            _ => None,
+        })
+    }
+
+    /// Horizontal slider over `rect` for values in `range`. Pressing sets the value from the
+    /// pointer x and dragging keeps updating it until release (even outside `rect`), each change
+    /// emitting `UiEvent::ValueChanged`. The caller owns the value and passes it back every frame;
+    /// a step set with `set_slider_step` persists.
+    pub fn ensure_slider(&mut self, id: UiId, rect: Rect, value: f32, range: RangeInclusive<f32>) {
+        let mut slider = Slider::new(rect, *range.start(), *range.end(), value);
+        if let Some(step) = self.slider(id).and_then(|previous| previous.step) {
+            slider = slider.with_step(step);
+        }
+        self.ensure_node(id, UiNodeKind::Slider { slider }, rect);
+    }
+
+    /// Enables (`Some`) or disables step snapping for a slider.
+    pub fn set_slider_step(&mut self, id: UiId, step: Option<f32>) {
+        if let Some(UiNodeKind::Slider { slider }) =
+            self.nodes.get_mut(&id).map(|node| &mut node.kind)
+        {
+            *slider = match step {
+                Some(step) => slider.with_step(step),
+                None => Slider {
+                    step: None,
+                    ..*slider
+                },
+            };
+        }
+    }
+
+    /// Slider geometry and current value for drawing (thumb, fill, `value_label`).
+    pub fn slider(&self, id: UiId) -> Option<Slider> {
+        match self.nodes.get(&id).map(|node| &node.kind) {
+            Some(UiNodeKind::Slider { slider }) => Some(*slider),
+            _ => None,
+        }
+    }
+
     pub fn add_root(&mut self, id: UiId) {
         self.roots.push(id);
     }
@@ -123,6 +319,30 @@ impl UiTree {
         }
     }
 
+    /// Layers `id` (and its subtree) above or below its siblings. Defaults to 0 and persists
+    /// across frames. Input goes to the hit with the highest effective z; ties go to the node
+    /// added last.
+    pub fn set_z_index(&mut self, id: UiId, z_index: i32) {
+        if let Some(node) = self.nodes.get_mut(&id) {
+            node.z_index = z_index;
+        }
+    }
+
+    pub fn z_index(&self, id: UiId) -> i32 {
+        self.nodes.get(&id).map_or(0, |node| node.z_index)
+    }
+
+    /// Visible nodes reachable from the roots in back-to-front draw order: ascending effective z,
+    /// with add order kept among equal layers.
+    pub fn nodes_by_z(&self) -> Vec<UiId> {
+        let mut ordered = Vec::new();
+        for root in &self.roots {
+            self.collect_draw_order(*root, 0, &mut ordered);
+        }
+        ordered.sort_by_key(|(z, _)| *z);
+        ordered.into_iter().map(|(_, id)| id).collect()
+    }
+
     pub fn process_input(&mut self, input: UiInput) -> Vec<UiEvent> {
         let mut events = Vec::new();
         if let Some(pos) = input.mouse_pos {
@@ -141,35 +361,195 @@ impl UiTree {
                     });
                 }
                 self.state.hovered = hovered;
+                self.hover_dwell = Duration::ZERO;
+                events.extend(self.shown_tooltip.take().map(|id| UiEvent::HoverEnd { id }));
             }
         }
+        events.extend(self.advance_tooltip(input.dt));
 
         if input.mouse_down {
             self.state.pressed = self.state.hovered;
+            self.state.focused = self
+                .state
+                .hovered
+                .filter(|id| self.text_input_value(*id).is_some());
+        }
+        if let Some(pos) = input.mouse_pos {
+            events.extend(self.drag_slider(pos.0));
         }
 
         if input.mouse_up {
             let pressed = self.state.pressed;
-            if let (Some(pressed_id), Some(hovered_id)) = (pressed, self.state.hovered) {
-                if pressed_id == hovered_id {
-                    if let Some(node) = self.nodes.get(&pressed_id) {
-                        if let UiNodeKind::Button { action } = node.kind {
-                            if node.enabled {
-                                events.push(UiEvent::Click {
-                                    id: pressed_id,
-                                    action,
-                                });
-                            }
-                        }
-                    }
-                }
+            if let (Some(pressed_id), Some(hovered_id)) = (pressed, self.state.hovered)
+                && pressed_id == hovered_id
+            {
+                events.extend(self.activate(pressed_id));
             }
             self.state.pressed = None;
         }
 
+        events.extend(self.apply_text_events(&input.text_events));
+        if let Some(pos) = input.mouse_pos.filter(|_| input.scroll_y != 0.0) {
+            events.extend(self.apply_scroll(pos, input.scroll_y));
+        }
+
         events
     }
 
+    /// Click on an enabled interactive node: buttons report their action, checkboxes flip and
+    /// radios select themselves.
+    fn activate(&mut self, id: UiId) -> Option<UiEvent> {
+        let node = self.nodes.get_mut(&id).filter(|node| node.enabled)?;
+        match &mut node.kind {
+            UiNodeKind::Button { action } => Some(UiEvent::Click {
+                id,
+                action: *action,
+            }),
+            UiNodeKind::Checkbox { checked } => {
+                *checked = !*checked;
+                Some(UiEvent::Toggled {
+                    id,
+                    checked: *checked,
+                })
+            }
+            UiNodeKind::Radio { group, selected } => {
+                if *selected {
+                    return None;
+                }
+                let group = *group;
+                for other in self.nodes.values_mut() {
+                    if let UiNodeKind::Radio { group: g, selected } = &mut other.kind
+                        && *g == group
+                    {
+                        *selected = other.id == id;
+                    }
+                }
+                Some(UiEvent::RadioSelected { group, id })
+            }
+            _ => None,
+        }
+    }
+
+    /// Moves the pressed slider (if any) to pointer x.
+    fn drag_slider(&mut self, x: u32) -> Option<UiEvent> {
+        let id = self.state.pressed?;
+        let node = self.nodes.get_mut(&id).filter(|node| node.enabled)?;
+        let UiNodeKind::Slider { slider } = &mut node.kind else {
+            return None;
+        };
+        let value = slider.value_from_x(x);
+        if value == slider.value {
+            return None;
+        }
+        slider.value = value;
+        Some(UiEvent::ValueChanged { id, value })
+    }
+
+    fn advance_tooltip(&mut self, dt: Duration) -> Option<UiEvent> {
+        let hovered = self.state.hovered?;
+        if self.shown_tooltip.is_some() {
+            return None;
+        }
+        let id = self
+            .tooltips
+            .iter()
+            .filter(|(_, tooltip)| tooltip.target == hovered)
+            .map(|(id, _)| *id)
+            .min_by_key(|id| id.0)?;
+        self.hover_dwell = self.hover_dwell.saturating_add(dt);
+        if self.hover_dwell < self.tooltip_delay {
+            return None;
+        }
+        self.shown_tooltip = Some(id);
+        Some(UiEvent::HoverStart { id })
+    }
+
+    /// Current value of an enabled, visible text input.
+    fn text_input_value(&self, id: UiId) -> Option<&str> {
+        let node = self.nodes.get(&id)?;
+        match &node.kind {
+            UiNodeKind::TextInput { value } if node.visible && node.enabled => Some(value),
+            _ => None,
+        }
+    }
+
+    fn apply_scroll(&mut self, pos: (u32, u32), lines: f32) -> Option<UiEvent> {
+        let id = self
+            .roots
+            .iter()
+            .rev()
+            .find_map(|root| self.scroll_target(*root, pos))?;
+        let node = self.nodes.get_mut(&id)?;
+        let UiNodeKind::ScrollContainer {
+            content_height,
+            offset,
+        } = &mut node.kind
+        else {
+            return None;
+        };
+        let max_offset = content_height.saturating_sub(node.rect.h);
+        let next = (*offset as f32 - lines * SCROLL_LINE_PX).clamp(0.0, max_offset as f32) as u32;
+        if next == *offset {
+            return None;
+        }
+        *offset = next;
+        Some(UiEvent::Scrolled { id, offset: next })
+    }
+
+    /// Innermost visible scroll container under `pos`, searching from `id` down.
+    fn scroll_target(&self, id: UiId, pos: (u32, u32)) -> Option<UiId> {
+        let node = self.nodes.get(&id)?;
+        if !node.visible || !node.rect.contains(pos.0, pos.1) {
+            return None;
+        }
+        let child_pos = match node.kind {
+            UiNodeKind::ScrollContainer { offset, .. } => (pos.0, pos.1.saturating_add(offset)),
+            UiNodeKind::Canvas | UiNodeKind::Container => pos,
+            UiNodeKind::Button { .. }
+            | UiNodeKind::TextInput { .. }
+            | UiNodeKind::Checkbox { .. }
+            | UiNodeKind::Radio { .. }
+            | UiNodeKind::Slider { .. } => return None,
+        };
+        let inner = node
+            .children
+            .iter()
+            .rev()
+            .find_map(|child| self.scroll_target(*child, child_pos));
+        match node.kind {
+            UiNodeKind::ScrollContainer { .. } => inner.or(Some(id)),
+            _ => inner,
+        }
+    }
+
+    fn apply_text_events(&mut self, chars: &[char]) -> Option<UiEvent> {
+        if chars.is_empty() {
+            return None;
+        }
+        let id = self.state.focused?;
+        let mut value = self.text_input_value(id)?.to_string();
+        let mut changed = false;
+        for &c in chars {
+            match c {
+                '\u{8}' | '\u{7f}' => changed |= value.pop().is_some(),
+                c if c.is_control() => {}
+                c => {
+                    value.push(c);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return None;
+        }
+        if let Some(node) = self.nodes.get_mut(&id) {
+            node.kind = UiNodeKind::TextInput {
+                value: value.clone(),
+            };
+        }
+        Some(UiEvent::TextChanged { id, value })
+    }
+
     fn ensure_node(&mut self, id: UiId, kind: UiNodeKind, rect: Rect) {
         let node = self.nodes.entry(id).or_insert_with(|| UiNode {
             id,
@@ -178,6 +558,7 @@ impl UiTree {
             children: Vec::new(),
             visible: true,
             enabled: true,
+            z_index: 0,
         });
         node.kind = kind;
         node.rect = rect;
@@ -185,38 +566,400 @@ impl UiTree {
     }
 
     fn hit_test(&self, pos: (u32, u32)) -> Option<UiId> {
-        for root in self.roots.iter().rev() {
-            if let Some(hit) = self.hit_test_node(*root, pos) {
-                return Some(hit);
+        let mut best = None;
+        for root in &self.roots {
+            self.collect_topmost_hit(*root, pos, 0, &mut best);
+        }
+        best.map(|(_, id)| id)
+    }
+
+    /// Walks in add order, keeping the interactive hit with the highest effective z (later nodes
+    /// win ties, matching the old reverse-order first-hit behavior).
+    fn collect_topmost_hit(
+        &self,
+        id: UiId,
+        pos: (u32, u32),
+        parent_z: i32,
+        best: &mut Option<(i32, UiId)>,
+    ) {
+        let Some(node) = self.nodes.get(&id) else {
+            return;
+        };
+        if !node.visible || !node.rect.contains(pos.0, pos.1) {
+            return;
+        }
+        let z = parent_z.saturating_add(node.z_index);
+        let child_pos = match node.kind {
+            UiNodeKind::Button { .. }
+            | UiNodeKind::TextInput { .. }
+            | UiNodeKind::Checkbox { .. }
+            | UiNodeKind::Radio { .. }
+            | UiNodeKind::Slider { .. } => {
+                if node.enabled && best.is_none_or(|(best_z, _)| z >= best_z) {
+                    *best = Some((z, id));
+                }
+                return;
             }
+            UiNodeKind::Canvas | UiNodeKind::Container => pos,
+            // Children are laid out in content space; `pos` is already inside the viewport.
+            UiNodeKind::ScrollContainer { offset, .. } => (pos.0, pos.1.saturating_add(offset)),
+        };
+        for child in &node.children {
+            self.collect_topmost_hit(*child, child_pos, z, best);
         }
-        None
     }
 
-    fn hit_test_node(&self, id: UiId, pos: (u32, u32)) -> Option<UiId> {
-        let node = self.nodes.get(&id)?;
+    fn collect_draw_order(&self, id: UiId, parent_z: i32, out: &mut Vec<(i32, UiId)>) {
+        let Some(node) = self.nodes.get(&id) else {
+            return;
+        };
         if !node.visible {
-            return None;
+            return;
         }
-        if !node.rect.contains(pos.0, pos.1) {
-            return None;
+        let z = parent_z.saturating_add(node.z_index);
+        out.push((z, id));
+        for child in &node.children {
+            self.collect_draw_order(*child, z, out);
         }
-        match node.kind {
-            UiNodeKind::Button { .. } => {
-                if node.enabled {
-                    Some(id)
-                } else {
-                    None
-                }
-            }
-            UiNodeKind::Canvas | UiNodeKind::Container => {
-                for child in node.children.iter().rev() {
-                    if let Some(hit) = self.hit_test_node(*child, pos) {
-                        return Some(hit);
-                    }
-                }
-                None
-            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANVAS: UiId = UiId(1);
+    const FIELD: UiId = UiId(2);
+
+    fn tree_with_field(buffer: &str) -> UiTree {
+        let mut tree = UiTree::new();
+        tree.begin_frame();
+        tree.ensure_canvas(CANVAS, Rect::from_size(100, 100));
+        tree.add_root(CANVAS);
+        tree.ensure_text_input(FIELD, Rect::new(10, 10, 50, 12), buffer);
+        tree.add_child(CANVAS, FIELD);
+        tree
+    }
+
+    fn typed(text: &str) -> UiInput {
+        UiInput {
+            text_events: text.chars().collect(),
+            ..UiInput::default()
+        }
+    }
+
+    #[test]
+    fn text_input_edits_only_while_focused() {
+        let mut tree = tree_with_field("MOSS");
+        assert!(tree.process_input(typed("X")).is_empty());
+
+        let click = UiInput {
+            mouse_pos: Some((20, 15)),
+            mouse_down: true,
+            ..UiInput::default()
+        };
+        let _ = tree.process_input(click);
+        assert!(tree.is_focused(FIELD));
+
+        let events = tree.process_input(typed("\u{8}S\u{1b}!"));
+        assert_eq!(
+            events,
+            vec![UiEvent::TextChanged {
+                id: FIELD,
+                value: "MOSS!".to_string(),
+            }]
+        );
+
+        // Clicking empty canvas drops focus.
+        let _ = tree.process_input(UiInput {
+            mouse_pos: Some((90, 90)),
+            mouse_down: true,
+            ..UiInput::default()
+        });
+        assert!(!tree.is_focused(FIELD));
+        assert!(tree.process_input(typed("Z")).is_empty());
+    }
+
+    #[test]
+    fn scroll_container_offsets_child_hits_and_clamps() {
+        const LIST: UiId = UiId(3);
+        const ROW: UiId = UiId(4);
+        let mut tree = UiTree::new();
+        let build = |tree: &mut UiTree| {
+            tree.begin_frame();
+            tree.ensure_canvas(CANVAS, Rect::from_size(100, 100));
+            tree.add_root(CANVAS);
+            tree.ensure_scroll_container(LIST, Rect::new(0, 0, 100, 50), 120);
+            tree.add_child(CANVAS, LIST);
+            // Fourth 20px row: starts below the 50px viewport until scrolled.
+            tree.ensure_button(ROW, Rect::new(0, 60, 100, 20), Some(UiAction(7)));
+            tree.add_child(LIST, ROW);
+        };
+        build(&mut tree);
+
+        let hover = |y| UiInput {
+            mouse_pos: Some((10, y)),
+            ..UiInput::default()
+        };
+        let _ = tree.process_input(hover(70));
+        assert_eq!(
+            tree.state().hovered,
+            None,
+            "row outside viewport is clipped"
+        );
+
+        let events = tree.process_input(UiInput {
+            scroll_y: -1.0,
+            ..hover(30)
+        });
+        assert!(events.contains(&UiEvent::Scrolled {
+            id: LIST,
+            offset: SCROLL_LINE_PX as u32,
+        }));
+        let _ = tree.process_input(hover(45));
+        assert!(tree.is_hovered(ROW), "content y 69 lands on the row");
+
+        // Over-scrolling clamps to content_height - viewport height, and the offset persists
+        // across rebuilds.
+        let events = tree.process_input(UiInput {
+            scroll_y: -10.0,
+            ..hover(30)
+        });
+        assert!(events.contains(&UiEvent::Scrolled {
+            id: LIST,
+            offset: 70,
+        }));
+        build(&mut tree);
+        assert_eq!(tree.scroll_offset(LIST), 70);
+    }
+
+    #[test]
+    fn z_index_picks_topmost_hit_regardless_of_add_order() {
+        const POPUP: UiId = UiId(5);
+        const POPUP_BUTTON: UiId = UiId(6);
+        const BOARD_BUTTON: UiId = UiId(7);
+        let mut tree = UiTree::new();
+        tree.begin_frame();
+        tree.ensure_canvas(CANVAS, Rect::from_size(100, 100));
+        tree.add_root(CANVAS);
+        // The popup is added before the button it overlaps but layered above it.
+        tree.ensure_container(POPUP, Rect::new(0, 0, 60, 60));
+        tree.set_z_index(POPUP, 10);
+        tree.add_child(CANVAS, POPUP);
+        tree.ensure_button(POPUP_BUTTON, Rect::new(10, 10, 40, 20), None);
+        tree.add_child(POPUP, POPUP_BUTTON);
+        tree.ensure_button(BOARD_BUTTON, Rect::new(0, 0, 100, 100), None);
+        tree.add_child(CANVAS, BOARD_BUTTON);
+
+        let hover = |x, y| UiInput {
+            mouse_pos: Some((x, y)),
+            ..UiInput::default()
+        };
+        let _ = tree.process_input(hover(20, 15));
+        assert!(tree.is_hovered(POPUP_BUTTON));
+        let _ = tree.process_input(hover(20, 50));
+        assert!(
+            tree.is_hovered(BOARD_BUTTON),
+            "popup background has no button"
+        );
+
+        assert_eq!(
+            tree.nodes_by_z(),
+            vec![CANVAS, BOARD_BUTTON, POPUP, POPUP_BUTTON]
+        );
+
+        tree.set_z_index(POPUP, 0);
+        let _ = tree.process_input(hover(20, 15));
+        assert!(
+            tree.is_hovered(BOARD_BUTTON),
+            "equal layers fall back to add order"
+        );
+    }
+
+    #[test]
+    fn backspace_on_empty_buffer_emits_nothing() {
+        let mut tree = tree_with_field("");
+        tree.set_focus(Some(FIELD));
+        assert!(tree.process_input(typed("\u{8}")).is_empty());
+        assert_eq!(
+            tree.process_input(typed("A")),
+            vec![UiEvent::TextChanged {
+                id: FIELD,
+                value: "A".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn tooltip_shows_after_dwell_and_hides_on_leave() {
+        const BUTTON: UiId = UiId(3);
+        const TIP: UiId = UiId(4);
+        let mut tree = UiTree::new().with_tooltip_delay(Duration::from_millis(300));
+        tree.begin_frame();
+        tree.ensure_canvas(CANVAS, Rect::from_size(100, 100));
+        tree.add_root(CANVAS);
+        tree.ensure_button(BUTTON, Rect::new(10, 10, 20, 20), None);
+        tree.add_child(CANVAS, BUTTON);
+        tree.ensure_tooltip(TIP, BUTTON, "Buys the node");
+
+        let frame = |x, ms| UiInput {
+            mouse_pos: Some((x, 15)),
+            dt: Duration::from_millis(ms),
+            ..UiInput::default()
+        };
+
+        let events = tree.process_input(frame(15, 200));
+        assert!(!events.contains(&UiEvent::HoverStart { id: TIP }));
+        assert_eq!(tree.shown_tooltip(), None);
+
+        assert_eq!(
+            tree.process_input(frame(16, 100)),
+            vec![UiEvent::HoverStart { id: TIP }]
+        );
+        assert_eq!(tree.shown_tooltip(), Some((TIP, "Buys the node")));
+        assert!(tree.process_input(frame(16, 1000)).is_empty());
+
+        let events = tree.process_input(frame(60, 16));
+        assert!(events.contains(&UiEvent::HoverEnd { id: TIP }));
+        assert_eq!(tree.shown_tooltip(), None);
+
+        // Re-entering starts the dwell over.
+        let events = tree.process_input(frame(15, 250));
+        assert!(!events.contains(&UiEvent::HoverStart { id: TIP }));
+    }
+
+    #[test]
+    fn checkbox_click_toggles_and_reports_new_state() {
+        const BOX: UiId = UiId(3);
+        let mut tree = UiTree::new();
+        tree.begin_frame();
+        tree.ensure_canvas(CANVAS, Rect::from_size(100, 100));
+        tree.add_root(CANVAS);
+        tree.ensure_checkbox(BOX, Rect::new(10, 10, 20, 20), false);
+        tree.add_child(CANVAS, BOX);
+
+        let click = UiInput {
+            mouse_pos: Some((15, 15)),
+            mouse_down: true,
+            mouse_up: true,
+            ..UiInput::default()
+        };
+        let events = tree.process_input(click.clone());
+        assert!(events.contains(&UiEvent::Toggled {
+            id: BOX,
+            checked: true
+        }));
+        assert!(tree.is_checked(BOX));
+        assert!(
+            tree.process_input(click.clone())
+                .contains(&UiEvent::Toggled {
+                    id: BOX,
+                    checked: false
+                })
+        );
+
+        tree.set_enabled(BOX, false);
+        let events = tree.process_input(click);
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, UiEvent::Toggled { .. }))
+        );
+        assert!(!tree.is_checked(BOX));
+    }
+
+    #[test]
+    fn radio_selection_is_exclusive_within_its_group() {
+        const SPEED: UiId = UiId(10);
+        const OTHER: UiId = UiId(20);
+        const SLOW: UiId = UiId(11);
+        const FAST: UiId = UiId(12);
+        const UNRELATED: UiId = UiId(21);
+        let mut tree = UiTree::new();
+        tree.begin_frame();
+        tree.ensure_canvas(CANVAS, Rect::from_size(100, 100));
+        tree.add_root(CANVAS);
+        for (id, x, group, selected) in [
+            (SLOW, 0, SPEED, true),
+            (FAST, 20, SPEED, false),
+            (UNRELATED, 40, OTHER, true),
+        ] {
+            tree.ensure_radio(id, Rect::new(x, 0, 10, 10), group, selected);
+            tree.add_child(CANVAS, id);
         }
+        let click = |x| UiInput {
+            mouse_pos: Some((x, 5)),
+            mouse_down: true,
+            mouse_up: true,
+            ..UiInput::default()
+        };
+
+        assert!(
+            tree.process_input(click(25))
+                .contains(&UiEvent::RadioSelected {
+                    group: SPEED,
+                    id: FAST
+                })
+        );
+        assert_eq!(tree.selected_radio(SPEED), Some(FAST));
+        assert!(!tree.is_checked(SLOW));
+        assert_eq!(tree.selected_radio(OTHER), Some(UNRELATED));
+
+        // Re-clicking the selected option is a no-op.
+        let events = tree.process_input(click(26));
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, UiEvent::RadioSelected { .. }))
+        );
+        assert_eq!(tree.selected_radio(SPEED), Some(FAST));
+    }
+
+    #[test]
+    fn slider_press_positions_and_drag_updates_until_release() {
+        const VOLUME: UiId = UiId(3);
+        let mut tree = UiTree::new();
+        tree.begin_frame();
+        tree.ensure_canvas(CANVAS, Rect::from_size(200, 100));
+        tree.add_root(CANVAS);
+        tree.ensure_slider(VOLUME, Rect::new(0, 10, 101, 10), 0.0, 0.0..=1.0);
+        tree.set_slider_step(VOLUME, Some(0.1));
+        tree.add_child(CANVAS, VOLUME);
+        let at = |x, down, up| UiInput {
+            mouse_pos: Some((x, 15)),
+            mouse_down: down,
+            mouse_up: up,
+            ..UiInput::default()
+        };
+
+        let events = tree.process_input(at(52, true, false));
+        assert!(events.contains(&UiEvent::ValueChanged {
+            id: VOLUME,
+            value: 0.5
+        }));
+
+        // Dragging past the end clamps; leaving the track does not drop the drag.
+        let events = tree.process_input(at(180, false, false));
+        assert!(events.contains(&UiEvent::ValueChanged {
+            id: VOLUME,
+            value: 1.0
+        }));
+        assert!(tree.process_input(at(180, false, true)).is_empty());
+        let events = tree.process_input(at(20, false, false));
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, UiEvent::ValueChanged { .. })),
+            "released slider stops following the pointer"
+        );
+
+        // The step survives the caller re-declaring the slider next frame.
+        tree.begin_frame();
+        tree.add_root(CANVAS);
+        tree.ensure_slider(VOLUME, Rect::new(0, 10, 101, 10), 0.33, 0.0..=1.0);
+        tree.add_child(CANVAS, VOLUME);
+        let slider = tree.slider(VOLUME).expect("slider node");
+        assert!((slider.value - 0.3).abs() < 1e-6);
+        assert_eq!(slider.value_label(), "0.3");
     }
 }
@@ -6,9 +6,13 @@ use std::{
 
 use engine::{
     GameLogic,
-    graphics::CpuRenderer,
+    graphics::{CpuRenderer, Texture},
+    pixels_renderer::save_rgba_png,
     recording::{Mp4Config, Mp4Recorder},
-    regression::{VideoCaptureConfig, record_state_and_video_then_replay_and_compare},
+    regression::{
+        VideoCaptureConfig, compare_images, find_divergence, image_diff_path,
+        record_state_and_video_then_replay_and_compare, validate_recording,
+    },
     render::{CELL_SIZE, draw_board},
     surface::SurfaceSize,
 };
@@ -107,3 +111,117 @@ fn engine_regression_harness_record_replay_video_roundtrips() {
     let _ = fs::remove_file(artifacts.replay_mp4);
     let _ = fs::remove_dir_all(out_dir);
 }
+
+#[test]
+fn validate_recording_reports_first_violating_frame() {
+    let game = GridGame {
+        width: 3,
+        height: 2,
+    };
+    let mut runner = engine::HeadlessRunner::new(game);
+    for (x, v) in [(0, 1), (1, 2), (2, 9), (0, 3)] {
+        runner.step(SetCell { x, y: 0, v });
+    }
+    let tm = runner.timemachine();
+
+    let small_cells = |_frame: usize, state: &Vec<Vec<u8>>| {
+        let too_big = state.iter().flatten().find(|&&v| v > 5);
+        too_big.map_or(Ok(()), |v| Err(format!("cell value {v} exceeds 5")))
+    };
+    let err = validate_recording(tm, small_cells).expect_err("frame 3 writes a 9");
+    assert_eq!(err.frame, 3);
+    assert_eq!(err.message, "cell value 9 exceeds 5");
+    assert_eq!(
+        err.to_string(),
+        "invariant violated at frame 3: cell value 9 exceeds 5"
+    );
+
+    let mut visited = 0;
+    validate_recording(tm, |_, state: &Vec<Vec<u8>>| {
+        visited += 1;
+        if state.len() == 2 {
+            Ok(())
+        } else {
+            Err("height changed".to_string())
+        }
+    })
+    .expect("clean recording validates");
+    assert_eq!(visited, tm.len());
+}
+
+#[test]
+fn find_divergence_reports_first_mismatching_recorded_frame() {
+    let game = GridGame {
+        width: 3,
+        height: 1,
+    };
+    let inputs: Vec<SetCell> = (0..6)
+        .map(|i| SetCell {
+            x: i % 3,
+            y: 0,
+            v: i as u8 + 1,
+        })
+        .collect();
+    let mut runner = engine::HeadlessRunner::new(game.clone());
+    runner.set_record_every_n_frames(2);
+    runner.run(inputs.iter().copied());
+    let tm = runner.timemachine();
+    assert_eq!(tm.len(), 4);
+
+    assert_eq!(find_divergence(game.clone(), tm, &inputs), None);
+
+    // Step 3 (recorded frame 2) writes a different value; step 2 alone is never compared.
+    let mut changed = inputs.clone();
+    changed[3].v = 42;
+    assert_eq!(find_divergence(game.clone(), tm, &changed), Some(2));
+
+    assert_eq!(find_divergence(game.clone(), tm, &inputs[..3]), Some(2));
+
+    let bigger = GridGame {
+        width: 4,
+        height: 1,
+    };
+    assert_eq!(find_divergence(bigger, tm, &inputs), Some(0));
+}
+
+#[test]
+fn compare_images_counts_pixels_beyond_tolerance_and_writes_diff() {
+    let dir = unique_temp_dir();
+    let reference_path = dir.join("menu.png");
+    let size = SurfaceSize::new(4, 2);
+    let reference = vec![100u8; 4 * 2 * 4];
+    save_rgba_png(&reference_path, size, &reference).expect("write reference");
+
+    let mut rgba = reference.clone();
+    rgba[0] = 103; // within tolerance
+    rgba[4] = 120; // beyond tolerance
+    rgba[8 + 3] = 0; // alpha counts too
+    let actual = Texture::from_rgba(4, 2, rgba).expect("texture");
+
+    let diff_dir = dir.join("diffs");
+    let diff = compare_images(&actual, &reference_path, 4, &diff_dir).expect("compare");
+    assert_eq!(diff.differing_pixels, 2);
+    assert_eq!(diff.total_pixels(), 8);
+    assert!((diff.differing_fraction() - 0.25).abs() < f64::EPSILON);
+
+    let diff_path = image_diff_path(&reference_path, &diff_dir);
+    assert_eq!(diff_path, diff_dir.join("menu.diff.png"));
+    // Nothing is written next to the reference.
+    assert_eq!(fs::read_dir(&dir).expect("read dir").count(), 2);
+    let diff_image = Texture::load_png(&diff_path).expect("diff png");
+    assert_eq!(diff_image.pixel(1, 0), Some([255, 0, 0, 255]));
+    assert_ne!(diff_image.pixel(0, 0), Some([255, 0, 0, 255]));
+
+    let exact = Texture::from_rgba(4, 2, reference).expect("texture");
+    assert!(
+        compare_images(&exact, &reference_path, 0, &diff_dir)
+            .unwrap()
+            .is_match()
+    );
+    assert!(!diff_path.exists());
+
+    let wrong_size = Texture::from_rgba(2, 2, vec![0; 16]).expect("texture");
+    assert!(compare_images(&wrong_size, &reference_path, 255, &diff_dir).is_err());
+
+    let _ = fs::remove_dir_all(&dir);
+}
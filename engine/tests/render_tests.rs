@@ -1,18 +1,23 @@
-use engine::graphics::CpuRenderer;
+use engine::graphics::{CpuRenderer, DirtyTracker, Renderer2d};
 use engine::render::{
-    CELL_SIZE, clip_rect_i32_to_viewport, clip_rect_to_viewport, color_for_cell, draw_board,
-    draw_board_cells, draw_board_cells_in_rect, draw_board_cells_in_rect_clipped,
+    CELL_SIZE, CellPalette, clip_rect_i32_to_viewport, clip_rect_to_viewport, color_for_cell,
+    draw_board, draw_board_cells, draw_board_cells_in_rect, draw_board_cells_in_rect_clipped,
     draw_board_cells_in_rect_clipped_with_owners,
+    draw_board_cells_in_rect_clipped_with_owners_and_palette,
+    draw_board_cells_in_rect_with_palette,
 };
 use engine::surface::SurfaceSize;
 use engine::ui::Rect;
 
 #[test]
 fn color_mapping_is_stable() {
-    assert_eq!(color_for_cell(0), [0, 0, 0, 255]);
-    assert_eq!(color_for_cell(1), [0, 229, 255, 255]);
-    assert_eq!(color_for_cell(2), [255, 215, 0, 255]);
-    assert_eq!(color_for_cell(3), [150, 208, 232, 255]);
+    assert_eq!(color_for_cell(&CellPalette::CLASSIC, 0), [0, 0, 0, 255]);
+    assert_eq!(color_for_cell(&CellPalette::CLASSIC, 1), [0, 229, 255, 255]);
+    assert_eq!(color_for_cell(&CellPalette::CLASSIC, 2), [255, 215, 0, 255]);
+    assert_eq!(
+        color_for_cell(&CellPalette::CLASSIC, 3),
+        [150, 208, 232, 255]
+    );
 }
 
 #[test]
@@ -175,7 +180,7 @@ fn draw_board_cells_in_rect_applies_origin_offset() {
         draw_board_cells_in_rect(&mut gfx, &board, shifted_rect);
     }
 
-    let piece = color_for_cell(1);
+    let piece = color_for_cell(&CellPalette::CLASSIC, 1);
     let x = base_rect.x + 6;
     let y_base = base_rect.y + 6;
     let y_shifted = shifted_rect.y + 6;
@@ -221,7 +226,7 @@ fn draw_board_cells_in_rect_clipped_keeps_content_inside_viewport() {
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped(&mut gfx, &board, shifted_content, viewport);
 
-    let piece = color_for_cell(1);
+    let piece = color_for_cell(&CellPalette::CLASSIC, 1);
     let stroke = [
         ((piece[0] as u16 * 11) / 20) as u8,
         ((piece[1] as u16 * 11) / 20) as u8,
@@ -258,7 +263,7 @@ fn draw_board_cells_in_rect_clipped_draws_only_exposed_edges_for_filled_cells()
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped(&mut gfx, &board, viewport, viewport);
 
-    let fill = color_for_cell(1);
+    let fill = color_for_cell(&CellPalette::CLASSIC, 1);
     let stroke = [
         ((fill[0] as u16 * 11) / 20) as u8,
         ((fill[1] as u16 * 11) / 20) as u8,
@@ -317,7 +322,7 @@ fn draw_board_cells_in_rect_clipped_with_owners_draws_seam_between_pieces() {
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped_with_owners(&mut gfx, &board, Some(&owners), viewport, viewport);
 
-    let fill = color_for_cell(1);
+    let fill = color_for_cell(&CellPalette::CLASSIC, 1);
     let stroke = [
         ((fill[0] as u16 * 11) / 20) as u8,
         ((fill[1] as u16 * 11) / 20) as u8,
@@ -354,7 +359,7 @@ fn draw_board_cells_in_rect_clipped_with_owners_keeps_same_piece_interior_unstro
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped_with_owners(&mut gfx, &board, Some(&owners), viewport, viewport);
 
-    let fill = color_for_cell(1);
+    let fill = color_for_cell(&CellPalette::CLASSIC, 1);
 
     // Adjacent cells owned by the same piece should still look merged.
     assert_eq!(
@@ -386,14 +391,14 @@ fn draw_board_cells_in_rect_clipped_with_owners_draws_seam_for_mixed_owner_neigh
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped_with_owners(&mut gfx, &board, Some(&owners), viewport, viewport);
 
-    let left_fill = color_for_cell(1);
+    let left_fill = color_for_cell(&CellPalette::CLASSIC, 1);
     let left_stroke = [
         ((left_fill[0] as u16 * 11) / 20) as u8,
         ((left_fill[1] as u16 * 11) / 20) as u8,
         ((left_fill[2] as u16 * 11) / 20) as u8,
         left_fill[3],
     ];
-    let right_fill = color_for_cell(9);
+    let right_fill = color_for_cell(&CellPalette::CLASSIC, 9);
 
     // Seam is drawn once from the canonical side.
     assert_eq!(
@@ -424,7 +429,7 @@ fn draw_board_cells_in_rect_clipped_with_owners_uses_three_px_internal_seams() {
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped_with_owners(&mut gfx, &board, Some(&owners), viewport, viewport);
 
-    let left_fill = color_for_cell(1);
+    let left_fill = color_for_cell(&CellPalette::CLASSIC, 1);
     let left_stroke = [
         ((left_fill[0] as u16 * 11) / 20) as u8,
         ((left_fill[1] as u16 * 11) / 20) as u8,
@@ -468,7 +473,7 @@ fn draw_board_cells_in_rect_clipped_with_owners_bridges_interior_seam_corners()
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped_with_owners(&mut gfx, &board, Some(&owners), viewport, viewport);
 
-    let fill = color_for_cell(1);
+    let fill = color_for_cell(&CellPalette::CLASSIC, 1);
     let stroke = [
         ((fill[0] as u16 * 11) / 20) as u8,
         ((fill[1] as u16 * 11) / 20) as u8,
@@ -496,7 +501,7 @@ fn draw_board_cells_in_rect_clipped_with_owners_keeps_unowned_same_type_neighbor
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped_with_owners(&mut gfx, &board, Some(&owners), viewport, viewport);
 
-    let fill = color_for_cell(9);
+    let fill = color_for_cell(&CellPalette::CLASSIC, 9);
     let y = viewport.y + 8;
     assert_eq!(
         pixel_at(&frame, width, viewport.x + CELL_SIZE - 1, y),
@@ -524,7 +529,7 @@ fn draw_board_cells_in_rect_clipped_bridges_concave_inside_corners() {
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     draw_board_cells_in_rect_clipped(&mut gfx, &board, viewport, viewport);
 
-    let fill = color_for_cell(2);
+    let fill = color_for_cell(&CellPalette::CLASSIC, 2);
     let stroke = [
         ((fill[0] as u16 * 11) / 20) as u8,
         ((fill[1] as u16 * 11) / 20) as u8,
@@ -557,7 +562,7 @@ fn draw_board_cells_renders_grass_cells_by_tile_type() {
     draw_board_cells_in_rect(&mut gfx, &board, board_rect);
 
     let grass = [94u8, 152u8, 72u8, 255u8];
-    let garbage = color_for_cell(8);
+    let garbage = color_for_cell(&CellPalette::CLASSIC, 8);
 
     // y=0 is bottom row in board space.
     let grass_row_inverted_y = (board.len() as u32 - 1).saturating_sub(0);
@@ -574,7 +579,419 @@ fn draw_board_cells_renders_grass_cells_by_tile_type() {
     );
 }
 
+#[test]
+fn draw_board_cells_in_rect_with_palette_uses_the_given_colors() {
+    let width = 3 * CELL_SIZE;
+    let height = 3 * CELL_SIZE;
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut palette = CellPalette::CLASSIC;
+    palette.colors[8] = [12, 34, 56, 255];
+
+    let board = vec![vec![8u8]];
+    let board_rect = Rect::new(CELL_SIZE, CELL_SIZE, CELL_SIZE, CELL_SIZE);
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+    draw_board_cells_in_rect_with_palette(&mut gfx, &board, board_rect, &palette);
+
+    let center = CELL_SIZE + CELL_SIZE / 2;
+    assert_eq!(pixel_at(&frame, width, center, center), [12, 34, 56, 255]);
+}
+
 fn pixel_at(frame: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
     let idx = ((y * width + x) * 4) as usize;
     [frame[idx], frame[idx + 1], frame[idx + 2], frame[idx + 3]]
 }
+
+#[test]
+fn read_pixel_reflects_composited_frame_and_rejects_out_of_bounds() {
+    let width = 4u32;
+    let height = 3u32;
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+    gfx.clear([10, 20, 30, 255]);
+    gfx.fill_rect(Rect::new(1, 1, 2, 1), [200, 0, 0, 255]);
+    gfx.blend_rect(Rect::new(2, 1, 1, 1), [0, 0, 200, 255], 128);
+
+    assert_eq!(gfx.read_pixel(0, 0), Some([10, 20, 30, 255]));
+    assert_eq!(gfx.read_pixel(1, 1), Some([200, 0, 0, 255]));
+    assert_eq!(gfx.read_pixel(2, 1), Some([100, 0, 100, 255]));
+    assert_eq!(gfx.read_pixel(width, 0), None);
+    assert_eq!(gfx.read_pixel(0, height), None);
+}
+
+fn lit_pixels(frame: &[u8], width: u32) -> Vec<(u32, u32)> {
+    frame
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(_, px)| px[0] != 0)
+        .map(|(i, _)| (i as u32 % width, i as u32 / width))
+        .collect()
+}
+
+#[test]
+fn draw_line_plots_diagonal_and_clips_to_surface() {
+    let (width, height) = (8u32, 8u32);
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+
+    // Starts and ends off-surface; only the on-surface diagonal should be drawn.
+    gfx.draw_line(-4, -4, 20, 20, [255, 255, 255, 255]);
+
+    let expected: Vec<(u32, u32)> = (0..8).map(|i| (i, i)).collect();
+    assert_eq!(lit_pixels(&frame, width), expected);
+}
+
+#[test]
+fn draw_line_fully_off_surface_draws_nothing() {
+    let (width, height) = (8u32, 8u32);
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+
+    gfx.draw_line(-10, 2, -1, 6, [255, 255, 255, 255]);
+    gfx.draw_line(i32::MIN, i32::MIN, i32::MAX, i32::MIN, [255, 255, 255, 255]);
+
+    assert!(lit_pixels(&frame, width).is_empty());
+}
+
+#[test]
+fn draw_line_with_translucent_color_blends_like_blend_rect() {
+    let (width, height) = (4u32, 1u32);
+    let mut line_frame = vec![0u8; (width * height * 4) as usize];
+    let mut rect_frame = line_frame.clone();
+
+    CpuRenderer::new(&mut line_frame, SurfaceSize::new(width, height)).draw_line(
+        0,
+        0,
+        3,
+        0,
+        [200, 100, 50, 128],
+    );
+    CpuRenderer::new(&mut rect_frame, SurfaceSize::new(width, height)).blend_rect(
+        Rect::new(0, 0, 4, 1),
+        [200, 100, 50, 128],
+        128,
+    );
+
+    assert_eq!(line_frame, rect_frame);
+}
+
+#[test]
+fn fill_circle_and_outline_stay_within_radius_and_clip() {
+    let (width, height) = (16u32, 16u32);
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+
+    // Centered near the corner so part of the circle is clipped.
+    gfx.fill_circle(2, 2, 5, [255, 0, 0, 255]);
+    let filled = lit_pixels(&frame, width);
+    assert!(filled.contains(&(0, 0)));
+    assert!(filled.contains(&(7, 2)));
+    for (x, y) in &filled {
+        let (dx, dy) = (*x as i32 - 2, *y as i32 - 2);
+        assert!(dx * dx + dy * dy <= 25, "({x}, {y}) outside radius");
+    }
+
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+    gfx.draw_circle_outline(8, 8, 4, [255, 0, 0, 255]);
+    let outline = lit_pixels(&frame, width);
+    assert!(outline.contains(&(12, 8)));
+    assert!(outline.contains(&(8, 4)));
+    assert!(!outline.contains(&(8, 8)));
+}
+
+#[test]
+fn measure_text_matches_rendered_extent() {
+    let (width, height) = (200u32, 40u32);
+    for text in ["MILESTONE WALL 80", "M\nM0", "0"] {
+        let mut frame = vec![0u8; (width * height * 4) as usize];
+        let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+        gfx.draw_text(3, 4, text, [255, 255, 255, 255]);
+        let measured = gfx.measure_text(text);
+
+        let lit = lit_pixels(&frame, width);
+        let max_x = lit.iter().map(|(x, _)| *x).max().unwrap();
+        let max_y = lit.iter().map(|(_, y)| *y).max().unwrap();
+        assert_eq!(measured, (max_x + 1 - 3, max_y + 1 - 4), "text {text:?}");
+    }
+    assert_eq!(engine::graphics::measure_text_scaled("", 2), (0, 0));
+}
+
+#[test]
+fn save_rgba_png_roundtrips_cpu_frame() {
+    let (width, height) = (6u32, 4u32);
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+    gfx.clear([10, 20, 30, 255]);
+    gfx.fill_rect(Rect::new(1, 1, 3, 2), [200, 100, 50, 255]);
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let path = std::env::temp_dir()
+        .join(format!("rollout_engine_test_screenshot_{nanos}"))
+        .join("shot.png");
+    engine::pixels_renderer::save_rgba_png(&path, SurfaceSize::new(width, height), &frame)
+        .expect("save png");
+
+    let decoder = png::Decoder::new(std::io::BufReader::new(
+        std::fs::File::open(&path).expect("open png"),
+    ));
+    let mut reader = decoder.read_info().expect("png header");
+    let mut decoded = vec![0u8; reader.output_buffer_size().expect("buffer size")];
+    let info = reader.next_frame(&mut decoded).expect("png frame");
+    assert_eq!((info.width, info.height), (width, height));
+    assert_eq!(&decoded[..info.buffer_size()], &frame[..]);
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn text_scale_for_height_grows_past_1080p() {
+    use engine::graphics::{DEFAULT_TEXT_SCALE, text_scale_for_height};
+    assert_eq!(text_scale_for_height(360), DEFAULT_TEXT_SCALE);
+    assert_eq!(text_scale_for_height(1080), DEFAULT_TEXT_SCALE);
+    assert_eq!(text_scale_for_height(2160), 4);
+    assert_eq!(text_scale_for_height(u32::MAX), 8);
+    assert_eq!(
+        engine::graphics::measure_text_scaled("AB", 4),
+        (2 * 4 * 4 - 4, 5 * 4)
+    );
+}
+
+#[test]
+fn clip_stack_intersects_nested_clips_and_pops_back() {
+    let (width, height) = (16u32, 16u32);
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+    let white = [255, 255, 255, 255];
+
+    gfx.push_clip(Rect::new(2, 2, 8, 8));
+    gfx.push_clip(Rect::new(6, 6, 8, 8));
+    assert_eq!(gfx.clip_rect(), Some(Rect::new(6, 6, 4, 4)));
+    gfx.fill_rect(Rect::from_size(width, height), white);
+    gfx.draw_text(0, 0, "WWWWW\nWWWWW", white);
+    gfx.pop_clip();
+    assert_eq!(gfx.clip_rect(), Some(Rect::new(2, 2, 8, 8)));
+    gfx.draw_line(0, 12, 15, 12, white);
+    gfx.pop_clip();
+    gfx.pop_clip();
+    assert_eq!(gfx.clip_rect(), None);
+
+    let lit = lit_pixels(&frame, width);
+    let expected: Vec<(u32, u32)> = (6..10).flat_map(|y| (6..10).map(move |x| (x, y))).collect();
+    assert_eq!(lit, expected);
+}
+
+#[test]
+fn clip_stack_with_disjoint_clips_draws_nothing() {
+    let (width, height) = (8u32, 8u32);
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+
+    gfx.push_clip(Rect::new(0, 0, 2, 2));
+    gfx.push_clip(Rect::new(4, 4, 2, 2));
+    gfx.fill_rect(Rect::from_size(width, height), [255, 255, 255, 255]);
+    gfx.blend_rect(Rect::from_size(width, height), [255, 255, 255, 255], 128);
+
+    assert!(lit_pixels(&frame, width).is_empty());
+}
+
+#[test]
+fn draw_board_cells_in_rect_clipped_respects_enclosing_clip() {
+    let width = 4 * CELL_SIZE;
+    let height = 4 * CELL_SIZE;
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+
+    let board = vec![vec![1u8, 1u8]];
+    let viewport = Rect::new(CELL_SIZE, CELL_SIZE, 2 * CELL_SIZE, CELL_SIZE);
+    // A panel covering only the left cell.
+    let panel = Rect::new(0, 0, 2 * CELL_SIZE, height);
+
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+    gfx.push_clip(panel);
+    draw_board_cells_in_rect_clipped(&mut gfx, &board, viewport, viewport);
+    assert_eq!(gfx.clip_rect(), Some(panel));
+    gfx.pop_clip();
+
+    let piece = color_for_cell(&CellPalette::CLASSIC, 1);
+    let mid_y = CELL_SIZE + CELL_SIZE / 2;
+    assert_eq!(
+        pixel_at(&frame, width, CELL_SIZE + CELL_SIZE / 2, mid_y),
+        piece
+    );
+    assert_eq!(
+        pixel_at(&frame, width, 2 * CELL_SIZE + CELL_SIZE / 2, mid_y),
+        [0, 0, 0, 0]
+    );
+}
+
+#[test]
+fn draw_texture_composites_texels_like_blend_rect() {
+    use engine::graphics::Texture;
+
+    let (width, height) = (4u32, 2u32);
+    let texture = Texture::from_rgba(
+        2,
+        1,
+        vec![
+            200, 100, 50, 255, // opaque
+            200, 100, 50, 128, // half
+        ],
+    )
+    .expect("texture");
+    let mut blit_frame = vec![0u8; (width * height * 4) as usize];
+    let mut rect_frame = blit_frame.clone();
+
+    let mut gfx = CpuRenderer::new(&mut blit_frame, SurfaceSize::new(width, height));
+    gfx.clear([10, 20, 30, 255]);
+    gfx.draw_texture(1, 1, &texture);
+
+    let mut expected = CpuRenderer::new(&mut rect_frame, SurfaceSize::new(width, height));
+    expected.clear([10, 20, 30, 255]);
+    expected.fill_rect(Rect::new(1, 1, 1, 1), [200, 100, 50, 255]);
+    expected.blend_rect(Rect::new(2, 1, 1, 1), [200, 100, 50, 255], 128);
+
+    assert_eq!(blit_frame, rect_frame);
+}
+
+#[test]
+fn draw_texture_scaled_skips_transparent_texels_and_clips() {
+    use engine::graphics::Texture;
+
+    let (width, height) = (8u32, 8u32);
+    let texture = Texture::from_rgba(
+        2,
+        2,
+        vec![
+            255, 255, 255, 255, 0, 0, 0, 0, //
+            0, 0, 0, 0, 255, 255, 255, 255,
+        ],
+    )
+    .expect("texture");
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+
+    // Scaled 3x and shifted so the top-left texel block hangs off the surface.
+    gfx.draw_texture_scaled(-1, -1, &texture, 3);
+    // Fully off-surface blits are no-ops.
+    gfx.draw_texture_scaled(-100, 100, &texture, 3);
+
+    let mut expected: Vec<(u32, u32)> = Vec::new();
+    for y in 0..2 {
+        for x in 0..2 {
+            expected.push((x, y));
+        }
+    }
+    for y in 2..5 {
+        for x in 2..5 {
+            expected.push((x, y));
+        }
+    }
+    expected.sort_by_key(|&(x, y)| (y, x));
+    assert_eq!(lit_pixels(&frame, width), expected);
+}
+
+#[test]
+fn texture_loads_from_png_and_rejects_bad_lengths() {
+    use engine::graphics::Texture;
+
+    assert!(Texture::from_rgba(2, 2, vec![0; 15]).is_err());
+
+    let size = SurfaceSize::new(3, 2);
+    let rgba: Vec<u8> = (0..size.width * size.height * 4).map(|v| v as u8).collect();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let path = std::env::temp_dir()
+        .join(format!("rollout_engine_test_texture_{nanos}"))
+        .join("sprite.png");
+    engine::pixels_renderer::save_rgba_png(&path, size, &rgba).expect("save png");
+
+    let texture = Texture::load_png(&path).expect("load png");
+    assert_eq!((texture.width(), texture.height()), (3, 2));
+    assert_eq!(texture.rgba(), &rgba[..]);
+    assert_eq!(texture.pixel(1, 0), Some([4, 5, 6, 7]));
+    assert_eq!(texture.pixel(3, 0), None);
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn dirty_tracker_records_clipped_draws_and_defers_begin_frame() {
+    let size = SurfaceSize::new(20, 10);
+    let mut frame = vec![0u8; (size.width * size.height * 4) as usize];
+    let mut cpu = CpuRenderer::new(&mut frame, size);
+    cpu.fill_rect(Rect::new(0, 0, 1, 1), [9, 9, 9, 255]);
+
+    {
+        let mut tracker = DirtyTracker::new(&mut cpu, size);
+        tracker.begin_frame(size);
+        assert!(tracker.dirty_rects().is_empty());
+    }
+    assert_eq!(cpu.read_pixel(0, 0), Some([9, 9, 9, 255]));
+
+    let mut tracker = DirtyTracker::new(&mut cpu, size);
+    tracker.push_clip(Rect::new(0, 0, 10, 10));
+    tracker.fill_rect(Rect::new(5, 5, 10, 10), [255, 0, 0, 255]);
+    tracker.pop_clip();
+    tracker.draw_text(0, 0, "A", [255, 255, 255, 255]);
+    tracker.fill_rect(Rect::new(30, 0, 4, 4), [0, 255, 0, 255]);
+
+    let (text_w, text_h) = tracker.measure_text("A");
+    assert_eq!(
+        tracker.into_dirty_rects(),
+        vec![Rect::new(5, 5, 5, 5), Rect::new(0, 0, text_w, text_h)]
+    );
+    assert_eq!(cpu.read_pixel(12, 5), Some([0, 0, 0, 0]));
+    assert_eq!(cpu.read_pixel(9, 9), Some([255, 0, 0, 255]));
+}
+
+#[test]
+fn colorblind_palette_marks_pieces_and_high_contrast_brightens_grid() {
+    let width = 2 * CELL_SIZE;
+    let height = CELL_SIZE;
+    let board = vec![vec![2u8, 0]];
+    let rect = Rect::new(0, 0, width, height);
+    let render = |palette: &CellPalette| {
+        let mut frame = vec![0u8; (width * height * 4) as usize];
+        let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+        draw_board_cells_in_rect_clipped_with_owners_and_palette(
+            &mut gfx, &board, None, rect, rect, palette,
+        );
+        frame
+    };
+    let pixel = |frame: &[u8], x: u32, y: u32| {
+        let idx = ((y * width + x) * 4) as usize;
+        [frame[idx], frame[idx + 1], frame[idx + 2], frame[idx + 3]]
+    };
+    let center = CELL_SIZE / 2;
+
+    let classic = render(&CellPalette::CLASSIC);
+    assert_eq!(
+        pixel(&classic, center, center),
+        color_for_cell(&CellPalette::CLASSIC, 2)
+    );
+
+    let colorblind = render(&CellPalette::COLORBLIND);
+    let marker = pixel(&colorblind, center, center);
+    assert_ne!(marker, color_for_cell(&CellPalette::COLORBLIND, 2));
+    assert_eq!(marker, [20, 20, 24, 255], "light fills get a dark marker");
+    assert_eq!(
+        pixel(&colorblind, 1 + CELL_SIZE / 4, center),
+        color_for_cell(&CellPalette::COLORBLIND, 2)
+    );
+
+    let grid_dot = (CELL_SIZE + center, center);
+    assert_eq!(
+        pixel(&colorblind, grid_dot.0, grid_dot.1),
+        CellPalette::COLORBLIND.grid
+    );
+    let high_contrast = render(&CellPalette::COLORBLIND.with_high_contrast_grid());
+    assert_eq!(
+        pixel(&high_contrast, grid_dot.0, grid_dot.1),
+        [88, 88, 112, 255]
+    );
+}
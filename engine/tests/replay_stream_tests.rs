@@ -0,0 +1,66 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use engine::replay_stream::{StreamingReplay, frame_index_path};
+use engine::{ReplayableState, TimeMachine};
+
+fn unique_temp_ndjson_path() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    std::env::temp_dir().join(format!("rollout_engine_test_stream_{nanos}.ndjson"))
+}
+
+fn recording(len: i32) -> TimeMachine<Vec<i32>> {
+    let mut tm = TimeMachine::new(vec![0]);
+    for i in 1..len {
+        tm.record(vec![i; (i % 5) as usize + 1]);
+    }
+    tm
+}
+
+#[test]
+fn streaming_replay_seeks_forward_and_rewinds_via_index() {
+    let tm = recording(50);
+    let out = unique_temp_ndjson_path();
+    tm.save_ndjson_file(&out).expect("save ndjson");
+
+    let mut replay = StreamingReplay::<Vec<i32>>::open(&out).expect("open stream");
+    assert!(replay.is_indexed());
+    assert_eq!(replay.len(), 50);
+    assert_eq!(replay.state(), &vec![0]);
+
+    assert_eq!(replay.seek(37).expect("seek"), 37);
+    assert_eq!(replay.state(), tm.state_at(37).unwrap());
+    assert_eq!(replay.forward(5).expect("forward"), 42);
+    assert_eq!(replay.state(), tm.state_at(42).unwrap());
+    assert_eq!(replay.rewind(40).expect("rewind"), 2);
+    assert_eq!(replay.state(), tm.state_at(2).unwrap());
+
+    replay.replay_forward(1000);
+    assert_eq!(replay.replay_frame(), 49);
+    assert_eq!(replay.state(), tm.state_at(49).unwrap());
+
+    let _ = fs::remove_file(frame_index_path(&out));
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn streaming_replay_without_index_falls_back_to_full_load() {
+    let tm = recording(8);
+    let out = unique_temp_ndjson_path();
+    tm.save_ndjson_file(&out).expect("save ndjson");
+    fs::remove_file(frame_index_path(&out)).expect("remove index");
+
+    let mut replay = StreamingReplay::<Vec<i32>>::open(&out).expect("open stream");
+    assert!(!replay.is_indexed());
+    assert_eq!(replay.len(), 8);
+    replay.seek(6).expect("seek");
+    assert_eq!(replay.state(), tm.state_at(6).unwrap());
+
+    let _ = fs::remove_file(out);
+}
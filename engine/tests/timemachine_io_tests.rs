@@ -1,10 +1,12 @@
 use std::{
-    fs,
+    fs, io,
     path::PathBuf,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use engine::TimeMachine;
+use engine::{
+    GameLogic, HeadlessRunner, RECORDING_FORMAT_VERSION, RecordingMeta, TagConflict, TimeMachine,
+};
 
 fn unique_temp_json_path() -> PathBuf {
     let nanos = SystemTime::now()
@@ -37,3 +39,390 @@ fn timemachine_save_and_load_roundtrips_history_and_frame() {
 
     let _ = fs::remove_file(out);
 }
+
+#[test]
+fn timemachine_bookmarks_roundtrip_and_out_of_range_ones_are_pruned_on_load() {
+    let mut tm = TimeMachine::new(0i32);
+    tm.record(1);
+    tm.record(2);
+    tm.set_bookmark("before_game_over", 1);
+
+    let out = unique_temp_json_path();
+    tm.save_json_file(&out).expect("save timemachine json");
+    let mut loaded = TimeMachine::<i32>::load_json_file(&out).expect("load timemachine json");
+    assert_eq!(loaded.seek_bookmark("before_game_over"), Some(1));
+    assert_eq!(loaded.state(), &1);
+
+    // Hand-edited or truncated recordings may carry stale bookmarks.
+    fs::write(
+        &out,
+        r#"{"states":[0,1],"frame":0,"bookmarks":{"ok":1,"stale":5}}"#,
+    )
+    .expect("write recording json");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load timemachine json");
+    assert_eq!(loaded.bookmark("ok"), Some(1));
+    assert_eq!(loaded.bookmark("stale"), None);
+
+    // Older recordings without bookmarks still load.
+    fs::write(&out, r#"{"states":[0],"frame":0}"#).expect("write recording json");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load timemachine json");
+    assert!(loaded.bookmarks().is_empty());
+
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn timemachine_trimmed_save_drops_leading_frames_and_rebases_cursor() {
+    let mut tm = TimeMachine::new(0i32);
+    for v in [0, 0, 5, 6, 7] {
+        tm.record(v);
+    }
+    tm.set_bookmark("idle", 1);
+    tm.set_bookmark("peak", 5);
+    tm.seek(4);
+
+    let out = unique_temp_json_path();
+    let mut seen = Vec::new();
+    tm.save_json_file_trimmed(&out, |state| {
+        seen.push(*state);
+        *state > 0
+    })
+    .expect("save trimmed recording");
+    assert_eq!(
+        seen,
+        vec![0, 0, 0, 5],
+        "predicate should see each state until it matches"
+    );
+
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load trimmed recording");
+    assert_eq!(loaded.history(), &[5, 6, 7]);
+    assert_eq!(loaded.frame(), 1);
+    assert_eq!(loaded.bookmark("peak"), Some(2));
+    assert_eq!(loaded.bookmark("idle"), None);
+
+    // The live machine is untouched.
+    assert_eq!(tm.len(), 6);
+    assert_eq!(tm.frame(), 4);
+
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn timemachine_range_save_clamps_end_and_rejects_empty_results() {
+    let mut tm = TimeMachine::new(0i32);
+    tm.record(1);
+    tm.record(2);
+    tm.record(3);
+
+    let out = unique_temp_json_path();
+    tm.save_json_file_range(&out, 2, 99)
+        .expect("save range recording");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load range recording");
+    assert_eq!(loaded.history(), &[2, 3]);
+    assert_eq!(loaded.frame(), 1);
+    let _ = fs::remove_file(&out);
+
+    let err = tm
+        .save_json_file_trimmed(&out, |state| *state > 100)
+        .expect_err("predicate never matching should be an error");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(!out.exists(), "no file should be written for an empty trim");
+
+    let err = tm
+        .save_json_file_range(&out, 10, 20)
+        .expect_err("range past the end should be an error");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn timemachine_timestamps_roundtrip_and_default_to_empty() {
+    let mut tm = TimeMachine::new(0i32);
+    tm.record_timed(1, Duration::from_millis(16));
+    tm.record_timed(2, Duration::from_millis(40));
+
+    let out = unique_temp_json_path();
+    tm.save_json_file(&out).expect("save timemachine json");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load timemachine json");
+    assert_eq!(loaded.timestamps(), tm.timestamps());
+
+    fs::write(&out, r#"{"states":[0,1],"frame":1}"#).expect("write recording json");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load timemachine json");
+    assert!(loaded.timestamps().is_empty());
+    assert_eq!(loaded.timestamp_at(1), None);
+
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn timemachine_append_skips_duplicate_seam_and_seeks_across_boundary() {
+    let mut warmup = TimeMachine::new(0i32);
+    warmup.record(1);
+    warmup.record(2);
+
+    let mut main = TimeMachine::new(2i32);
+    main.record(3);
+    main.record(4);
+    main.set_bookmark("main_start", 0);
+    main.set_bookmark("main_end", 2);
+
+    warmup.append(main).expect("append recordings");
+    assert_eq!(warmup.history(), &[0, 1, 2, 3, 4]);
+    assert_eq!(warmup.frame(), 4);
+    assert_eq!(warmup.bookmark("main_start"), Some(2));
+    assert_eq!(warmup.bookmark("main_end"), Some(4));
+
+    assert_eq!(warmup.seek(1), 1);
+    assert_eq!(warmup.state(), &1);
+    assert_eq!(warmup.forward(2), 3);
+    assert_eq!(warmup.state(), &3);
+}
+
+#[test]
+fn timemachine_concat_keeps_distinct_seams_and_rejects_mismatched_cadence() {
+    let tagged = |tm: &mut TimeMachine<i32>, mode: &str| {
+        let mut meta = tm.meta().clone();
+        meta.tags.insert("mode".to_string(), mode.to_string());
+        tm.set_meta(meta);
+    };
+    let mut a = TimeMachine::new(0i32);
+    a.record(1);
+    tagged(&mut a, "sprint");
+    let mut b = TimeMachine::new(5i32);
+    tagged(&mut b, "dig");
+    let mut c = TimeMachine::new(6i32);
+    c.record(7);
+    tagged(&mut c, "sprint");
+
+    let (merged, report) = engine::concat(vec![a, b, c]).expect("concat recordings");
+    assert_eq!(merged.history(), &[0, 1, 5, 6, 7]);
+    assert_eq!(merged.frame(), 4);
+    assert_eq!(
+        report.tag_conflicts,
+        vec![TagConflict {
+            key: "mode".to_string(),
+            kept: "sprint".to_string(),
+            dropped: "dig".to_string(),
+        }]
+    );
+
+    let mut coarse = TimeMachine::new(0i32);
+    coarse.set_record_every_n_frames(2);
+    let err = engine::concat(vec![TimeMachine::new(0i32), coarse]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let err = engine::concat(Vec::<TimeMachine<i32>>::new()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn timemachine_append_merges_metadata_and_stays_valid() {
+    let mut first = TimeMachine::new(0i32);
+    first.record_timed(1, Duration::from_millis(100));
+    first.record_timed(2, Duration::from_millis(200));
+    first.set_bookmark("first_mid", 1);
+    let mut meta = first.meta().clone();
+    meta.tags.insert("mode".to_string(), "dig".to_string());
+    meta.tags.insert("player".to_string(), "a".to_string());
+    first.set_meta(meta);
+
+    let mut second = TimeMachine::new(10i32);
+    second.record_timed(11, Duration::from_millis(50));
+    second.set_bookmark("second_start", 0);
+    second.set_bookmark("second_end", 1);
+    let mut meta = second.meta().clone();
+    meta.tags.insert("mode".to_string(), "sprint".to_string());
+    meta.tags.insert("seed".to_string(), "7".to_string());
+    second.set_meta(meta);
+
+    let report = first.append(second).expect("append recordings");
+    first.validate().expect("merged recording should validate");
+
+    assert_eq!(first.history(), &[0, 1, 2, 10, 11]);
+    assert_eq!(first.bookmark("first_mid"), Some(1));
+    assert_eq!(first.bookmark("second_start"), Some(3));
+    assert_eq!(first.bookmark("second_end"), Some(4));
+    assert_eq!(
+        first.timestamps(),
+        &[
+            Duration::ZERO,
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(200),
+            Duration::from_millis(250),
+        ]
+    );
+
+    let tags = &first.meta().tags;
+    assert_eq!(tags.get("mode").map(String::as_str), Some("dig"));
+    assert_eq!(tags.get("player").map(String::as_str), Some("a"));
+    assert_eq!(tags.get("seed").map(String::as_str), Some("7"));
+    assert_eq!(report.tag_conflicts.len(), 1);
+    assert_eq!(report.tag_conflicts[0].key, "mode");
+    assert_eq!(report.tag_conflicts[0].dropped, "sprint");
+
+    let out = unique_temp_json_path();
+    first.save_json_file(&out).expect("save merged");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load merged");
+    assert_eq!(loaded.meta().tags, first.meta().tags);
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn timemachine_append_rejects_format_version_mismatch() {
+    let mut current = TimeMachine::new(0i32);
+    let mut legacy = TimeMachine::new(1i32);
+    legacy.set_meta(RecordingMeta::default());
+
+    let err = current.append(legacy).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(current.history(), &[0]);
+}
+
+#[test]
+fn timemachine_slice_clamps_and_saves_as_valid_recording() {
+    let mut tm = TimeMachine::new(0i32);
+    for v in 1..=5 {
+        tm.record(v);
+    }
+    tm.set_record_every_n_frames(3);
+    tm.set_bookmark("glitch", 3);
+
+    let slice = tm.slice(2, 4);
+    assert_eq!(slice.history(), &[2, 3, 4]);
+    assert_eq!(slice.frame(), 0);
+    assert_eq!(slice.record_every_n_frames(), 3);
+    assert_eq!(slice.bookmark("glitch"), Some(1));
+
+    assert_eq!(tm.slice(4, 100).history(), &[4, 5]);
+    assert_eq!(tm.slice(9, 1).history(), &[1]);
+
+    let out = unique_temp_json_path();
+    tm.slice(100, 200)
+        .save_json_file(&out)
+        .expect("save sliced timemachine json");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load sliced timemachine json");
+    assert_eq!(loaded.history(), &[5]);
+    assert_eq!(loaded.frame(), 0);
+
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn timemachine_meta_roundtrips_and_defaults_for_old_files() {
+    let mut tm = TimeMachine::new(0i32);
+    tm.set_meta(RecordingMeta::new("test_game"));
+    tm.record(1);
+
+    let out = unique_temp_json_path();
+    tm.save_json_file(&out).expect("save timemachine json");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load timemachine json");
+    assert_eq!(loaded.meta(), tm.meta());
+    assert_eq!(loaded.meta().format_version, RECORDING_FORMAT_VERSION);
+    assert_eq!(loaded.meta().game_id, "test_game");
+
+    fs::write(&out, r#"{"states":[0],"frame":0}"#).expect("write recording json");
+    let loaded = TimeMachine::<i32>::load_json_file(&out).expect("load old recording");
+    assert_eq!(loaded.meta(), &RecordingMeta::default());
+
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn timemachine_load_rejects_newer_format_version() {
+    let out = unique_temp_json_path();
+    let newer = RECORDING_FORMAT_VERSION + 1;
+    fs::write(
+        &out,
+        format!(r#"{{"meta":{{"format_version":{newer}}},"states":[0],"frame":0}}"#),
+    )
+    .expect("write recording json");
+
+    let err = TimeMachine::<i32>::load_json_file(&out).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("format version"), "{err}");
+
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn timemachine_writer_and_reader_match_the_file_format() {
+    let mut tm = TimeMachine::new(0i32);
+    tm.record(1);
+    tm.record(2);
+    tm.set_bookmark("mid", 1);
+
+    let mut buffer = Vec::new();
+    tm.save_json_writer(&mut buffer).expect("save to buffer");
+    let out = unique_temp_json_path();
+    tm.save_json_file(&out).expect("save timemachine json");
+    assert_eq!(fs::read(&out).expect("read recording json"), buffer);
+    let _ = fs::remove_file(out);
+
+    let loaded = TimeMachine::<i32>::load_json_reader(buffer.as_slice()).expect("load buffer");
+    assert_eq!(loaded.history(), tm.history());
+    assert_eq!(loaded.frame(), tm.frame());
+    assert_eq!(loaded.bookmark("mid"), Some(1));
+
+    // Validation is shared with the file loader.
+    let err =
+        TimeMachine::<i32>::load_json_reader(&br#"{"states":[0],"frame":3}"#[..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let newer = RECORDING_FORMAT_VERSION + 1;
+    let json = format!(r#"{{"meta":{{"format_version":{newer}}},"states":[0],"frame":0}}"#);
+    let err = TimeMachine::<i32>::load_json_reader(json.as_bytes()).unwrap_err();
+    assert!(err.to_string().contains("format version"), "{err}");
+}
+
+#[derive(Clone, Copy)]
+struct Counter {
+    scale: i64,
+}
+
+impl GameLogic for Counter {
+    type State = i64;
+    type Input = i64;
+
+    fn initial_state(&self) -> Self::State {
+        0
+    }
+
+    fn step(&self, state: &Self::State, input: Self::Input) -> Self::State {
+        state * 2 + input * self.scale
+    }
+}
+
+#[test]
+fn input_log_roundtrips_and_replays_under_patched_logic() {
+    let mut runner = HeadlessRunner::new(Counter { scale: 1 }).with_input_log();
+    runner.run([1, 2, 3]);
+    runner.rewind(1);
+    runner.step_frame([4, 5]);
+    assert_eq!(runner.timemachine().inputs().len(), 3);
+
+    let out = unique_temp_json_path();
+    runner.timemachine().save_json_file(&out).expect("save");
+    let loaded = TimeMachine::<i64>::load_json_file(&out).expect("load");
+    let inputs = loaded.logged_inputs::<i64>().expect("decode inputs");
+    assert_eq!(inputs, vec![vec![1], vec![2], vec![4, 5]]);
+
+    let same = HeadlessRunner::replay_with(Counter { scale: 1 }, inputs.clone());
+    assert_eq!(same.history(), loaded.history());
+
+    let patched = HeadlessRunner::replay_with(Counter { scale: 10 }, inputs);
+    assert_eq!(patched.history().len(), loaded.history().len());
+    assert_ne!(patched.state(), loaded.state());
+
+    let untyped = TimeMachine::<i64>::load_json_file(&out).expect("load");
+    assert!(untyped.logged_inputs::<String>().is_err());
+
+    // A runner without its own input log still drops the logged future when it branches.
+    let mut unlogged = HeadlessRunner::from_timemachine(Counter { scale: 1 }, untyped);
+    unlogged.seek(1);
+    unlogged.step(9);
+    assert_eq!(
+        unlogged.timemachine().inputs(),
+        &[vec![serde_json::json!(1)]]
+    );
+
+    let _ = fs::remove_file(out);
+}
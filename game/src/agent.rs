@@ -1,5 +1,6 @@
-use engine::GameLogic;
+use engine::{GameLogic, HeadlessRunner};
 
+use crate::playtest::{BlockLogic, InputAction};
 use crate::state::GameState;
 use crate::tetris_core::{Piece, RotationDir, TetrisCore, Vec2i};
 
@@ -44,6 +45,14 @@ impl GameLogic for BlockGame {
         GameState::new(core)
     }
 
+    fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    fn reseed_state(&self, state: &mut Self::State, seed: u64) {
+        state.tetris.reseed(seed);
+    }
+
     fn step(&self, state: &Self::State, input: Self::Input) -> Self::State {
         let mut next = state.clone();
         apply_action(&mut next.tetris, input);
@@ -78,3 +87,226 @@ fn apply_action(core: &mut TetrisCore, action: TetrisAction) {
     }
     core.advance_material_turn();
 }
+
+/// Chooses the inputs that place the active piece. Policies only see the core, so the same
+/// policy can drive `BlockLogic` through a `HeadlessRunner` or plan against a cloned core.
+pub trait PlayPolicy {
+    /// Inputs for the current piece, normally ending in `HardDrop`. Empty when there is
+    /// nothing to place.
+    fn plan(&self, core: &TetrisCore) -> Vec<InputAction>;
+}
+
+/// Linear weights for `HeuristicBot`. Features are multiplied by their weight and summed, so
+/// penalties carry negative weights. Defaults are the well-known height/lines/holes/bumpiness
+/// tuning for standard Tetris.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeuristicWeights {
+    pub aggregate_height: f32,
+    pub complete_lines: f32,
+    pub holes: f32,
+    pub bumpiness: f32,
+}
+
+impl Default for HeuristicWeights {
+    fn default() -> Self {
+        Self {
+            aggregate_height: -0.510_066,
+            complete_lines: 0.760_666,
+            holes: -0.356_630,
+            bumpiness: -0.184_483,
+        }
+    }
+}
+
+/// Board shape after a placement. Complete rows are counted, then ignored for the other
+/// features, as if the clear had already happened.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoardFeatures {
+    pub aggregate_height: u32,
+    pub complete_lines: u32,
+    pub holes: u32,
+    pub bumpiness: u32,
+}
+
+impl BoardFeatures {
+    pub fn from_board(board: &[Vec<u8>]) -> Self {
+        let complete_lines = board
+            .iter()
+            .filter(|row| !row.is_empty() && row.iter().all(|&cell| cell != 0))
+            .count() as u32;
+        let rows: Vec<&Vec<u8>> = board.iter().filter(|row| row.contains(&0)).collect();
+        let width = board.first().map_or(0, Vec::len);
+
+        let mut heights = vec![0u32; width];
+        let mut holes = 0u32;
+        for (x, height) in heights.iter_mut().enumerate() {
+            if let Some(top) = rows.iter().rposition(|row| row[x] != 0) {
+                *height = top as u32 + 1;
+                holes += rows[..top].iter().filter(|row| row[x] == 0).count() as u32;
+            }
+        }
+        let bumpiness = heights.windows(2).map(|w| w[0].abs_diff(w[1])).sum();
+
+        Self {
+            aggregate_height: heights.iter().sum(),
+            complete_lines,
+            holes,
+            bumpiness,
+        }
+    }
+
+    pub fn score(&self, weights: &HeuristicWeights) -> f32 {
+        self.aggregate_height as f32 * weights.aggregate_height
+            + self.complete_lines as f32 * weights.complete_lines
+            + self.holes as f32 * weights.holes
+            + self.bumpiness as f32 * weights.bumpiness
+    }
+}
+
+/// One-piece lookahead bot: tries every rotation and column reachable by rotating then
+/// shifting from spawn, hard-drops on a cloned core, and keeps the best-scoring placement.
+/// Ties go to the first candidate, so play is deterministic for a given seed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HeuristicBot {
+    pub weights: HeuristicWeights,
+}
+
+impl HeuristicBot {
+    pub fn new(weights: HeuristicWeights) -> Self {
+        Self { weights }
+    }
+
+    fn rate(&self, core: &TetrisCore) -> Option<f32> {
+        let mut sim = core.clone();
+        sim.hard_drop();
+        if sim.is_game_over() {
+            return None;
+        }
+        Some(BoardFeatures::from_board(sim.board()).score(&self.weights))
+    }
+}
+
+impl PlayPolicy for HeuristicBot {
+    fn plan(&self, core: &TetrisCore) -> Vec<InputAction> {
+        if core.is_game_over() || core.current_piece().is_none() {
+            return Vec::new();
+        }
+
+        let rotations: [&[InputAction]; 4] = [
+            &[],
+            &[InputAction::RotateCw],
+            &[InputAction::Rotate180],
+            &[InputAction::RotateCcw],
+        ];
+        let mut best: Option<(f32, Vec<InputAction>)> = None;
+        for rotation in rotations {
+            let mut rotated = core.clone();
+            if !rotation
+                .iter()
+                .all(|&action| apply_plan_action(&mut rotated, action))
+            {
+                continue;
+            }
+            let mut consider = |sim: &TetrisCore, actions: &[InputAction]| {
+                if let Some(score) = self.rate(sim)
+                    && best
+                        .as_ref()
+                        .is_none_or(|(best_score, _)| score > *best_score)
+                {
+                    best = Some((score, actions.to_vec()));
+                }
+            };
+            consider(&rotated, rotation);
+            for shift in [InputAction::MoveLeft, InputAction::MoveRight] {
+                let mut shifted = rotated.clone();
+                let mut actions = rotation.to_vec();
+                while apply_plan_action(&mut shifted, shift) {
+                    actions.push(shift);
+                    consider(&shifted, &actions);
+                }
+            }
+        }
+
+        let mut plan = best.map(|(_, actions)| actions).unwrap_or_default();
+        plan.push(InputAction::HardDrop);
+        plan
+    }
+}
+
+fn apply_plan_action(core: &mut TetrisCore, action: InputAction) -> bool {
+    match action {
+        InputAction::MoveLeft => core.move_piece(Vec2i::new(-1, 0)),
+        InputAction::MoveRight => core.move_piece(Vec2i::new(1, 0)),
+        InputAction::RotateCw => core.rotate_piece(RotationDir::Cw),
+        InputAction::RotateCcw => core.rotate_piece(RotationDir::Ccw),
+        InputAction::Rotate180 => core.rotate_piece(RotationDir::Half),
+        _ => false,
+    }
+}
+
+/// Outcome of `run_policy_games`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyReport {
+    /// Lines cleared per game, in seed order.
+    pub lines_per_game: Vec<u32>,
+    /// Pieces placed per game, in seed order.
+    pub pieces_per_game: Vec<u32>,
+}
+
+impl PolicyReport {
+    pub fn games(&self) -> usize {
+        self.lines_per_game.len()
+    }
+
+    pub fn average_lines(&self) -> f64 {
+        if self.lines_per_game.is_empty() {
+            return 0.0;
+        }
+        let total: u64 = self
+            .lines_per_game
+            .iter()
+            .map(|&lines| u64::from(lines))
+            .sum();
+        total as f64 / self.lines_per_game.len() as f64
+    }
+}
+
+/// Play one game per seed with `policy` driving `logic` through a `HeadlessRunner`, stopping
+/// each game at game over or after `max_pieces` placements. Each placement is one recorded
+/// frame.
+pub fn run_policy_games<P: PlayPolicy>(
+    logic: &BlockLogic,
+    policy: &P,
+    seeds: &[u64],
+    max_pieces: u32,
+) -> PolicyReport {
+    let mut report = PolicyReport::default();
+    for &seed in seeds {
+        let mut game = logic.clone();
+        game.reseed(seed);
+        let mut runner = HeadlessRunner::new(game);
+        let mut pieces = 0u32;
+        while pieces < max_pieces {
+            let core = &runner.state().tetris;
+            if core.is_game_over() {
+                break;
+            }
+            if core.current_piece().is_none() {
+                if !core.is_line_clear_active() {
+                    break;
+                }
+                let dt_ms = core.line_clear_delay_ms();
+                runner.step(InputAction::GravityTick { dt_ms });
+                continue;
+            }
+            let plan = policy.plan(core);
+            runner.step_frame(plan);
+            pieces += 1;
+        }
+        report
+            .lines_per_game
+            .push(runner.state().tetris.lines_cleared());
+        report.pieces_per_game.push(pieces);
+    }
+    report
+}
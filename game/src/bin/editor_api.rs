@@ -24,9 +24,10 @@ use serde::Serialize;
 use tower_http::cors::{Any, CorsLayer};
 
 use engine::editor::{
-    EditorAction, EditorManifest, EditorSnapshot, EditorTimeline, FramesRequest, SeekRequest,
-    StepRequest,
+    EditorAction, EditorManifest, EditorSnapshot, EditorTimeline, FramesRequest,
+    LoadRecordingRequest, SeekRequest, SetFrameStateRequest, StepRequest,
 };
+use game::headful_editor_api::EDITOR_TOKEN_ENV;
 
 type HttpClient = Client<HttpConnector, Full<Bytes>>;
 
@@ -34,6 +35,8 @@ type HttpClient = Client<HttpConnector, Full<Bytes>>;
 struct AppState {
     remote_base: Arc<Mutex<Option<String>>>,
     client: HttpClient,
+    /// Bearer token forwarded to the game's headful editor API, if it requires one.
+    game_token: Option<String>,
 }
 
 fn build_http_client() -> HttpClient {
@@ -41,47 +44,7 @@ fn build_http_client() -> HttpClient {
 }
 
 fn default_manifest() -> EditorManifest {
-    EditorManifest {
-        title: "Tetree (Tetris)".to_string(),
-        actions: vec![
-            EditorAction {
-                id: "moveLeft".to_string(),
-                label: "Left".to_string(),
-            },
-            EditorAction {
-                id: "moveRight".to_string(),
-                label: "Right".to_string(),
-            },
-            EditorAction {
-                id: "softDrop".to_string(),
-                label: "Down".to_string(),
-            },
-            EditorAction {
-                id: "rotateCw".to_string(),
-                label: "Rotate CW".to_string(),
-            },
-            EditorAction {
-                id: "rotateCcw".to_string(),
-                label: "Rotate CCW".to_string(),
-            },
-            EditorAction {
-                id: "rotate180".to_string(),
-                label: "Rotate 180".to_string(),
-            },
-            EditorAction {
-                id: "hardDrop".to_string(),
-                label: "Hard Drop".to_string(),
-            },
-            EditorAction {
-                id: "hold".to_string(),
-                label: "Hold".to_string(),
-            },
-            EditorAction {
-                id: "noop".to_string(),
-                label: "Noop".to_string(),
-            },
-        ],
-    }
+    game::editor_actions::default_manifest()
 }
 
 fn router(state: AppState) -> Router {
@@ -95,13 +58,17 @@ fn router(state: AppState) -> Router {
         .route("/api/game/status", get(game_status_with_state))
         .route("/api/game/launch", post(game_launch))
         .route("/api/manifest", get(manifest))
+        .route("/api/actions", get(actions))
         .route("/api/agent/state", get(agent_state))
         .route("/api/agent/timeline", get(agent_timeline))
         .route("/api/agent/step", post(agent_step))
+        .route("/api/agent/step-many", post(agent_step_many))
         .route("/api/agent/rewind", post(agent_rewind))
         .route("/api/agent/forward", post(agent_forward))
         .route("/api/agent/seek", post(agent_seek))
         .route("/api/agent/reset", post(agent_reset))
+        .route("/api/agent/load-recording", post(agent_load_recording))
+        .route("/api/agent/set-state", post(agent_set_state))
         .with_state(state)
         .layer(cors)
 }
@@ -263,10 +230,14 @@ async fn proxy_to_game(
         )
     })?;
 
-    let req = Request::builder()
+    let mut req = Request::builder()
         .method(method)
         .uri(uri)
-        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(token) = state.game_token.as_deref() {
+        req = req.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let req = req
         .body(Full::new(Bytes::from(body.unwrap_or_default())))
         .map_err(|e| {
             (
@@ -381,6 +352,10 @@ async fn manifest() -> Json<EditorManifest> {
     Json(default_manifest())
 }
 
+async fn actions() -> Json<Vec<EditorAction>> {
+    Json(game::editor_actions::default_actions())
+}
+
 async fn agent_state(
     State(state): State<AppState>,
 ) -> Result<Json<EditorSnapshot>, (StatusCode, String)> {
@@ -421,6 +396,22 @@ async fn agent_step(
     Ok(Json(snapshot))
 }
 
+async fn agent_step_many(
+    State(state): State<AppState>,
+    Json(action_ids): Json<Vec<String>>,
+) -> Result<Json<EditorSnapshot>, (StatusCode, String)> {
+    let body = serde_json::to_vec(&action_ids)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (status, bytes) =
+        proxy_to_game(&state, Method::POST, "/api/agent/step-many", Some(body)).await?;
+    if !status.is_success() {
+        return Err((status, String::from_utf8_lossy(&bytes).to_string()));
+    }
+    let snapshot: EditorSnapshot =
+        serde_json::from_slice(&bytes).map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+    Ok(Json(snapshot))
+}
+
 async fn agent_rewind(
     State(state): State<AppState>,
     Json(payload): Json<FramesRequest>,
@@ -469,6 +460,43 @@ async fn agent_seek(
     Ok(Json(snapshot))
 }
 
+async fn agent_load_recording(
+    State(state): State<AppState>,
+    Json(payload): Json<LoadRecordingRequest>,
+) -> Result<Json<EditorSnapshot>, (StatusCode, String)> {
+    let body = serde_json::to_vec(&payload)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (status, bytes) = proxy_to_game(
+        &state,
+        Method::POST,
+        "/api/agent/load-recording",
+        Some(body),
+    )
+    .await?;
+    if !status.is_success() {
+        return Err((status, String::from_utf8_lossy(&bytes).to_string()));
+    }
+    let snapshot: EditorSnapshot =
+        serde_json::from_slice(&bytes).map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+    Ok(Json(snapshot))
+}
+
+async fn agent_set_state(
+    State(state): State<AppState>,
+    Json(payload): Json<SetFrameStateRequest>,
+) -> Result<Json<EditorSnapshot>, (StatusCode, String)> {
+    let body = serde_json::to_vec(&payload)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (status, bytes) =
+        proxy_to_game(&state, Method::POST, "/api/agent/set-state", Some(body)).await?;
+    if !status.is_success() {
+        return Err((status, String::from_utf8_lossy(&bytes).to_string()));
+    }
+    let snapshot: EditorSnapshot =
+        serde_json::from_slice(&bytes).map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+    Ok(Json(snapshot))
+}
+
 async fn agent_reset(
     State(state): State<AppState>,
 ) -> Result<Json<EditorSnapshot>, (StatusCode, String)> {
@@ -486,6 +514,7 @@ async fn main() {
     let state = AppState {
         remote_base: Arc::new(Mutex::new(None)),
         client: build_http_client(),
+        game_token: env::var(EDITOR_TOKEN_ENV).ok().filter(|v| !v.is_empty()),
     };
     let app = router(state);
 
@@ -628,6 +657,7 @@ mod tests {
         AppState {
             remote_base: Arc::new(Mutex::new(remote_base)),
             client: build_http_client(),
+            game_token: None,
         }
     }
 
@@ -1,4 +1,6 @@
 use super::*;
+use engine::app::simulate;
+use engine::view_tree::ViewNode;
 use game::tetris_core::{BOARD_HEIGHT, BOARD_WIDTH, CELL_GARBAGE, Piece, TetrisCore, Vec2i};
 
 fn make_test_headful(view: GameView) -> (HeadfulApp, HeadlessRunner<TetrisLogic>) {
@@ -9,6 +11,7 @@ fn make_test_headful(view: GameView) -> (HeadfulApp, HeadlessRunner<TetrisLogic>
         DEFAULT_GRAVITY_INTERVAL,
     );
     app.sfx = None;
+    app.round_countdown = Duration::ZERO;
     app.dig_camera = DigCameraController::new_with_config(true, DigCameraConfig::default());
 
     let mut runner = HeadlessRunner::new(base_logic);
@@ -130,21 +133,19 @@ fn horizontal_repeat_ignores_os_repeat_pressed_events_and_repeats_on_timer() {
 
     // Before the repeat delay expires: no repeat.
     assert_eq!(
-        repeat.next_repeat_action(t0 + HorizontalRepeat::REPEAT_DELAY - Duration::from_millis(1)),
+        repeat.next_repeat_action(t0 + repeat.delay() - Duration::from_millis(1)),
         None
     );
 
     // Once the delay expires: we should get a move action even without any further key events.
     assert_eq!(
-        repeat.next_repeat_action(t0 + HorizontalRepeat::REPEAT_DELAY),
+        repeat.next_repeat_action(t0 + repeat.delay()),
         Some(InputAction::MoveLeft)
     );
 
     // And again at the interval.
     assert_eq!(
-        repeat.next_repeat_action(
-            t0 + HorizontalRepeat::REPEAT_DELAY + HorizontalRepeat::REPEAT_INTERVAL
-        ),
+        repeat.next_repeat_action(t0 + repeat.delay() + repeat.interval()),
         Some(InputAction::MoveLeft)
     );
 }
@@ -312,15 +313,15 @@ fn gravity_dt_integration_locks_after_expected_timing() {
     let (mut app, mut runner) = make_test_headful(GameView::Tetris { paused: false });
     setup_grounded_lock_delay_case(&mut runner);
 
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(400));
+    app.advance_logical_time(&mut runner, Duration::from_millis(400));
     assert_eq!(runner.state().tetris.current_piece_pos(), Vec2i::new(4, 1));
     assert_eq!(runner.state().tetris.grounded_lock_ms(), 300);
 
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(100));
+    app.advance_logical_time(&mut runner, Duration::from_millis(100));
     assert_eq!(runner.state().tetris.current_piece_pos(), Vec2i::new(4, 1));
     assert_eq!(runner.state().tetris.grounded_lock_ms(), 400);
 
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(100));
+    app.advance_logical_time(&mut runner, Duration::from_millis(100));
     assert_eq!(
         runner.state().tetris.current_piece_pos(),
         Vec2i::new(4, runner.state().tetris.board().len() as i32)
@@ -334,21 +335,21 @@ fn paused_state_does_not_advance_lock_delay() {
     setup_grounded_lock_delay_case(&mut runner);
 
     runner.state_mut().view = GameView::Tetris { paused: true };
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(500));
+    app.advance_logical_time(&mut runner, Duration::from_millis(500));
     assert_eq!(runner.state().tetris.current_piece_pos(), Vec2i::new(4, 1));
     assert_eq!(runner.state().tetris.grounded_lock_ms(), 0);
 
     runner.state_mut().view = GameView::Tetris { paused: false };
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(400));
+    app.advance_logical_time(&mut runner, Duration::from_millis(400));
     assert_eq!(runner.state().tetris.grounded_lock_ms(), 300);
 
     runner.state_mut().view = GameView::Tetris { paused: true };
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(1000));
+    app.advance_logical_time(&mut runner, Duration::from_millis(1000));
     assert_eq!(runner.state().tetris.current_piece_pos(), Vec2i::new(4, 1));
     assert_eq!(runner.state().tetris.grounded_lock_ms(), 300);
 
     runner.state_mut().view = GameView::Tetris { paused: false };
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(200));
+    app.advance_logical_time(&mut runner, Duration::from_millis(200));
     assert_eq!(
         runner.state().tetris.current_piece_pos(),
         Vec2i::new(4, runner.state().tetris.board().len() as i32)
@@ -373,7 +374,7 @@ fn grounded_move_stalls_even_when_gravity_tick_is_due_same_frame() {
     }
 
     runner.step_profiled(InputAction::MoveRight, &mut app.debug_hud);
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(1));
+    app.advance_logical_time(&mut runner, Duration::from_millis(1));
 
     assert_eq!(runner.state().tetris.current_piece_pos(), Vec2i::new(5, 1));
     assert!(
@@ -393,7 +394,7 @@ fn debug_hud_timer_toggle_disables_timer_tick_and_timeout() {
     runner.state_mut().round_timer = RoundTimer::new(Duration::from_millis(200));
 
     app.debug_hud.toggle_round_timer_disabled();
-    app.update_round_timer_and_game_over(&mut runner, Duration::from_secs(1));
+    app.advance_logical_time(&mut runner, Duration::from_secs(1));
     assert!(
         matches!(runner.state().view, GameView::Tetris { paused: false }),
         "timer-disabled mode should not trigger timeout game over"
@@ -405,7 +406,7 @@ fn debug_hud_timer_toggle_disables_timer_tick_and_timeout() {
     );
 
     app.debug_hud.toggle_round_timer_disabled();
-    app.update_round_timer_and_game_over(&mut runner, Duration::from_millis(250));
+    app.advance_logical_time(&mut runner, Duration::from_millis(250));
     assert!(
         matches!(runner.state().view, GameView::GameOver),
         "re-enabled timer should restore timeout game over behavior"
@@ -418,13 +419,13 @@ fn gravity_timeline_is_deterministic_for_same_inputs() {
         let (mut app, mut runner) = make_test_headful(GameView::Tetris { paused: false });
         setup_grounded_lock_delay_case(&mut runner);
 
-        app.apply_gravity_steps(&mut runner, Duration::from_millis(250));
+        app.advance_logical_time(&mut runner, Duration::from_millis(250));
         runner.step_profiled(InputAction::MoveRight, &mut app.debug_hud);
-        app.apply_gravity_steps(&mut runner, Duration::from_millis(250));
+        app.advance_logical_time(&mut runner, Duration::from_millis(250));
         runner.state_mut().view = GameView::Tetris { paused: true };
-        app.apply_gravity_steps(&mut runner, Duration::from_millis(500));
+        app.advance_logical_time(&mut runner, Duration::from_millis(500));
         runner.state_mut().view = GameView::Tetris { paused: false };
-        app.apply_gravity_steps(&mut runner, Duration::from_millis(200));
+        app.advance_logical_time(&mut runner, Duration::from_millis(200));
 
         (
             runner.state().tetris.snapshot(),
@@ -469,7 +470,7 @@ fn line_clear_delay_progresses_with_frame_dt_instead_of_waiting_for_gravity_inte
     assert_eq!(runner.state().tetris.lines_cleared(), 0);
 
     // Even though 100ms is below the 500ms gravity interval, clear progress should advance.
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(100));
+    app.advance_logical_time(&mut runner, Duration::from_millis(100));
     let progress = runner.state().tetris.line_clear_progress();
     assert!(
         progress > 0.0 && progress < 1.0,
@@ -478,7 +479,188 @@ fn line_clear_delay_progresses_with_frame_dt_instead_of_waiting_for_gravity_inte
     assert!(runner.state().tetris.is_line_clear_active());
     assert_eq!(runner.state().tetris.lines_cleared(), 0);
 
-    app.apply_gravity_steps(&mut runner, Duration::from_millis(80));
+    app.advance_logical_time(&mut runner, Duration::from_millis(80));
     assert!(!runner.state().tetris.is_line_clear_active());
     assert_eq!(runner.state().tetris.lines_cleared(), 1);
 }
+
+#[test]
+fn settings_sliders_keep_shake_range_and_music_slider_overrides_mute() {
+    let (mut app, _runner) = make_test_headful(GameView::MainMenu);
+    app.settings_open = true;
+
+    app.apply_settings_ui_event(&UiEvent::ValueChanged {
+        id: UI_SETTINGS_SLIDER_SHAKE,
+        value: 50.0,
+    });
+    assert_eq!(app.player_settings.video.screen_shake_percent, 50);
+
+    app.apply_settings_ui_event(&UiEvent::ValueChanged {
+        id: UI_SETTINGS_SLIDER_MASTER,
+        value: 3.0,
+    });
+    assert_eq!(app.player_settings.audio.master_volume, 1.0);
+
+    app.player_settings.audio.music_volume = 0.8;
+    app.toggle_music_mute();
+    app.apply_settings_ui_event(&UiEvent::ValueChanged {
+        id: UI_SETTINGS_SLIDER_MUSIC,
+        value: 0.3,
+    });
+    app.toggle_music_mute();
+    assert!(!app.player_settings.audio.is_music_muted());
+    assert_approx_eq(app.player_settings.audio.music_volume, 0.3);
+}
+
+#[test]
+fn simulate_clicks_main_menu_start_and_hard_drops_through_the_app() {
+    let (mut app, _) = make_test_headful(GameView::MainMenu);
+    let (width, height) = (1280, 720);
+    let start = build_menu_view_tree(GameView::MainMenu, width, height)
+        .nodes
+        .into_iter()
+        .find_map(|node| match node {
+            ViewNode::Button(button) if matches!(button.action, GameUiAction::StartGame) => {
+                Some(button.rect)
+            }
+            _ => None,
+        })
+        .expect("main menu has a start button");
+    let pos = Some((start.x + start.w / 2, start.y + start.h / 2));
+
+    let mut press = InputFrame {
+        mouse_pos: pos,
+        mouse_down: true,
+        ..InputFrame::default()
+    };
+    press.mouse_buttons_down.insert(MouseButton::Left);
+    press.mouse_buttons_pressed.insert(MouseButton::Left);
+    let mut release = InputFrame {
+        mouse_pos: pos,
+        mouse_up: true,
+        ..InputFrame::default()
+    };
+    release.mouse_buttons_released.insert(MouseButton::Left);
+    let hard_drop = input_frame_for_keys(&[VirtualKeyCode::Space], &[VirtualKeyCode::Space], &[]);
+
+    let config = || AppConfig {
+        title: "simulate".to_string(),
+        desired_size: PhysicalSize::new(width, height),
+        clamp_to_monitor: false,
+        vsync: None,
+        present_mode: None,
+        max_frame_dt: Some(DEFAULT_MAX_FRAME_DT),
+        target_fps: 0,
+        pause_on_unfocus: false,
+        localizer: None,
+    };
+    let filled = |runner: &HeadlessRunner<TetrisLogic>| {
+        let board = runner.state().tetris.board();
+        board.iter().flatten().filter(|&&cell| cell != 0).count()
+    };
+
+    let started = simulate(config(), &mut app, vec![press.clone(), release.clone()]);
+    assert_eq!(started.state().view, GameView::Tetris { paused: false });
+
+    let (mut app, _) = make_test_headful(GameView::MainMenu);
+    let dropped = simulate(config(), &mut app, vec![press, release, hard_drop]);
+    assert!(
+        filled(&dropped) > filled(&started),
+        "hard drop should lock a piece onto the board"
+    );
+}
+
+/// Press and release frames that click the main menu's start button.
+fn start_game_click_frames(width: u32, height: u32) -> Vec<InputFrame> {
+    let start = build_menu_view_tree(GameView::MainMenu, width, height)
+        .nodes
+        .into_iter()
+        .find_map(|node| match node {
+            ViewNode::Button(button) if matches!(button.action, GameUiAction::StartGame) => {
+                Some(button.rect)
+            }
+            _ => None,
+        })
+        .expect("main menu has a start button");
+    let pos = Some((start.x + start.w / 2, start.y + start.h / 2));
+    let mut press = InputFrame {
+        mouse_pos: pos,
+        mouse_down: true,
+        ..InputFrame::default()
+    };
+    press.mouse_buttons_down.insert(MouseButton::Left);
+    press.mouse_buttons_pressed.insert(MouseButton::Left);
+    let mut release = InputFrame {
+        mouse_pos: pos,
+        mouse_up: true,
+        ..InputFrame::default()
+    };
+    release.mouse_buttons_released.insert(MouseButton::Left);
+    vec![press, release]
+}
+
+fn simulate_config(width: u32, height: u32, pause_on_unfocus: bool) -> AppConfig {
+    AppConfig {
+        title: "simulate".to_string(),
+        desired_size: PhysicalSize::new(width, height),
+        clamp_to_monitor: false,
+        vsync: None,
+        present_mode: None,
+        max_frame_dt: Some(DEFAULT_MAX_FRAME_DT),
+        target_fps: 0,
+        pause_on_unfocus,
+        localizer: None,
+    }
+}
+
+#[test]
+fn held_key_auto_repeat_follows_simulated_frame_time() {
+    let (width, height) = (1280, 720);
+    let mut frames = start_game_click_frames(width, height);
+    frames.push(input_frame_for_keys(
+        &[VirtualKeyCode::Right],
+        &[VirtualKeyCode::Right],
+        &[],
+    ));
+    // 300ms of simulated time: past DAS, so repeats fire even though the test runs in
+    // well under a millisecond of wall time.
+    frames.extend((0..18).map(|_| input_frame_for_keys(&[], &[VirtualKeyCode::Right], &[])));
+
+    let start_x = {
+        let (mut app, _) = make_test_headful(GameView::MainMenu);
+        let started = simulate(
+            simulate_config(width, height, false),
+            &mut app,
+            frames[..2].to_vec(),
+        );
+        started.state().tetris.current_piece_pos().x
+    };
+    let (mut app, _) = make_test_headful(GameView::MainMenu);
+    let held = simulate(simulate_config(width, height, false), &mut app, frames);
+    assert!(
+        held.state().tetris.current_piece_pos().x >= start_x + 2,
+        "holding right past DAS should auto-repeat on simulated time"
+    );
+}
+
+#[test]
+fn focus_loss_under_pause_on_unfocus_still_releases_held_keys() {
+    let (width, height) = (1280, 720);
+    let mut frames = start_game_click_frames(width, height);
+    frames.push(input_frame_for_keys(
+        &[VirtualKeyCode::Right],
+        &[VirtualKeyCode::Right],
+        &[],
+    ));
+    let unfocused = InputFrame {
+        window_focused: false,
+        ..InputFrame::default()
+    };
+    frames.extend([unfocused.clone(), unfocused]);
+
+    let (mut app, _) = make_test_headful(GameView::MainMenu);
+    app.player_settings.gameplay.auto_pause_on_focus_loss = true;
+    let runner = simulate(simulate_config(width, height, true), &mut app, frames);
+    assert!(app.horizontal_repeat.active.is_none());
+    assert_eq!(runner.state().view, GameView::Tetris { paused: true });
+}
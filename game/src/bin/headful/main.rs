@@ -1,19 +1,29 @@
 use std::{
     cell::Cell,
-    io,
+    collections::HashMap,
+    fs, io,
     io::Cursor,
+    path::Path,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use engine::HeadlessRunner;
+use engine::TimeMachine;
 use engine::app::{
-    AppConfig, AppContext, CaptureCli, GameApp, InputFrame, ProfileConfig, RecordingConfig,
-    ReplayConfig, RunMode, default_recording_path, parse_capture_cli_with_default_path, run_game,
-    run_game_with_profile, run_game_with_recording, run_game_with_replay,
+    AppConfig, AppContext, CaptureCli, DEFAULT_MAX_FRAME_DT, DEFAULT_TARGET_FPS, GameApp,
+    InputFrame, ProfileConfig, RecordingConfig, ReplayConfig, RunMode, default_recording_path,
+    default_screenshot_path, parse_capture_cli_with_default_path, run_game,
+    run_game_with_input_recording, run_game_with_input_replay, run_game_with_profile,
+    run_game_with_recording, run_game_with_replay,
 };
+use engine::localization::Localizer;
+use engine::logical_clock::StepAccumulator;
+use engine::recording::FrameExportConfig;
 use engine::ui_tree::{UiEvent, UiInput, UiTree};
-use engine::audio::{MusicRuntime, Quantize, Scene, StepPattern, Track, Waveform};
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use engine::audio::{MusicDucker, MusicRuntime, Quantize, Scene, StepPattern, Track, Waveform};
+use rodio::source::Buffered;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
 #[cfg(test)]
 use winit::event::VirtualKeyCode;
 use winit::{
@@ -23,37 +33,58 @@ use winit::{
 };
 
 use game::debug::DebugHud;
+use game::gravity_curve::GravityCurve;
 use game::headful::dig_camera as headful_dig_camera;
 use game::headful::input_adapter as headful_input;
 use game::headful::remote_control as headful_remote;
 use game::headful::render_pipeline::{RenderCache, render_frame as render_headful_frame};
 use game::headful::skilltree_camera as headful_camera;
 use game::headful::view_transitions as headful_view;
-use game::headful_editor_api::RemoteServer;
+use game::headful_editor_api::{EDITOR_TOKEN_ENV, RemoteServer};
+use game::localization;
 use game::playtest::{InputAction, TetrisLogic};
+use game::replay_export;
 use game::round_timer::RoundTimer;
-use game::settings::{AudioSettings, PlayerSettings, SettingsStore};
-use game::sfx::{ACTION_SFX_VOLUME, GLASS_BREAK_SFX_VOLUME, MUSIC_VOLUME};
+use game::settings::{AudioSettings, PlayerSettings, RepeatTiming, SettingsStore};
+use game::sfx::{
+    ACTION_SFX_VOLUME, GLASS_BREAK_SFX_VOLUME, LINE_CLEAR_SFX_VOLUME, MUSIC_TRACK, MUSIC_VOLUME,
+    SFX_HARD_DROP, SFX_LINE_CLEAR, SFX_MENU_CLICK, find_sound_file, sfx_dir_from_env,
+};
 use game::skilltree::{SkillTreeEditorTool, SkillTreeRunMods, SkillTreeRuntime};
-use game::state::{DEFAULT_GRAVITY_INTERVAL, DEFAULT_ROUND_LIMIT, GameState};
+use game::state::{
+    DEFAULT_GRAVITY_INTERVAL, DEFAULT_ROUND_COUNTDOWN, DEFAULT_ROUND_LIMIT, GameState, LOGIC_STEP,
+};
 use game::tetris_core::{
     BottomwellRunMods, DEFAULT_DEPTH_WALL_DAMAGE_PER_LINE, DEFAULT_DEPTH_WALL_MULTI_CLEAR_BONUS_PERCENT,
     Piece, default_depth_wall_defs,
 };
 use game::tetris_ui::{
     GameOverMenuLayout, MainMenuLayout, PauseMenuLayout, Rect, SettingsMenuLayout, SkillTreeLayout,
-    UiLayout,
+    Theme, UiLayout,
+};
+use game::ui_ids::{
+    UI_SETTINGS_SLIDER_MASTER, UI_SETTINGS_SLIDER_MUSIC, UI_SETTINGS_SLIDER_SFX,
+    UI_SETTINGS_SLIDER_SHAKE,
 };
 use game::view::GameView;
 use game::view_tree::{
     GameUiAction, build_hud_view_tree, build_menu_view_tree, build_skilltree_toolbar_view_tree,
 };
 
+enum HeadfulEffect {
+    /// Dump the presented frame to `target/screenshots/` (bound to F12).
+    CaptureScreenshot,
+}
+
 struct HeadfulApp {
-    profile_mode: bool,
+    run_mode: RunMode,
+    /// UI labels; replaced by `AppContext::localizer` in `init_state`.
+    labels: Arc<dyn Localizer>,
     base_logic: TetrisLogic,
     base_round_limit: Duration,
     base_gravity_interval: Duration,
+    /// Pre-round countdown for each new run (`ROLLOUT_ROUND_COUNTDOWN_MS`, 0 disables it).
+    round_countdown: Duration,
     sfx: Option<Sfx>,
     debug_hud: DebugHud,
     ui_tree: UiTree,
@@ -63,15 +94,21 @@ struct HeadfulApp {
     last_skilltree: SkillTreeLayout,
     last_game_over_menu: GameOverMenuLayout,
     last_settings_menu: SettingsMenuLayout,
+    pause_restart_armed: bool,
     mouse_x: u32,
     mouse_y: u32,
     skilltree_cam_input: SkillTreeCameraInput,
     horizontal_repeat: HorizontalRepeat,
     dig_camera: DigCameraController,
-    frame_interval: Duration,
-    next_redraw: Instant,
     remote_editor_api: Option<RemoteServer>,
+    /// `(frame, history_len)` last pushed to editor stream clients.
+    last_streamed_frame: Option<(usize, usize)>,
     last_frame_dt: Duration,
+    /// Input-timing clock advanced only by the frame `dt`s the app is handed, so DAS/ARR
+    /// repeats line up with the frames of an input replay rather than the wall clock.
+    input_now: Instant,
+    /// Wall time not yet turned into a whole `LOGIC_STEP`.
+    logic_steps: StepAccumulator,
     exit_requested: bool,
     mouse_release_was_drag: bool,
     consume_next_mouse_up: bool,
@@ -80,7 +117,6 @@ struct HeadfulApp {
     player_settings: PlayerSettings,
     settings_open: bool,
     settings_origin: SettingsOrigin,
-    active_settings_slider: Option<ActiveSettingsSlider>,
     settings_dirty: bool,
 }
 
@@ -91,14 +127,6 @@ enum SettingsOrigin {
     PauseMenu,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ActiveSettingsSlider {
-    Master,
-    Music,
-    Sfx,
-    ScreenShake,
-}
-
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let CaptureCli {
         help,
@@ -117,6 +145,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("replay: {}", path.display());
     }
 
+    if let Some(dir) = std::env::var_os("ROLLOUT_REPLAY_EXPORT_DIR") {
+        let Some(path) = replay_path.as_ref() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "ROLLOUT_REPLAY_EXPORT_DIR requires --replay",
+            )
+            .into());
+        };
+        return export_replay_frames(path, Path::new(&dir));
+    }
+
     let profile_frames = env_usize("ROLLOUT_HEADFUL_PROFILE_FRAMES").unwrap_or(0);
     if replay_path.is_some() && profile_frames > 0 {
         return Err(io::Error::new(
@@ -140,6 +179,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         clamp_to_monitor: true,
         vsync: env_bool("ROLLOUT_HEADFUL_VSYNC"),
         present_mode: env_present_mode("ROLLOUT_HEADFUL_PRESENT_MODE"),
+        // `ROLLOUT_HEADFUL_MAX_FRAME_DT_MS=0` disables the clamp.
+        max_frame_dt: match env_u32("ROLLOUT_HEADFUL_MAX_FRAME_DT_MS") {
+            Some(0) => None,
+            Some(ms) => Some(Duration::from_millis(ms as u64)),
+            None => Some(DEFAULT_MAX_FRAME_DT),
+        },
+        // `ROLLOUT_HEADFUL_TARGET_FPS=0` uncaps the redraw rate.
+        target_fps: env_u32("ROLLOUT_HEADFUL_TARGET_FPS").unwrap_or(DEFAULT_TARGET_FPS),
+        pause_on_unfocus: env_bool("ROLLOUT_HEADFUL_PAUSE_ON_UNFOCUS").unwrap_or(false),
+        localizer: Some(Arc::new(localization::english())),
     };
 
     let mut base_logic = TetrisLogic::new(0, Piece::all()).with_bottomwell(true);
@@ -158,18 +207,41 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let multi_bonus_percent = env_u32("ROLLOUT_DEPTH_WALL_MULTI_BONUS_PERCENT")
         .unwrap_or(DEFAULT_DEPTH_WALL_MULTI_CLEAR_BONUS_PERCENT);
     base_logic = base_logic.with_depth_wall_damage_tuning(per_line_damage, multi_bonus_percent);
+    if env_bool("ROLLOUT_GRAVITY_CURVE").unwrap_or(false) {
+        base_logic = base_logic.with_gravity_curve(GravityCurve::classic());
+    }
     let app = HeadfulApp::new(base_logic, DEFAULT_ROUND_LIMIT, DEFAULT_GRAVITY_INTERVAL);
 
     if let Some(path) = replay_path {
-        run_game_with_replay(config, app, ReplayConfig { path, fps: 15 })
+        // Surface schema-migration failures as readable text instead of a nested io::Error.
+        let shown = path.display().to_string();
+        let speed = std::env::var("ROLLOUT_REPLAY_SPEED")
+            .ok()
+            .and_then(|v| v.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        run_game_with_replay(
+            config,
+            app,
+            ReplayConfig {
+                path,
+                fps: 15,
+                speed,
+            },
+        )
+        .map_err(|err| format!("failed to load replay {shown}: {err}").into())
     } else if let Some(path) = record_path {
         run_game_with_recording(config, app, RecordingConfig { path })
+    } else if let Some(path) = std::env::var_os("ROLLOUT_HEADFUL_INPUT_REPLAY") {
+        run_game_with_input_replay(config, app, path.into())
+    } else if let Some(path) = std::env::var_os("ROLLOUT_HEADFUL_INPUT_RECORD") {
+        run_game_with_input_recording(config, app, path.into())
     } else if profile_frames > 0 {
         run_game_with_profile(
             config,
             app,
             ProfileConfig {
                 target_frames: profile_frames,
+                write_csv: env_bool("ROLLOUT_HEADFUL_PROFILE_CSV").unwrap_or(false),
             },
         )
     } else {
@@ -184,9 +256,23 @@ impl HeadfulApp {
         base_gravity_interval: Duration,
     ) -> Self {
         let settings_store = SettingsStore::from_env();
-        let player_settings = settings_store.load();
+        let mut player_settings = settings_store.load();
+        if let Ok(name) = std::env::var("ROLLOUT_REPEAT_PRESET") {
+            match RepeatTiming::preset(&name) {
+                Some(timing) => player_settings.gameplay.repeat_timing = timing,
+                None => eprintln!(
+                    "warning: unknown ROLLOUT_REPEAT_PRESET {name:?} (expected default or fast)"
+                ),
+            }
+        }
+        for conflict in player_settings.key_bindings.conflicts() {
+            eprintln!("warning: key binding conflict: {conflict}");
+        }
         let sfx = match Sfx::new() {
-            Ok(sfx) => Some(sfx),
+            Ok(mut sfx) => {
+                sfx.load_sound_dir(&sfx_dir_from_env());
+                Some(sfx)
+            }
             Err(err) => {
                 eprintln!("warning: audio disabled: {err}");
                 if is_running_in_wsl() {
@@ -197,12 +283,17 @@ impl HeadfulApp {
                 None
             }
         };
-        let frame_interval = Duration::from_secs_f64(1.0 / 60.0);
+        let editor_token = env_token(EDITOR_TOKEN_ENV);
         let remote_editor_api = match env_u16("ROLLOUT_HEADFUL_EDITOR_PORT").unwrap_or(0) {
             0 => None,
-            port => match RemoteServer::start(port) {
+            port => match RemoteServer::start_with_token(port, editor_token.clone()) {
                 Ok(server) => {
                     println!("headful editor api: http://{}", server.info.addr);
+                    if editor_token.is_none() {
+                        eprintln!(
+                            "warning: headful editor api is unauthenticated; set {EDITOR_TOKEN_ENV} to require a bearer token"
+                        );
+                    }
                     Some(server)
                 }
                 Err(err) => {
@@ -218,10 +309,14 @@ impl HeadfulApp {
             debug_hud.set_round_timer_disabled(true);
         }
         let app = Self {
-            profile_mode: false,
+            run_mode: RunMode::Normal,
+            labels: Arc::new(localization::english()),
             base_logic,
             base_round_limit,
             base_gravity_interval,
+            round_countdown: env_u32("ROLLOUT_ROUND_COUNTDOWN_MS")
+                .map(|ms| Duration::from_millis(u64::from(ms)))
+                .unwrap_or(DEFAULT_ROUND_COUNTDOWN),
             sfx,
             debug_hud,
             ui_tree: UiTree::new(),
@@ -231,15 +326,19 @@ impl HeadfulApp {
             last_skilltree: SkillTreeLayout::default(),
             last_game_over_menu: GameOverMenuLayout::default(),
             last_settings_menu: SettingsMenuLayout::default(),
+            pause_restart_armed: false,
             mouse_x: 0,
             mouse_y: 0,
             skilltree_cam_input: SkillTreeCameraInput::default(),
-            horizontal_repeat: HorizontalRepeat::default(),
+            horizontal_repeat: HorizontalRepeat::with_timing(
+                player_settings.gameplay.repeat_timing,
+            ),
             dig_camera: DigCameraController::from_env(),
-            frame_interval,
-            next_redraw: Instant::now(),
             remote_editor_api,
+            last_streamed_frame: None,
             last_frame_dt: Duration::ZERO,
+            input_now: Instant::now(),
+            logic_steps: StepAccumulator::new(LOGIC_STEP),
             exit_requested: false,
             mouse_release_was_drag: false,
             consume_next_mouse_up: false,
@@ -248,7 +347,6 @@ impl HeadfulApp {
             player_settings,
             settings_open: false,
             settings_origin: SettingsOrigin::default(),
-            active_settings_slider: None,
             settings_dirty: false,
         };
         app.apply_audio_settings();
@@ -257,8 +355,7 @@ impl HeadfulApp {
 
     fn play_click_sfx(&self) {
         if let Some(sfx) = self.sfx.as_ref() {
-            let gain = self.player_settings.audio.effective_sfx_gain();
-            sfx.play_click(ACTION_SFX_VOLUME * gain);
+            sfx.play_or_click(SFX_MENU_CLICK, ACTION_SFX_VOLUME);
         }
     }
 
@@ -295,97 +392,79 @@ impl HeadfulApp {
         };
         self.settings_open = true;
         self.settings_origin = origin;
-        self.active_settings_slider = None;
+        true
+    }
+
+    /// Restart from the pause menu discards the run, so the first request only arms it and the
+    /// second one (button click or `R`) performs the reset.
+    fn request_pause_restart(&mut self, state: &mut HeadlessRunner<TetrisLogic>) -> bool {
+        let view = state.state().view;
+        if !matches!(view, GameView::Tetris { paused: true }) {
+            return false;
+        }
+        if !self.pause_restart_armed {
+            self.pause_restart_armed = true;
+            return true;
+        }
+        self.pause_restart_armed = false;
+        let transition = headful_view::restart_run(view);
+        state.state_mut().view = transition.next_view;
+        if transition.reset_tetris {
+            self.reset_active_run(state);
+        }
         true
     }
 
     fn close_settings(&mut self) {
         self.settings_open = false;
-        self.active_settings_slider = None;
         self.save_settings_if_dirty();
     }
 
-    fn toggle_music_enabled(&mut self) {
-        self.player_settings.audio.music_enabled = !self.player_settings.audio.music_enabled;
+    fn toggle_music_mute(&mut self) {
+        self.player_settings.audio.toggle_music_mute();
         self.apply_audio_settings();
         self.mark_settings_dirty();
     }
 
-    fn settings_slider_from_pointer(&self, x: u32, y: u32) -> Option<ActiveSettingsSlider> {
-        let layout = self.last_settings_menu;
-        if layout.master_track.contains(x, y) {
-            Some(ActiveSettingsSlider::Master)
-        } else if layout.music_track.contains(x, y) {
-            Some(ActiveSettingsSlider::Music)
-        } else if layout.sfx_track.contains(x, y) {
-            Some(ActiveSettingsSlider::Sfx)
-        } else if layout.shake_track.contains(x, y) {
-            Some(ActiveSettingsSlider::ScreenShake)
-        } else {
-            None
-        }
-    }
-
-    fn apply_settings_slider_x(&mut self, slider: ActiveSettingsSlider, x: u32) {
-        let mut changed = false;
-        match slider {
-            ActiveSettingsSlider::Master => {
-                let slider = engine::slider::Slider::new(
-                    self.last_settings_menu.master_track,
-                    0.0,
-                    1.0,
-                    self.player_settings.audio.master_volume,
-                );
-                let next = slider.value_from_x(x);
-                if (next - self.player_settings.audio.master_volume).abs() > 1e-4 {
-                    self.player_settings.audio.master_volume = next;
-                    changed = true;
+    /// Applies a settings-menu slider change from the UI tree.
+    fn apply_settings_ui_event(&mut self, event: &UiEvent) {
+        let UiEvent::ValueChanged { id, value } = *event else {
+            return;
+        };
+        if !self.settings_open {
+            return;
+        }
+        let volume = value.clamp(0.0, 1.0);
+        let audio = &mut self.player_settings.audio;
+        let sfx = self.sfx.as_ref();
+        match id {
+            UI_SETTINGS_SLIDER_MASTER => {
+                audio.master_volume = volume;
+                if let Some(sfx) = sfx {
+                    sfx.set_master_volume(volume);
                 }
             }
-            ActiveSettingsSlider::Music => {
-                let slider = engine::slider::Slider::new(
-                    self.last_settings_menu.music_track,
-                    0.0,
-                    1.0,
-                    self.player_settings.audio.music_volume,
-                );
-                let next = slider.value_from_x(x);
-                if (next - self.player_settings.audio.music_volume).abs() > 1e-4 {
-                    self.player_settings.audio.music_volume = next;
-                    changed = true;
+            UI_SETTINGS_SLIDER_MUSIC => {
+                audio.music_volume = volume;
+                // An explicit volume replaces whatever unmuting would have restored.
+                audio.music_volume_before_mute = None;
+                if let Some(sfx) = sfx {
+                    sfx.set_music_volume(volume);
                 }
             }
-            ActiveSettingsSlider::Sfx => {
-                let slider = engine::slider::Slider::new(
-                    self.last_settings_menu.sfx_track,
-                    0.0,
-                    1.0,
-                    self.player_settings.audio.sfx_volume,
-                );
-                let next = slider.value_from_x(x);
-                if (next - self.player_settings.audio.sfx_volume).abs() > 1e-4 {
-                    self.player_settings.audio.sfx_volume = next;
-                    changed = true;
+            UI_SETTINGS_SLIDER_SFX => {
+                audio.sfx_volume = volume;
+                if let Some(sfx) = sfx {
+                    sfx.set_sfx_volume(volume);
                 }
             }
-            ActiveSettingsSlider::ScreenShake => {
-                let slider = engine::slider::Slider::new(
-                    self.last_settings_menu.shake_track,
-                    0.0,
-                    100.0,
-                    self.player_settings.video.screen_shake_percent as f32,
-                );
-                let next = slider.value_from_x(x).round().clamp(0.0, 100.0) as u8;
-                if next != self.player_settings.video.screen_shake_percent {
-                    self.player_settings.video.screen_shake_percent = next;
-                    changed = true;
-                }
+            UI_SETTINGS_SLIDER_SHAKE => {
+                self.player_settings.video.screen_shake_percent =
+                    value.round().clamp(0.0, 100.0) as u8;
             }
+            _ => return,
         }
-        if changed {
-            self.apply_audio_settings();
-            self.mark_settings_dirty();
-        }
+        self.mark_settings_dirty();
     }
 
     fn reset_active_run(&mut self, state: &mut HeadlessRunner<TetrisLogic>) {
@@ -394,6 +473,7 @@ impl HeadfulApp {
             &self.base_logic,
             self.base_round_limit,
             self.base_gravity_interval,
+            self.round_countdown,
             &mut self.horizontal_repeat,
         );
         self.dig_camera
@@ -411,7 +491,7 @@ impl HeadfulApp {
                     self.debug_hud.toggle();
                 }
                 headful_input::HeadfulInputCommand::ToggleMusic => {
-                    self.toggle_music_enabled();
+                    self.toggle_music_mute();
                 }
                 headful_input::HeadfulInputCommand::ExitRequested => {
                     self.exit_requested = true;
@@ -422,14 +502,15 @@ impl HeadfulApp {
                 headful_input::HeadfulInputCommand::ResetRun => {
                     self.reset_active_run(runner);
                 }
+                headful_input::HeadfulInputCommand::RequestRestart => {
+                    self.request_pause_restart(runner);
+                }
+                headful_input::HeadfulInputCommand::OpenSettings => {
+                    let view = runner.state().view;
+                    self.open_settings_from_view(view);
+                }
                 headful_input::HeadfulInputCommand::ApplyAction(action) => {
-                    apply_action(
-                        runner,
-                        self.sfx.as_ref(),
-                        self.player_settings.audio,
-                        &mut self.debug_hud,
-                        action,
-                    );
+                    apply_action(runner, self.sfx.as_ref(), &mut self.debug_hud, action);
                 }
             }
         }
@@ -518,13 +599,25 @@ impl HeadfulApp {
                     return true;
                 }
             }
+            GameUiAction::RestartRun => {
+                if self.request_pause_restart(state) {
+                    self.play_click_sfx();
+                    return true;
+                }
+            }
+            GameUiAction::OpenSettings => {
+                let view = state.state().view;
+                if self.open_settings_from_view(view) {
+                    self.play_click_sfx();
+                    return true;
+                }
+            }
             GameUiAction::HoldPiece => {
                 let view = state.state().view;
                 if matches!(view, GameView::Tetris { paused: false }) {
                     apply_action(
                         state,
                         self.sfx.as_ref(),
-                        self.player_settings.audio,
                         &mut self.debug_hud,
                         InputAction::Hold,
                     );
@@ -603,27 +696,41 @@ impl HeadfulApp {
         false
     }
 
-    fn update_round_timer_and_game_over(
-        &mut self,
-        state: &mut HeadlessRunner<TetrisLogic>,
-        dt: Duration,
-    ) {
-        if self.profile_mode {
+    /// Maps this frame's wall time onto whole logical steps and runs them as one
+    /// `InputAction::Tick`, so the round timer and gravity only ever see logical time and
+    /// recordings replay frame-exactly.
+    fn advance_logical_time(&mut self, state: &mut HeadlessRunner<TetrisLogic>, dt: Duration) {
+        let timer_disabled =
+            self.run_mode == RunMode::Profile || self.debug_hud.round_timer_disabled();
+        if state.state().round_timer_disabled != timer_disabled {
+            state.state_mut().round_timer_disabled = timer_disabled;
+        }
+
+        let steps = self.logic_steps.accumulate(dt);
+        if steps > 0 {
+            let lines_before = state.state().tetris.lines_cleared();
+            let tick_start = Instant::now();
+            state.step_profiled(InputAction::Tick { steps }, &mut self.debug_hud);
+            self.debug_hud.record_gravity(tick_start.elapsed());
+            if state.state().tetris.lines_cleared() > lines_before
+                && let Some(sfx) = self.sfx.as_ref()
+            {
+                sfx.play(SFX_LINE_CLEAR, LINE_CLEAR_SFX_VOLUME);
+            }
+        }
+
+        self.apply_round_timeout(state);
+    }
+
+    fn apply_round_timeout(&mut self, state: &mut HeadlessRunner<TetrisLogic>) {
+        if self.run_mode == RunMode::Profile || self.debug_hud.round_timer_disabled() {
             return;
         }
 
         let mut trigger_game_over = false;
         {
             let state = state.state_mut();
-            if !self.debug_hud.round_timer_disabled() {
-                state
-                    .round_timer
-                    .tick_if_running(dt, state.view.is_tetris_playing());
-            }
-            if !self.debug_hud.round_timer_disabled()
-                && state.view.is_tetris_playing()
-                && state.round_timer.is_up()
-            {
+            if state.view.is_tetris_playing() && state.round_timer.is_up() {
                 let earned = money_earned_from_run(state);
                 if earned > 0 {
                     state.skilltree.add_money(earned);
@@ -639,53 +746,6 @@ impl HeadfulApp {
         }
     }
 
-    fn apply_gravity_steps(&mut self, state: &mut HeadlessRunner<TetrisLogic>, dt: Duration) {
-        let mut gravity_steps = 0usize;
-        let mut gravity_step_ms = 0u32;
-        let mut line_clear_dt_ms = 0u32;
-        {
-            let state = state.state_mut();
-            if state.view.is_tetris_playing() {
-                if state.tetris.is_line_clear_active() {
-                    // Drive clear animation by frame dt so clear timing does not
-                    // quantize to the gravity interval.
-                    line_clear_dt_ms = duration_to_ms_u32(dt);
-                } else {
-                    gravity_step_ms = duration_to_ms_u32(state.gravity_interval);
-                    state.gravity_elapsed = state.gravity_elapsed.saturating_add(dt);
-                    while state.gravity_elapsed >= state.gravity_interval {
-                        state.gravity_elapsed =
-                            state.gravity_elapsed.saturating_sub(state.gravity_interval);
-                        gravity_steps = gravity_steps.saturating_add(1);
-                    }
-                }
-            } else {
-                state.gravity_elapsed = Duration::ZERO;
-            }
-        }
-        if line_clear_dt_ms > 0 {
-            let gravity_start = Instant::now();
-            state.step_profiled(
-                InputAction::GravityTick {
-                    dt_ms: line_clear_dt_ms,
-                },
-                &mut self.debug_hud,
-            );
-            self.debug_hud.record_gravity(gravity_start.elapsed());
-            return;
-        }
-        for _ in 0..gravity_steps {
-            let gravity_start = Instant::now();
-            state.step_profiled(
-                InputAction::GravityTick {
-                    dt_ms: gravity_step_ms,
-                },
-                &mut self.debug_hud,
-            );
-            self.debug_hud.record_gravity(gravity_start.elapsed());
-        }
-    }
-
     #[cfg(test)]
     fn sync_horizontal_repeat_from_frame(
         &mut self,
@@ -694,20 +754,15 @@ impl HeadfulApp {
         now: Instant,
     ) {
         let mut immediate_actions = Vec::new();
-        headful_input::sync_horizontal_repeat_from_frame(
+        headful_input::sync_horizontal_repeat_from_frame_with_bindings(
             input,
+            &self.player_settings.key_bindings,
             &mut self.horizontal_repeat,
             now,
             |action| immediate_actions.push(action),
         );
         for action in immediate_actions {
-            apply_action(
-                runner,
-                self.sfx.as_ref(),
-                self.player_settings.audio,
-                &mut self.debug_hud,
-                action,
-            );
+            apply_action(runner, self.sfx.as_ref(), &mut self.debug_hud, action);
         }
     }
 
@@ -717,9 +772,10 @@ impl HeadfulApp {
         input: &InputFrame,
         now: Instant,
     ) {
-        let commands = headful_input::process_keyboard_frame(
+        let commands = headful_input::process_keyboard_frame_with_bindings(
             runner,
             input,
+            &self.player_settings.key_bindings,
             now,
             &mut self.horizontal_repeat,
             self.last_skilltree,
@@ -757,15 +813,17 @@ impl HeadfulApp {
 impl GameApp for HeadfulApp {
     type State = HeadlessRunner<TetrisLogic>;
     type Action = GameUiAction;
-    type Effect = ();
+    type Effect = HeadfulEffect;
 
-    fn init_state(&mut self, _ctx: &mut AppContext) -> Self::State {
-        let mut runner = HeadlessRunner::new(self.base_logic.clone());
+    fn init_state(&mut self, ctx: &mut AppContext) -> Self::State {
+        self.run_mode = ctx.run_mode();
+        self.labels = ctx.localizer();
+        let mut runner = HeadlessRunner::new(self.base_logic.clone()).with_timestamps();
         if let Some(record_every) = env_usize("ROLLOUT_RECORD_EVERY_N_FRAMES") {
             runner.set_record_every_n_frames(record_every.max(1));
         }
         let state = runner.state_mut();
-        state.view = if self.profile_mode {
+        state.view = if self.run_mode == RunMode::Profile {
             GameView::Tetris { paused: false }
         } else {
             GameView::MainMenu
@@ -775,17 +833,19 @@ impl GameApp for HeadfulApp {
             self.debug_hud.log_warning(warning.to_string());
             eprintln!("warning: skilltree load issue: {warning}");
         }
-        state.round_timer = RoundTimer::new(self.base_round_limit);
+        state.round_timer =
+            RoundTimer::new(self.base_round_limit).with_countdown(self.round_countdown);
         state.gravity_interval = self.base_gravity_interval;
         state.gravity_elapsed = Duration::ZERO;
+        state.hold_enabled = self.player_settings.gameplay.hold_enabled;
         self.dig_camera.reset(state.tetris.background_depth_rows());
         self.render_state = Some(runner.state().clone());
         runner
     }
 
     fn on_run_mode(&mut self, mode: RunMode, state: &mut Self::State, _ctx: &mut AppContext) {
-        self.profile_mode = matches!(mode, RunMode::Profile);
-        if self.profile_mode {
+        self.run_mode = mode;
+        if mode == RunMode::Profile {
             state.state_mut().view = GameView::Tetris { paused: false };
         }
     }
@@ -795,7 +855,7 @@ impl GameApp for HeadfulApp {
         state: &Self::State,
         ctx: &AppContext,
     ) -> engine::view_tree::ViewTree<Self::Action> {
-        let size = ctx.renderer.size();
+        let size = ctx.surface_size;
         let mut tree = build_menu_view_tree(state.state().view, size.width, size.height);
         tree.nodes
             .extend(build_hud_view_tree(state.state(), size.width, size.height).nodes);
@@ -814,7 +874,11 @@ impl GameApp for HeadfulApp {
         ctx: &mut AppContext,
     ) -> Vec<Self::Effect> {
         self.last_frame_dt = dt;
-        let now = Instant::now();
+        self.input_now += dt;
+        let now = self.input_now;
+        if let Some(sfx) = self.sfx.as_ref() {
+            sfx.update_music_ducking(dt);
+        }
 
         self.drain_remote_commands(state);
 
@@ -823,18 +887,25 @@ impl GameApp for HeadfulApp {
             self.mouse_y = my;
         }
         let pointer_pos = Some((self.mouse_x, self.mouse_y));
-        if input.mouse_pos.is_some() {
-            let _ = self.ui_tree.process_input(UiInput {
-                mouse_pos: pointer_pos,
-                mouse_down: false,
-                mouse_up: false,
-            });
+        // Runs every frame, even without pointer motion, so tooltip dwell time keeps advancing
+        // and held sliders follow the pointer.
+        let hover_events = self.ui_tree.process_input(UiInput {
+            mouse_pos: pointer_pos,
+            mouse_down: false,
+            mouse_up: false,
+            text_events: input.text_events.clone(),
+            scroll_y: input.scroll_y,
+            dt,
+        });
+        for event in &hover_events {
+            self.apply_settings_ui_event(event);
         }
         if !input.window_focused {
             self.horizontal_repeat.clear();
             self.skilltree_cam_input.left_down = false;
             self.skilltree_cam_input.drag_started = false;
             self.skilltree_cam_input.drag_started_in_view = false;
+            state.state_mut().skilltree.editor_cancel_box_select();
             if self.player_settings.gameplay.auto_pause_on_focus_loss
                 && matches!(state.state().view, GameView::Tetris { paused: false })
             {
@@ -849,6 +920,11 @@ impl GameApp for HeadfulApp {
         let left_mouse_down = input.mouse_buttons_down.contains(&MouseButton::Left);
         let pressed = |key| input.keys_pressed.contains(&key);
 
+        let mut effects = Vec::new();
+        if pressed(winit::event::VirtualKeyCode::F12) {
+            effects.push(HeadfulEffect::CaptureScreenshot);
+        }
+
         if self.settings_open {
             if pressed(winit::event::VirtualKeyCode::Escape) {
                 self.close_settings();
@@ -865,48 +941,50 @@ impl GameApp for HeadfulApp {
         }
 
         if left_mouse_pressed {
-            let size = ctx.renderer.size();
+            let size = ctx.surface_size;
             let debug_clicked =
                 self.debug_hud
                     .handle_click(self.mouse_x, self.mouse_y, size.width, size.height);
             if debug_clicked {
                 self.consume_next_mouse_up = true;
-            } else if self.settings_open {
-                if let Some(slider) = self.settings_slider_from_pointer(self.mouse_x, self.mouse_y)
-                {
-                    self.active_settings_slider = Some(slider);
-                    self.apply_settings_slider_x(slider, self.mouse_x);
-                } else {
-                    self.active_settings_slider = None;
-                }
             } else {
-                let _ = self.ui_tree.process_input(UiInput {
+                let press_events = self.ui_tree.process_input(UiInput {
                     mouse_pos: pointer_pos,
                     mouse_down: true,
                     mouse_up: false,
+                    ..UiInput::default()
                 });
-                if matches!(state.state().view, GameView::SkillTree) {
+                for event in &press_events {
+                    self.apply_settings_ui_event(event);
+                }
+                if !self.settings_open && matches!(state.state().view, GameView::SkillTree) {
                     self.skilltree_cam_input.left_down = true;
                     self.skilltree_cam_input.drag_started = false;
                     self.skilltree_cam_input.drag_started_in_view =
                         skilltree_grid_viewport(self.last_skilltree)
                             .map(|r| r.contains(self.mouse_x, self.mouse_y))
-                            .unwrap_or(false);
+                            .unwrap_or(false)
+                            && !headful_camera::skilltree_minimap_contains(
+                                self.last_skilltree,
+                                self.mouse_x,
+                                self.mouse_y,
+                            );
                     self.skilltree_cam_input.down_x = self.mouse_x;
                     self.skilltree_cam_input.down_y = self.mouse_y;
                     self.skilltree_cam_input.last_x = self.mouse_x;
                     self.skilltree_cam_input.last_y = self.mouse_y;
+                    headful_input::begin_skilltree_box_select(
+                        state,
+                        &input,
+                        self.last_skilltree,
+                        self.mouse_x,
+                        self.mouse_y,
+                    );
                 }
             }
         }
 
-        if self.settings_open {
-            if left_mouse_down {
-                if let Some(active) = self.active_settings_slider {
-                    self.apply_settings_slider_x(active, self.mouse_x);
-                }
-            }
-        } else {
+        if !self.settings_open {
             self.update_skilltree_drag_from_frame(state, left_mouse_down);
         }
 
@@ -915,19 +993,30 @@ impl GameApp for HeadfulApp {
             self.skilltree_cam_input.left_down = false;
             self.skilltree_cam_input.drag_started = false;
             self.skilltree_cam_input.drag_started_in_view = false;
-            self.active_settings_slider = None;
+            let commands = headful_input::finish_skilltree_box_select(
+                state,
+                &input,
+                self.mouse_release_was_drag,
+            );
+            self.apply_input_commands(state, commands);
+        }
+        if left_mouse_released && self.settings_open {
+            // Ends any slider drag; settings buttons and toggles are still matched by layout below.
+            let release_events = self.ui_tree.process_input(UiInput {
+                mouse_pos: pointer_pos,
+                mouse_down: false,
+                mouse_up: true,
+                ..UiInput::default()
+            });
+            for event in &release_events {
+                self.apply_settings_ui_event(event);
+            }
         }
 
         let view = state.state().view;
         if view.is_tetris_playing() {
             if let Some(action) = self.horizontal_repeat.next_repeat_action(now) {
-                apply_action(
-                    state,
-                    self.sfx.as_ref(),
-                    self.player_settings.audio,
-                    &mut self.debug_hud,
-                    action,
-                );
+                apply_action(state, self.sfx.as_ref(), &mut self.debug_hud, action);
             }
         }
 
@@ -947,6 +1036,9 @@ impl GameApp for HeadfulApp {
             } else if l.reset_button.contains(self.mouse_x, self.mouse_y) {
                 self.player_settings = PlayerSettings::default();
                 self.apply_audio_settings();
+                self.horizontal_repeat
+                    .set_timing(self.player_settings.gameplay.repeat_timing);
+                state.state_mut().hold_enabled = self.player_settings.gameplay.hold_enabled;
                 self.mark_settings_dirty();
                 self.save_settings_if_dirty();
                 self.play_click_sfx();
@@ -958,10 +1050,7 @@ impl GameApp for HeadfulApp {
                 self.play_click_sfx();
                 ui_handled = true;
             } else if l.music_toggle.contains(self.mouse_x, self.mouse_y) {
-                self.player_settings.audio.music_enabled =
-                    !self.player_settings.audio.music_enabled;
-                self.apply_audio_settings();
-                self.mark_settings_dirty();
+                self.toggle_music_mute();
                 self.play_click_sfx();
                 ui_handled = true;
             } else if l.show_timer_toggle.contains(self.mouse_x, self.mouse_y) {
@@ -988,6 +1077,36 @@ impl GameApp for HeadfulApp {
                 self.mark_settings_dirty();
                 self.play_click_sfx();
                 ui_handled = true;
+            } else if l.das_meter_toggle.contains(self.mouse_x, self.mouse_y) {
+                self.player_settings.gameplay.show_das_meter =
+                    !self.player_settings.gameplay.show_das_meter;
+                self.mark_settings_dirty();
+                self.play_click_sfx();
+                ui_handled = true;
+            } else if l.ghost_toggle.contains(self.mouse_x, self.mouse_y) {
+                self.player_settings.gameplay.show_ghost =
+                    !self.player_settings.gameplay.show_ghost;
+                self.mark_settings_dirty();
+                self.play_click_sfx();
+                ui_handled = true;
+            } else if l.hold_toggle.contains(self.mouse_x, self.mouse_y) {
+                self.player_settings.gameplay.hold_enabled =
+                    !self.player_settings.gameplay.hold_enabled;
+                state.state_mut().hold_enabled = self.player_settings.gameplay.hold_enabled;
+                self.mark_settings_dirty();
+                self.play_click_sfx();
+                ui_handled = true;
+            } else if l.theme_button.contains(self.mouse_x, self.mouse_y) {
+                self.player_settings.video.theme = self.player_settings.video.theme.next();
+                self.mark_settings_dirty();
+                self.play_click_sfx();
+                ui_handled = true;
+            } else if l.piece_palette_button.contains(self.mouse_x, self.mouse_y) {
+                let accessibility = &mut self.player_settings.accessibility;
+                accessibility.piece_palette = accessibility.piece_palette.next();
+                self.mark_settings_dirty();
+                self.play_click_sfx();
+                ui_handled = true;
             }
             self.save_settings_if_dirty();
         }
@@ -999,10 +1118,6 @@ impl GameApp for HeadfulApp {
                     .last_main_menu
                     .settings_button
                     .contains(self.mouse_x, self.mouse_y),
-                GameView::Tetris { paused: true } => self
-                    .last_pause_menu
-                    .settings_button
-                    .contains(self.mouse_x, self.mouse_y),
                 _ => false,
             };
             if clicked_settings && self.open_settings_from_view(view) {
@@ -1022,6 +1137,7 @@ impl GameApp for HeadfulApp {
                 mouse_pos: pointer_pos,
                 mouse_down: left_mouse_pressed,
                 mouse_up: left_mouse_released,
+                ..UiInput::default()
             });
             for event in ui_events {
                 if let UiEvent::Click {
@@ -1041,6 +1157,7 @@ impl GameApp for HeadfulApp {
         if left_mouse_released && allow_ui && !ui_handled && !self.settings_open {
             let commands = headful_input::handle_skilltree_world_click(
                 state,
+                &input,
                 self.last_skilltree,
                 self.mouse_x,
                 self.mouse_y,
@@ -1053,8 +1170,11 @@ impl GameApp for HeadfulApp {
             self.mouse_release_was_drag = false;
         }
 
-        self.update_round_timer_and_game_over(state, dt);
-        self.apply_gravity_steps(state, dt);
+        if !matches!(state.state().view, GameView::Tetris { paused: true }) {
+            self.pause_restart_armed = false;
+        }
+
+        self.advance_logical_time(state, dt);
 
         let view = state.state().view;
         if matches!(view, GameView::SkillTree) {
@@ -1077,15 +1197,51 @@ impl GameApp for HeadfulApp {
             headful_camera::finalize_camera(skilltree, self.last_skilltree);
         }
 
-        self.update_dig_camera_state(state, dt);
+        headful_remote::publish_recorded_frame(
+            self.remote_editor_api.as_ref(),
+            state,
+            &mut self.last_streamed_frame,
+        );
         self.render_state = Some(state.state().clone());
-        Vec::new()
+        effects
+    }
+
+    fn handle_effects(&mut self, effects: Vec<Self::Effect>, ctx: &mut AppContext) {
+        for effect in effects {
+            match effect {
+                HeadfulEffect::CaptureScreenshot => {
+                    // Effects run after draw + present, so this captures the frame just shown.
+                    // Headless simulations have no frame to capture.
+                    let Some(renderer) = ctx.renderer.as_mut() else {
+                        continue;
+                    };
+                    let path = default_screenshot_path("headful");
+                    match renderer.save_png(&path) {
+                        Ok(()) => println!("saved screenshot to {}", path.display()),
+                        Err(err) => {
+                            let msg = format!("screenshot failed: {err}");
+                            self.debug_hud.log_warning(msg.clone());
+                            eprintln!("{msg}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn fixed_update_interval(&self) -> Option<Duration> {
+        Some(headful_dig_camera::DIG_CAMERA_STEP)
+    }
+
+    fn fixed_update(&mut self, state: &mut Self::State, step: Duration, _ctx: &mut AppContext) {
+        self.update_dig_camera_state(state, step);
     }
 
     fn render(
         &mut self,
         _view: &engine::view_tree::ViewTree<Self::Action>,
         renderer: &mut dyn engine::graphics::Renderer2d,
+        alpha: f32,
     ) {
         let Some(state) = self.render_state.as_ref() else {
             return;
@@ -1099,7 +1255,7 @@ impl GameApp for HeadfulApp {
             last_game_over_menu: self.last_game_over_menu,
             last_settings_menu: self.last_settings_menu,
         };
-        let mut camera_offset = self.dig_camera.offset_y_px();
+        let mut camera_offset = self.dig_camera.interpolated_offset_y_px(alpha);
         if self.player_settings.accessibility.reduce_motion {
             camera_offset = 0.0;
         } else {
@@ -1117,7 +1273,27 @@ impl GameApp for HeadfulApp {
             self.last_frame_dt,
             self.settings_open.then_some(&self.player_settings),
             self.player_settings.gameplay.show_round_timer,
+            self.player_settings.gameplay.show_ghost,
+            &Theme::for_settings(
+                self.player_settings.video.theme,
+                &self.player_settings.accessibility,
+            ),
+            self.pause_restart_armed,
+            self.base_logic.gravity_level(state),
+            self.player_settings
+                .gameplay
+                .show_das_meter
+                .then(|| self.horizontal_repeat.charge(self.input_now))
+                .flatten(),
+            self.labels.as_ref(),
         );
+        if self.run_mode == RunMode::Replay {
+            let size = renderer.size();
+            let badge = self.labels.label("hud.replay");
+            let (text_w, _) = renderer.measure_text(&badge);
+            let x = size.width.saturating_sub(text_w.saturating_add(12));
+            renderer.draw_text(x, 12, &badge, [245, 198, 92, 255]);
+        }
         self.last_layout = cache.last_layout;
         self.last_main_menu = cache.last_main_menu;
         self.last_pause_menu = cache.last_pause_menu;
@@ -1135,8 +1311,7 @@ impl GameApp for HeadfulApp {
         control_flow: &mut ControlFlow,
     ) -> bool {
         // Lifecycle-only: gameplay/UI input is handled via `InputFrame` in `update_state`.
-        *control_flow = ControlFlow::WaitUntil(self.next_redraw);
-
+        // Redraw pacing comes from `AppConfig::target_fps`.
         if self.exit_requested {
             *control_flow = ControlFlow::Exit;
             return true;
@@ -1150,13 +1325,6 @@ impl GameApp for HeadfulApp {
                 *control_flow = ControlFlow::Exit;
                 return true;
             }
-            Event::RedrawRequested(_) => {
-                let now = Instant::now();
-                if now < self.next_redraw {
-                    return true;
-                }
-                self.next_redraw = now + self.frame_interval;
-            }
             Event::LoopDestroyed => {
                 self.save_settings_if_dirty();
                 if let Some(remote) = self.remote_editor_api.as_mut() {
@@ -1197,10 +1365,6 @@ fn gravity_interval_from_percent(base: Duration, faster_percent: u32) -> Duratio
     Duration::from_millis(ms.max(25))
 }
 
-fn duration_to_ms_u32(duration: Duration) -> u32 {
-    duration.as_millis().min(u128::from(u32::MAX)) as u32
-}
-
 fn bottomwell_run_mods_from_skill_mods(mods: SkillTreeRunMods) -> BottomwellRunMods {
     BottomwellRunMods {
         deep_shaft_rows: mods.deep_shaft_rows,
@@ -1236,10 +1400,12 @@ fn reset_run(
     base_logic: &TetrisLogic,
     base_round_limit: Duration,
     base_gravity_interval: Duration,
+    round_countdown: Duration,
     horizontal_repeat: &mut HorizontalRepeat,
 ) {
     let skilltree = runner.state().skilltree.clone();
     let view = runner.state().view;
+    let hold_enabled = runner.state().hold_enabled;
     let mods = skilltree.run_mods();
     let tuning = run_tuning_from_mods(base_round_limit, base_gravity_interval, mods);
 
@@ -1247,14 +1413,15 @@ fn reset_run(
         .clone()
         .with_score_bonus_per_line(tuning.score_bonus_per_line)
         .with_bottomwell_run_mods(bottomwell_run_mods_from_skill_mods(mods));
-    let mut next_runner = HeadlessRunner::new(logic);
+    let mut next_runner = HeadlessRunner::new(logic).with_timestamps();
     {
         let state = next_runner.state_mut();
         state.skilltree = skilltree;
         state.view = view;
-        state.round_timer = RoundTimer::new(tuning.round_limit);
+        state.round_timer = RoundTimer::new(tuning.round_limit).with_countdown(round_countdown);
         state.gravity_interval = tuning.gravity_interval;
         state.gravity_elapsed = Duration::ZERO;
+        state.hold_enabled = hold_enabled;
     }
     *runner = next_runner;
     horizontal_repeat.clear();
@@ -1274,6 +1441,11 @@ fn env_u16(name: &str) -> Option<u16> {
     std::env::var(name).ok().and_then(|v| v.parse::<u16>().ok())
 }
 
+/// Non-empty value of `name`; an empty variable counts as unset.
+fn env_token(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
 fn env_bool(name: &str) -> Option<bool> {
     std::env::var(name)
         .ok()
@@ -1302,6 +1474,26 @@ fn is_running_in_wsl() -> bool {
     std::env::var_os("WSL_INTEROP").is_some() || std::env::var_os("WSL_DISTRO_NAME").is_some()
 }
 
+/// Renders `--replay PATH` to `dir` without opening a window.
+fn export_replay_frames(path: &Path, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let tm = TimeMachine::<GameState>::load_json_file(path)
+        .map_err(|err| format!("failed to load replay {}: {err}", path.display()))?;
+    let defaults = replay_export::default_export_config();
+    let config = FrameExportConfig {
+        width: env_u32("ROLLOUT_REPLAY_EXPORT_WIDTH")
+            .unwrap_or(defaults.width)
+            .max(1),
+        height: env_u32("ROLLOUT_REPLAY_EXPORT_HEIGHT")
+            .unwrap_or(defaults.height)
+            .max(1),
+        fps: defaults.fps,
+    };
+    let gif = env_bool("ROLLOUT_REPLAY_EXPORT_GIF").unwrap_or(false);
+    let written = replay_export::export_replay(&tm, dir, config, gif)?;
+    println!("exported {} files to {}", written.len(), dir.display());
+    Ok(())
+}
+
 fn print_headful_help() {
     // go.sh is the primary control surface, but `cargo run -p game --bin headful -- --help`
     // should still be self-explanatory.
@@ -1320,9 +1512,21 @@ Flags:
                      Space: play/pause
                      Left/Right: step -/+1 frame (pauses)
                      Home/End: jump to start/end (pauses)
-                     Up/Down: speed x2 / ÷2
+                     Up/Down: speed x2 / ÷2 (0.25x-8x)
+                     1-9 / 0: scrub to 10%-90% / start (pauses)
+                     Click: scrub to the pointer's horizontal position (pauses)
                      Esc: quit
   --help, -h        Show this help.
+
+Environment:
+  ROLLOUT_REPLAY_EXPORT_DIR=DIR   With --replay, write frame_%05d.png per recorded frame to DIR and
+                                  exit instead of opening a window.
+  ROLLOUT_REPLAY_EXPORT_GIF=1     Also write DIR/replay.gif.
+  ROLLOUT_REPLAY_EXPORT_WIDTH/HEIGHT  Export size (default 480x640).
+  ROLLOUT_REPLAY_SPEED=X          Initial --replay speed multiplier (0.25-8, default 1).
+  ROLLOUT_HEADFUL_INPUT_RECORD=PATH   Record only the per-frame input and dt stream to PATH on exit.
+  ROLLOUT_HEADFUL_INPUT_REPLAY=PATH   Play an input recording back through the game logic.
+  ROLLOUT_REPEAT_PRESET=NAME      Left/right DAS/ARR preset (default, fast); overrides settings.json.
 "#
     );
 }
@@ -1526,12 +1730,18 @@ impl rodio::Source for GlassBreakNoise {
     }
 }
 
+type LoadedSound = Buffered<Decoder<Cursor<Vec<u8>>>>;
+
 struct Sfx {
     _stream: OutputStream,
     handle: OutputStreamHandle,
     click_wav: &'static [u8],
+    sounds: HashMap<String, LoadedSound>,
     music_sink: Option<Sink>,
     music_playing: Cell<bool>,
+    music_gain: Cell<f32>,
+    music_ducker: Cell<MusicDucker>,
+    audio: Cell<AudioSettings>,
 }
 
 impl Sfx {
@@ -1546,16 +1756,104 @@ impl Sfx {
             _stream: stream,
             handle,
             click_wav: include_bytes!("../../../assets/sfx/click.wav"),
+            sounds: HashMap::new(),
             music_playing: Cell::new(music_sink.is_some()),
             music_sink,
+            music_gain: Cell::new(MUSIC_VOLUME),
+            music_ducker: Cell::new(MusicDucker::default()),
+            audio: Cell::new(AudioSettings::default()),
         })
     }
 
+    /// Registers the optional named sounds and music track found in `dir`. Files that are
+    /// missing are skipped; files that fail to decode are reported and skipped.
+    fn load_sound_dir(&mut self, dir: &Path) {
+        for name in [SFX_HARD_DROP, SFX_LINE_CLEAR, SFX_MENU_CLICK] {
+            let Some(path) = find_sound_file(dir, name) else {
+                continue;
+            };
+            if let Err(err) = self.load_sound(name, &path) {
+                eprintln!("warning: failed to load sound {}: {err}", path.display());
+            }
+        }
+        if let Some(path) = find_sound_file(dir, MUSIC_TRACK)
+            && let Err(err) = self.set_music_track(&path)
+        {
+            eprintln!("warning: failed to load music {}: {err}", path.display());
+        }
+    }
+
+    /// Decodes a WAV/OGG file and registers it under `name` for `play`.
+    fn load_sound(&mut self, name: &str, path: &Path) -> io::Result<()> {
+        let bytes = fs::read(path)?;
+        let decoder = Decoder::new(Cursor::new(bytes))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.sounds.insert(name.to_string(), decoder.buffered());
+        Ok(())
+    }
+
+    /// Plays a sound registered with `load_sound`; returns false if `name` is not loaded.
+    fn play(&self, name: &str, volume: f32) -> bool {
+        let Some(sound) = self.sounds.get(name) else {
+            return false;
+        };
+        self.duck_music();
+        if let Ok(sink) = Sink::try_new(&self.handle) {
+            sink.set_volume(volume * self.sfx_gain());
+            sink.append(sound.clone());
+            sink.detach();
+        }
+        true
+    }
+
+    fn play_or_click(&self, name: &str, volume: f32) {
+        if !self.play(name, volume) {
+            self.play_click(volume);
+        }
+    }
+
+    /// Replaces the current music (the procedural `BgMusic` by default) with a looping track.
+    fn set_music_track(&mut self, path: &Path) -> io::Result<()> {
+        let file = fs::File::open(path)?;
+        let source = Decoder::new_looped(io::BufReader::new(file))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let sink = Sink::try_new(&self.handle).map_err(io::Error::other)?;
+        sink.set_volume(self.music_gain.get() * self.music_ducker.get().gain());
+        sink.append(source);
+        if !self.music_playing.get() {
+            sink.pause();
+        }
+        if let Some(previous) = self.music_sink.replace(sink) {
+            previous.stop();
+        }
+        Ok(())
+    }
+
+    fn duck_music(&self) {
+        let mut ducker = self.music_ducker.get();
+        ducker.trigger();
+        self.music_ducker.set(ducker);
+    }
+
+    /// Advances music ducking by one frame and pushes the resulting volume to the music sink.
+    fn update_music_ducking(&self, dt: Duration) {
+        let mut ducker = self.music_ducker.get();
+        let before = ducker.gain();
+        let gain = ducker.update(dt);
+        self.music_ducker.set(ducker);
+        if gain != before {
+            if let Some(sink) = self.music_sink.as_ref() {
+                sink.set_volume(self.music_gain.get() * gain);
+            }
+        }
+    }
+
     fn play_click(&self, volume: f32) {
+        self.duck_music();
         let Ok(sink) = Sink::try_new(&self.handle) else {
             return;
         };
-        sink.set_volume(volume);
+        sink.set_volume(volume * self.sfx_gain());
 
         let Ok(source) = Decoder::new(Cursor::new(self.click_wav)) else {
             return;
@@ -1565,21 +1863,58 @@ impl Sfx {
     }
 
     fn play_glass_break(&self, volume: f32) {
+        self.duck_music();
         let Ok(sink) = Sink::try_new(&self.handle) else {
             return;
         };
-        sink.set_volume(volume);
+        sink.set_volume(volume * self.sfx_gain());
         sink.append(GlassBreakNoise::new(0xA53F_91C7));
         sink.detach();
     }
 
+    /// Settings the music sink and new SFX sinks currently use.
+    fn audio_settings(&self) -> AudioSettings {
+        self.audio.get()
+    }
+
+    fn sfx_gain(&self) -> f32 {
+        self.audio.get().effective_sfx_gain()
+    }
+
+    fn set_master_volume(&self, volume: f32) {
+        let mut audio = self.audio_settings();
+        audio.master_volume = volume;
+        self.apply_audio_settings(audio.clamp());
+    }
+
+    fn set_music_volume(&self, volume: f32) {
+        let mut audio = self.audio_settings();
+        audio.music_volume = volume;
+        self.apply_audio_settings(audio.clamp());
+    }
+
+    fn set_sfx_volume(&self, volume: f32) {
+        let mut audio = self.audio_settings();
+        audio.sfx_volume = volume;
+        self.apply_audio_settings(audio.clamp());
+    }
+
     fn apply_audio_settings(&self, audio: AudioSettings) {
+        self.audio.set(audio);
+        let mut ducker = self.music_ducker.get();
+        ducker.configure(
+            audio.music_ducking,
+            Duration::from_millis(u64::from(audio.music_ducking_recovery_ms)),
+        );
+        self.music_ducker.set(ducker);
+
         let Some(sink) = self.music_sink.as_ref() else {
             return;
         };
 
         let gain = MUSIC_VOLUME * audio.effective_music_gain();
-        sink.set_volume(gain);
+        self.music_gain.set(gain);
+        sink.set_volume(gain * ducker.gain());
         if audio.music_enabled && !audio.mute_all {
             sink.play();
             self.music_playing.set(true);
@@ -1609,7 +1944,6 @@ fn should_play_action_sfx(action: InputAction) -> bool {
 fn apply_action(
     runner: &mut HeadlessRunner<TetrisLogic>,
     sfx: Option<&Sfx>,
-    audio: AudioSettings,
     debug_hud: &mut DebugHud,
     action: InputAction,
 ) {
@@ -1620,11 +1954,11 @@ fn apply_action(
 
     if let Some(sfx) = sfx {
         if should_play_action_sfx(action) {
-            sfx.play_click(ACTION_SFX_VOLUME * audio.effective_sfx_gain());
+            sfx.play_or_click(SFX_HARD_DROP, ACTION_SFX_VOLUME);
         }
         let after_glass_shatters = runner.state().tetris.glass_shatter_count();
         if after_glass_shatters > before_glass_shatters {
-            sfx.play_glass_break(GLASS_BREAK_SFX_VOLUME * audio.effective_sfx_gain());
+            sfx.play_glass_break(GLASS_BREAK_SFX_VOLUME);
         }
     }
 
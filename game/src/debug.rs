@@ -7,8 +7,8 @@ use crate::perf_budget::{
     PerfBudgetConfig, PerfBudgetHealth, PerfBudgetSample, PerfBudgetStatus, PerfBudgetThreshold,
     classify_budget, summarize_statuses,
 };
-use engine::graphics::Renderer2d;
-use engine::profiling::{Profiler, StepTimings};
+use engine::graphics::{Renderer2d, text_scale_for_height};
+use engine::profiling::{FrameTimings, Profiler, StepTimings};
 use engine::ui::Rect;
 
 const COLOR_TEXT: [u8; 4] = [235, 235, 245, 255];
@@ -19,11 +19,21 @@ const COLOR_PANEL_BORDER: [u8; 4] = [40, 40, 55, 255];
 const MAX_LOG_LINES: usize = 8;
 
 // A tiny block font (no external deps). Kept deliberately simple.
-const FONT_SCALE: u32 = 2;
 const GLYPH_W: u32 = 3;
 const GLYPH_H: u32 = 5;
-const GLYPH_ADVANCE_X: u32 = (GLYPH_W + 1) * FONT_SCALE;
-const LINE_ADVANCE_Y: u32 = (GLYPH_H + 1) * FONT_SCALE;
+
+fn glyph_advance_x(scale: u32) -> u32 {
+    (GLYPH_W + 1) * scale
+}
+
+fn line_advance_y(scale: u32) -> u32 {
+    (GLYPH_H + 1) * scale
+}
+
+/// Overlay text scale for a surface `height` pixels tall (2 at 1080p, larger on HiDPI).
+fn overlay_text_scale(height: u32) -> u32 {
+    text_scale_for_height(height)
+}
 
 #[derive(Debug, Clone)]
 struct RollingMs {
@@ -188,44 +198,17 @@ impl DebugHud {
         self.round_timer_disabled
     }
 
+    /// Adds input-handling time to the next frame; used when `FrameTimings::input` is `None`.
     pub fn record_input(&mut self, dt: Duration) {
         self.pending_input = self.pending_input.saturating_add(dt);
         self.last_input_at = Some(Instant::now());
     }
 
+    /// Adds gravity-step time to the next frame; used when `FrameTimings::gravity` is `None`.
     pub fn record_gravity(&mut self, dt: Duration) {
         self.pending_gravity = self.pending_gravity.saturating_add(dt);
     }
 
-    pub fn on_frame(
-        &mut self,
-        frame_dt: Duration,
-        board_dt: Duration,
-        draw_dt: Duration,
-        overlay_dt: Duration,
-        present_dt: Duration,
-        frame_total_dt: Duration,
-    ) {
-        if !self.enabled {
-            self.pending_input = Duration::ZERO;
-            self.pending_gravity = Duration::ZERO;
-            return;
-        }
-
-        let input_dt = std::mem::take(&mut self.pending_input);
-        let gravity_dt = std::mem::take(&mut self.pending_gravity);
-
-        self.frame_dt.push(frame_dt);
-        self.input_dt.push(input_dt);
-        self.gravity_dt.push(gravity_dt);
-        self.board_dt.push(board_dt);
-        self.draw_dt.push(draw_dt);
-        self.overlay_dt.push(overlay_dt);
-        self.present_dt.push(present_dt);
-        self.frame_total_dt.push(frame_total_dt);
-        self.update_budget_samples();
-    }
-
     fn body_lines_colored(&self) -> Vec<HudLine> {
         let avg_frame_ms = self.frame_dt.avg();
         let fps = if avg_frame_ms > 0.0 {
@@ -378,12 +361,12 @@ impl DebugHud {
             .collect()
     }
 
-    fn panel_rect_for_lines(width: u32, height: u32, lines: &[String]) -> (Rect, u32) {
+    fn panel_rect_for_lines(width: u32, height: u32, lines: &[String], scale: u32) -> (Rect, u32) {
         let max_chars = lines.iter().map(|l| l.len() as u32).max().unwrap_or(0);
 
-        let pad = 6u32 * FONT_SCALE;
-        let inner_w = max_chars.saturating_mul(GLYPH_ADVANCE_X);
-        let inner_h = (lines.len() as u32).saturating_mul(LINE_ADVANCE_Y);
+        let pad = 6u32 * scale;
+        let inner_w = max_chars.saturating_mul(glyph_advance_x(scale));
+        let inner_h = (lines.len() as u32).saturating_mul(line_advance_y(scale));
         let panel_w = (inner_w + pad * 2).min(width);
         let panel_h = (inner_h + pad * 2).min(height);
 
@@ -393,22 +376,22 @@ impl DebugHud {
         (Rect::new(x0, y0, panel_w, panel_h), pad)
     }
 
-    fn header_rect_for_panel(panel: Rect, pad: u32) -> Rect {
-        let header_h = (pad + LINE_ADVANCE_Y).min(panel.h);
+    fn header_rect_for_panel(panel: Rect, pad: u32, scale: u32) -> Rect {
+        let header_h = (pad + line_advance_y(scale)).min(panel.h);
         Rect::new(panel.x, panel.y, panel.w, header_h)
     }
 
-    fn line_rect_for_index(panel: Rect, pad: u32, line_index: usize) -> Option<Rect> {
+    fn line_rect_for_index(panel: Rect, pad: u32, line_index: usize, scale: u32) -> Option<Rect> {
         let line_index = u32::try_from(line_index).ok()?;
         let y = panel
             .y
             .saturating_add(pad)
-            .saturating_add(line_index.saturating_mul(LINE_ADVANCE_Y));
+            .saturating_add(line_index.saturating_mul(line_advance_y(scale)));
         let bottom = panel.y.saturating_add(panel.h);
         if y >= bottom {
             return None;
         }
-        let h = LINE_ADVANCE_Y.min(bottom.saturating_sub(y));
+        let h = line_advance_y(scale).min(bottom.saturating_sub(y));
         if h == 0 {
             return None;
         }
@@ -420,9 +403,14 @@ impl DebugHud {
         Some(Rect::new(x, y, w, h))
     }
 
-    fn timer_toggle_rect_for_lines(panel: Rect, pad: u32, lines: &[String]) -> Option<Rect> {
+    fn timer_toggle_rect_for_lines(
+        panel: Rect,
+        pad: u32,
+        lines: &[String],
+        scale: u32,
+    ) -> Option<Rect> {
         let index = lines.iter().position(|line| line.starts_with("TIMER "))?;
-        Self::line_rect_for_index(panel, pad, index)
+        Self::line_rect_for_index(panel, pad, index, scale)
     }
 
     pub fn timer_toggle_rect(&self, width: u32, height: u32) -> Option<Rect> {
@@ -430,8 +418,9 @@ impl DebugHud {
             return None;
         }
         let lines = self.overlay_lines();
-        let (panel, pad) = Self::panel_rect_for_lines(width, height, &lines);
-        Self::timer_toggle_rect_for_lines(panel, pad, &lines)
+        let scale = overlay_text_scale(height);
+        let (panel, pad) = Self::panel_rect_for_lines(width, height, &lines, scale);
+        Self::timer_toggle_rect_for_lines(panel, pad, &lines, scale)
     }
 
     pub fn handle_click(&mut self, x: u32, y: u32, width: u32, height: u32) -> bool {
@@ -440,13 +429,14 @@ impl DebugHud {
         }
 
         let lines = self.overlay_lines();
-        let (panel, pad) = Self::panel_rect_for_lines(width, height, &lines);
-        let header = Self::header_rect_for_panel(panel, pad);
+        let scale = overlay_text_scale(height);
+        let (panel, pad) = Self::panel_rect_for_lines(width, height, &lines, scale);
+        let header = Self::header_rect_for_panel(panel, pad, scale);
         if header.contains(x, y) {
             self.toggle_minimized();
             return true;
         }
-        if let Some(toggle_rect) = Self::timer_toggle_rect_for_lines(panel, pad, &lines) {
+        if let Some(toggle_rect) = Self::timer_toggle_rect_for_lines(panel, pad, &lines, scale) {
             if toggle_rect.contains(x, y) {
                 self.toggle_round_timer_disabled();
                 return true;
@@ -455,21 +445,24 @@ impl DebugHud {
         false
     }
 
+    /// Draws the overlay with text scaled to the surface height (see `text_scale_for_height`);
+    /// `handle_click` and `timer_toggle_rect` use the same scale so hit-testing stays aligned.
     pub fn draw_overlay(&self, gfx: &mut dyn Renderer2d, width: u32, height: u32) {
         if !self.enabled {
             return;
         }
 
+        let scale = overlay_text_scale(height);
         let lines = self.overlay_lines_colored();
         let line_texts: Vec<String> = lines.iter().map(|line| line.text.clone()).collect();
-        let (panel, pad) = Self::panel_rect_for_lines(width, height, &line_texts);
+        let (panel, pad) = Self::panel_rect_for_lines(width, height, &line_texts, scale);
         gfx.fill_rect(panel, COLOR_PANEL_BG);
         gfx.rect_outline(panel, COLOR_PANEL_BORDER);
 
         let mut y = panel.y + pad;
         for line in &lines {
-            gfx.draw_text(panel.x + pad, y, &line.text, line.color);
-            y = y.saturating_add(LINE_ADVANCE_Y);
+            gfx.draw_text_scaled(panel.x + pad, y, &line.text, line.color, scale);
+            y = y.saturating_add(line_advance_y(scale));
             if y >= panel.y + panel.h {
                 break;
             }
@@ -545,4 +538,24 @@ impl Profiler for DebugHud {
         self.engine_total_dt.push(timings.total);
         self.engine_record_dt.push(timings.record);
     }
+
+    /// Unmeasured phases show as zero.
+    fn on_frame(&mut self, timings: FrameTimings) {
+        let pending_input = std::mem::take(&mut self.pending_input);
+        let pending_gravity = std::mem::take(&mut self.pending_gravity);
+        if !self.enabled {
+            return;
+        }
+
+        self.frame_dt.push(timings.frame_dt);
+        self.input_dt.push(timings.input.unwrap_or(pending_input));
+        self.gravity_dt
+            .push(timings.gravity.unwrap_or(pending_gravity));
+        self.board_dt.push(timings.board.unwrap_or_default());
+        self.draw_dt.push(timings.draw.unwrap_or_default());
+        self.overlay_dt.push(timings.overlay.unwrap_or_default());
+        self.present_dt.push(timings.present.unwrap_or_default());
+        self.frame_total_dt.push(timings.total);
+        self.update_budget_samples();
+    }
 }
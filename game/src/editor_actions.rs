@@ -1,36 +1,68 @@
 use engine::editor::{EditorAction, EditorManifest};
+use engine::localization::Localizer;
 
+use crate::localization;
 use crate::playtest::InputAction;
 
 const EDITOR_TITLE: &str = "Tetree (Tetris)";
 
+/// `(action id, label key, action)`; label keys are resolved through a `Localizer`.
 const ACTION_SPECS: &[(&str, &str, InputAction)] = &[
-    ("moveLeft", "Left", InputAction::MoveLeft),
-    ("moveRight", "Right", InputAction::MoveRight),
-    ("softDrop", "Down", InputAction::SoftDrop),
-    ("rotateCw", "Rotate CW", InputAction::RotateCw),
-    ("rotateCcw", "Rotate CCW", InputAction::RotateCcw),
-    ("rotate180", "Rotate 180", InputAction::Rotate180),
-    ("hardDrop", "Hard Drop", InputAction::HardDrop),
-    ("hold", "Hold", InputAction::Hold),
-    ("noop", "Noop", InputAction::Noop),
+    ("moveLeft", "action.moveLeft", InputAction::MoveLeft),
+    ("moveRight", "action.moveRight", InputAction::MoveRight),
+    ("softDrop", "action.softDrop", InputAction::SoftDrop),
+    ("rotateCw", "action.rotateCw", InputAction::RotateCw),
+    ("rotateCcw", "action.rotateCcw", InputAction::RotateCcw),
+    ("rotate180", "action.rotate180", InputAction::Rotate180),
+    ("hardDrop", "action.hardDrop", InputAction::HardDrop),
+    ("hold", "action.hold", InputAction::Hold),
+    ("noop", "action.noop", InputAction::Noop),
 ];
 
 pub fn default_manifest() -> EditorManifest {
+    manifest_with_labels(&localization::english())
+}
+
+/// Editor manifest with action labels resolved through `labels`.
+pub fn manifest_with_labels(labels: &dyn Localizer) -> EditorManifest {
     EditorManifest {
         title: EDITOR_TITLE.to_string(),
-        actions: ACTION_SPECS
-            .iter()
-            .map(|(id, label, _)| EditorAction {
-                id: (*id).to_string(),
-                label: (*label).to_string(),
-            })
-            .collect(),
+        actions: actions_with_labels(labels),
     }
 }
 
+/// Every id `action_from_id` accepts, with English labels.
+pub fn default_actions() -> Vec<EditorAction> {
+    actions_with_labels(&localization::english())
+}
+
+pub fn actions_with_labels(labels: &dyn Localizer) -> Vec<EditorAction> {
+    ACTION_SPECS
+        .iter()
+        .map(|(id, label_key, _)| EditorAction {
+            id: (*id).to_string(),
+            label: labels.label(label_key),
+        })
+        .collect()
+}
+
 pub fn action_from_id(id: &str) -> Option<InputAction> {
     ACTION_SPECS
         .iter()
         .find_map(|(action_id, _, action)| (*action_id == id).then_some(*action))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listed_actions_all_resolve() {
+        let actions = default_actions();
+        assert_eq!(actions.len(), ACTION_SPECS.len());
+        for action in &actions {
+            assert!(action_from_id(&action.id).is_some(), "{}", action.id);
+            assert!(!action.label.is_empty());
+        }
+    }
+}
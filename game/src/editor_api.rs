@@ -158,7 +158,7 @@ fn default_tetris_palette() -> Vec<EditorPaletteEntry> {
     for value in 0u8..=7u8 {
         entries.push(EditorPaletteEntry {
             value,
-            rgba: engine::render::color_for_cell(value),
+            rgba: engine::render::color_for_cell(&engine::render::CellPalette::CLASSIC, value),
             label: None,
         });
     }
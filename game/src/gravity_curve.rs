@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+/// Shortest gravity interval a curve can produce (matches the skill-tree clamp in headful).
+pub const MIN_GRAVITY_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Level-based gravity speedup.
+///
+/// The level is derived from the in-state line count, so the curve is deterministic under replay.
+/// Curves scale the run's base interval (`GameState::gravity_interval`, which already includes the
+/// skill-tree `gravity_faster_percent`), so the two speedups stack multiplicatively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GravityCurve {
+    /// Interval per level as a percent of the base interval. The last entry holds for every higher
+    /// level; an empty table leaves gravity unchanged.
+    Table {
+        lines_per_level: u32,
+        percent_by_level: Vec<u32>,
+    },
+    /// Each level keeps `keep_percent` of the previous level's interval, never dropping below
+    /// `floor_percent` of the base.
+    Geometric {
+        lines_per_level: u32,
+        keep_percent: u32,
+        floor_percent: u32,
+    },
+}
+
+impl GravityCurve {
+    /// Classic-feeling default: a new level every 10 lines, each ~15% faster than the last.
+    pub fn classic() -> Self {
+        Self::Geometric {
+            lines_per_level: 10,
+            keep_percent: 85,
+            floor_percent: 10,
+        }
+    }
+
+    pub fn lines_per_level(&self) -> u32 {
+        match self {
+            Self::Table {
+                lines_per_level, ..
+            }
+            | Self::Geometric {
+                lines_per_level, ..
+            } => (*lines_per_level).max(1),
+        }
+    }
+
+    /// Zero-based gravity level for a cleared-line count.
+    pub fn level(&self, lines_cleared: u32) -> u32 {
+        lines_cleared / self.lines_per_level()
+    }
+
+    /// Interval at `level` as a percent of the base interval.
+    pub fn percent_of_base(&self, level: u32) -> u32 {
+        match self {
+            Self::Table {
+                percent_by_level, ..
+            } => {
+                let idx = (level as usize).min(percent_by_level.len().saturating_sub(1));
+                percent_by_level.get(idx).copied().unwrap_or(100)
+            }
+            Self::Geometric {
+                keep_percent,
+                floor_percent,
+                ..
+            } => {
+                let keep = (*keep_percent).min(100) as u64;
+                let floor = (*floor_percent).min(100) as u64;
+                // Track in basis points so small steps don't round away immediately.
+                let mut bp = 10_000u64;
+                for _ in 0..level {
+                    let next = bp * keep / 100;
+                    if next <= floor * 100 || next == bp {
+                        bp = next.max(floor * 100);
+                        break;
+                    }
+                    bp = next;
+                }
+                (bp.max(floor * 100) / 100) as u32
+            }
+        }
+    }
+
+    /// Effective interval after `lines_cleared` lines, applied on top of `base`.
+    pub fn interval(&self, base: Duration, lines_cleared: u32) -> Duration {
+        let pct = self.percent_of_base(self.level(lines_cleared)) as u128;
+        let ms = base.as_millis().saturating_mul(pct) / 100;
+        let ms = ms.min(u128::from(u64::MAX)) as u64;
+        Duration::from_millis(ms).max(MIN_GRAVITY_INTERVAL.min(base))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_curve_holds_last_entry_past_the_end() {
+        let curve = GravityCurve::Table {
+            lines_per_level: 5,
+            percent_by_level: vec![100, 80, 50],
+        };
+        let base = Duration::from_millis(500);
+
+        assert_eq!(curve.level(4), 0);
+        assert_eq!(curve.interval(base, 4), Duration::from_millis(500));
+        assert_eq!(curve.interval(base, 5), Duration::from_millis(400));
+        assert_eq!(curve.interval(base, 10), Duration::from_millis(250));
+        assert_eq!(curve.interval(base, 500), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn geometric_curve_speeds_up_monotonically_and_respects_floor() {
+        let curve = GravityCurve::classic();
+        let base = Duration::from_millis(500);
+
+        let mut prev = curve.interval(base, 0);
+        assert_eq!(prev, base);
+        for level in 1..40 {
+            let next = curve.interval(base, level * 10);
+            assert!(next <= prev, "level {level} slowed gravity down");
+            prev = next;
+        }
+        assert_eq!(prev, Duration::from_millis(50));
+        assert_eq!(curve.interval(base, u32::MAX), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn curve_never_goes_below_minimum_interval() {
+        let curve = GravityCurve::Table {
+            lines_per_level: 1,
+            percent_by_level: vec![1],
+        };
+        assert_eq!(
+            curve.interval(Duration::from_millis(500), 0),
+            MIN_GRAVITY_INTERVAL
+        );
+        assert_eq!(
+            curve.interval(Duration::from_millis(10), 0),
+            Duration::from_millis(10)
+        );
+    }
+}
@@ -5,6 +5,9 @@ const STEP_PX_PER_ROW_ENV: &str = "ROLLOUT_DIG_CAMERA_STEP_PX_PER_ROW";
 const MAX_OFFSET_PX_ENV: &str = "ROLLOUT_DIG_CAMERA_MAX_OFFSET_PX";
 const RETURN_PX_PER_S_ENV: &str = "ROLLOUT_DIG_CAMERA_RETURN_PX_PER_S";
 
+/// Fixed step the headful app advances the camera on; rendering blends between steps.
+pub const DIG_CAMERA_STEP: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DigCameraConfig {
     pub step_px_per_row: f32,
@@ -42,6 +45,7 @@ impl DigCameraConfig {
 pub struct DigCameraController {
     enabled: bool,
     last_depth_rows: u32,
+    prev_offset_y_px: f32,
     offset_y_px: f32,
     config: DigCameraConfig,
 }
@@ -58,6 +62,7 @@ impl DigCameraController {
         Self {
             enabled,
             last_depth_rows: 0,
+            prev_offset_y_px: 0.0,
             offset_y_px: 0.0,
             config,
         }
@@ -75,12 +80,20 @@ impl DigCameraController {
         self.offset_y_px
     }
 
+    /// Offset blended between the previous and current `update` by `alpha` (0..1).
+    pub fn interpolated_offset_y_px(&self, alpha: f32) -> f32 {
+        let alpha = alpha.clamp(0.0, 1.0);
+        self.prev_offset_y_px + (self.offset_y_px - self.prev_offset_y_px) * alpha
+    }
+
     pub fn reset(&mut self, depth_rows: u32) {
         self.last_depth_rows = depth_rows;
+        self.prev_offset_y_px = 0.0;
         self.offset_y_px = 0.0;
     }
 
     pub fn update(&mut self, depth_rows: u32, dt: Duration, paused: bool) {
+        self.prev_offset_y_px = self.offset_y_px;
         if !self.enabled {
             self.last_depth_rows = depth_rows;
             self.offset_y_px = 0.0;
@@ -195,6 +208,28 @@ mod tests {
         assert_approx_eq(controller.offset_y_px(), 0.0);
     }
 
+    #[test]
+    fn interpolation_blends_previous_and_current_step() {
+        let mut controller = DigCameraController::new_with_config(
+            true,
+            DigCameraConfig {
+                step_px_per_row: 10.0,
+                max_offset_px: 200.0,
+                return_px_per_s: 20.0,
+            },
+        );
+        controller.reset(0);
+        controller.update(3, Duration::ZERO, false);
+        controller.update(3, Duration::from_millis(500), false);
+
+        assert_approx_eq(controller.interpolated_offset_y_px(0.0), 30.0);
+        assert_approx_eq(controller.interpolated_offset_y_px(0.5), 25.0);
+        assert_approx_eq(controller.interpolated_offset_y_px(1.0), 20.0);
+
+        controller.reset(3);
+        assert_approx_eq(controller.interpolated_offset_y_px(0.0), 0.0);
+    }
+
     #[test]
     fn max_clamp_prevents_runaway_offset() {
         let mut controller = DigCameraController::new_with_config(
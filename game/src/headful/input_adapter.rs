@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::time::{Duration, Instant};
 
 use engine::HeadlessRunner;
@@ -8,18 +9,24 @@ use winit::event::VirtualKeyCode;
 use super::skilltree_camera as headful_camera;
 use super::view_transitions as headful_view;
 use crate::playtest::{InputAction, TetrisLogic};
-use crate::skilltree::{SkillTreeEditorTool, SkillTreeRuntime};
+use crate::settings::{KeyBindings, RepeatTiming};
+use crate::skilltree::{
+    BuySfxMode, SkillTreeEditorTool, SkillTreeRuntime, default_skilltree_export_path,
+};
 use crate::tetris_core::Vec2i;
 use crate::tetris_ui::SkillTreeLayout;
 use crate::ui_ids::{
     ACTION_GAME_OVER_QUIT, ACTION_GAME_OVER_RESTART, ACTION_GAME_OVER_SKILLTREE,
     ACTION_MAIN_MENU_QUIT, ACTION_MAIN_MENU_SKILLTREE_EDITOR, ACTION_MAIN_MENU_START,
-    ACTION_PAUSE_END_RUN, ACTION_PAUSE_RESUME, ACTION_SKILLTREE_START_RUN,
-    ACTION_SKILLTREE_TOOL_ADD_CELL, ACTION_SKILLTREE_TOOL_LINK, ACTION_SKILLTREE_TOOL_MOVE,
-    ACTION_SKILLTREE_TOOL_REMOVE_CELL, ACTION_SKILLTREE_TOOL_SELECT,
+    ACTION_PAUSE_END_RUN, ACTION_PAUSE_RESTART, ACTION_PAUSE_RESUME, ACTION_PAUSE_SETTINGS,
+    ACTION_SKILLTREE_START_RUN, ACTION_SKILLTREE_TOOL_ADD_CELL, ACTION_SKILLTREE_TOOL_LINK,
+    ACTION_SKILLTREE_TOOL_MOVE, ACTION_SKILLTREE_TOOL_REMOVE_CELL, ACTION_SKILLTREE_TOOL_SELECT,
 };
 use crate::view::GameView;
 
+/// Click SFX policy for shift-click path purchases in the skilltree.
+const SKILLTREE_BUY_PATH_SFX: BuySfxMode = BuySfxMode::Once;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HorizontalDir {
     Left,
@@ -32,6 +39,20 @@ pub struct HorizontalRepeat {
     pub right_down: bool,
     pub active: Option<HorizontalDir>,
     pub next_repeat_at: Option<Instant>,
+    /// True once the initial delay has elapsed and moves fire every `interval()`.
+    pub repeating: bool,
+    /// DAS/ARR from `GameplaySettings::repeat_timing`.
+    pub timing: RepeatTiming,
+}
+
+/// Progress of the held direction towards its next auto-repeat move, for HUD indicators.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepeatCharge {
+    pub dir: HorizontalDir,
+    /// 0.0 right after the last move (or the press), 1.0 when the next repeat is due.
+    pub fraction: f32,
+    /// False while charging the initial delay, true once auto-repeat has kicked in.
+    pub repeating: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -41,6 +62,9 @@ pub enum HeadfulInputCommand {
     ExitRequested,
     PlayClick,
     ResetRun,
+    /// Restart the paused run; the app asks for a second press before discarding it.
+    RequestRestart,
+    OpenSettings,
     ApplyAction(InputAction),
 }
 
@@ -51,16 +75,36 @@ pub struct UiActionResult {
 }
 
 impl HorizontalRepeat {
-    // Roughly "DAS/ARR"-ish defaults, but the key property is that repeating is driven by our
-    // own timer, not the OS key-repeat (so it won't get interrupted by other keypresses).
-    pub const REPEAT_DELAY: Duration = Duration::from_millis(170);
-    pub const REPEAT_INTERVAL: Duration = Duration::from_millis(50);
+    // Repeating is driven by our own timer, not the OS key-repeat (so it won't get interrupted
+    // by other keypresses); only the DAS/ARR durations come from settings.
+    pub fn with_timing(timing: RepeatTiming) -> Self {
+        Self {
+            timing,
+            ..Self::default()
+        }
+    }
+
+    /// Takes effect from the next press or repeat; a timer already running keeps its deadline.
+    pub fn set_timing(&mut self, timing: RepeatTiming) {
+        self.timing = timing;
+    }
+
+    /// Hold time before the first repeat (DAS).
+    pub fn delay(&self) -> Duration {
+        self.timing.das()
+    }
+
+    /// Time between repeats once repeating (ARR).
+    pub fn interval(&self) -> Duration {
+        self.timing.arr()
+    }
 
     pub fn clear(&mut self) {
         self.left_down = false;
         self.right_down = false;
         self.active = None;
         self.next_repeat_at = None;
+        self.repeating = false;
     }
 
     pub fn on_press(&mut self, dir: HorizontalDir, now: Instant) -> bool {
@@ -79,7 +123,8 @@ impl HorizontalRepeat {
         }
 
         self.active = Some(dir);
-        self.next_repeat_at = Some(now + Self::REPEAT_DELAY);
+        self.next_repeat_at = Some(now + self.delay());
+        self.repeating = false;
         true
     }
 
@@ -101,7 +146,8 @@ impl HorizontalRepeat {
         };
 
         self.active = new_active;
-        self.next_repeat_at = new_active.map(|_| now + Self::REPEAT_DELAY);
+        self.next_repeat_at = new_active.map(|_| now + self.delay());
+        self.repeating = false;
     }
 
     pub fn next_repeat_action(&mut self, now: Instant) -> Option<InputAction> {
@@ -111,29 +157,67 @@ impl HorizontalRepeat {
             return None;
         }
 
-        self.next_repeat_at = Some(now + Self::REPEAT_INTERVAL);
+        self.next_repeat_at = Some(now + self.interval());
+        self.repeating = true;
         Some(match dir {
             HorizontalDir::Left => InputAction::MoveLeft,
             HorizontalDir::Right => InputAction::MoveRight,
         })
     }
+
+    /// How far the held direction is towards its next auto-repeat, read from the same timer that
+    /// `next_repeat_action` fires on. `None` when no direction is held.
+    pub fn charge(&self, now: Instant) -> Option<RepeatCharge> {
+        let dir = self.active?;
+        let next_at = self.next_repeat_at?;
+        let period = if self.repeating {
+            self.interval()
+        } else {
+            self.delay()
+        };
+        let remaining = next_at.saturating_duration_since(now);
+        // A zero DAS is due immediately; keep the division finite.
+        let fraction = 1.0 - remaining.as_secs_f32() / period.as_secs_f32().max(f32::EPSILON);
+        Some(RepeatCharge {
+            dir,
+            fraction: fraction.clamp(0.0, 1.0),
+            repeating: self.repeating,
+        })
+    }
 }
 
 pub fn sync_horizontal_repeat_from_frame<F>(
     input: &InputFrame,
     repeat: &mut HorizontalRepeat,
     now: Instant,
+    on_initial_action: F,
+) where
+    F: FnMut(InputAction),
+{
+    sync_horizontal_repeat_from_frame_with_bindings(
+        input,
+        &KeyBindings::default(),
+        repeat,
+        now,
+        on_initial_action,
+    );
+}
+
+pub fn sync_horizontal_repeat_from_frame_with_bindings<F>(
+    input: &InputFrame,
+    bindings: &KeyBindings,
+    repeat: &mut HorizontalRepeat,
+    now: Instant,
     mut on_initial_action: F,
 ) where
     F: FnMut(InputAction),
 {
-    let left_down_now = input.keys_down.contains(&VirtualKeyCode::Left);
-    let right_down_now = input.keys_down.contains(&VirtualKeyCode::Right)
-        || input.keys_down.contains(&VirtualKeyCode::D);
-    let right_released = input.keys_released.contains(&VirtualKeyCode::Right)
-        || input.keys_released.contains(&VirtualKeyCode::D);
+    let left_down_now = any_bound_key_in(&input.keys_down, bindings, InputAction::MoveLeft);
+    let left_released = any_bound_key_in(&input.keys_released, bindings, InputAction::MoveLeft);
+    let right_down_now = any_bound_key_in(&input.keys_down, bindings, InputAction::MoveRight);
+    let right_released = any_bound_key_in(&input.keys_released, bindings, InputAction::MoveRight);
 
-    if input.keys_released.contains(&VirtualKeyCode::Left) && !left_down_now && repeat.left_down {
+    if left_released && !left_down_now && repeat.left_down {
         repeat.on_release(HorizontalDir::Left, now);
     }
     if right_released && !right_down_now && repeat.right_down {
@@ -157,19 +241,18 @@ pub fn sync_horizontal_repeat_from_frame<F>(
     }
 }
 
+/// Action for `key` under the default layout; the headful app uses `PlayerSettings::key_bindings`.
 pub fn map_key_to_action(key: VirtualKeyCode) -> Option<InputAction> {
-    match key {
-        VirtualKeyCode::Left => Some(InputAction::MoveLeft),
-        VirtualKeyCode::Right | VirtualKeyCode::D => Some(InputAction::MoveRight),
-        VirtualKeyCode::Down | VirtualKeyCode::S => Some(InputAction::SoftDrop),
-        VirtualKeyCode::Up | VirtualKeyCode::W => Some(InputAction::RotateCw),
-        VirtualKeyCode::Z => Some(InputAction::RotateCcw),
-        VirtualKeyCode::X => Some(InputAction::RotateCw),
-        VirtualKeyCode::A => Some(InputAction::Rotate180),
-        VirtualKeyCode::Space => Some(InputAction::HardDrop),
-        VirtualKeyCode::C => Some(InputAction::Hold),
-        _ => None,
-    }
+    KeyBindings::default().action_for(key)
+}
+
+fn any_bound_key_in(
+    keys: &HashSet<VirtualKeyCode>,
+    bindings: &KeyBindings,
+    action: InputAction,
+) -> bool {
+    keys.iter()
+        .any(|&key| bindings.action_for(key) == Some(action))
 }
 
 pub fn should_play_action_sfx(action: InputAction) -> bool {
@@ -307,27 +390,41 @@ fn focus_skilltree_camera_on_selected(
     true
 }
 
-fn apply_editor_tool_at_world(skilltree: &mut SkillTreeRuntime, world: Vec2i) -> bool {
+/// `additive` (shift held) grows/shrinks the selection set for Select and Move instead of
+/// replacing it.
+fn apply_editor_tool_at_world(
+    skilltree: &mut SkillTreeRuntime,
+    world: Vec2i,
+    additive: bool,
+) -> bool {
     skilltree.editor_set_cursor_world(world);
     let hit_id = headful_camera::skilltree_node_at_world(skilltree, world).map(|s| s.to_string());
     match skilltree.editor.tool {
         SkillTreeEditorTool::Select => {
             if let Some(id) = hit_id {
-                skilltree.editor_select(&id, None);
+                if additive {
+                    skilltree.editor_toggle_select(&id, None);
+                } else {
+                    skilltree.editor_select(&id, None);
+                }
                 true
             } else {
-                skilltree.editor_clear_selection();
+                if !additive {
+                    skilltree.editor_clear_selection();
+                }
                 false
             }
         }
         SkillTreeEditorTool::Move => {
             if let Some(id) = hit_id {
-                if let Some(idx) = skilltree.node_index(&id) {
+                let grab = skilltree.node_index(&id).map(|idx| {
                     let pos = skilltree.def.nodes[idx].pos;
-                    let grab = Vec2i::new(world.x - pos.x, world.y - pos.y);
-                    skilltree.editor_select(&id, Some(grab));
+                    Vec2i::new(world.x - pos.x, world.y - pos.y)
+                });
+                if additive {
+                    skilltree.editor_toggle_select(&id, grab);
                 } else {
-                    skilltree.editor_select(&id, None);
+                    skilltree.editor_grab(&id, grab);
                 }
                 true
             } else {
@@ -388,6 +485,28 @@ pub fn process_keyboard_frame(
     now: Instant,
     horizontal_repeat: &mut HorizontalRepeat,
     last_skilltree: SkillTreeLayout,
+    mouse_x: u32,
+    mouse_y: u32,
+) -> Vec<HeadfulInputCommand> {
+    process_keyboard_frame_with_bindings(
+        runner,
+        input,
+        &KeyBindings::default(),
+        now,
+        horizontal_repeat,
+        last_skilltree,
+        mouse_x,
+        mouse_y,
+    )
+}
+
+pub fn process_keyboard_frame_with_bindings(
+    runner: &mut HeadlessRunner<TetrisLogic>,
+    input: &InputFrame,
+    bindings: &KeyBindings,
+    now: Instant,
+    horizontal_repeat: &mut HorizontalRepeat,
+    last_skilltree: SkillTreeLayout,
     _mouse_x: u32,
     _mouse_y: u32,
 ) -> Vec<HeadfulInputCommand> {
@@ -479,8 +598,12 @@ pub fn process_keyboard_frame(
                     }
                     let query = skilltree.editor.search_query.clone();
                     if pressed(VirtualKeyCode::Return) {
-                        if let Some(id) = skilltree.editor_select_matching(&query) {
-                            let _ = focus_skilltree_camera_on_selected(skilltree, last_skilltree);
+                        if let Some(id) = skilltree.editor_find_first_matching(&query) {
+                            skilltree.editor_focus_node(
+                                &id,
+                                last_skilltree.grid_cols,
+                                last_skilltree.grid_rows,
+                            );
                             skilltree.editor.search_open = false;
                             skilltree.editor.search_query.clear();
                             skilltree.editor.status = Some(format!("JUMP {id}"));
@@ -520,6 +643,28 @@ pub fn process_keyboard_frame(
                         commands.push(HeadfulInputCommand::PlayClick);
                     }
                 }
+                if ctrl && pressed(VirtualKeyCode::E) {
+                    let path = default_skilltree_export_path();
+                    skilltree.editor.status = Some(match skilltree.export_def_file(&path) {
+                        Ok(()) => format!("EXPORTED {}", path.display()),
+                        Err(e) => format!("EXPORT FAILED: {e}"),
+                    });
+                    commands.push(HeadfulInputCommand::PlayClick);
+                }
+                if ctrl && pressed(VirtualKeyCode::O) {
+                    let path = default_skilltree_export_path();
+                    match skilltree.import_def_file(&path) {
+                        Ok(()) => commands.push(HeadfulInputCommand::PlayClick),
+                        Err(e) => skilltree.editor.status = Some(format!("IMPORT FAILED: {e}")),
+                    }
+                }
+                if ctrl && pressed(VirtualKeyCode::C) && skilltree.editor_copy_selected() {
+                    commands.push(HeadfulInputCommand::PlayClick);
+                }
+                if ctrl && pressed(VirtualKeyCode::V) && skilltree.editor_paste_at(cursor).is_some()
+                {
+                    commands.push(HeadfulInputCommand::PlayClick);
+                }
 
                 if shift {
                     if pressed(VirtualKeyCode::J)
@@ -590,6 +735,27 @@ pub fn process_keyboard_frame(
                     skilltree.editor_cycle_tool();
                     commands.push(HeadfulInputCommand::PlayClick);
                 }
+                if pressed(VirtualKeyCode::G) {
+                    if shift {
+                        skilltree.editor_cycle_snap_step();
+                    } else {
+                        skilltree.editor_toggle_snap();
+                    }
+                    commands.push(HeadfulInputCommand::PlayClick);
+                }
+                if pressed(VirtualKeyCode::H) {
+                    let aligned = if shift {
+                        skilltree.editor_align_selected_vertical()
+                    } else {
+                        skilltree.editor_align_selected_horizontal()
+                    };
+                    if aligned {
+                        commands.push(HeadfulInputCommand::PlayClick);
+                    }
+                }
+                if pressed(VirtualKeyCode::B) && skilltree.editor_distribute_selected() {
+                    commands.push(HeadfulInputCommand::PlayClick);
+                }
 
                 let pan_step = if shift { 4.0 } else { 1.0 };
                 if pressed(VirtualKeyCode::Left) {
@@ -634,14 +800,14 @@ pub fn process_keyboard_frame(
                     }
                 }
                 if pressed(VirtualKeyCode::Return) || pressed(VirtualKeyCode::Space) {
-                    if apply_editor_tool_at_world(skilltree, skilltree.editor.cursor_world) {
+                    if apply_editor_tool_at_world(skilltree, skilltree.editor.cursor_world, false) {
                         commands.push(HeadfulInputCommand::PlayClick);
                     }
                 }
                 if pressed(VirtualKeyCode::S) {
                     match skilltree.save_def() {
                         Ok(()) => {
-                            skilltree.editor.dirty = false;
+                            skilltree.editor_mark_saved();
                             skilltree.editor.status = Some("SAVED".to_string());
                         }
                         Err(e) => {
@@ -710,31 +876,43 @@ pub fn process_keyboard_frame(
             }
 
             if paused {
+                if pressed(VirtualKeyCode::R) {
+                    commands.push(HeadfulInputCommand::RequestRestart);
+                    commands.push(HeadfulInputCommand::PlayClick);
+                }
                 return commands;
             }
 
-            sync_horizontal_repeat_from_frame(input, horizontal_repeat, now, |action| {
-                commands.push(HeadfulInputCommand::ApplyAction(action));
-            });
-            let soft_drop_down = input.keys_down.contains(&VirtualKeyCode::Down)
-                || input.keys_down.contains(&VirtualKeyCode::S);
-            if soft_drop_down {
+            sync_horizontal_repeat_from_frame_with_bindings(
+                input,
+                bindings,
+                horizontal_repeat,
+                now,
+                |action| {
+                    commands.push(HeadfulInputCommand::ApplyAction(action));
+                },
+            );
+            if any_bound_key_in(&input.keys_down, bindings, InputAction::SoftDrop) {
                 commands.push(HeadfulInputCommand::ApplyAction(InputAction::SoftDrop));
             }
-            for key in [
-                VirtualKeyCode::Up,
-                VirtualKeyCode::W,
-                VirtualKeyCode::Z,
-                VirtualKeyCode::X,
-                VirtualKeyCode::A,
-                VirtualKeyCode::Space,
-                VirtualKeyCode::C,
-            ] {
-                if pressed(key) {
-                    if let Some(action) = map_key_to_action(key) {
-                        commands.push(HeadfulInputCommand::ApplyAction(action));
-                    }
+            // Movement and soft drop are held-key actions handled above; everything else fires
+            // once per press, in the order the bindings list them. Each key resolves through
+            // `action_for`, so a key bound twice only drives its first action.
+            let mut seen = HashSet::new();
+            for binding in bindings.bindings() {
+                if !pressed(binding.key) || !seen.insert(binding.key) {
+                    continue;
+                }
+                let Some(action) = bindings.action_for(binding.key) else {
+                    continue;
+                };
+                if matches!(
+                    action,
+                    InputAction::MoveLeft | InputAction::MoveRight | InputAction::SoftDrop
+                ) {
+                    continue;
                 }
+                commands.push(HeadfulInputCommand::ApplyAction(action));
             }
         }
     }
@@ -790,6 +968,20 @@ pub fn handle_ui_tree_click_action(
                 result.handled = true;
             }
         }
+        ACTION_PAUSE_RESTART => {
+            if matches!(runner.state().view, GameView::Tetris { paused: true }) {
+                result.commands.push(HeadfulInputCommand::RequestRestart);
+                result.commands.push(HeadfulInputCommand::PlayClick);
+                result.handled = true;
+            }
+        }
+        ACTION_PAUSE_SETTINGS => {
+            if matches!(runner.state().view, GameView::Tetris { paused: true }) {
+                result.commands.push(HeadfulInputCommand::OpenSettings);
+                result.commands.push(HeadfulInputCommand::PlayClick);
+                result.handled = true;
+            }
+        }
         ACTION_PAUSE_END_RUN => {
             let view = runner.state().view;
             if matches!(view, GameView::Tetris { paused: true }) {
@@ -849,8 +1041,60 @@ pub fn handle_ui_tree_click_action(
     result
 }
 
+/// Ctrl+press with the editor's Select tool starts a rubber-band selection; the drag then
+/// stretches it instead of panning the camera.
+pub fn begin_skilltree_box_select(
+    runner: &mut HeadlessRunner<TetrisLogic>,
+    input: &InputFrame,
+    last_skilltree: SkillTreeLayout,
+    mouse_x: u32,
+    mouse_y: u32,
+) -> bool {
+    let state = runner.state();
+    if !matches!(state.view, GameView::SkillTree)
+        || !state.skilltree.editor.enabled
+        || state.skilltree.editor.tool != SkillTreeEditorTool::Select
+        || !ctrl_down(input)
+    {
+        return false;
+    }
+    let Some(world) = headful_camera::skilltree_world_cell_at_screen(
+        &state.skilltree,
+        last_skilltree,
+        mouse_x,
+        mouse_y,
+    ) else {
+        return false;
+    };
+    runner.state_mut().skilltree.editor_begin_box_select(world);
+    true
+}
+
+/// Mouse release for a pending box selection: a drag past the threshold commits it (shift adds
+/// to the current selection); a plain click drops it and falls through to the normal click.
+pub fn finish_skilltree_box_select(
+    runner: &mut HeadlessRunner<TetrisLogic>,
+    input: &InputFrame,
+    mouse_release_was_drag: bool,
+) -> Vec<HeadfulInputCommand> {
+    let mut commands = Vec::new();
+    let skilltree = &mut runner.state_mut().skilltree;
+    if skilltree.editor.box_select.is_none() {
+        return commands;
+    }
+    if !mouse_release_was_drag {
+        skilltree.editor_cancel_box_select();
+        return commands;
+    }
+    if skilltree.editor_commit_box_select(shift_down(input)) > 0 {
+        commands.push(HeadfulInputCommand::PlayClick);
+    }
+    commands
+}
+
 pub fn handle_skilltree_world_click(
     runner: &mut HeadlessRunner<TetrisLogic>,
+    input: &InputFrame,
     last_skilltree: SkillTreeLayout,
     mouse_x: u32,
     mouse_y: u32,
@@ -862,6 +1106,16 @@ pub fn handle_skilltree_world_click(
         return commands;
     }
 
+    if headful_camera::recenter_on_minimap_click(
+        &mut runner.state_mut().skilltree,
+        last_skilltree,
+        mouse_x,
+        mouse_y,
+    ) {
+        commands.push(HeadfulInputCommand::PlayClick);
+        return commands;
+    }
+
     let skilltree_editor_enabled = runner.state().skilltree.editor.enabled;
     if skilltree_editor_enabled {
         if let Some(world) = headful_camera::skilltree_world_cell_at_screen(
@@ -870,8 +1124,9 @@ pub fn handle_skilltree_world_click(
             mouse_x,
             mouse_y,
         ) {
+            let additive = shift_down(input);
             let skilltree = &mut runner.state_mut().skilltree;
-            if apply_editor_tool_at_world(skilltree, world) {
+            if apply_editor_tool_at_world(skilltree, world, additive) {
                 commands.push(HeadfulInputCommand::PlayClick);
             }
         }
@@ -899,8 +1154,20 @@ pub fn handle_skilltree_world_click(
             let transition = headful_view::open_skilltree(runner.state().view);
             let state = runner.state_mut();
             state.view = transition.next_view;
-            state.skilltree.try_buy(&id);
-            commands.push(HeadfulInputCommand::PlayClick);
+            // Ctrl-click refunds a purchased leaf; shift-click buys the whole locked
+            // prerequisite path in one go.
+            if ctrl_down(input) {
+                if state.skilltree.refund(&id).is_ok() {
+                    commands.push(HeadfulInputCommand::PlayClick);
+                }
+            } else if shift_down(input) {
+                let result = state.skilltree.buy_path(&id);
+                let plays = result.sfx_plays(SKILLTREE_BUY_PATH_SFX).max(1);
+                commands.extend(std::iter::repeat_n(HeadfulInputCommand::PlayClick, plays));
+            } else {
+                state.skilltree.try_buy(&id);
+                commands.push(HeadfulInputCommand::PlayClick);
+            }
         }
     }
 
@@ -910,6 +1177,7 @@ pub fn handle_skilltree_world_click(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::settings::KeyBinding;
     use crate::skilltree::SkillTreeRuntime;
     use crate::tetris_core::Piece;
 
@@ -974,6 +1242,36 @@ mod tests {
         assert!(should_play_action_sfx(InputAction::HardDrop));
     }
 
+    #[test]
+    fn repeat_charge_tracks_delay_then_interval() {
+        let mut repeat = HorizontalRepeat::default();
+        let now = Instant::now();
+        assert_eq!(repeat.charge(now), None);
+
+        repeat.on_press(HorizontalDir::Right, now);
+        let charge = repeat.charge(now).expect("held direction");
+        assert_eq!(charge.dir, HorizontalDir::Right);
+        assert!(!charge.repeating);
+        assert_eq!(charge.fraction, 0.0);
+
+        let halfway = now + repeat.delay() / 2;
+        let charge = repeat.charge(halfway).expect("held direction");
+        assert!((charge.fraction - 0.5).abs() < 1e-3);
+
+        let fired = now + repeat.delay();
+        assert_eq!(repeat.charge(fired).map(|c| c.fraction), Some(1.0));
+        assert_eq!(
+            repeat.next_repeat_action(fired),
+            Some(InputAction::MoveRight)
+        );
+        let charge = repeat.charge(fired).expect("held direction");
+        assert!(charge.repeating);
+        assert_eq!(charge.fraction, 0.0);
+
+        repeat.on_release(HorizontalDir::Right, fired);
+        assert_eq!(repeat.charge(fired), None);
+    }
+
     #[test]
     fn sync_horizontal_repeat_consumes_frame_sets() {
         let mut repeat = HorizontalRepeat::default();
@@ -999,6 +1297,84 @@ mod tests {
         assert_eq!(repeat.active, None);
     }
 
+    #[test]
+    fn keyboard_frame_follows_custom_bindings() {
+        let mut bindings = KeyBindings::default();
+        bindings
+            .rebind(InputAction::HardDrop, VirtualKeyCode::Return)
+            .expect("Return is unbound");
+        bindings
+            .rebind(InputAction::MoveLeft, VirtualKeyCode::J)
+            .expect("J is unbound");
+
+        let mut runner = make_runner(GameView::Tetris { paused: false });
+        let mut repeat = HorizontalRepeat::default();
+        let keys = [
+            VirtualKeyCode::Space,
+            VirtualKeyCode::Return,
+            VirtualKeyCode::J,
+        ];
+        let commands = process_keyboard_frame_with_bindings(
+            &mut runner,
+            &input_frame_for_keys(&keys, &keys, &[]),
+            &bindings,
+            Instant::now(),
+            &mut repeat,
+            SkillTreeLayout::default(),
+            0,
+            0,
+        );
+
+        assert_eq!(
+            commands,
+            vec![
+                HeadfulInputCommand::ApplyAction(InputAction::MoveLeft),
+                HeadfulInputCommand::ApplyAction(InputAction::HardDrop),
+            ]
+        );
+        assert_eq!(repeat.active, Some(HorizontalDir::Left));
+    }
+
+    #[test]
+    fn keyboard_frame_uses_first_action_for_a_duplicated_key() {
+        let mut value = serde_json::to_value(KeyBindings::default()).expect("serialize bindings");
+        for action in [InputAction::Hold, InputAction::MoveLeft] {
+            let duplicate = KeyBinding {
+                key: VirtualKeyCode::Space,
+                action,
+            };
+            value["bindings"]
+                .as_array_mut()
+                .expect("bindings list")
+                .push(serde_json::to_value(duplicate).expect("serialize binding"));
+        }
+        let bindings: KeyBindings = serde_json::from_value(value).expect("deserialize bindings");
+        assert_eq!(
+            bindings.action_for(VirtualKeyCode::Space),
+            Some(InputAction::HardDrop)
+        );
+
+        let mut runner = make_runner(GameView::Tetris { paused: false });
+        let mut repeat = HorizontalRepeat::default();
+        let keys = [VirtualKeyCode::Space];
+        let commands = process_keyboard_frame_with_bindings(
+            &mut runner,
+            &input_frame_for_keys(&keys, &keys, &[]),
+            &bindings,
+            Instant::now(),
+            &mut repeat,
+            SkillTreeLayout::default(),
+            0,
+            0,
+        );
+
+        assert_eq!(
+            commands,
+            vec![HeadfulInputCommand::ApplyAction(InputAction::HardDrop)]
+        );
+        assert_eq!(repeat.active, None);
+    }
+
     #[test]
     fn keyboard_start_game_emits_reset_and_click_commands() {
         let mut runner = make_runner(GameView::MainMenu);
@@ -1082,6 +1458,58 @@ mod tests {
         assert!(blocked.commands.is_empty());
     }
 
+    #[test]
+    fn pause_menu_restart_and_settings_actions_only_apply_while_paused() {
+        let mut runner = make_runner(GameView::Tetris { paused: true });
+        let restart = handle_ui_tree_click_action(&mut runner, ACTION_PAUSE_RESTART);
+        assert!(restart.handled);
+        assert_eq!(
+            restart.commands,
+            vec![
+                HeadfulInputCommand::RequestRestart,
+                HeadfulInputCommand::PlayClick
+            ]
+        );
+        let settings = handle_ui_tree_click_action(&mut runner, ACTION_PAUSE_SETTINGS);
+        assert!(settings.handled);
+        assert_eq!(
+            settings.commands,
+            vec![
+                HeadfulInputCommand::OpenSettings,
+                HeadfulInputCommand::PlayClick
+            ]
+        );
+        assert_eq!(runner.state().view, GameView::Tetris { paused: true });
+
+        runner.state_mut().view = GameView::Tetris { paused: false };
+        let blocked = handle_ui_tree_click_action(&mut runner, ACTION_PAUSE_RESTART);
+        assert!(!blocked.handled);
+        assert!(blocked.commands.is_empty());
+    }
+
+    #[test]
+    fn keyboard_r_requests_restart_while_paused() {
+        let mut runner = make_runner(GameView::Tetris { paused: true });
+        let mut repeat = HorizontalRepeat::default();
+        let commands = process_keyboard_frame(
+            &mut runner,
+            &input_frame_for_keys(&[VirtualKeyCode::R], &[VirtualKeyCode::R], &[]),
+            Instant::now(),
+            &mut repeat,
+            SkillTreeLayout::default(),
+            0,
+            0,
+        );
+
+        assert_eq!(
+            commands,
+            vec![
+                HeadfulInputCommand::RequestRestart,
+                HeadfulInputCommand::PlayClick
+            ]
+        );
+    }
+
     #[test]
     fn skilltree_editor_direct_tool_hotkeys_select_expected_tool() {
         let mut runner = make_runner(GameView::SkillTree);
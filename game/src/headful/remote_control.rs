@@ -1,9 +1,15 @@
-use engine::HeadlessRunner;
+use std::path::Path;
+
 use engine::editor::{EditorSnapshot, EditorTimeline};
+use engine::graphics::CpuRenderer;
+use engine::surface::SurfaceSize;
+use engine::{HeadlessRunner, ReplayableState};
 use tokio::sync::mpsc::error::TryRecvError;
 
-use crate::headful_editor_api::{RemoteCmd, RemoteServer};
+use crate::headful_editor_api::{MAX_RENDER_DIM, MIN_RENDER_DIM, RemoteCmd, RemoteServer};
 use crate::playtest::TetrisLogic;
+use crate::state::GameState;
+use crate::tetris_ui::draw_tetris_world;
 
 pub fn drain_remote_commands(
     remote: Option<&mut RemoteServer>,
@@ -22,6 +28,28 @@ pub fn drain_remote_commands(
     }
 }
 
+/// Publishes a snapshot to `/api/agent/stream` clients whenever the runner's frame or history
+/// changed since the last call. `last_published` holds the `(frame, history_len)` last sent.
+pub fn publish_recorded_frame(
+    remote: Option<&RemoteServer>,
+    runner: &HeadlessRunner<TetrisLogic>,
+    last_published: &mut Option<(usize, usize)>,
+) {
+    let Some(remote) = remote else {
+        return;
+    };
+    if !remote.has_stream_subscribers() {
+        *last_published = None;
+        return;
+    }
+    let key = (runner.frame(), runner.history().len());
+    if *last_published == Some(key) {
+        return;
+    }
+    *last_published = Some(key);
+    remote.publish_snapshot(snapshot(runner));
+}
+
 fn next_command(remote: &mut RemoteServer) -> Option<RemoteCmd> {
     match remote.rx.try_recv() {
         Ok(cmd) => Some(cmd),
@@ -41,6 +69,9 @@ fn handle_remote_command(
         RemoteCmd::GetTimeline { respond } => {
             let _ = respond.send(timeline(runner));
         }
+        RemoteCmd::ListActions { respond } => {
+            let _ = respond.send(crate::editor_actions::default_actions());
+        }
         RemoteCmd::Step { action_id, respond } => {
             match crate::editor_api::action_from_id(&action_id) {
                 Some(action) => {
@@ -52,6 +83,12 @@ fn handle_remote_command(
                 }
             }
         }
+        RemoteCmd::StepMany {
+            action_ids,
+            respond,
+        } => {
+            let _ = respond.send(step_many(runner, &action_ids));
+        }
         RemoteCmd::Rewind { frames, respond } => {
             runner.rewind(frames);
             let _ = respond.send(snapshot(runner));
@@ -68,7 +105,105 @@ fn handle_remote_command(
             reset_run(runner);
             let _ = respond.send(snapshot(runner));
         }
+        RemoteCmd::LoadRecording { path, respond } => {
+            let _ = respond.send(load_recording(runner, &path));
+        }
+        RemoteCmd::SetFrameState {
+            frame,
+            snapshot: state,
+            respond,
+        } => {
+            let _ = respond.send(set_frame_state(runner, frame, state));
+        }
+        RemoteCmd::RenderState {
+            width,
+            height,
+            respond,
+        } => {
+            let _ = respond.send(render_state_png(runner, width, height));
+        }
+    }
+}
+
+/// Draw the runner's current frame with the software renderer and encode it as PNG.
+///
+/// This never steps the simulation. Dimensions are clamped to
+/// `MIN_RENDER_DIM..=MAX_RENDER_DIM` so a bad request can't allocate a huge buffer.
+pub fn render_state_png(
+    runner: &HeadlessRunner<TetrisLogic>,
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, String> {
+    let width = width.clamp(MIN_RENDER_DIM, MAX_RENDER_DIM);
+    let height = height.clamp(MIN_RENDER_DIM, MAX_RENDER_DIM);
+
+    let mut rgba = vec![0u8; (width as usize) * (height as usize) * 4];
+    {
+        let mut gfx = CpuRenderer::new(&mut rgba, SurfaceSize::new(width, height));
+        let _ = draw_tetris_world(&mut gfx, width, height, &runner.state().tetris);
     }
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|err| format!("png header: {err}"))?;
+    writer
+        .write_image_data(&rgba)
+        .map_err(|err| format!("png data: {err}"))?;
+    writer
+        .finish()
+        .map_err(|err| format!("png finish: {err}"))?;
+    Ok(out)
+}
+
+/// Steps every action in `action_ids`, one frame each. All ids are resolved first so a bad id
+/// leaves the run untouched; the error names the first one.
+pub fn step_many(
+    runner: &mut HeadlessRunner<TetrisLogic>,
+    action_ids: &[String],
+) -> Result<EditorSnapshot, String> {
+    let actions = action_ids
+        .iter()
+        .enumerate()
+        .map(|(index, id)| {
+            crate::editor_api::action_from_id(id)
+                .ok_or_else(|| format!("unknown actionId at index {index}: {id}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for action in actions {
+        runner.step(action);
+    }
+    Ok(snapshot(runner))
+}
+
+/// Swaps in the recording at `path`. The live run is left untouched if it can't be loaded.
+pub fn load_recording(
+    runner: &mut HeadlessRunner<TetrisLogic>,
+    path: &Path,
+) -> Result<EditorSnapshot, String> {
+    let loaded = runner
+        .replay_load(path)
+        .map_err(|err| format!("load recording {}: {err}", path.display()))?;
+    *runner = loaded;
+    Ok(snapshot(runner))
+}
+
+/// Overwrites recorded `frame` with `state` (a snapshot's `state` JSON) and makes it live.
+/// Frames after it are dropped, so stepping continues from the injected state.
+pub fn set_frame_state(
+    runner: &mut HeadlessRunner<TetrisLogic>,
+    frame: usize,
+    state: serde_json::Value,
+) -> Result<EditorSnapshot, String> {
+    let state: GameState =
+        serde_json::from_value(state).map_err(|err| format!("invalid game state: {err}"))?;
+    runner
+        .overwrite_frame(frame, state)
+        .map_err(|err| err.to_string())?;
+    Ok(snapshot(runner))
 }
 
 fn snapshot(runner: &HeadlessRunner<TetrisLogic>) -> EditorSnapshot {
@@ -85,3 +220,82 @@ fn timeline(runner: &HeadlessRunner<TetrisLogic>) -> EditorTimeline {
         can_forward: tm.can_forward(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetris_core::Piece;
+
+    fn png_size(bytes: &[u8]) -> (u32, u32) {
+        let decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        let reader = decoder.read_info().expect("valid png");
+        let info = reader.info();
+        (info.width, info.height)
+    }
+
+    #[test]
+    fn render_state_png_clamps_size_and_does_not_step() {
+        let runner = HeadlessRunner::new(TetrisLogic::new(0, Piece::all()));
+        let frame_before = runner.frame();
+
+        let png = render_state_png(&runner, 1, 100_000).expect("render should succeed");
+        assert_eq!(png_size(&png), (MIN_RENDER_DIM, MAX_RENDER_DIM));
+
+        let png = render_state_png(&runner, 320, 240).expect("render should succeed");
+        assert_eq!(png_size(&png), (320, 240));
+
+        assert_eq!(runner.frame(), frame_before);
+    }
+
+    #[test]
+    fn step_many_rejects_the_whole_batch_on_a_bad_id() {
+        let mut runner = HeadlessRunner::new(TetrisLogic::new(0, Piece::all()));
+        let ids = |ids: &[&str]| ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        let err = step_many(&mut runner, &ids(&["moveLeft", "jump", "bogus"])).unwrap_err();
+        assert_eq!(err, "unknown actionId at index 1: jump");
+        assert_eq!(runner.frame(), 0);
+
+        let snap = step_many(&mut runner, &ids(&["moveLeft", "moveLeft", "noop"])).unwrap();
+        assert_eq!(snap.frame, 3);
+        assert_eq!(runner.frame(), 3);
+    }
+
+    #[test]
+    fn set_frame_state_validates_before_touching_the_run() {
+        let mut runner = HeadlessRunner::new(TetrisLogic::new(0, Piece::all()));
+        runner.step(crate::playtest::InputAction::MoveLeft);
+        runner.step(crate::playtest::InputAction::MoveLeft);
+        let injected = snapshot(&runner).state;
+
+        assert!(set_frame_state(&mut runner, 0, serde_json::json!({"view": 1})).is_err());
+        assert!(set_frame_state(&mut runner, 3, injected.clone()).is_err());
+        assert_eq!(runner.history().len(), 3);
+
+        let snap = set_frame_state(&mut runner, 1, injected.clone()).expect("valid state");
+        assert_eq!(snap.frame, 1);
+        assert_eq!(snap.state, injected);
+        assert_eq!(runner.history().len(), 2);
+    }
+
+    #[test]
+    fn load_recording_swaps_the_run_and_keeps_it_on_error() {
+        let mut recorded = HeadlessRunner::new(TetrisLogic::new(0, Piece::all()));
+        recorded.step(crate::playtest::InputAction::MoveLeft);
+        let path =
+            std::env::temp_dir().join(format!("remote_load_recording_{}.json", std::process::id()));
+        recorded
+            .timemachine()
+            .save_json_file(&path)
+            .expect("save recording");
+
+        let mut runner = HeadlessRunner::new(TetrisLogic::new(0, Piece::all()));
+        assert!(load_recording(&mut runner, &path.with_extension("missing")).is_err());
+        assert_eq!(runner.history().len(), 1);
+
+        let snap = load_recording(&mut runner, &path).expect("load recording");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(snap.frame, 1);
+        assert_eq!(runner.history().len(), 2);
+    }
+}
@@ -1,16 +1,20 @@
 use std::time::{Duration, Instant};
 
 use engine::graphics::Renderer2d;
+use engine::localization::Localizer;
+use engine::profiling::{FrameTimings, Profiler};
 use engine::ui_tree::UiTree;
 
+use super::input_adapter::{HorizontalDir, RepeatCharge};
 use crate::debug::DebugHud;
 use crate::settings::PlayerSettings;
 use crate::state::GameState;
 use crate::tetris_ui::{
     GameOverMenuLayout, MainMenuLayout, PauseMenuLayout, Rect, SettingsMenuLayout, SkillTreeLayout,
-    UiLayout, draw_game_over_menu_with_ui, draw_main_menu_with_ui, draw_pause_menu_with_ui,
-    draw_settings_menu_with_ui, draw_skilltree_runtime_with_ui_and_mouse, draw_tetris_hud_view,
-    draw_tetris_world_with_camera_offset,
+    Theme, UiLayout, draw_game_over_menu_with_ui_and_theme, draw_main_menu_with_ui_and_theme,
+    draw_pause_menu_with_ui_restart_confirm_and_theme, draw_settings_menu_with_ui_and_theme,
+    draw_skilltree_runtime_with_ui_mouse_and_theme, draw_tetris_hud_view_with_theme,
+    draw_tetris_world_with_camera_offset_ghost_and_theme,
 };
 use crate::ui_ids::UI_CANVAS;
 use crate::view::GameView;
@@ -37,6 +41,12 @@ pub fn render_frame(
     last_frame_dt: Duration,
     settings_overlay: Option<&PlayerSettings>,
     show_round_timer: bool,
+    show_ghost: bool,
+    theme: &Theme,
+    pause_restart_armed: bool,
+    gravity_level: Option<u32>,
+    das_charge: Option<RepeatCharge>,
+    labels: &dyn Localizer,
 ) {
     let frame_start = Instant::now();
     let board_start = Instant::now();
@@ -52,21 +62,24 @@ pub fn render_frame(
     if matches!(view, GameView::SkillTree | GameView::MainMenu) {
         cache.last_layout = UiLayout::default();
     } else {
-        let tetris_layout = draw_tetris_world_with_camera_offset(
+        let tetris_layout = draw_tetris_world_with_camera_offset_ghost_and_theme(
             renderer,
             size.width,
             size.height,
             state.tetris(),
             world_offset_y_px,
+            show_ghost,
+            theme,
         );
         if view.is_tetris() {
-            draw_tetris_hud_view(
+            draw_tetris_hud_view_with_theme(
                 renderer,
                 size.width,
                 size.height,
                 state.tetris(),
                 tetris_layout,
                 Some((mouse_x, mouse_y)),
+                theme,
             );
         }
         cache.last_layout = tetris_layout;
@@ -82,26 +95,75 @@ pub fn render_frame(
             .saturating_add(28);
         let timer_disabled = debug_hud.round_timer_disabled();
         let timer_text = if timer_disabled {
-            "TIME OFF".to_string()
+            labels.label("hud.timeOff")
         } else {
             let remaining_s = state.round_timer.remaining().as_secs_f32();
-            format!("TIME {remaining_s:>4.1}")
+            format!("{} {remaining_s:>4.1}", labels.label("hud.time"))
         };
         let timer_color = if timer_disabled {
             [245, 198, 92, 255]
         } else {
-            [235, 235, 245, 255]
+            theme.hud_text
         };
         renderer.draw_text(hud_x, hud_y, &timer_text, timer_color);
     }
 
+    if let Some(level) = gravity_level.filter(|_| view.is_tetris()) {
+        let hud_x = cache.last_layout.pause_button.x.saturating_sub(180);
+        let hud_y = cache
+            .last_layout
+            .pause_button
+            .y
+            .saturating_add(6)
+            .saturating_add(42);
+        // Levels are zero-based internally; show them one-based like classic Tetris.
+        let level_text = format!("{} {}", labels.label("hud.level"), level.saturating_add(1));
+        renderer.draw_text(hud_x, hud_y, &level_text, theme.hud_text);
+    }
+
+    if view.is_tetris() {
+        let hud_x = cache.last_layout.pause_button.x.saturating_sub(180);
+        let hud_y = cache
+            .last_layout
+            .pause_button
+            .y
+            .saturating_add(6)
+            .saturating_add(56);
+        let pps = state.tetris().stats().pieces_per_second();
+        let pps_text = format!("{} {pps:>4.2}", labels.label("hud.pps"));
+        renderer.draw_text(hud_x, hud_y, &pps_text, theme.hud_text);
+    }
+
+    if view.is_tetris() && state.in_countdown() {
+        let remaining = state.round_timer.countdown_remaining();
+        let secs_left = remaining.as_millis().div_ceil(1000);
+        let board = cache.last_layout.board;
+        let text = secs_left.to_string();
+        renderer.draw_text(
+            board.x.saturating_add(board.w / 2).saturating_sub(4),
+            board.y.saturating_add(board.h / 3),
+            &text,
+            theme.hud_text,
+        );
+    }
+
+    if let Some(charge) = das_charge.filter(|_| view.is_tetris()) {
+        draw_das_meter(renderer, cache.last_layout.hold_panel, charge);
+    }
+
     let draw_dt = draw_start.elapsed();
 
     let overlay_start = Instant::now();
     match view {
         GameView::MainMenu => {
-            cache.last_main_menu =
-                draw_main_menu_with_ui(renderer, size.width, size.height, ui_tree);
+            cache.last_main_menu = draw_main_menu_with_ui_and_theme(
+                renderer,
+                size.width,
+                size.height,
+                ui_tree,
+                labels,
+                theme,
+            );
             cache.last_pause_menu = PauseMenuLayout::default();
             cache.last_skilltree = SkillTreeLayout::default();
             cache.last_game_over_menu = GameOverMenuLayout::default();
@@ -110,21 +172,29 @@ pub fn render_frame(
         GameView::SkillTree => {
             cache.last_main_menu = MainMenuLayout::default();
             cache.last_pause_menu = PauseMenuLayout::default();
-            cache.last_skilltree = draw_skilltree_runtime_with_ui_and_mouse(
+            cache.last_skilltree = draw_skilltree_runtime_with_ui_mouse_and_theme(
                 renderer,
                 size.width,
                 size.height,
                 ui_tree,
                 &state.skilltree,
                 Some((mouse_x, mouse_y)),
+                theme,
             );
             cache.last_game_over_menu = GameOverMenuLayout::default();
             cache.last_settings_menu = SettingsMenuLayout::default();
         }
         GameView::Tetris { paused: true } => {
             cache.last_main_menu = MainMenuLayout::default();
-            cache.last_pause_menu =
-                draw_pause_menu_with_ui(renderer, size.width, size.height, ui_tree);
+            cache.last_pause_menu = draw_pause_menu_with_ui_restart_confirm_and_theme(
+                renderer,
+                size.width,
+                size.height,
+                ui_tree,
+                pause_restart_armed,
+                labels,
+                theme,
+            );
             cache.last_skilltree = SkillTreeLayout::default();
             cache.last_game_over_menu = GameOverMenuLayout::default();
             cache.last_settings_menu = SettingsMenuLayout::default();
@@ -140,28 +210,91 @@ pub fn render_frame(
             cache.last_main_menu = MainMenuLayout::default();
             cache.last_pause_menu = PauseMenuLayout::default();
             cache.last_skilltree = SkillTreeLayout::default();
-            cache.last_game_over_menu =
-                draw_game_over_menu_with_ui(renderer, size.width, size.height, ui_tree);
+            cache.last_game_over_menu = draw_game_over_menu_with_ui_and_theme(
+                renderer,
+                size.width,
+                size.height,
+                ui_tree,
+                labels,
+                theme,
+            );
             cache.last_settings_menu = SettingsMenuLayout::default();
         }
     }
     if let Some(settings) = settings_overlay {
-        cache.last_settings_menu =
-            draw_settings_menu_with_ui(renderer, size.width, size.height, ui_tree, settings);
+        cache.last_settings_menu = draw_settings_menu_with_ui_and_theme(
+            renderer,
+            size.width,
+            size.height,
+            ui_tree,
+            settings,
+            labels,
+            theme,
+        );
     } else {
         cache.last_settings_menu = SettingsMenuLayout::default();
     }
+    if let Some((_, text)) = ui_tree.shown_tooltip() {
+        draw_tooltip(renderer, mouse_x, mouse_y, text);
+    }
     debug_hud.draw_overlay(renderer, size.width, size.height);
     let overlay_dt = overlay_start.elapsed();
 
-    let present_dt = Duration::ZERO;
-    let frame_total_dt = frame_start.elapsed();
-    debug_hud.on_frame(
-        last_frame_dt,
-        board_dt,
-        draw_dt,
-        overlay_dt,
-        present_dt,
-        frame_total_dt,
+    // Present happens after `render` returns, so it is not measured here.
+    debug_hud.on_frame(FrameTimings {
+        frame_dt: last_frame_dt,
+        board: Some(board_dt),
+        draw: Some(draw_dt),
+        overlay: Some(overlay_dt),
+        total: frame_start.elapsed(),
+        ..FrameTimings::default()
+    });
+}
+
+/// Horizontal auto-repeat charge bar under the hold panel. It fills towards the held direction:
+/// amber while charging the initial delay, green once moves are auto-repeating.
+fn draw_das_meter(renderer: &mut dyn Renderer2d, hold_panel: Rect, charge: RepeatCharge) {
+    if hold_panel.w == 0 {
+        return;
+    }
+    let track = Rect::new(
+        hold_panel.x,
+        hold_panel.y.saturating_add(hold_panel.h).saturating_add(8),
+        hold_panel.w,
+        8,
+    );
+    let fill_w = ((track.w as f32) * charge.fraction).round() as u32;
+    let fill_x = match charge.dir {
+        HorizontalDir::Left => track.x.saturating_add(track.w - fill_w),
+        HorizontalDir::Right => track.x,
+    };
+    let fill_color = if charge.repeating {
+        [120, 220, 140, 255]
+    } else {
+        [245, 198, 92, 255]
+    };
+    renderer.fill_rect(track, [30, 30, 40, 255]);
+    renderer.fill_rect(Rect::new(fill_x, track.y, fill_w, track.h), fill_color);
+    renderer.rect_outline(track, [235, 235, 245, 255]);
+}
+
+/// Tooltip box just below and right of the cursor, nudged back inside the surface at the edges.
+fn draw_tooltip(renderer: &mut dyn Renderer2d, mouse_x: u32, mouse_y: u32, text: &str) {
+    let size = renderer.size();
+    let (text_w, text_h) = renderer.measure_text(text);
+    let w = text_w.saturating_add(12);
+    let h = text_h.saturating_add(10);
+    let x = mouse_x.saturating_add(14).min(size.width.saturating_sub(w));
+    let y = mouse_y
+        .saturating_add(18)
+        .min(size.height.saturating_sub(h));
+    let rect = Rect::new(x, y, w, h);
+    renderer.fill_rect(rect, [24, 24, 32, 255]);
+    renderer.rect_outline(rect, [235, 235, 245, 255]);
+    renderer.draw_text(
+        x.saturating_add(6),
+        y.saturating_add(5),
+        text,
+        [235, 235, 245, 255],
     );
 }
@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+pub use crate::skilltree::SKILLTREE_CAMERA_BOUNDS_PAD_CELLS;
 use crate::skilltree::{
     SkillTreeRuntime, Vec2f, clamp_camera_min_to_bounds, skilltree_world_bounds,
 };
@@ -11,7 +12,6 @@ pub const SKILLTREE_CAMERA_MAX_CELL_PX: f32 = 64.0;
 pub const SKILLTREE_EDGE_PAN_MARGIN_PX: f32 = 28.0;
 pub const SKILLTREE_EDGE_PAN_MAX_SPEED_PX_PER_S: f32 = 900.0;
 pub const SKILLTREE_DRAG_THRESHOLD_PX: f32 = 4.0;
-pub const SKILLTREE_CAMERA_BOUNDS_PAD_CELLS: f32 = 6.0;
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct SkillTreeCameraInput {
@@ -43,7 +43,7 @@ pub fn skilltree_world_cell_at_screen(
     sy: u32,
 ) -> Option<Vec2i> {
     let view = skilltree_grid_viewport(layout)?;
-    if !view.contains(sx, sy) {
+    if !view.contains(sx, sy) || skilltree_minimap_contains(layout, sx, sy) {
         return None;
     }
 
@@ -70,6 +70,33 @@ pub fn skilltree_world_cell_at_screen(
     Some(Vec2i::new(world_x.floor() as i32, world_y.floor() as i32))
 }
 
+/// The minimap sits on top of the grid, so it hides the cells under it from hit-testing.
+pub fn skilltree_minimap_contains(layout: SkillTreeLayout, sx: u32, sy: u32) -> bool {
+    layout.minimap.is_some_and(|map| map.rect.contains(sx, sy))
+}
+
+/// Minimap click: eases the camera toward the clicked world cell by setting `target_pan`.
+pub fn recenter_on_minimap_click(
+    skilltree: &mut SkillTreeRuntime,
+    layout: SkillTreeLayout,
+    sx: u32,
+    sy: u32,
+) -> bool {
+    let Some(world) = layout.minimap.and_then(|map| map.world_at_screen(sx, sy)) else {
+        return false;
+    };
+    if layout.grid_cols == 0 || layout.grid_rows == 0 {
+        return false;
+    }
+    let default_cam_min_x = -(layout.grid_cols as i32) / 2;
+    skilltree.camera.target_pan = Vec2f::new(
+        world.x as f32 + 0.5 - layout.grid_cols as f32 * 0.5 - default_cam_min_x as f32,
+        world.y as f32 + 0.5 - layout.grid_rows as f32 * 0.5,
+    );
+    clamp_skilltree_camera_to_bounds(skilltree, layout.grid_cols, layout.grid_rows);
+    true
+}
+
 pub fn skilltree_node_at_world<'a>(
     skilltree: &'a SkillTreeRuntime,
     world: Vec2i,
@@ -158,7 +185,13 @@ pub fn update_drag_from_frame(
         }
     }
 
-    if cam_input.drag_started {
+    if cam_input.drag_started && skilltree.editor.box_select.is_some() {
+        // Ctrl+drag in the editor stretches the selection rect instead of panning.
+        let corner = skilltree_world_cell_at_screen(skilltree, last_skilltree, new_x, new_y);
+        if let Some(world) = corner {
+            skilltree.editor_update_box_select(world);
+        }
+    } else if cam_input.drag_started {
         let mut cam_min = Vec2f::new(
             last_skilltree.grid_cam_min_x as f32,
             last_skilltree.grid_cam_min_y as f32,
@@ -353,6 +386,7 @@ pub fn finalize_camera(skilltree: &mut SkillTreeRuntime, last_skilltree: SkillTr
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tetris_ui::SkillTreeMinimap;
 
     #[test]
     fn grid_viewport_requires_non_zero_grid() {
@@ -367,4 +401,33 @@ mod tests {
             .is_some()
         );
     }
+
+    #[test]
+    fn minimap_click_sets_target_pan_and_hides_grid_cells_below_it() {
+        let mut skilltree = SkillTreeRuntime::load_default();
+        let bounds = skilltree_world_bounds(&skilltree.def).unwrap();
+        let grid = Rect::new(0, 0, 800, 600);
+        let layout = SkillTreeLayout {
+            grid,
+            grid_cell: 20,
+            grid_cols: 40,
+            grid_rows: 30,
+            minimap: SkillTreeMinimap::fit(bounds, grid),
+            ..SkillTreeLayout::default()
+        };
+        let map = layout.minimap.unwrap();
+        let (sx, sy) = (map.rect.x + map.rect.w / 2, map.rect.y + map.rect.h / 2);
+
+        assert!(skilltree_world_cell_at_screen(&skilltree, layout, sx, sy).is_none());
+        assert!(skilltree_world_cell_at_screen(&skilltree, layout, 10, 10).is_some());
+
+        assert!(recenter_on_minimap_click(&mut skilltree, layout, sx, sy));
+        let centered = skilltree.camera.target_pan;
+        let (left, right) = (map.rect.x, map.rect.x + map.rect.w - 1);
+        assert!(recenter_on_minimap_click(&mut skilltree, layout, left, sy));
+        let left_pan = skilltree.camera.target_pan.x;
+        assert!(recenter_on_minimap_click(&mut skilltree, layout, right, sy));
+        assert!(left_pan <= centered.x && centered.x <= skilltree.camera.target_pan.x);
+        assert!(!recenter_on_minimap_click(&mut skilltree, layout, 10, 10));
+    }
 }
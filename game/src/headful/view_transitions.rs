@@ -39,6 +39,10 @@ pub fn game_over(view: GameView) -> TransitionResult {
     apply_view_event(view, GameViewEvent::GameOver)
 }
 
+pub fn restart_run(view: GameView) -> TransitionResult {
+    apply_view_event(view, GameViewEvent::Restart)
+}
+
 pub fn money_earned_from_run(state: &GameState) -> u32 {
     // Simple, deterministic conversion from in-run performance to meta-currency.
     // Tunable later; for now it makes the buy-loop visible quickly.
@@ -1,22 +1,30 @@
 use std::{
     io,
     net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener},
+    path::PathBuf,
+    sync::Arc,
     thread,
     time::Duration,
 };
 
 use axum::{
     Json, Router,
-    extract::State,
-    http::StatusCode,
+    extract::{
+        Query, Request, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{HeaderMap, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
-use serde::Serialize;
-use tokio::sync::{mpsc, oneshot};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tower_http::cors::{Any, CorsLayer};
 
 use engine::editor::{
-    EditorManifest, EditorSnapshot, EditorTimeline, FramesRequest, SeekRequest, StepRequest,
+    EditorAction, EditorManifest, EditorSnapshot, EditorTimeline, FramesRequest,
+    LoadRecordingRequest, SeekRequest, SetFrameStateRequest, StepRequest,
 };
 
 use crate::editor_actions;
@@ -29,10 +37,19 @@ pub enum RemoteCmd {
     GetTimeline {
         respond: oneshot::Sender<EditorTimeline>,
     },
+    /// Action ids `Step` accepts, with display labels.
+    ListActions {
+        respond: oneshot::Sender<Vec<EditorAction>>,
+    },
     Step {
         action_id: String,
         respond: oneshot::Sender<Result<EditorSnapshot, String>>,
     },
+    /// Step each action in order, one frame apiece. Nothing is applied if any id is unknown.
+    StepMany {
+        action_ids: Vec<String>,
+        respond: oneshot::Sender<Result<EditorSnapshot, String>>,
+    },
     Rewind {
         frames: usize,
         respond: oneshot::Sender<EditorSnapshot>,
@@ -48,11 +65,77 @@ pub enum RemoteCmd {
     Reset {
         respond: oneshot::Sender<EditorSnapshot>,
     },
+    /// Replace the live run with a saved recording, keeping its cursor.
+    LoadRecording {
+        path: PathBuf,
+        respond: oneshot::Sender<Result<EditorSnapshot, String>>,
+    },
+    /// Overwrite the recorded state at `frame` and drop the frames after it.
+    SetFrameState {
+        frame: usize,
+        snapshot: serde_json::Value,
+        respond: oneshot::Sender<Result<EditorSnapshot, String>>,
+    },
+    /// Render the current frame (without stepping) to PNG bytes.
+    RenderState {
+        width: u32,
+        height: u32,
+        respond: oneshot::Sender<Result<Vec<u8>, String>>,
+    },
 }
 
+/// Size used by `/api/agent/render` when the query omits a dimension.
+pub const DEFAULT_RENDER_WIDTH: u32 = 480;
+pub const DEFAULT_RENDER_HEIGHT: u32 = 640;
+/// Render requests are clamped to this range on each axis.
+pub const MIN_RENDER_DIM: u32 = 64;
+pub const MAX_RENDER_DIM: u32 = 2048;
+
+/// Snapshots buffered per `/api/agent/stream` client before older ones are dropped.
+pub const SNAPSHOT_STREAM_CAPACITY: usize = 8;
+
+/// Shared secret that, when set, every request except `/api/health` must send as
+/// `Authorization: Bearer <token>`.
+pub const EDITOR_TOKEN_ENV: &str = "ROLLOUT_HEADFUL_EDITOR_TOKEN";
+
 #[derive(Clone)]
 struct RemoteState {
     tx: mpsc::UnboundedSender<RemoteCmd>,
+    snapshots: broadcast::Sender<EditorSnapshot>,
+}
+
+async fn require_token(
+    State(token): State<Option<Arc<str>>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if let Some(expected) = token.as_deref()
+        && !bearer_token_matches(req.headers(), expected)
+    {
+        return (
+            StatusCode::UNAUTHORIZED,
+            "missing or invalid bearer token".to_string(),
+        )
+            .into_response();
+    }
+    next.run(req).await
+}
+
+fn bearer_token_matches(headers: &HeaderMap, expected: &str) -> bool {
+    let Some(provided) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    // Compare every byte so the response time doesn't leak how much of the token matched.
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
 }
 
 async fn health() -> &'static str {
@@ -105,6 +188,14 @@ async fn agent_timeline(
     Ok(Json(timeline))
 }
 
+async fn actions(
+    State(state): State<RemoteState>,
+) -> Result<Json<Vec<EditorAction>>, (StatusCode, String)> {
+    let (tx, rx) = oneshot::channel();
+    let actions = send_cmd(&state.tx, RemoteCmd::ListActions { respond: tx }, rx).await?;
+    Ok(Json(actions))
+}
+
 async fn agent_step(
     State(state): State<RemoteState>,
     Json(payload): Json<StepRequest>,
@@ -126,6 +217,27 @@ async fn agent_step(
     }
 }
 
+async fn agent_step_many(
+    State(state): State<RemoteState>,
+    Json(action_ids): Json<Vec<String>>,
+) -> Result<Json<EditorSnapshot>, (StatusCode, String)> {
+    let (tx, rx) = oneshot::channel();
+    let res = send_cmd(
+        &state.tx,
+        RemoteCmd::StepMany {
+            action_ids,
+            respond: tx,
+        },
+        rx,
+    )
+    .await?;
+
+    match res {
+        Ok(snapshot) => Ok(Json(snapshot)),
+        Err(msg) => Err((StatusCode::BAD_REQUEST, msg)),
+    }
+}
+
 async fn agent_rewind(
     State(state): State<RemoteState>,
     Json(payload): Json<FramesRequest>,
@@ -185,23 +297,159 @@ async fn agent_reset(
     Ok(Json(snapshot))
 }
 
-fn router(tx: mpsc::UnboundedSender<RemoteCmd>) -> Router {
+async fn agent_load_recording(
+    State(state): State<RemoteState>,
+    Json(payload): Json<LoadRecordingRequest>,
+) -> Result<Json<EditorSnapshot>, (StatusCode, String)> {
+    let (tx, rx) = oneshot::channel();
+    let res = send_cmd(
+        &state.tx,
+        RemoteCmd::LoadRecording {
+            path: PathBuf::from(payload.path),
+            respond: tx,
+        },
+        rx,
+    )
+    .await?;
+
+    match res {
+        Ok(snapshot) => Ok(Json(snapshot)),
+        Err(msg) => Err((StatusCode::BAD_REQUEST, msg)),
+    }
+}
+
+async fn agent_set_state(
+    State(state): State<RemoteState>,
+    Json(payload): Json<SetFrameStateRequest>,
+) -> Result<Json<EditorSnapshot>, (StatusCode, String)> {
+    let (tx, rx) = oneshot::channel();
+    let res = send_cmd(
+        &state.tx,
+        RemoteCmd::SetFrameState {
+            frame: payload.frame,
+            snapshot: payload.snapshot,
+            respond: tx,
+        },
+        rx,
+    )
+    .await?;
+
+    match res {
+        Ok(snapshot) => Ok(Json(snapshot)),
+        Err(msg) => Err((StatusCode::BAD_REQUEST, msg)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct RenderQuery {
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+async fn agent_render(
+    State(state): State<RemoteState>,
+    Query(query): Query<RenderQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let (tx, rx) = oneshot::channel();
+    let res = send_cmd(
+        &state.tx,
+        RemoteCmd::RenderState {
+            width: query.width.unwrap_or(DEFAULT_RENDER_WIDTH),
+            height: query.height.unwrap_or(DEFAULT_RENDER_HEIGHT),
+            respond: tx,
+        },
+        rx,
+    )
+    .await?;
+
+    match res {
+        Ok(png) => Ok(([(header::CONTENT_TYPE, "image/png")], png)),
+        Err(msg) => Err((StatusCode::INTERNAL_SERVER_ERROR, msg)),
+    }
+}
+
+async fn agent_stream(ws: WebSocketUpgrade, State(state): State<RemoteState>) -> Response {
+    let rx = state.snapshots.subscribe();
+    ws.on_upgrade(move |socket| stream_snapshots(socket, rx))
+}
+
+/// Pushes each recorded frame's snapshot as a JSON text message until the client disconnects.
+/// Incoming messages are ignored; control still goes through the HTTP endpoints.
+async fn stream_snapshots(mut socket: WebSocket, mut rx: broadcast::Receiver<EditorSnapshot>) {
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            snapshot = next_latest_snapshot(&mut rx) => {
+                let Some(snapshot) = snapshot else {
+                    break;
+                };
+                let Ok(text) = serde_json::to_string(&snapshot) else {
+                    continue;
+                };
+                if socket.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Waits for the next snapshot, then skips ahead to the newest one queued. A client that falls
+/// behind (including past the channel capacity) only ever receives the latest frame.
+async fn next_latest_snapshot(
+    rx: &mut broadcast::Receiver<EditorSnapshot>,
+) -> Option<EditorSnapshot> {
+    loop {
+        match rx.recv().await {
+            Ok(snapshot) => return Some(try_latest_snapshot(rx).unwrap_or(snapshot)),
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return None,
+        }
+    }
+}
+
+fn try_latest_snapshot(rx: &mut broadcast::Receiver<EditorSnapshot>) -> Option<EditorSnapshot> {
+    let mut latest = None;
+    loop {
+        match rx.try_recv() {
+            Ok(snapshot) => latest = Some(snapshot),
+            Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+            Err(_) => return latest,
+        }
+    }
+}
+
+fn router(
+    tx: mpsc::UnboundedSender<RemoteCmd>,
+    snapshots: broadcast::Sender<EditorSnapshot>,
+    token: Option<Arc<str>>,
+) -> Router {
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
     Router::new()
-        .route("/api/health", get(health))
         .route("/api/manifest", get(manifest))
+        .route("/api/actions", get(actions))
         .route("/api/agent/state", get(agent_state))
         .route("/api/agent/timeline", get(agent_timeline))
         .route("/api/agent/step", post(agent_step))
+        .route("/api/agent/step-many", post(agent_step_many))
         .route("/api/agent/rewind", post(agent_rewind))
         .route("/api/agent/forward", post(agent_forward))
         .route("/api/agent/seek", post(agent_seek))
         .route("/api/agent/reset", post(agent_reset))
-        .with_state(RemoteState { tx })
+        .route("/api/agent/load-recording", post(agent_load_recording))
+        .route("/api/agent/set-state", post(agent_set_state))
+        .route("/api/agent/render", get(agent_render))
+        .route("/api/agent/stream", get(agent_stream))
+        .route_layer(middleware::from_fn_with_state(token, require_token))
+        .route("/api/health", get(health))
+        .with_state(RemoteState { tx, snapshots })
         .layer(cors)
 }
 
@@ -213,14 +461,25 @@ pub struct RemoteServerInfo {
 
 pub struct RemoteServer {
     pub rx: mpsc::UnboundedReceiver<RemoteCmd>,
+    snapshots: broadcast::Sender<EditorSnapshot>,
     shutdown: Option<oneshot::Sender<()>>,
     pub info: RemoteServerInfo,
 }
 
 impl RemoteServer {
+    /// Starts an unauthenticated server on `127.0.0.1:port`.
     pub fn start(port: u16) -> io::Result<Self> {
+        Self::start_with_token(port, None)
+    }
+
+    /// Starts the server on `127.0.0.1:port`; with `Some(token)`, requests must carry it as a
+    /// bearer token (see `EDITOR_TOKEN_ENV`). It never binds a non-loopback address.
+    pub fn start_with_token(port: u16, token: Option<String>) -> io::Result<Self> {
         let (tx, rx) = mpsc::unbounded_channel::<RemoteCmd>();
         let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let (snapshots, _) = broadcast::channel::<EditorSnapshot>(SNAPSHOT_STREAM_CAPACITY);
+        let stream_snapshots = snapshots.clone();
+        let token = token.map(Arc::<str>::from);
 
         let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
 
@@ -235,7 +494,7 @@ impl RemoteServer {
             rt.block_on(async move {
                 let listener = tokio::net::TcpListener::from_std(std_listener)
                     .expect("remote editor api listener should convert");
-                let app = router(tx);
+                let app = router(tx, stream_snapshots, token);
 
                 let serve = axum::serve(listener, app).with_graceful_shutdown(async move {
                     let _ = shutdown_rx.await;
@@ -249,14 +508,99 @@ impl RemoteServer {
 
         Ok(Self {
             rx,
+            snapshots,
             shutdown: Some(shutdown_tx),
             info,
         })
     }
 
+    /// Whether any `/api/agent/stream` client is connected, so callers can skip building
+    /// snapshots nobody will read.
+    pub fn has_stream_subscribers(&self) -> bool {
+        self.snapshots.receiver_count() > 0
+    }
+
+    /// Sends a snapshot to every connected stream client.
+    pub fn publish_snapshot(&self, snapshot: EditorSnapshot) {
+        let _ = self.snapshots.send(snapshot);
+    }
+
     pub fn shutdown(&mut self) {
         if let Some(tx) = self.shutdown.take() {
             let _ = tx.send(());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playtest::TetrisLogic;
+    use crate::tetris_core::Piece;
+    use axum::body::Body;
+    use engine::HeadlessRunner;
+    use tower::ServiceExt;
+
+    fn snapshot_at(frame: usize) -> EditorSnapshot {
+        let runner = HeadlessRunner::new(TetrisLogic::new(0, Piece::all()));
+        crate::editor_api::snapshot_from_state(frame, runner.state())
+    }
+
+    #[test]
+    fn lagging_stream_client_skips_to_latest_snapshot() {
+        let (tx, mut rx) = broadcast::channel::<EditorSnapshot>(2);
+        for frame in 1..=5 {
+            tx.send(snapshot_at(frame)).expect("receiver is alive");
+        }
+
+        let latest = try_latest_snapshot(&mut rx).expect("snapshots were queued");
+        assert_eq!(latest.frame, 5);
+        assert!(try_latest_snapshot(&mut rx).is_none());
+    }
+
+    async fn status_for(app: &Router, path: &str, auth: Option<&str>) -> StatusCode {
+        let mut req = axum::http::Request::builder().uri(path);
+        if let Some(auth) = auth {
+            req = req.header(header::AUTHORIZATION, auth);
+        }
+        let req = req.body(Body::empty()).expect("request should build");
+        app.clone()
+            .oneshot(req)
+            .await
+            .expect("router should respond")
+            .status()
+    }
+
+    #[tokio::test]
+    async fn token_is_required_when_configured() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (snapshots, _) = broadcast::channel(SNAPSHOT_STREAM_CAPACITY);
+        let app = router(tx, snapshots, Some(Arc::from("s3cret")));
+
+        assert_eq!(
+            status_for(&app, "/api/manifest", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status_for(&app, "/api/manifest", Some("Bearer wrong!")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status_for(&app, "/api/manifest", Some("Bearer s3cret")).await,
+            StatusCode::OK
+        );
+        assert_eq!(status_for(&app, "/api/health", None).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn tokenless_router_stays_open() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (snapshots, _) = broadcast::channel(SNAPSHOT_STREAM_CAPACITY);
+        let app = router(tx, snapshots, None);
+
+        assert_eq!(
+            status_for(&app, "/api/manifest", None).await,
+            StatusCode::OK
+        );
+    }
+}
@@ -3,10 +3,13 @@ pub mod background;
 pub mod debug;
 pub mod editor_actions;
 pub mod editor_api;
+pub mod gravity_curve;
 pub mod headful;
 pub mod headful_editor_api;
+pub mod localization;
 pub mod perf_budget;
 pub mod playtest;
+pub mod replay_export;
 pub mod round_timer;
 pub mod serde_duration;
 pub mod settings;
@@ -0,0 +1,99 @@
+use engine::localization::TableLocalizer;
+
+/// Default English UI labels, keyed the way draw code and `editor_actions` look them up.
+///
+/// Action keys are `action.<editor action id>`; menu and HUD keys are `menu.*`, `settings.*` and
+/// `hud.*`. Keys missing from a replacement table render as the key itself.
+const ENGLISH: &[(&str, &str)] = &[
+    ("action.moveLeft", "Left"),
+    ("action.moveRight", "Right"),
+    ("action.softDrop", "Down"),
+    ("action.rotateCw", "Rotate CW"),
+    ("action.rotateCcw", "Rotate CCW"),
+    ("action.rotate180", "Rotate 180"),
+    ("action.hardDrop", "Hard Drop"),
+    ("action.hold", "Hold"),
+    ("action.noop", "Noop"),
+    ("menu.paused", "PAUSED"),
+    ("menu.escToResume", "ESC TO RESUME"),
+    ("menu.resume", "RESUME"),
+    ("menu.settings", "SETTINGS"),
+    ("menu.restart", "RESTART"),
+    ("menu.confirmRestart", "CONFIRM RESTART"),
+    ("menu.endRun", "END RUN"),
+    ("menu.start", "START"),
+    ("menu.skilltreeEditor", "SKILLTREE EDITOR"),
+    ("menu.quit", "QUIT"),
+    ("menu.gameOver", "GAME OVER"),
+    ("menu.runEnded", "RUN ENDED"),
+    ("menu.enterToRestart", "ENTER TO RESTART"),
+    ("menu.skillTreeHint", "K: SKILL TREE"),
+    ("menu.mainMenuHint", "ESC: MAIN MENU"),
+    ("menu.skillTree", "SKILL TREE"),
+    ("settings.hint", "ESC: BACK  DRAG SLIDERS TO APPLY"),
+    ("settings.masterVolume", "MASTER VOLUME"),
+    ("settings.musicVolume", "MUSIC VOLUME"),
+    ("settings.sfxVolume", "SFX VOLUME"),
+    ("settings.screenShake", "SCREEN SHAKE"),
+    ("settings.muteAll", "MUTE ALL"),
+    ("settings.musicEnabled", "MUSIC ENABLED"),
+    ("settings.showTimer", "SHOW TIMER"),
+    ("settings.autoPause", "AUTO PAUSE (FOCUS)"),
+    ("settings.highContrast", "HIGH CONTRAST UI"),
+    ("settings.reduceMotion", "REDUCE MOTION"),
+    ("settings.dasMeter", "DAS METER"),
+    ("settings.showGhost", "GHOST PIECE"),
+    ("settings.holdEnabled", "HOLD PIECE"),
+    ("settings.theme", "THEME"),
+    ("settings.themeClassic", "CLASSIC"),
+    ("settings.themeMono", "MONO"),
+    ("settings.piecePalette", "PIECES"),
+    ("settings.piecePaletteClassic", "CLASSIC"),
+    ("settings.piecePaletteColorblind", "COLORBLIND"),
+    ("settings.on", "ON"),
+    ("settings.off", "OFF"),
+    ("settings.back", "BACK"),
+    ("settings.resetDefaults", "RESET DEFAULTS"),
+    ("hud.time", "TIME"),
+    ("hud.timeOff", "TIME OFF"),
+    ("hud.level", "LEVEL"),
+    ("hud.pps", "PPS"),
+    ("hud.replay", "REPLAY"),
+];
+
+/// The built-in English localizer (the default injected into the headful app).
+pub fn english() -> TableLocalizer {
+    TableLocalizer::from_pairs(ENGLISH.iter().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use engine::localization::Localizer;
+
+    use super::*;
+    use crate::editor_actions::{default_manifest, manifest_with_labels};
+
+    #[test]
+    fn english_keys_are_unique_and_non_empty() {
+        let labels = english();
+        assert_eq!(labels.len(), ENGLISH.len());
+        for (key, text) in ENGLISH {
+            assert!(!text.is_empty(), "{key} has no text");
+            assert_eq!(labels.label(key), *text);
+        }
+    }
+
+    #[test]
+    fn manifest_labels_route_through_the_localizer() {
+        let english_manifest = default_manifest();
+        assert_eq!(english_manifest.actions[0].id, "moveLeft");
+        assert_eq!(english_manifest.actions[0].label, "Left");
+
+        let partial = TableLocalizer::new().with_label("action.moveLeft", "Gauche");
+        let manifest = manifest_with_labels(&partial);
+        assert_eq!(manifest.actions[0].label, "Gauche");
+        // Untranslated actions show their key rather than failing.
+        assert_eq!(manifest.actions[1].label, "action.moveRight");
+        assert_eq!(manifest.actions.len(), english_manifest.actions.len());
+    }
+}
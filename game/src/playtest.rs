@@ -1,22 +1,48 @@
 use std::time::Duration;
 
 use engine::GameLogic;
-
-use crate::state::GameState;
-use crate::tetris_core::{BottomwellRunMods, DepthWallDef, Piece, RotationDir, TetrisCore, Vec2i};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use engine::logical_clock::FixedTimestep;
+use serde::{Deserialize, Serialize};
+
+use crate::gravity_curve::GravityCurve;
+use crate::round_timer::RoundPhase;
+use crate::state::{DEFAULT_LOCK_DELAY, GameState};
+use crate::tetris_core::{
+    BottomwellRunMods, DepthWallDef, LOCK_RESET_LIMIT_DEFAULT, Piece, Randomizer, RotationDir,
+    TetrisCore, Vec2i,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputAction {
     Noop,
     MoveLeft,
     MoveRight,
     SoftDrop,
     GravityTick { dt_ms: u32 },
+    Tick { steps: u32 },
     RotateCw,
     RotateCcw,
     Rotate180,
     HardDrop,
     Hold,
+    ReceiveGarbage { rows: u8, hole_col: u8 },
+}
+
+impl InputAction {
+    /// Actions a player issues directly, as opposed to clock or environment inputs.
+    pub fn is_player_action(self) -> bool {
+        matches!(
+            self,
+            InputAction::MoveLeft
+                | InputAction::MoveRight
+                | InputAction::SoftDrop
+                | InputAction::RotateCw
+                | InputAction::RotateCcw
+                | InputAction::Rotate180
+                | InputAction::HardDrop
+                | InputAction::Hold
+        )
+    }
 }
 
 fn duration_to_ms_u32(duration: Duration) -> u32 {
@@ -28,7 +54,12 @@ pub struct BlockLogic {
     seed: u64,
     available_pieces: Vec<Piece>,
     gravity_enabled: bool,
+    gravity_curve: Option<GravityCurve>,
     score_bonus_per_line: u32,
+    board_size: Option<(usize, usize)>,
+    randomizer: Randomizer,
+    lock_delay: Duration,
+    lock_reset_limit: u32,
     bottomwell_enabled: bool,
     bottomwell_run_mods: BottomwellRunMods,
     depth_wall_defs_override: Option<Vec<DepthWallDef>>,
@@ -41,7 +72,12 @@ impl BlockLogic {
             seed,
             available_pieces,
             gravity_enabled: false,
+            gravity_curve: None,
             score_bonus_per_line: 0,
+            board_size: None,
+            randomizer: Randomizer::default(),
+            lock_delay: DEFAULT_LOCK_DELAY,
+            lock_reset_limit: LOCK_RESET_LIMIT_DEFAULT,
             bottomwell_enabled: false,
             bottomwell_run_mods: BottomwellRunMods::default(),
             depth_wall_defs_override: None,
@@ -54,11 +90,59 @@ impl BlockLogic {
         self
     }
 
+    /// Speed gravity up as lines are cleared. The curve scales `GameState::gravity_interval`, so
+    /// skill-tree gravity modifiers still apply on top.
+    pub fn with_gravity_curve(mut self, curve: GravityCurve) -> Self {
+        self.gravity_curve = Some(curve);
+        self
+    }
+
+    pub fn gravity_curve(&self) -> Option<&GravityCurve> {
+        self.gravity_curve.as_ref()
+    }
+
+    /// Current gravity level, or `None` when no curve is configured.
+    pub fn gravity_level(&self, state: &GameState) -> Option<u32> {
+        self.gravity_curve
+            .as_ref()
+            .map(|curve| curve.level(state.tetris.lines_cleared()))
+    }
+
+    /// Gravity interval for `state` after applying the curve (if any).
+    pub fn effective_gravity_interval(&self, state: &GameState) -> Duration {
+        match self.gravity_curve.as_ref() {
+            Some(curve) => curve.interval(state.gravity_interval, state.tetris.lines_cleared()),
+            None => state.gravity_interval,
+        }
+    }
+
     pub fn with_score_bonus_per_line(mut self, bonus: u32) -> Self {
         self.score_bonus_per_line = bonus;
         self
     }
 
+    /// Play on a `cols` x `rows` board instead of the standard 10x20. The size lives on the
+    /// `TetrisCore` inside `GameState`, so recordings replay on the same board.
+    pub fn with_board_size(mut self, cols: usize, rows: usize) -> Self {
+        self.board_size = Some((cols, rows));
+        self
+    }
+
+    /// Choose how the next queue is refilled; see `Randomizer`.
+    pub fn with_randomizer(mut self, randomizer: Randomizer) -> Self {
+        self.randomizer = randomizer;
+        self
+    }
+
+    /// Grounded pieces lock after `delay`; each successful grounded move/rotate restarts the
+    /// timer, up to `max_resets` times per piece. The timer lives on the `TetrisCore` inside
+    /// `GameState`, so `step` stays pure.
+    pub fn with_lock_delay(mut self, delay: Duration, max_resets: u32) -> Self {
+        self.lock_delay = delay;
+        self.lock_reset_limit = max_resets;
+        self
+    }
+
     pub fn with_bottomwell(mut self, enabled: bool) -> Self {
         self.bottomwell_enabled = enabled;
         self
@@ -82,6 +166,59 @@ impl BlockLogic {
         self.depth_wall_damage_tuning = Some((per_line_damage, multi_bonus_percent));
         self
     }
+
+    /// `InputAction::Tick`: advance `GameState::clock` by whole logical steps, then run the round
+    /// timer and gravity off the logical time that passed. Real-time drivers map wall time onto
+    /// these steps; nothing here reads the wall clock. Each due drop is a full `GravityTick` step
+    /// so per-step effects (material turns, line bonuses) match a driver that issued the drops
+    /// itself. The round timer is paused while the view isn't playing and resumed only if this
+    /// paused it; a pause set elsewhere is left alone.
+    fn step_logical_time(&self, state: &GameState, steps: u32) -> GameState {
+        let mut next = state.clone();
+        let dt = next.clock.advance(steps);
+        if !next.view.is_tetris_playing() {
+            if !next.round_timer.is_paused() {
+                next.round_timer.pause();
+                next.round_timer_paused_by_view = true;
+            }
+            next.gravity_elapsed = Duration::ZERO;
+            return next;
+        }
+        if next.round_timer_paused_by_view {
+            next.round_timer.resume();
+            next.round_timer_paused_by_view = false;
+        }
+        next.tetris.advance_effects(duration_to_ms_u32(dt));
+
+        if !next.round_timer_disabled {
+            let counting_down = next.round_timer.phase() == RoundPhase::Countdown;
+            next.round_timer.tick(dt);
+            if counting_down || next.round_timer.is_up() {
+                return next;
+            }
+        }
+        next.tetris.add_play_time_ms(duration_to_ms_u32(dt));
+
+        if next.tetris.is_line_clear_active() {
+            // Clear animations run on elapsed time so they don't quantize to the gravity interval.
+            return self.step(
+                &next,
+                InputAction::GravityTick {
+                    dt_ms: duration_to_ms_u32(dt),
+                },
+            );
+        }
+
+        let interval = self.effective_gravity_interval(&next);
+        let mut gravity = FixedTimestep::from_remainder(next.gravity_elapsed);
+        let drops = gravity.advance(dt, interval);
+        next.gravity_elapsed = gravity.remainder();
+        let dt_ms = duration_to_ms_u32(interval);
+        for _ in 0..drops {
+            next = self.step(&next, InputAction::GravityTick { dt_ms });
+        }
+        next
+    }
 }
 
 impl GameLogic for BlockLogic {
@@ -91,6 +228,12 @@ impl GameLogic for BlockLogic {
     fn initial_state(&self) -> Self::State {
         let mut core = TetrisCore::new(self.seed);
         core.set_available_pieces(self.available_pieces.clone());
+        core.set_randomizer(self.randomizer);
+        core.set_lock_delay_ms(duration_to_ms_u32(self.lock_delay));
+        core.set_lock_reset_limit(self.lock_reset_limit);
+        if let Some((cols, rows)) = self.board_size {
+            core.set_board_size(cols, rows);
+        }
         core.set_bottomwell_enabled(self.bottomwell_enabled);
         core.set_bottomwell_run_mods(self.bottomwell_run_mods);
         if let Some(defs) = self.depth_wall_defs_override.as_ref() {
@@ -103,7 +246,23 @@ impl GameLogic for BlockLogic {
         GameState::new(core)
     }
 
+    fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    fn reseed_state(&self, state: &mut Self::State, seed: u64) {
+        state.tetris.reseed(seed);
+    }
+
     fn step(&self, state: &Self::State, input: Self::Input) -> Self::State {
+        if let InputAction::Tick { steps } = input {
+            return self.step_logical_time(state, steps);
+        }
+
+        if state.in_countdown() && input.is_player_action() {
+            return state.clone();
+        }
+
         let mut next = state.clone();
         let prev_lines = state.tetris.lines_cleared();
         let mut apply_gravity = self.gravity_enabled;
@@ -124,6 +283,7 @@ impl GameLogic for BlockLogic {
                 next.tetris.advance_with_gravity(dt_ms);
                 apply_gravity = false;
             }
+            InputAction::Tick { .. } => unreachable!("handled by step_logical_time"),
             InputAction::RotateCw => {
                 next.tetris.rotate_piece(RotationDir::Cw);
             }
@@ -138,13 +298,21 @@ impl GameLogic for BlockLogic {
                 apply_gravity = false;
             }
             InputAction::Hold => {
-                next.tetris.hold_piece();
+                if next.hold_enabled {
+                    next.tetris.hold_piece();
+                }
+            }
+            // Versus-style attack, recorded like any other input so replays see it.
+            InputAction::ReceiveGarbage { rows, hole_col } => {
+                next.tetris
+                    .receive_garbage(usize::from(rows), usize::from(hole_col));
+                apply_gravity = false;
             }
         }
 
         if apply_gravity {
             next.tetris
-                .advance_with_gravity(duration_to_ms_u32(state.gravity_interval));
+                .advance_with_gravity(duration_to_ms_u32(self.effective_gravity_interval(state)));
         }
 
         next.tetris.advance_material_turn();
@@ -165,7 +333,12 @@ pub type TetrisLogic = BlockLogic;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tetris_core::{BOARD_HEIGHT, BOARD_WIDTH};
+    use crate::round_timer::RoundTimer;
+    use crate::tetris_core::{
+        BOARD_HEIGHT, BOARD_WIDTH, CELL_EMPTY, CELL_GARBAGE, GameStats, HARD_DROP_TRAIL_MS,
+        LOCK_FLASH_MS,
+    };
+    use crate::view::GameView;
 
     #[test]
     fn score_bonus_is_added_per_line_cleared() {
@@ -239,6 +412,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gravity_curve_scales_the_state_gravity_interval() {
+        let logic = TetrisLogic::new(0, vec![Piece::O]);
+        let mut state = logic.initial_state();
+        state.gravity_interval = Duration::from_millis(400);
+        assert_eq!(logic.gravity_level(&state), None);
+        assert_eq!(
+            logic.effective_gravity_interval(&state),
+            Duration::from_millis(400)
+        );
+
+        // A skill-tree speedup lowers `gravity_interval`; the curve multiplies on top of it.
+        let curve_logic = logic.with_gravity_curve(GravityCurve::Table {
+            lines_per_level: 10,
+            percent_by_level: vec![50],
+        });
+        assert_eq!(curve_logic.gravity_level(&state), Some(0));
+        assert_eq!(
+            curve_logic.effective_gravity_interval(&state),
+            Duration::from_millis(200)
+        );
+    }
+
     #[test]
     fn soft_drop_with_gravity_enabled_moves_exactly_one_row() {
         let logic = TetrisLogic::new(0, vec![Piece::O]).with_gravity(true);
@@ -303,4 +499,224 @@ mod tests {
             "piece should not lock into the board on the same step as a valid grounded horizontal move"
         );
     }
+
+    #[test]
+    fn tick_runs_timer_and_gravity_on_logical_time_only() {
+        let logic = TetrisLogic::new(0, vec![Piece::O]);
+        let mut state = logic.initial_state();
+        state.view = GameView::Tetris { paused: false };
+        let start_y = state.tetris.current_piece_pos().y;
+
+        // 499 steps is just short of one gravity interval; splitting them differently across
+        // ticks must not change the outcome.
+        let first = logic.step(&state, InputAction::Tick { steps: 300 });
+        let split = logic.step(&first, InputAction::Tick { steps: 199 });
+        let whole = logic.step(&state, InputAction::Tick { steps: 499 });
+        assert_eq!(split.tetris.snapshot(), whole.tetris.snapshot());
+        assert_eq!(split.clock, whole.clock);
+        assert_eq!(whole.clock.elapsed(), Duration::from_millis(499));
+        assert_eq!(whole.round_timer.elapsed(), Duration::from_millis(499));
+        assert_eq!(whole.tetris.current_piece_pos().y, start_y);
+
+        let dropped = logic.step(&whole, InputAction::Tick { steps: 1 });
+        assert_eq!(dropped.tetris.current_piece_pos().y, start_y - 1);
+        assert_eq!(dropped.gravity_elapsed, Duration::ZERO);
+
+        let mut paused = dropped.clone();
+        paused.view = GameView::Tetris { paused: true };
+        let still = logic.step(&paused, InputAction::Tick { steps: 1000 });
+        assert_eq!(still.round_timer.elapsed(), dropped.round_timer.elapsed());
+        assert_eq!(still.clock.ticks(), dropped.clock.ticks() + 1000);
+    }
+
+    #[test]
+    fn step_tracks_run_stats_and_fresh_runs_reset_them() {
+        let logic = TetrisLogic::new(0, vec![Piece::O]);
+        let mut state = logic.initial_state();
+        state.view = GameView::Tetris { paused: false };
+        assert_eq!(state.tetris.stats(), GameStats::default());
+
+        let held = logic.step(&state, InputAction::Hold);
+        let dropped = logic.step(&held, InputAction::HardDrop);
+        let dropped = logic.step(&dropped, InputAction::HardDrop);
+        let ticked = logic.step(&dropped, InputAction::Tick { steps: 250 });
+        let stats = ticked.tetris.stats();
+        assert_eq!(stats.holds_used, 1);
+        assert_eq!(stats.pieces_placed, 2);
+        assert_eq!(stats.play_time_ms, 250);
+        assert_eq!(stats.pieces_per_second(), 8.0);
+
+        let mut fresh = ticked.clone();
+        fresh.tetris.initialize_game();
+        assert_eq!(fresh.tetris.stats(), GameStats::default());
+    }
+
+    #[test]
+    fn hard_drop_effects_fade_on_logical_time_and_survive_serde() {
+        let logic = TetrisLogic::new(0, vec![Piece::O]);
+        let mut state = logic.initial_state();
+        state.view = GameView::Tetris { paused: false };
+
+        let dropped = logic.step(&state, InputAction::HardDrop);
+        let (trail, progress) = dropped.tetris.hard_drop_trail().expect("trail");
+        assert_eq!(progress, 0.0);
+        assert_eq!(trail.to_row, 0);
+        assert!(trail.from_row > trail.to_row);
+        assert_eq!(trail.col_end - trail.col_start, 2);
+        let (flash, _) = dropped.tetris.lock_flash().expect("lock flash");
+        assert_eq!(flash.cells.len(), 4);
+        assert!(flash.cells.iter().all(|cell| cell.y <= 1));
+
+        let json = serde_json::to_string(&dropped.tetris).expect("serialize");
+        let restored: TetrisCore = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(restored.hard_drop_trail(), dropped.tetris.hard_drop_trail());
+
+        let half = logic.step(
+            &dropped,
+            InputAction::Tick {
+                steps: LOCK_FLASH_MS / 2,
+            },
+        );
+        let (_, progress) = half.tetris.lock_flash().expect("still flashing");
+        assert_eq!(progress, 0.5);
+
+        let faded = logic.step(
+            &dropped,
+            InputAction::Tick {
+                steps: HARD_DROP_TRAIL_MS,
+            },
+        );
+        assert!(faded.tetris.hard_drop_trail().is_none());
+        assert!(faded.tetris.lock_flash().is_none());
+    }
+
+    #[test]
+    fn randomizer_choice_is_seeded_and_reaches_the_queue() {
+        let queue = |randomizer| {
+            let state = TetrisLogic::new(5, Piece::all())
+                .with_randomizer(randomizer)
+                .initial_state();
+            let mut pieces = vec![state.tetris.current_piece().unwrap()];
+            pieces.extend_from_slice(state.tetris.next_queue());
+            pieces
+        };
+
+        // Current piece + 5 queued = one full bag of the six pieces.
+        let bag = queue(Randomizer::SevenBag);
+        assert_eq!(bag.len(), Piece::ALL.len());
+        for piece in Piece::ALL {
+            assert!(bag.contains(&piece), "{piece:?} missing from {bag:?}");
+        }
+        assert_eq!(bag, queue(Randomizer::SevenBag));
+        assert_eq!(queue(Randomizer::Uniform), queue(Randomizer::Uniform));
+    }
+
+    #[test]
+    fn received_garbage_is_removed_by_a_line_clear() {
+        let logic = TetrisLogic::new(0, vec![Piece::O]);
+        let mut state = logic.initial_state();
+        state.tetris.set_line_clear_delay_ms(0);
+
+        let garbage = InputAction::ReceiveGarbage {
+            rows: 2,
+            hole_col: 9,
+        };
+        let state = logic.step(&state, garbage);
+        for y in 0..2 {
+            let row = &state.tetris.board()[y];
+            assert_eq!(row[9], CELL_EMPTY);
+            assert!(row[..9].iter().all(|&cell| cell == CELL_GARBAGE));
+        }
+
+        // Plug the bottom hole; the next lock clears that row and only one garbage row remains.
+        let mut plugged = state.clone();
+        plugged.tetris.set_cell(9, 0, CELL_GARBAGE);
+        let dropped = logic.step(&plugged, InputAction::HardDrop);
+        let dropped = logic.step(&dropped, InputAction::GravityTick { dt_ms: 0 });
+        assert_eq!(dropped.tetris.lines_cleared(), 1);
+        let board = dropped.tetris.board();
+        assert_eq!(board[0][9], CELL_EMPTY);
+        assert!(board[0][..9].iter().all(|&cell| cell == CELL_GARBAGE));
+        assert!(!board[1].contains(&CELL_GARBAGE));
+        assert!(!dropped.tetris.is_game_over());
+    }
+
+    #[test]
+    fn garbage_pushing_the_stack_past_the_top_ends_the_game() {
+        let logic = TetrisLogic::new(0, vec![Piece::O]);
+        let mut state = logic.initial_state();
+        state.tetris.set_cell(0, BOARD_HEIGHT - 1, CELL_GARBAGE);
+
+        let garbage = InputAction::ReceiveGarbage {
+            rows: 1,
+            hole_col: 3,
+        };
+        let next = logic.step(&state, garbage);
+        assert!(next.tetris.is_game_over());
+    }
+
+    #[test]
+    fn hold_is_ignored_when_disabled() {
+        let logic = TetrisLogic::new(2, Piece::all());
+        let mut state = logic.initial_state();
+        state.hold_enabled = false;
+
+        let next = logic.step(&state, InputAction::Hold);
+        assert_eq!(next.tetris.held_piece(), None);
+        assert_eq!(next.tetris.current_piece(), state.tetris.current_piece());
+
+        state.hold_enabled = true;
+        let held = logic.step(&state, InputAction::Hold);
+        assert_eq!(held.tetris.held_piece(), state.tetris.current_piece());
+    }
+
+    #[test]
+    fn countdown_holds_input_and_gravity_until_it_expires() {
+        let logic = TetrisLogic::new(0, vec![Piece::O]);
+        let mut state = logic.initial_state();
+        state.view = GameView::Tetris { paused: false };
+        state.round_timer =
+            RoundTimer::new(Duration::from_secs(20)).with_countdown(Duration::from_millis(1000));
+        let start = state.tetris.current_piece_pos();
+
+        let moved = logic.step(&state, InputAction::MoveLeft);
+        assert_eq!(moved.tetris.current_piece_pos(), start);
+
+        let waited = logic.step(&state, InputAction::Tick { steps: 999 });
+        assert!(waited.in_countdown());
+        assert_eq!(waited.round_timer.phase(), RoundPhase::Countdown);
+        assert_eq!(waited.tetris.current_piece_pos(), start);
+        assert_eq!(waited.round_timer.elapsed(), Duration::ZERO);
+
+        let started = logic.step(&waited, InputAction::Tick { steps: 1 });
+        assert_eq!(started.round_timer.phase(), RoundPhase::Running);
+        let moved = logic.step(&started, InputAction::MoveLeft);
+        assert_eq!(moved.tetris.current_piece_pos().x, start.x - 1);
+
+        let mut paused = started.clone();
+        paused.view = GameView::Tetris { paused: true };
+        let paused = logic.step(&paused, InputAction::Tick { steps: 10 });
+        assert_eq!(paused.round_timer.phase(), RoundPhase::Paused);
+    }
+
+    #[test]
+    fn tick_only_resumes_a_round_timer_pause_it_took() {
+        let logic = TetrisLogic::new(0, vec![Piece::O]);
+        let mut state = logic.initial_state();
+        state.view = GameView::Tetris { paused: true };
+        let paused = logic.step(&state, InputAction::Tick { steps: 10 });
+        assert!(paused.round_timer.is_paused());
+
+        let mut resumed = paused.clone();
+        resumed.view = GameView::Tetris { paused: false };
+        let resumed = logic.step(&resumed, InputAction::Tick { steps: 10 });
+        assert!(!resumed.round_timer.is_paused());
+        assert_eq!(resumed.round_timer.elapsed(), Duration::from_millis(10));
+
+        let mut held = resumed.clone();
+        held.round_timer.pause();
+        let held = logic.step(&held, InputAction::Tick { steps: 10 });
+        assert!(held.round_timer.is_paused());
+        assert_eq!(held.round_timer.elapsed(), Duration::from_millis(10));
+    }
 }
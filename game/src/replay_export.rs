@@ -0,0 +1,117 @@
+//! Renders a saved run to a PNG sequence or an animated GIF, for attaching to bug reports.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use engine::TimeMachine;
+use engine::graphics::CpuRenderer;
+use engine::recording::{FrameExportConfig, export_gif, export_png_sequence};
+use engine::surface::SurfaceSize;
+
+use crate::state::GameState;
+use crate::tetris_ui::draw_tetris_world;
+
+/// Matches the headful `--replay` playback rate.
+pub const DEFAULT_EXPORT_FPS: u32 = 15;
+pub const DEFAULT_EXPORT_WIDTH: u32 = 480;
+pub const DEFAULT_EXPORT_HEIGHT: u32 = 640;
+
+/// File name `export_replay` gives the GIF inside the export directory.
+pub const EXPORT_GIF_FILE_NAME: &str = "replay.gif";
+
+pub fn default_export_config() -> FrameExportConfig {
+    FrameExportConfig {
+        width: DEFAULT_EXPORT_WIDTH,
+        height: DEFAULT_EXPORT_HEIGHT,
+        fps: DEFAULT_EXPORT_FPS,
+    }
+}
+
+/// Writes `frame_%05d.png` for every recorded state into `dir`.
+pub fn export_replay_pngs(
+    tm: &TimeMachine<GameState>,
+    dir: impl AsRef<Path>,
+    config: FrameExportConfig,
+) -> io::Result<Vec<PathBuf>> {
+    export_png_sequence(tm, dir, config, |state, rgba| {
+        render_state(state, rgba, config)
+    })
+}
+
+pub fn export_replay_gif(
+    tm: &TimeMachine<GameState>,
+    output: impl AsRef<Path>,
+    config: FrameExportConfig,
+) -> io::Result<()> {
+    export_gif(tm, output, config, |state, rgba| {
+        render_state(state, rgba, config)
+    })
+}
+
+/// PNG frames into `dir`, plus `dir/replay.gif` when `gif` is set. Returns the files written.
+pub fn export_replay(
+    tm: &TimeMachine<GameState>,
+    dir: impl AsRef<Path>,
+    config: FrameExportConfig,
+    gif: bool,
+) -> io::Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    let mut written = export_replay_pngs(tm, dir, config)?;
+    if gif {
+        let path = dir.join(EXPORT_GIF_FILE_NAME);
+        export_replay_gif(tm, &path, config)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn render_state(state: &GameState, rgba: &mut [u8], config: FrameExportConfig) {
+    let mut gfx = CpuRenderer::new(rgba, SurfaceSize::new(config.width, config.height));
+    let _ = draw_tetris_world(&mut gfx, config.width, config.height, &state.tetris);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playtest::{InputAction, TetrisLogic};
+    use crate::tetris_core::Piece;
+    use engine::HeadlessRunner;
+    use engine::graphics::Texture;
+
+    #[test]
+    fn png_names_follow_the_record_stride() {
+        let mut runner = HeadlessRunner::new(TetrisLogic::new(0, Piece::all()));
+        runner.set_record_every_n_frames(2);
+        runner.run([InputAction::MoveLeft; 4]);
+        assert_eq!(runner.history().len(), 3);
+
+        let dir = std::env::temp_dir().join(format!("replay_export_{}", std::process::id()));
+        let config = FrameExportConfig {
+            width: 64,
+            height: 96,
+            fps: DEFAULT_EXPORT_FPS,
+        };
+        let written = export_replay(runner.timemachine(), &dir, config, true).expect("export");
+
+        let names: Vec<_> = written
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "frame_00000.png",
+                "frame_00002.png",
+                "frame_00004.png",
+                EXPORT_GIF_FILE_NAME
+            ]
+        );
+        let frame = Texture::load_png(&written[0]).expect("png frame");
+        assert_eq!((frame.width(), frame.height()), (64, 96));
+        assert!(std::fs::metadata(&written[3]).expect("gif").len() > 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
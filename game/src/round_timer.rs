@@ -2,6 +2,15 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundPhase {
+    /// The pre-round countdown is still running; gameplay input is ignored.
+    Countdown,
+    Running,
+    Paused,
+    Up,
+}
+
 /// A tiny helper for "time boxed" game sessions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RoundTimer {
@@ -9,6 +18,12 @@ pub struct RoundTimer {
     elapsed: Duration,
     #[serde(with = "crate::serde_duration")]
     limit: Duration,
+    #[serde(default, with = "crate::serde_duration")]
+    countdown: Duration,
+    #[serde(default, with = "crate::serde_duration")]
+    countdown_remaining: Duration,
+    #[serde(default)]
+    paused: bool,
 }
 
 impl RoundTimer {
@@ -16,11 +31,51 @@ impl RoundTimer {
         Self {
             elapsed: Duration::ZERO,
             limit,
+            countdown: Duration::ZERO,
+            countdown_remaining: Duration::ZERO,
+            paused: false,
         }
     }
 
+    /// Runs a `countdown` before the round clock starts; `reset` restarts it.
+    pub fn with_countdown(mut self, countdown: Duration) -> Self {
+        self.countdown = countdown;
+        self.countdown_remaining = countdown;
+        self
+    }
+
     pub fn reset(&mut self) {
         self.elapsed = Duration::ZERO;
+        self.countdown_remaining = self.countdown;
+        self.paused = false;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn phase(&self) -> RoundPhase {
+        if self.is_up() {
+            RoundPhase::Up
+        } else if self.paused {
+            RoundPhase::Paused
+        } else if !self.countdown_remaining.is_zero() {
+            RoundPhase::Countdown
+        } else {
+            RoundPhase::Running
+        }
+    }
+
+    pub fn countdown_remaining(&self) -> Duration {
+        self.countdown_remaining
     }
 
     pub fn elapsed(&self) -> Duration {
@@ -39,11 +94,20 @@ impl RoundTimer {
         self.elapsed >= self.limit
     }
 
-    pub fn tick_if_running(&mut self, dt: Duration, running: bool) {
-        if !running || self.is_up() {
+    /// Advances the countdown first and then the round clock; does nothing while paused or up.
+    pub fn tick(&mut self, dt: Duration) {
+        if self.paused || self.is_up() {
             return;
         }
-        self.elapsed = self.elapsed.saturating_add(dt);
+        let counted = dt.min(self.countdown_remaining);
+        self.countdown_remaining -= counted;
+        self.elapsed = self.elapsed.saturating_add(dt - counted);
+    }
+
+    pub fn tick_if_running(&mut self, dt: Duration, running: bool) {
+        if running {
+            self.tick(dt);
+        }
     }
 }
 
@@ -88,4 +152,44 @@ mod tests {
         assert_eq!(t.elapsed(), Duration::ZERO);
         assert!(!t.is_up());
     }
+
+    #[test]
+    fn countdown_runs_before_the_round_clock() {
+        let mut t = RoundTimer::new(Duration::from_secs(20)).with_countdown(Duration::from_secs(3));
+        assert_eq!(t.phase(), RoundPhase::Countdown);
+
+        t.tick(Duration::from_secs(2));
+        assert_eq!(t.phase(), RoundPhase::Countdown);
+        assert_eq!(t.countdown_remaining(), Duration::from_secs(1));
+        assert_eq!(t.elapsed(), Duration::ZERO);
+
+        // Time past the end of the countdown carries into the round.
+        t.tick(Duration::from_millis(1500));
+        assert_eq!(t.phase(), RoundPhase::Running);
+        assert_eq!(t.elapsed(), Duration::from_millis(500));
+
+        t.reset();
+        assert_eq!(t.phase(), RoundPhase::Countdown);
+        assert_eq!(t.countdown_remaining(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn pause_freezes_countdown_and_round_clock() {
+        let mut t = RoundTimer::new(Duration::from_secs(20)).with_countdown(Duration::from_secs(1));
+        t.pause();
+        assert_eq!(t.phase(), RoundPhase::Paused);
+        t.tick(Duration::from_secs(5));
+        assert_eq!(t.countdown_remaining(), Duration::from_secs(1));
+
+        t.resume();
+        t.tick(Duration::from_secs(3));
+        assert_eq!(t.elapsed(), Duration::from_secs(2));
+        t.pause();
+        t.tick(Duration::from_secs(30));
+        assert_eq!(t.elapsed(), Duration::from_secs(2));
+
+        t.resume();
+        t.tick(Duration::from_secs(30));
+        assert_eq!(t.phase(), RoundPhase::Up);
+    }
 }
@@ -1,8 +1,13 @@
+use std::fmt;
 use std::fs;
 use std::io;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use winit::event::VirtualKeyCode;
+
+use crate::playtest::InputAction;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub struct AudioSettings {
@@ -11,6 +16,14 @@ pub struct AudioSettings {
     pub sfx_volume: f32,
     pub mute_all: bool,
     pub music_enabled: bool,
+    /// Fraction of music volume removed while an SFX plays; 0.0 disables ducking.
+    #[serde(default)]
+    pub music_ducking: f32,
+    #[serde(default = "default_music_ducking_recovery_ms")]
+    pub music_ducking_recovery_ms: u32,
+    /// Music volume to restore when `toggle_music_mute` unmutes.
+    #[serde(default)]
+    pub music_volume_before_mute: Option<f32>,
 }
 
 impl Default for AudioSettings {
@@ -21,18 +34,46 @@ impl Default for AudioSettings {
             sfx_volume: 1.0,
             mute_all: false,
             music_enabled: true,
+            music_ducking: 0.0,
+            music_ducking_recovery_ms: default_music_ducking_recovery_ms(),
+            music_volume_before_mute: None,
         }
     }
 }
 
+fn default_music_ducking_recovery_ms() -> u32 {
+    250
+}
+
 impl AudioSettings {
     pub fn clamp(mut self) -> Self {
         self.master_volume = self.master_volume.clamp(0.0, 1.0);
         self.music_volume = self.music_volume.clamp(0.0, 1.0);
         self.sfx_volume = self.sfx_volume.clamp(0.0, 1.0);
+        self.music_ducking = self.music_ducking.clamp(0.0, 1.0);
+        self.music_ducking_recovery_ms = self.music_ducking_recovery_ms.min(5_000);
+        self.music_volume_before_mute = self.music_volume_before_mute.map(|v| v.clamp(0.0, 1.0));
         self
     }
 
+    pub fn is_music_muted(self) -> bool {
+        !self.music_enabled
+    }
+
+    /// Mutes music by zeroing its volume, remembering the prior value; a second call restores it.
+    pub fn toggle_music_mute(&mut self) {
+        if self.music_enabled {
+            self.music_volume_before_mute = Some(self.music_volume);
+            self.music_volume = 0.0;
+            self.music_enabled = false;
+        } else {
+            if let Some(volume) = self.music_volume_before_mute.take() {
+                self.music_volume = volume;
+            }
+            self.music_enabled = true;
+        }
+    }
+
     pub fn effective_music_gain(self) -> f32 {
         if self.mute_all || !self.music_enabled {
             0.0
@@ -54,6 +95,83 @@ impl AudioSettings {
 pub struct GameplaySettings {
     pub show_round_timer: bool,
     pub auto_pause_on_focus_loss: bool,
+    /// Draw the horizontal auto-repeat (DAS) charge next to the board.
+    #[serde(default)]
+    pub show_das_meter: bool,
+    /// Draw the ghost piece where the active piece would land.
+    #[serde(default = "default_true")]
+    pub show_ghost: bool,
+    /// Allow `InputAction::Hold`; mirrored into `GameState::hold_enabled` for each run.
+    #[serde(default = "default_true")]
+    pub hold_enabled: bool,
+    /// DAS/ARR for held left/right movement.
+    #[serde(default)]
+    pub repeat_timing: RepeatTiming,
+}
+
+/// Horizontal auto-repeat timing: hold for `das_ms` before the first repeat, then move every
+/// `arr_ms`. Read through `das()`/`arr()`, which apply `clamp`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RepeatTiming {
+    pub das_ms: u32,
+    pub arr_ms: u32,
+}
+
+impl Default for RepeatTiming {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl RepeatTiming {
+    pub const DEFAULT: RepeatTiming = RepeatTiming {
+        das_ms: 170,
+        arr_ms: 50,
+    };
+    pub const FAST: RepeatTiming = RepeatTiming {
+        das_ms: 110,
+        arr_ms: 25,
+    };
+    /// Named presets; the headful app's `ROLLOUT_REPEAT_PRESET` selects one by name.
+    pub const PRESETS: [(&'static str, RepeatTiming); 2] =
+        [("default", Self::DEFAULT), ("fast", Self::FAST)];
+
+    /// One 60 Hz frame. Repeats are polled once per frame, so a shorter ARR can't move any
+    /// faster; it would only keep the repeat timer permanently due.
+    pub const MIN_ARR_MS: u32 = 16;
+    pub const MAX_ARR_MS: u32 = 500;
+    pub const MAX_DAS_MS: u32 = 1000;
+
+    pub fn preset(name: &str) -> Option<RepeatTiming> {
+        let name = name.trim();
+        Self::PRESETS
+            .iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+            .map(|(_, timing)| *timing)
+    }
+
+    /// Name of the preset these values match, if any.
+    pub fn preset_name(self) -> Option<&'static str> {
+        Self::PRESETS
+            .iter()
+            .find(|(_, timing)| *timing == self)
+            .map(|(name, _)| *name)
+    }
+
+    pub fn clamp(self) -> Self {
+        Self {
+            das_ms: self.das_ms.min(Self::MAX_DAS_MS),
+            arr_ms: self.arr_ms.clamp(Self::MIN_ARR_MS, Self::MAX_ARR_MS),
+        }
+    }
+
+    pub fn das(self) -> Duration {
+        Duration::from_millis(u64::from(self.clamp().das_ms))
+    }
+
+    pub fn arr(self) -> Duration {
+        Duration::from_millis(u64::from(self.clamp().arr_ms))
+    }
 }
 
 impl Default for GameplaySettings {
@@ -61,14 +179,43 @@ impl Default for GameplaySettings {
         Self {
             show_round_timer: true,
             auto_pause_on_focus_loss: true,
+            show_das_meter: false,
+            show_ghost: true,
+            hold_enabled: true,
+            repeat_timing: RepeatTiming::DEFAULT,
         }
     }
 }
 
+fn default_true() -> bool {
+    true
+}
+
+/// Built-in color themes for the board, HUD, and menus (see `tetris_ui::Theme`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeId {
+    #[default]
+    Classic,
+    Mono,
+}
+
+impl ThemeId {
+    pub const ALL: [ThemeId; 2] = [ThemeId::Classic, ThemeId::Mono];
+
+    /// Next theme in `ALL`, wrapping around; used by the settings menu button.
+    pub fn next(self) -> ThemeId {
+        let index = Self::ALL.iter().position(|id| *id == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct VideoSettings {
     pub screen_shake_percent: u8,
     pub vsync: bool,
+    #[serde(default)]
+    pub theme: ThemeId,
 }
 
 impl Default for VideoSettings {
@@ -76,6 +223,7 @@ impl Default for VideoSettings {
         Self {
             screen_shake_percent: 100,
             vsync: true,
+            theme: ThemeId::Classic,
         }
     }
 }
@@ -86,10 +234,33 @@ impl VideoSettings {
     }
 }
 
+/// Board piece colors (see `Theme::for_settings`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PiecePalette {
+    #[default]
+    Classic,
+    /// `CellPalette::COLORBLIND`: luminance-separated colors with pattern markers.
+    Colorblind,
+}
+
+impl PiecePalette {
+    pub const ALL: [PiecePalette; 2] = [PiecePalette::Classic, PiecePalette::Colorblind];
+
+    /// Next palette in `ALL`, wrapping around; used by the settings menu button.
+    pub fn next(self) -> PiecePalette {
+        let index = Self::ALL.iter().position(|id| *id == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AccessibilitySettings {
+    /// Also brightens the board grid.
     pub high_contrast_ui: bool,
     pub reduce_motion: bool,
+    #[serde(default)]
+    pub piece_palette: PiecePalette,
 }
 
 impl Default for AccessibilitySettings {
@@ -97,10 +268,127 @@ impl Default for AccessibilitySettings {
         Self {
             high_contrast_ui: false,
             reduce_motion: false,
+            piece_palette: PiecePalette::Classic,
         }
     }
 }
 
+/// One key -> action entry in `KeyBindings`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub key: VirtualKeyCode,
+    pub action: InputAction,
+}
+
+/// `key` is already bound to `existing`, so it cannot also drive `requested`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBindingConflict {
+    pub key: VirtualKeyCode,
+    pub existing: InputAction,
+    pub requested: InputAction,
+}
+
+impl fmt::Display for KeyBindingConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "key {:?} is bound to both {:?} and {:?}",
+            self.key, self.existing, self.requested
+        )
+    }
+}
+
+/// Keyboard layout for gameplay input. An action may have several keys, but a key drives at most
+/// one action.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: Vec<KeyBinding>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let bindings = [
+            (VirtualKeyCode::Left, InputAction::MoveLeft),
+            (VirtualKeyCode::Right, InputAction::MoveRight),
+            (VirtualKeyCode::D, InputAction::MoveRight),
+            (VirtualKeyCode::Down, InputAction::SoftDrop),
+            (VirtualKeyCode::S, InputAction::SoftDrop),
+            (VirtualKeyCode::Up, InputAction::RotateCw),
+            (VirtualKeyCode::W, InputAction::RotateCw),
+            (VirtualKeyCode::X, InputAction::RotateCw),
+            (VirtualKeyCode::Z, InputAction::RotateCcw),
+            (VirtualKeyCode::A, InputAction::Rotate180),
+            (VirtualKeyCode::Space, InputAction::HardDrop),
+            (VirtualKeyCode::C, InputAction::Hold),
+        ]
+        .into_iter()
+        .map(|(key, action)| KeyBinding { key, action })
+        .collect();
+        Self { bindings }
+    }
+}
+
+impl KeyBindings {
+    pub fn bindings(&self) -> &[KeyBinding] {
+        &self.bindings
+    }
+
+    /// The action `key` drives; with conflicting entries the first one wins.
+    pub fn action_for(&self, key: VirtualKeyCode) -> Option<InputAction> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.key == key)
+            .map(|binding| binding.action)
+    }
+
+    pub fn keys_for(&self, action: InputAction) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.action == action)
+            .map(|binding| binding.key)
+    }
+
+    /// Makes `key` the only key for `action`. Leaves the bindings untouched and returns the
+    /// conflict if `key` already drives a different action.
+    pub fn rebind(
+        &mut self,
+        action: InputAction,
+        key: VirtualKeyCode,
+    ) -> Result<(), KeyBindingConflict> {
+        if let Some(existing) = self.action_for(key)
+            && existing != action
+        {
+            return Err(KeyBindingConflict {
+                key,
+                existing,
+                requested: action,
+            });
+        }
+        self.bindings.retain(|binding| binding.action != action);
+        self.bindings.push(KeyBinding { key, action });
+        Ok(())
+    }
+
+    /// Keys bound to more than one action, e.g. from a hand-edited settings file.
+    pub fn conflicts(&self) -> Vec<KeyBindingConflict> {
+        let mut conflicts = Vec::new();
+        for (i, later) in self.bindings.iter().enumerate() {
+            if let Some(first) = self.bindings[..i]
+                .iter()
+                .find(|binding| binding.key == later.key)
+                && first.action != later.action
+            {
+                conflicts.push(KeyBindingConflict {
+                    key: later.key,
+                    existing: first.action,
+                    requested: later.action,
+                });
+            }
+        }
+        conflicts
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PlayerSettings {
     #[serde(default = "default_version")]
@@ -113,6 +401,8 @@ pub struct PlayerSettings {
     pub video: VideoSettings,
     #[serde(default)]
     pub accessibility: AccessibilitySettings,
+    #[serde(default)]
+    pub key_bindings: KeyBindings,
 }
 
 impl Default for PlayerSettings {
@@ -123,6 +413,7 @@ impl Default for PlayerSettings {
             gameplay: GameplaySettings::default(),
             video: VideoSettings::default(),
             accessibility: AccessibilitySettings::default(),
+            key_bindings: KeyBindings::default(),
         }
     }
 }
@@ -132,6 +423,7 @@ impl PlayerSettings {
         self.version = default_version();
         self.audio = self.audio.clamp();
         self.video.screen_shake_percent = self.video.screen_shake_percent.min(100);
+        self.gameplay.repeat_timing = self.gameplay.repeat_timing.clamp();
         self
     }
 }
@@ -210,6 +502,24 @@ mod tests {
         assert_eq!(audio.effective_music_gain(), 0.0);
     }
 
+    #[test]
+    fn toggle_music_mute_remembers_prior_volume() {
+        let mut audio = AudioSettings {
+            music_volume: 0.6,
+            ..AudioSettings::default()
+        };
+
+        audio.toggle_music_mute();
+        assert!(audio.is_music_muted());
+        assert_eq!(audio.music_volume, 0.0);
+        assert_eq!(audio.effective_music_gain(), 0.0);
+
+        audio.toggle_music_mute();
+        assert!(!audio.is_music_muted());
+        assert_eq!(audio.music_volume, 0.6);
+        assert_eq!(audio.music_volume_before_mute, None);
+    }
+
     #[test]
     fn player_settings_sanitized_clamps_expected_fields() {
         let settings = PlayerSettings {
@@ -220,10 +530,21 @@ mod tests {
                 sfx_volume: 0.5,
                 mute_all: false,
                 music_enabled: true,
+                music_ducking: 1.5,
+                music_ducking_recovery_ms: 60_000,
+                music_volume_before_mute: Some(4.0),
             },
             video: VideoSettings {
                 screen_shake_percent: 200,
                 vsync: true,
+                theme: ThemeId::Mono,
+            },
+            gameplay: GameplaySettings {
+                repeat_timing: RepeatTiming {
+                    das_ms: 5_000,
+                    arr_ms: 0,
+                },
+                ..GameplaySettings::default()
             },
             ..PlayerSettings::default()
         }
@@ -232,7 +553,43 @@ mod tests {
         assert_eq!(settings.version, 1);
         assert_eq!(settings.audio.master_volume, 1.0);
         assert_eq!(settings.audio.music_volume, 0.0);
+        assert_eq!(settings.audio.music_ducking, 1.0);
+        assert_eq!(settings.audio.music_ducking_recovery_ms, 5_000);
+        assert_eq!(settings.audio.music_volume_before_mute, Some(1.0));
         assert_eq!(settings.video.screen_shake_percent, 100);
+        assert_eq!(
+            settings.gameplay.repeat_timing,
+            RepeatTiming {
+                das_ms: RepeatTiming::MAX_DAS_MS,
+                arr_ms: RepeatTiming::MIN_ARR_MS,
+            }
+        );
+    }
+
+    #[test]
+    fn repeat_timing_presets_resolve_by_name() {
+        assert_eq!(RepeatTiming::preset("fast"), Some(RepeatTiming::FAST));
+        assert_eq!(
+            RepeatTiming::preset(" Default "),
+            Some(RepeatTiming::DEFAULT)
+        );
+        assert_eq!(RepeatTiming::preset("instant"), None);
+        assert_eq!(RepeatTiming::FAST.preset_name(), Some("fast"));
+        assert_eq!(
+            RepeatTiming {
+                das_ms: 90,
+                arr_ms: 20,
+            }
+            .preset_name(),
+            None
+        );
+
+        let unclamped = RepeatTiming {
+            das_ms: 0,
+            arr_ms: 1,
+        };
+        assert_eq!(unclamped.das(), Duration::ZERO);
+        assert_eq!(unclamped.arr(), Duration::from_millis(16));
     }
 
     #[test]
@@ -240,8 +597,109 @@ mod tests {
         let parsed: PlayerSettings =
             serde_json::from_str(r#"{"version":1,"audio":{"master_volume":0.5,"music_volume":0.5,"sfx_volume":0.5,"mute_all":false,"music_enabled":true}}"#)
                 .expect("settings JSON should parse");
+        assert_eq!(parsed.audio.music_ducking, 0.0);
+        assert_eq!(parsed.audio.music_ducking_recovery_ms, 250);
         assert_eq!(parsed.gameplay, GameplaySettings::default());
+        assert!(parsed.gameplay.show_ghost && parsed.gameplay.hold_enabled);
         assert_eq!(parsed.video, VideoSettings::default());
+        assert_eq!(parsed.video.theme, ThemeId::Classic);
         assert_eq!(parsed.accessibility, AccessibilitySettings::default());
+        assert_eq!(parsed.key_bindings, KeyBindings::default());
+    }
+
+    #[test]
+    fn theme_id_round_trips_and_cycles() {
+        let video = VideoSettings {
+            theme: ThemeId::Mono,
+            ..VideoSettings::default()
+        };
+        let json = serde_json::to_string(&video).expect("video settings should serialize");
+        assert!(
+            json.contains(r#""theme":"mono""#),
+            "unexpected JSON: {json}"
+        );
+        let parsed: VideoSettings = serde_json::from_str(&json).expect("should parse back");
+        assert_eq!(parsed, video);
+
+        assert_eq!(ThemeId::Classic.next(), ThemeId::Mono);
+        assert_eq!(ThemeId::Mono.next(), ThemeId::Classic);
+    }
+
+    #[test]
+    fn piece_palette_round_trips_and_defaults_for_old_files() {
+        let accessibility = AccessibilitySettings {
+            piece_palette: PiecePalette::Colorblind,
+            ..AccessibilitySettings::default()
+        };
+        let json = serde_json::to_string(&accessibility).expect("should serialize");
+        assert!(
+            json.contains(r#""piece_palette":"colorblind""#),
+            "unexpected JSON: {json}"
+        );
+        let parsed: AccessibilitySettings = serde_json::from_str(&json).expect("should parse");
+        assert_eq!(parsed, accessibility);
+
+        let old: AccessibilitySettings =
+            serde_json::from_str(r#"{"high_contrast_ui":true,"reduce_motion":false}"#)
+                .expect("pre-palette settings should parse");
+        assert_eq!(old.piece_palette, PiecePalette::Classic);
+        assert_eq!(PiecePalette::Colorblind.next(), PiecePalette::Classic);
+    }
+
+    #[test]
+    fn rebind_replaces_action_keys_and_reports_conflicts() {
+        let mut bindings = KeyBindings::default();
+        assert_eq!(
+            bindings.action_for(VirtualKeyCode::D),
+            Some(InputAction::MoveRight)
+        );
+
+        bindings
+            .rebind(InputAction::MoveRight, VirtualKeyCode::L)
+            .expect("L is unbound");
+        assert_eq!(
+            bindings.action_for(VirtualKeyCode::L),
+            Some(InputAction::MoveRight)
+        );
+        assert_eq!(bindings.action_for(VirtualKeyCode::Right), None);
+        assert_eq!(bindings.action_for(VirtualKeyCode::D), None);
+
+        let before = bindings.clone();
+        let conflict = bindings
+            .rebind(InputAction::Hold, VirtualKeyCode::Space)
+            .expect_err("Space already hard drops");
+        assert_eq!(conflict.existing, InputAction::HardDrop);
+        assert_eq!(conflict.requested, InputAction::Hold);
+        assert_eq!(bindings, before);
+        assert!(bindings.conflicts().is_empty());
+    }
+
+    #[test]
+    fn key_bindings_round_trip_and_detect_duplicate_keys() {
+        let mut settings = PlayerSettings::default();
+        settings
+            .key_bindings
+            .rebind(InputAction::Hold, VirtualKeyCode::LShift)
+            .expect("LShift is unbound");
+        let json = serde_json::to_string(&settings).expect("settings should serialize");
+        let parsed: PlayerSettings = serde_json::from_str(&json).expect("should parse back");
+        assert_eq!(parsed.key_bindings, settings.key_bindings);
+
+        let edited: KeyBindings = serde_json::from_str(
+            r#"{"bindings":[{"key":"Space","action":"HardDrop"},{"key":"Space","action":"Hold"}]}"#,
+        )
+        .expect("bindings JSON should parse");
+        assert_eq!(
+            edited.action_for(VirtualKeyCode::Space),
+            Some(InputAction::HardDrop)
+        );
+        assert_eq!(
+            edited.conflicts(),
+            vec![KeyBindingConflict {
+                key: VirtualKeyCode::Space,
+                existing: InputAction::HardDrop,
+                requested: InputAction::Hold,
+            }]
+        );
     }
 }
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 /// Shared SFX volume constants (0.0..=1.0).
 ///
 /// These are used by headful clients and validated by tests.
@@ -10,3 +12,31 @@ pub const GLASS_BREAK_SFX_VOLUME: f32 = 0.55;
 ///
 /// Kept intentionally low so it sits under the gameplay SFX.
 pub const MUSIC_VOLUME: f32 = 0.12;
+
+/// Names of optional sounds the headful client looks for in the SFX directory, as
+/// `<name>.wav` or `<name>.ogg`. Missing files fall back to the built-in click (or silence).
+pub const SFX_HARD_DROP: &str = "hard_drop";
+pub const SFX_LINE_CLEAR: &str = "line_clear";
+pub const SFX_MENU_CLICK: &str = "menu_click";
+/// Loops in place of the procedural background music when present.
+pub const MUSIC_TRACK: &str = "music";
+
+pub const SOUND_FILE_EXTENSIONS: [&str; 2] = ["wav", "ogg"];
+
+/// `ROLLOUT_SFX_DIR`, or the crate's `assets/sfx` directory.
+pub fn sfx_dir_from_env() -> PathBuf {
+    if let Ok(dir) = std::env::var("ROLLOUT_SFX_DIR") {
+        return PathBuf::from(dir);
+    }
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join("sfx")
+}
+
+/// First existing `<dir>/<name>.<ext>` for the supported extensions.
+pub fn find_sound_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    SOUND_FILE_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{name}.{ext}")))
+        .find(|path| path.is_file())
+}
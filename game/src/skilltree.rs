@@ -1,6 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
-    fs,
+    fmt, fs,
     path::{Path, PathBuf},
 };
 
@@ -123,6 +123,125 @@ impl Default for SkillTreeDef {
     }
 }
 
+/// Structural problem that makes a `SkillTreeDef` unsafe to save.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkillTreeError {
+    /// Prereq loop; each id requires the next and the last requires the first.
+    PrereqCycle { ids: Vec<String> },
+    /// `node` lists a prerequisite id that no node defines.
+    DanglingPrereq { node: String, missing: String },
+}
+
+impl fmt::Display for SkillTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PrereqCycle { ids } => {
+                write!(f, "prereq cycle {}", ids.join(" -> "))?;
+                if let Some(first) = ids.first() {
+                    write!(f, " -> {first}")?;
+                }
+                Ok(())
+            }
+            Self::DanglingPrereq { node, missing } => {
+                write!(f, "{node} requires unknown node {missing}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SkillTreeError {}
+
+impl SkillTreeDef {
+    /// Checks that every prereq names a real node and that no prereq chain loops back on itself.
+    pub fn validate(&self) -> Result<(), Vec<SkillTreeError>> {
+        let ids: HashSet<&str> = self.nodes.iter().map(|n| n.id.as_str()).collect();
+        let mut errors: Vec<SkillTreeError> = self
+            .nodes
+            .iter()
+            .flat_map(|node| {
+                node.requires
+                    .iter()
+                    .filter(|r| !ids.contains(r.as_str()))
+                    .map(|missing| SkillTreeError::DanglingPrereq {
+                        node: node.id.clone(),
+                        missing: missing.clone(),
+                    })
+            })
+            .collect();
+        errors.extend(
+            self.find_prereq_cycles()
+                .into_iter()
+                .map(|ids| SkillTreeError::PrereqCycle { ids }),
+        );
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Every prereq loop reachable by a depth-first walk, in node order. Never fails, so the
+    /// editor can highlight offending nodes while the def is still being edited.
+    ///
+    /// Unknown prereq ids are ignored here; `validate` reports them separately.
+    pub fn find_prereq_cycles(&self) -> Vec<Vec<String>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            Unvisited,
+            OnStack,
+            Done,
+        }
+
+        fn visit(
+            def: &SkillTreeDef,
+            index: &HashMap<&str, usize>,
+            idx: usize,
+            marks: &mut [Mark],
+            stack: &mut Vec<usize>,
+            cycles: &mut Vec<Vec<String>>,
+        ) {
+            marks[idx] = Mark::OnStack;
+            stack.push(idx);
+            for req in &def.nodes[idx].requires {
+                let Some(&next) = index.get(req.as_str()) else {
+                    continue;
+                };
+                match marks[next] {
+                    Mark::Unvisited => visit(def, index, next, marks, stack, cycles),
+                    Mark::OnStack => {
+                        let start = stack.iter().position(|&i| i == next).unwrap_or(0);
+                        cycles.push(
+                            stack[start..]
+                                .iter()
+                                .map(|&i| def.nodes[i].id.clone())
+                                .collect(),
+                        );
+                    }
+                    Mark::Done => {}
+                }
+            }
+            stack.pop();
+            marks[idx] = Mark::Done;
+        }
+
+        let index: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.as_str(), i))
+            .collect();
+        let mut marks = vec![Mark::Unvisited; self.nodes.len()];
+        let mut stack = Vec::new();
+        let mut cycles = Vec::new();
+        for idx in 0..self.nodes.len() {
+            if marks[idx] == Mark::Unvisited {
+                visit(self, &index, idx, &mut marks, &mut stack, &mut cycles);
+            }
+        }
+        cycles
+    }
+}
+
 /// Dynamic player progress for the skilltree.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -158,12 +277,51 @@ pub enum SkillTreeEditorTool {
     ConnectPrereqs,
 }
 
+/// Rubber-band selection rectangle in world cells; `anchor` is where the drag started and
+/// `corner` follows the pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillTreeBoxSelect {
+    pub anchor: Vec2i,
+    pub corner: Vec2i,
+}
+
+impl SkillTreeBoxSelect {
+    pub fn min(&self) -> Vec2i {
+        Vec2i::new(
+            self.anchor.x.min(self.corner.x),
+            self.anchor.y.min(self.corner.y),
+        )
+    }
+
+    pub fn max(&self) -> Vec2i {
+        Vec2i::new(
+            self.anchor.x.max(self.corner.x),
+            self.anchor.y.max(self.corner.y),
+        )
+    }
+
+    /// Inclusive on both corners, so a zero-size drag still covers the cell it started in.
+    pub fn contains(&self, world: Vec2i) -> bool {
+        let (min, max) = (self.min(), self.max());
+        (min.x..=max.x).contains(&world.x) && (min.y..=max.y).contains(&world.y)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SkillTreeEditorState {
     pub enabled: bool,
     pub tool: SkillTreeEditorTool,
     pub selected: Option<String>,
 
+    /// Shift-click selection set. Only meaningful while it contains `selected` (the primary);
+    /// otherwise the editor falls back to single-select on `selected`.
+    #[serde(default)]
+    pub selection: Vec<String>,
+
+    /// In-progress Ctrl+drag selection rectangle; committed (or dropped) on mouse release.
+    #[serde(default)]
+    pub box_select: Option<SkillTreeBoxSelect>,
+
     /// For `SelectMove`: the clicked cell's offset from the node's `pos` (world coords).
     pub move_grab_offset: Option<Vec2i>,
 
@@ -188,6 +346,12 @@ pub struct SkillTreeEditorState {
     #[serde(default)]
     pub search_query: String,
 
+    /// Snap moved node anchors to multiples of `snap_step` (off = free placement).
+    #[serde(default)]
+    pub snap_enabled: bool,
+    #[serde(default = "default_editor_snap_step")]
+    pub snap_step: i32,
+
     pub dirty: bool,
     pub status: Option<String>,
 }
@@ -198,6 +362,8 @@ impl Default for SkillTreeEditorState {
             enabled: false,
             tool: SkillTreeEditorTool::Select,
             selected: None,
+            selection: Vec::new(),
+            box_select: None,
             move_grab_offset: None,
             connect_from: None,
             help_expanded: false,
@@ -205,6 +371,8 @@ impl Default for SkillTreeEditorState {
             pending_delete_id: None,
             search_open: false,
             search_query: String::new(),
+            snap_enabled: false,
+            snap_step: DEFAULT_EDITOR_SNAP_STEP,
             dirty: false,
             status: None,
         }
@@ -215,6 +383,33 @@ fn default_editor_cursor_world() -> Vec2i {
     Vec2i::new(0, 0)
 }
 
+/// Default editor snap grid, in world cells.
+pub const DEFAULT_EDITOR_SNAP_STEP: i32 = 2;
+
+/// Snap steps offered by `editor_cycle_snap_step`, ascending.
+pub const EDITOR_SNAP_STEPS: [i32; 4] = [1, 2, 4, 8];
+
+fn default_editor_snap_step() -> i32 {
+    DEFAULT_EDITOR_SNAP_STEP
+}
+
+/// Round `v` to the nearest multiple of `step` (ties round up).
+fn snap_to_step(v: i32, step: i32) -> i32 {
+    let step = step.max(1);
+    (v.saturating_add(step / 2))
+        .div_euclid(step)
+        .saturating_mul(step)
+}
+
+/// Visual-only editor hint: the selected node's anchor lines up with another node's anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SkillTreeAlignmentGuide {
+    /// Shared anchor column (world x).
+    Column(i32),
+    /// Shared anchor row (world y).
+    Row(i32),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct SkillTreeCamera {
     /// Current zoom level in pixels per grid cell (lerps towards `target_cell_px`).
@@ -271,6 +466,9 @@ pub fn skilltree_world_bounds(def: &SkillTreeDef) -> Option<SkillTreeWorldBounds
     })
 }
 
+/// How far (in cells) the skilltree camera may scroll past the outermost node.
+pub const SKILLTREE_CAMERA_BOUNDS_PAD_CELLS: f32 = 6.0;
+
 pub fn clamp_camera_min_to_bounds(
     cam_min: Vec2f,
     view_size_cells: Vec2f,
@@ -320,6 +518,8 @@ pub struct SkillTreeRuntime {
     pub editor: SkillTreeEditorState,
 
     editor_history: SkillTreeEditorHistory,
+    /// Node copied with `editor_copy_selected` (prereqs stripped); survives reloads.
+    editor_clipboard: Option<SkillNodeDef>,
 
     // Cached indices (rebuilt on load/edit).
     id_to_index: HashMap<String, usize>,
@@ -328,10 +528,75 @@ pub struct SkillTreeRuntime {
 
 const SKILLTREE_EDITOR_HISTORY_LIMIT: usize = 128;
 
+/// Outcome of [`SkillTreeRuntime::buy_path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuyResult {
+    /// Purchased node ids in dependency order (prerequisites first) and the total spent.
+    Bought { nodes: Vec<String>, cost: u32 },
+    /// The target is already unlocked; nothing was spent.
+    AlreadyUnlocked,
+    /// The path costs `cost` and the player is `shortfall` short; nothing was bought.
+    CannotAfford { cost: u32, shortfall: u32 },
+    /// The target or one of its prerequisites does not exist in the tree.
+    UnknownNode(String),
+}
+
+/// Why [`SkillTreeRuntime::refund`] refused to refund a node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefundError {
+    UnknownNode(String),
+    /// The node was never bought.
+    NotUnlocked,
+    /// The root `start` node is always owned.
+    Root,
+    /// Still-unlocked nodes (in def order) that list this node as a prerequisite.
+    RequiredBy(Vec<String>),
+}
+
+impl fmt::Display for RefundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownNode(id) => write!(f, "unknown skilltree node {id}"),
+            Self::NotUnlocked => write!(f, "node is not unlocked"),
+            Self::Root => write!(f, "the start node cannot be refunded"),
+            Self::RequiredBy(ids) => write!(f, "still required by {}", ids.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for RefundError {}
+
+/// How many buy sounds a multi-node purchase should trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BuySfxMode {
+    #[default]
+    Once,
+    PerNode,
+}
+
+impl BuyResult {
+    pub fn sfx_plays(&self, mode: BuySfxMode) -> usize {
+        match (self, mode) {
+            (Self::Bought { .. }, BuySfxMode::Once) => 1,
+            (Self::Bought { nodes, .. }, BuySfxMode::PerNode) => nodes.len(),
+            _ => 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct SkillTreeEditorHistory {
-    undo_defs: Vec<SkillTreeDef>,
-    redo_defs: Vec<SkillTreeDef>,
+    undo_defs: Vec<SkillTreeEditorHistoryEntry>,
+    redo_defs: Vec<SkillTreeEditorHistoryEntry>,
+}
+
+/// A `def` snapshot plus the editor state that goes with it, so undo lands on the selection the
+/// user had and knows whether that revision matches the file on disk.
+#[derive(Debug, Clone)]
+struct SkillTreeEditorHistoryEntry {
+    def: SkillTreeDef,
+    selected: Option<String>,
+    dirty: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -353,6 +618,7 @@ impl SkillTreeRuntime {
             camera: SkillTreeCamera::default(),
             editor: SkillTreeEditorState::default(),
             editor_history: SkillTreeEditorHistory::default(),
+            editor_clipboard: None,
             id_to_index: HashMap::new(),
             unlocked_set: HashSet::new(),
         };
@@ -378,6 +644,7 @@ impl SkillTreeRuntime {
             camera: snapshot.camera,
             editor: snapshot.editor,
             editor_history: SkillTreeEditorHistory::default(),
+            editor_clipboard: None,
             id_to_index: HashMap::new(),
             unlocked_set: HashSet::new(),
         };
@@ -398,6 +665,7 @@ impl SkillTreeRuntime {
             camera: SkillTreeCamera::default(),
             editor: SkillTreeEditorState::default(),
             editor_history: SkillTreeEditorHistory::default(),
+            editor_clipboard: None,
             id_to_index: HashMap::new(),
             unlocked_set: HashSet::new(),
         };
@@ -556,12 +824,120 @@ impl SkillTreeRuntime {
         true
     }
 
+    /// Buys every locked prerequisite of `id` plus `id` itself, prerequisites first.
+    ///
+    /// All-or-nothing: if the summed node costs exceed the current money, nothing is bought.
+    pub fn buy_path(&mut self, id: &str) -> BuyResult {
+        if !self.id_to_index.contains_key(id) {
+            return BuyResult::UnknownNode(id.to_string());
+        }
+        if self.is_unlocked(id) {
+            return BuyResult::AlreadyUnlocked;
+        }
+
+        // Post-order DFS over locked prerequisites; `visited` also guards against cycles.
+        let mut order: Vec<usize> = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![(id.to_string(), false)];
+        while let Some((node_id, expanded)) = stack.pop() {
+            let Some(idx) = self.id_to_index.get(&node_id).copied() else {
+                return BuyResult::UnknownNode(node_id);
+            };
+            if expanded {
+                order.push(idx);
+                continue;
+            }
+            if self.is_unlocked(&node_id) || !visited.insert(node_id.clone()) {
+                continue;
+            }
+            stack.push((node_id, true));
+            for req in self.def.nodes[idx].requires.iter().rev() {
+                if !visited.contains(req) {
+                    stack.push((req.clone(), false));
+                }
+            }
+        }
+
+        let cost = order.iter().fold(0u32, |sum, &idx| {
+            sum.saturating_add(self.def.nodes[idx].cost)
+        });
+        if cost > self.progress.money {
+            return BuyResult::CannotAfford {
+                cost,
+                shortfall: cost - self.progress.money,
+            };
+        }
+
+        let mut nodes = Vec::with_capacity(order.len());
+        for idx in order {
+            let node_id = self.def.nodes[idx].id.clone();
+            self.unlocked_set.insert(node_id.clone());
+            self.progress.unlocked.push(node_id.clone());
+            nodes.push(node_id);
+        }
+        self.progress.money -= cost;
+        let _ = save_progress(&self.progress_path, &self.progress);
+        BuyResult::Bought { nodes, cost }
+    }
+
+    /// Un-buys `id` and returns its cost to `money`; yields the refunded amount.
+    ///
+    /// Refused while any other unlocked node still requires it, so refunds peel the tree from
+    /// its leaves inward and `run_mods` never sees an orphaned upgrade.
+    pub fn refund(&mut self, id: &str) -> Result<u32, RefundError> {
+        let Some(idx) = self.id_to_index.get(id).copied() else {
+            return Err(RefundError::UnknownNode(id.to_string()));
+        };
+        if id == "start" {
+            return Err(RefundError::Root);
+        }
+        if !self.is_unlocked(id) {
+            return Err(RefundError::NotUnlocked);
+        }
+        let dependents: Vec<String> = self
+            .def
+            .nodes
+            .iter()
+            .filter(|n| self.is_unlocked(&n.id) && n.requires.iter().any(|r| r == id))
+            .map(|n| n.id.clone())
+            .collect();
+        if !dependents.is_empty() {
+            return Err(RefundError::RequiredBy(dependents));
+        }
+
+        let cost = self.def.nodes[idx].cost;
+        self.unlocked_set.remove(id);
+        self.progress.unlocked.retain(|u| u != id);
+        self.progress.money = self.progress.money.saturating_add(cost);
+        let _ = save_progress(&self.progress_path, &self.progress);
+        Ok(cost)
+    }
+
+    /// Refunds every purchased node (everything but `start`) and returns the total refunded.
+    pub fn respec_all(&mut self) -> u32 {
+        let refunded = self
+            .def
+            .nodes
+            .iter()
+            .filter(|n| n.id != "start" && self.is_unlocked(&n.id))
+            .fold(0u32, |sum, n| sum.saturating_add(n.cost));
+        self.progress.unlocked.retain(|u| u == "start");
+        self.unlocked_set = self.progress.unlocked.iter().cloned().collect();
+        self.progress.money = self.progress.money.saturating_add(refunded);
+        let _ = save_progress(&self.progress_path, &self.progress);
+        refunded
+    }
+
     pub fn add_money(&mut self, amount: u32) {
         self.progress.money = self.progress.money.saturating_add(amount);
         let _ = save_progress(&self.progress_path, &self.progress);
     }
 
+    /// Writes the def back to its asset file; refuses (`InvalidData`) if `validate` fails.
     pub fn save_def(&self) -> std::io::Result<()> {
+        if let Err(errors) = self.def.validate() {
+            return Err(invalid_def_error(&errors));
+        }
         let Some(path) = self.def_path.as_ref() else {
             return Ok(());
         };
@@ -569,6 +945,41 @@ impl SkillTreeRuntime {
         atomic_write(path, json.as_bytes())
     }
 
+    /// Pretty-printed JSON of the current def, suitable for sharing a layout outside the repo.
+    pub fn export_def_string(&self) -> String {
+        serde_json::to_string_pretty(&self.def).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Replaces the def with one parsed from `json` as a single undoable editor edit.
+    ///
+    /// The def is normalized like a file load and must pass `validate`; otherwise the current def
+    /// is left untouched and an `InvalidData` error is returned.
+    pub fn import_def_string(&mut self, json: &str) -> std::io::Result<()> {
+        let mut def: SkillTreeDef = serde_json::from_str(json)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        normalize_and_validate(&mut def);
+        if let Err(errors) = def.validate() {
+            return Err(invalid_def_error(&errors));
+        }
+        let count = def.nodes.len();
+        let before = std::mem::replace(&mut self.def, def);
+        self.rebuild_caches();
+        self.mark_editor_mutation(before, format!("IMPORT {count} NODES"));
+        Ok(())
+    }
+
+    pub fn export_def_file(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        atomic_write(path, self.export_def_string().as_bytes())
+    }
+
+    pub fn import_def_file(&mut self, path: &Path) -> std::io::Result<()> {
+        let json = fs::read_to_string(path)?;
+        self.import_def_string(&json)
+    }
+
     pub fn reload_def(&mut self) {
         let (def, def_path, load_warning) = load_def_from_default_path();
         self.def = def;
@@ -596,7 +1007,47 @@ impl SkillTreeRuntime {
         }
     }
 
-    fn push_history_entry(history: &mut Vec<SkillTreeDef>, snapshot: SkillTreeDef) {
+    /// Records that the current `def` was written to disk: clears `dirty` and flags every other
+    /// history revision as unsaved.
+    pub fn editor_mark_saved(&mut self) {
+        self.editor.dirty = false;
+        let history = &mut self.editor_history;
+        for entry in history
+            .undo_defs
+            .iter_mut()
+            .chain(history.redo_defs.iter_mut())
+        {
+            entry.dirty = true;
+        }
+    }
+
+    fn history_entry(&self, def: SkillTreeDef) -> SkillTreeEditorHistoryEntry {
+        SkillTreeEditorHistoryEntry {
+            def,
+            selected: self.editor.selected.clone(),
+            dirty: self.editor.dirty,
+        }
+    }
+
+    /// Swaps in a history revision, returning the current one for the opposite stack.
+    fn restore_history_entry(
+        &mut self,
+        entry: SkillTreeEditorHistoryEntry,
+    ) -> SkillTreeEditorHistoryEntry {
+        let previous_def = std::mem::replace(&mut self.def, entry.def);
+        let current = self.history_entry(previous_def);
+        self.editor.selected = entry.selected;
+        self.editor.dirty = entry.dirty;
+        self.editor.move_grab_offset = None;
+        self.editor.pending_delete_id = None;
+        self.rebuild_caches();
+        current
+    }
+
+    fn push_history_entry(
+        history: &mut Vec<SkillTreeEditorHistoryEntry>,
+        snapshot: SkillTreeEditorHistoryEntry,
+    ) {
         history.push(snapshot);
         if history.len() > SKILLTREE_EDITOR_HISTORY_LIMIT {
             let overflow = history.len() - SKILLTREE_EDITOR_HISTORY_LIMIT;
@@ -621,14 +1072,31 @@ impl SkillTreeRuntime {
         }
     }
 
-    fn mark_editor_mutation(&mut self, before: SkillTreeDef, status: String) {
-        Self::push_history_entry(&mut self.editor_history.undo_defs, before);
+    /// Records `before` for undo. Pass the selection the op started with, since ops such as
+    /// create or delete change it before calling this.
+    fn mark_editor_mutation_from(
+        &mut self,
+        before: SkillTreeDef,
+        selected_before: Option<String>,
+        status: String,
+    ) {
+        let entry = SkillTreeEditorHistoryEntry {
+            def: before,
+            selected: selected_before,
+            dirty: self.editor.dirty,
+        };
+        Self::push_history_entry(&mut self.editor_history.undo_defs, entry);
         self.editor_history.redo_defs.clear();
         self.editor.pending_delete_id = None;
         self.editor.dirty = true;
         self.editor.status = Some(status);
     }
 
+    fn mark_editor_mutation(&mut self, before: SkillTreeDef, status: String) {
+        let selected = self.editor.selected.clone();
+        self.mark_editor_mutation_from(before, selected, status);
+    }
+
     fn next_duplicate_id(&self, base_id: &str) -> String {
         let first = format!("{base_id}_copy");
         if !self.id_to_index.contains_key(&first) {
@@ -682,6 +1150,7 @@ impl SkillTreeRuntime {
 
     pub fn editor_select(&mut self, id: &str, grab_offset: Option<Vec2i>) {
         self.editor.selected = Some(id.to_string());
+        self.editor.selection = vec![id.to_string()];
         self.editor.move_grab_offset = grab_offset;
         self.editor.pending_delete_id = None;
         if let Some(idx) = self.node_index(id) {
@@ -694,8 +1163,128 @@ impl SkillTreeRuntime {
         self.editor.selected.as_deref()
     }
 
+    /// Adds `id` to the selection set (making it the primary), or drops it if already selected.
+    pub fn editor_toggle_select(&mut self, id: &str, grab_offset: Option<Vec2i>) {
+        let mut selection: Vec<String> = self
+            .editor_selected_ids()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        if let Some(i) = selection.iter().position(|s| s == id) {
+            selection.remove(i);
+            match selection.last().cloned() {
+                Some(primary) => {
+                    self.editor.selected = Some(primary);
+                    self.editor.selection = selection;
+                    self.editor.move_grab_offset = None;
+                    self.editor.pending_delete_id = None;
+                    self.editor.status = Some(format!("DESELECT {id}"));
+                }
+                None => self.editor_clear_selection(),
+            }
+            return;
+        }
+        selection.push(id.to_string());
+        self.editor_select(id, grab_offset);
+        let count = selection.len();
+        self.editor.selection = selection;
+        if count > 1 {
+            self.editor.status = Some(format!("SELECT {id} (+{})", count - 1));
+        }
+    }
+
+    /// Every selected node id; a lone `selected` counts as a one-node set.
+    pub fn editor_selected_ids(&self) -> Vec<&str> {
+        let Some(primary) = self.editor.selected.as_deref() else {
+            return Vec::new();
+        };
+        if !self.editor.selection.iter().any(|s| s == primary) {
+            return vec![primary];
+        }
+        self.editor
+            .selection
+            .iter()
+            .map(String::as_str)
+            .filter(|id| self.id_to_index.contains_key(*id))
+            .collect()
+    }
+
+    /// Move-tool grab: keeps the selection set when `id` is already in it, otherwise selects
+    /// just `id`.
+    pub fn editor_grab(&mut self, id: &str, grab_offset: Option<Vec2i>) {
+        if !self.editor_is_selected(id) {
+            self.editor_select(id, grab_offset);
+            return;
+        }
+        let selection = std::mem::take(&mut self.editor.selection);
+        self.editor_select(id, grab_offset);
+        self.editor.selection = selection;
+    }
+
+    pub fn editor_is_selected(&self, id: &str) -> bool {
+        self.editor_selected_ids().contains(&id)
+    }
+
+    pub fn editor_begin_box_select(&mut self, world: Vec2i) {
+        self.editor.box_select = Some(SkillTreeBoxSelect {
+            anchor: world,
+            corner: world,
+        });
+    }
+
+    pub fn editor_update_box_select(&mut self, world: Vec2i) {
+        if let Some(rect) = self.editor.box_select.as_mut() {
+            rect.corner = world;
+        }
+    }
+
+    pub fn editor_cancel_box_select(&mut self) {
+        self.editor.box_select = None;
+    }
+
+    /// Ends the drag and selects every node with a cell inside the rectangle. `additive` (shift
+    /// held) keeps the current selection as well. Returns how many nodes the rectangle caught.
+    pub fn editor_commit_box_select(&mut self, additive: bool) -> usize {
+        let Some(rect) = self.editor.box_select.take() else {
+            return 0;
+        };
+        let hits: Vec<String> = self
+            .def
+            .nodes
+            .iter()
+            .filter(|node| {
+                node.shape
+                    .iter()
+                    .any(|rel| rect.contains(Vec2i::new(node.pos.x + rel.x, node.pos.y + rel.y)))
+            })
+            .map(|node| node.id.clone())
+            .collect();
+        let mut selection: Vec<String> = if additive {
+            self.editor_selected_ids()
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        for id in &hits {
+            if !selection.contains(id) {
+                selection.push(id.clone());
+            }
+        }
+        let Some(primary) = selection.last().cloned() else {
+            self.editor_clear_selection();
+            return 0;
+        };
+        self.editor_select(&primary, None);
+        self.editor.selection = selection;
+        self.editor.status = Some(format!("BOX SELECT {}", hits.len()));
+        hits.len()
+    }
+
     pub fn editor_clear_selection(&mut self) {
         self.editor.selected = None;
+        self.editor.selection.clear();
         self.editor.move_grab_offset = None;
         self.editor.pending_delete_id = None;
         self.editor.status = Some("SELECT NONE".to_string());
@@ -775,6 +1364,54 @@ impl SkillTreeRuntime {
         None
     }
 
+    /// Nodes whose id or name contains `query` (case-insensitive), in def order.
+    pub fn find_nodes(&self, query: &str) -> Vec<&SkillNodeDef> {
+        let q = query.trim().to_ascii_lowercase();
+        if q.is_empty() {
+            return Vec::new();
+        }
+        self.def
+            .nodes
+            .iter()
+            .filter(|n| {
+                n.id.to_ascii_lowercase().contains(&q) || n.name.to_ascii_lowercase().contains(&q)
+            })
+            .collect()
+    }
+
+    /// Selects `id` and eases the camera (`target_pan`, current zoom) toward the centre of its
+    /// cells, clamped to the tree bounds for a `grid_cols` x `grid_rows` view.
+    pub fn editor_focus_node(&mut self, id: &str, grid_cols: u32, grid_rows: u32) -> bool {
+        let Some(idx) = self.node_index(id) else {
+            return false;
+        };
+        self.editor_select(id, None);
+        self.camera.target_cell_px = self.camera.cell_px;
+        if grid_cols > 0
+            && grid_rows > 0
+            && let Some(bounds) = skilltree_world_bounds(&self.def)
+        {
+            let node = &self.def.nodes[idx];
+            let count = node.shape.len().max(1) as f32;
+            let (sum_x, sum_y) = node.shape.iter().fold((0.0, 0.0), |(x, y), c| {
+                (x + (node.pos.x + c.x) as f32, y + (node.pos.y + c.y) as f32)
+            });
+            let center = Vec2f::new(sum_x / count + 0.5, sum_y / count + 0.5);
+            let view = Vec2f::new(grid_cols as f32, grid_rows as f32);
+            let cam_min = clamp_camera_min_to_bounds(
+                Vec2f::new(center.x - view.x * 0.5, center.y - view.y * 0.5),
+                view,
+                bounds,
+                SKILLTREE_CAMERA_BOUNDS_PAD_CELLS,
+            );
+            // Same default camera origin as the headful view: x centred on 0, y starting at 0.
+            let default_cam_min_x = (-(grid_cols as i32) / 2) as f32;
+            self.camera.target_pan = Vec2f::new(cam_min.x - default_cam_min_x, cam_min.y);
+        }
+        self.editor.status = Some(format!("FOCUS {id}"));
+        true
+    }
+
     pub fn editor_select_matching(&mut self, query: &str) -> Option<String> {
         let hit = self.editor_find_first_matching(query)?;
         self.editor_select(&hit, None);
@@ -790,15 +1427,14 @@ impl SkillTreeRuntime {
         !self.editor_history.redo_defs.is_empty()
     }
 
+    /// Restores the `def` and selection from before the last editor mutation. `dirty` returns to
+    /// whether that revision was unsaved.
     pub fn editor_undo(&mut self) -> bool {
         let Some(prev) = self.editor_history.undo_defs.pop() else {
             return false;
         };
-        Self::push_history_entry(&mut self.editor_history.redo_defs, self.def.clone());
-        self.def = prev;
-        self.rebuild_caches();
-        self.editor.pending_delete_id = None;
-        self.editor.dirty = true;
+        let current = self.restore_history_entry(prev);
+        Self::push_history_entry(&mut self.editor_history.redo_defs, current);
         self.editor.status = Some("UNDO".to_string());
         true
     }
@@ -807,11 +1443,8 @@ impl SkillTreeRuntime {
         let Some(next) = self.editor_history.redo_defs.pop() else {
             return false;
         };
-        Self::push_history_entry(&mut self.editor_history.undo_defs, self.def.clone());
-        self.def = next;
-        self.rebuild_caches();
-        self.editor.pending_delete_id = None;
-        self.editor.dirty = true;
+        let current = self.restore_history_entry(next);
+        Self::push_history_entry(&mut self.editor_history.undo_defs, current);
         self.editor.status = Some("REDO".to_string());
         true
     }
@@ -827,6 +1460,7 @@ impl SkillTreeRuntime {
 
     pub fn editor_create_node_at(&mut self, pos: Vec2i) -> String {
         let before = self.def.clone();
+        let selected_before = self.editor.selected.clone();
         let mut i = self.def.nodes.len().saturating_add(1);
         let mut id = format!("node{i}");
         while self.id_to_index.contains_key(&id) {
@@ -847,7 +1481,7 @@ impl SkillTreeRuntime {
         self.rebuild_caches();
         self.editor.selected = Some(id.clone());
         self.editor.cursor_world = pos;
-        self.mark_editor_mutation(before, format!("NEW {id}"));
+        self.mark_editor_mutation_from(before, selected_before, format!("NEW {id}"));
         id
     }
 
@@ -866,10 +1500,55 @@ impl SkillTreeRuntime {
         if let Some(new_idx) = self.node_index(&dup_id) {
             self.editor.cursor_world = self.def.nodes[new_idx].pos;
         }
-        self.mark_editor_mutation(before, format!("DUP {selected_id} -> {dup_id}"));
+        let status = format!("DUP {selected_id} -> {dup_id}");
+        self.mark_editor_mutation_from(before, Some(selected_id), status);
         Some(dup_id)
     }
 
+    /// Copies the selected node (shape, name, cost, color, effect) without its prereq links.
+    pub fn editor_copy_selected(&mut self) -> bool {
+        let Some(idx) = self
+            .editor
+            .selected
+            .as_deref()
+            .and_then(|id| self.node_index(id))
+        else {
+            return false;
+        };
+        let mut node = self.def.nodes[idx].clone();
+        node.requires.clear();
+        self.editor.status = Some(format!("COPY {}", node.id));
+        self.editor_clipboard = Some(node);
+        true
+    }
+
+    pub fn editor_has_clipboard(&self) -> bool {
+        self.editor_clipboard.is_some()
+    }
+
+    /// Pastes the clipboard node anchored at `world` (snapped when snapping is on) under a fresh
+    /// `<id>_copy<N>` id and selects it. Fails without a clipboard or if the shape would overlap an
+    /// existing node.
+    pub fn editor_paste_at(&mut self, world: Vec2i) -> Option<String> {
+        let mut node = self.editor_clipboard.clone()?;
+        let pos = self.editor_snap_pos(world);
+        if let Some(other) = self.shape_collision_at(&node.shape, pos, &[]) {
+            self.editor.status = Some(format!("PASTE BLOCKED: OVERLAPS {other}"));
+            return None;
+        }
+        let before = self.def.clone();
+        let selected_before = self.editor.selected.clone();
+        let id = self.next_duplicate_id(&node.id);
+        node.id = id.clone();
+        node.pos = pos;
+        self.def.nodes.push(node);
+        self.rebuild_caches();
+        self.editor.selected = Some(id.clone());
+        self.editor.cursor_world = pos;
+        self.mark_editor_mutation_from(before, selected_before, format!("PASTE {id}"));
+        Some(id)
+    }
+
     pub fn editor_request_delete_selected(&mut self) -> bool {
         let Some(selected) = self.editor.selected.clone() else {
             self.editor.pending_delete_id = None;
@@ -902,10 +1581,12 @@ impl SkillTreeRuntime {
         self.editor.connect_from = None;
         self.editor.move_grab_offset = None;
         self.rebuild_caches();
-        self.mark_editor_mutation(before, format!("DEL {id}"));
+        let status = format!("DEL {id}");
+        self.mark_editor_mutation_from(before, Some(id), status);
         true
     }
 
+    /// Nudges the selection by `delta`, scaled to whole snap steps while snapping is on.
     pub fn editor_nudge_selected_by(&mut self, delta: Vec2i) -> bool {
         let Some(id) = self.editor.selected.clone() else {
             return false;
@@ -913,26 +1594,247 @@ impl SkillTreeRuntime {
         let Some(idx) = self.node_index(&id) else {
             return false;
         };
+        let step = if self.editor.snap_enabled {
+            self.editor.snap_step.max(1)
+        } else {
+            1
+        };
         let pos = self.def.nodes[idx].pos;
-        let next = Vec2i::new(pos.x.saturating_add(delta.x), pos.y.saturating_add(delta.y));
+        let next = Vec2i::new(
+            pos.x.saturating_add(delta.x.saturating_mul(step)),
+            pos.y.saturating_add(delta.y.saturating_mul(step)),
+        );
         self.editor_move_selected_to(next)
     }
 
+    /// Steps `snap_step` through `EDITOR_SNAP_STEPS`, wrapping back to the smallest.
+    pub fn editor_cycle_snap_step(&mut self) {
+        let current = self.editor.snap_step.max(1);
+        self.editor.snap_step = EDITOR_SNAP_STEPS
+            .iter()
+            .copied()
+            .find(|&step| step > current)
+            .unwrap_or(EDITOR_SNAP_STEPS[0]);
+        self.editor.status = Some(format!("SNAP STEP {}", self.editor.snap_step));
+    }
+
+    pub fn editor_toggle_snap(&mut self) {
+        self.editor.snap_enabled = !self.editor.snap_enabled;
+        self.editor.status = Some(if self.editor.snap_enabled {
+            format!("SNAP ON ({})", self.editor.snap_step.max(1))
+        } else {
+            "SNAP OFF".to_string()
+        });
+    }
+
+    /// `pos` snapped to the editor grid, or unchanged when snapping is off.
+    pub fn editor_snap_pos(&self, pos: Vec2i) -> Vec2i {
+        if !self.editor.snap_enabled {
+            return pos;
+        }
+        let step = self.editor.snap_step;
+        Vec2i::new(snap_to_step(pos.x, step), snap_to_step(pos.y, step))
+    }
+
+    /// Id of the first node outside `skip` whose cells would overlap node `idx` anchored at `pos`.
+    fn node_collision_at(&self, idx: usize, pos: Vec2i, skip: &[usize]) -> Option<&str> {
+        self.shape_collision_at(&self.def.nodes[idx].shape, pos, skip)
+    }
+
+    /// First node (outside `skip`) sharing a cell with `shape` anchored at `pos`.
+    fn shape_collision_at(&self, shape: &[Vec2i], pos: Vec2i, skip: &[usize]) -> Option<&str> {
+        let cells: HashSet<(i32, i32)> = shape.iter().map(|c| (pos.x + c.x, pos.y + c.y)).collect();
+        self.def
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(other_idx, _)| !skip.contains(other_idx))
+            .find(|(_, other)| {
+                other
+                    .shape
+                    .iter()
+                    .any(|c| cells.contains(&(other.pos.x + c.x, other.pos.y + c.y)))
+            })
+            .map(|(_, other)| other.id.as_str())
+    }
+
+    /// Rows/columns where the selected node's anchor lines up with another node's anchor.
+    ///
+    /// Drawn by the editor layer while the Move tool is active; never affects placement.
+    pub fn editor_alignment_guides(&self) -> Vec<SkillTreeAlignmentGuide> {
+        let Some(idx) = self
+            .editor
+            .selected
+            .as_deref()
+            .and_then(|id| self.node_index(id))
+        else {
+            return Vec::new();
+        };
+        let pos = self.def.nodes[idx].pos;
+        let mut guides: Vec<SkillTreeAlignmentGuide> = self
+            .def
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(other_idx, _)| *other_idx != idx)
+            .flat_map(|(_, other)| {
+                let column =
+                    (other.pos.x == pos.x).then_some(SkillTreeAlignmentGuide::Column(pos.x));
+                let row = (other.pos.y == pos.y).then_some(SkillTreeAlignmentGuide::Row(pos.y));
+                column.into_iter().chain(row)
+            })
+            .collect();
+        guides.sort();
+        guides.dedup();
+        guides
+    }
+
+    /// Moves the primary selected node's anchor to `new_pos` (snapped when snapping is on),
+    /// carrying the rest of the selection set along by the same offset.
+    ///
+    /// Rejects the move if any moved node would overlap an unselected node.
     pub fn editor_move_selected_to(&mut self, new_pos: Vec2i) -> bool {
         let Some(id) = self.editor.selected.clone() else {
             return false;
         };
-        let before = self.def.clone();
-        let Some(node) = self.node_mut(&id) else {
+        let Some(idx) = self.node_index(&id) else {
             return false;
         };
-        if node.pos == new_pos {
+        let new_pos = self.editor_snap_pos(new_pos);
+        let pos = self.def.nodes[idx].pos;
+        self.editor_move_selection_by(Vec2i::new(new_pos.x - pos.x, new_pos.y - pos.y))
+    }
+
+    /// Translates every selected node by `delta` as one undoable edit.
+    ///
+    /// Collisions are only checked against unselected nodes, so a cluster can slide over
+    /// cells its own members vacate.
+    pub fn editor_move_selection_by(&mut self, delta: Vec2i) -> bool {
+        if delta.x == 0 && delta.y == 0 {
             return false;
         }
-        node.pos = new_pos;
-        let _ = node;
+        let targets: Vec<(usize, Vec2i)> = self
+            .selected_indices()
+            .into_iter()
+            .map(|idx| {
+                let pos = self.def.nodes[idx].pos;
+                let next = Vec2i::new(pos.x.saturating_add(delta.x), pos.y.saturating_add(delta.y));
+                (idx, next)
+            })
+            .collect();
+        self.place_selection(&targets, "MOVE")
+    }
+
+    /// Lines the selected nodes up on the primary node's row (shared anchor y).
+    pub fn editor_align_selected_horizontal(&mut self) -> bool {
+        self.align_selected(|primary, pos| Vec2i::new(pos.x, primary.y), "ALIGN ROW")
+    }
+
+    /// Lines the selected nodes up on the primary node's column (shared anchor x).
+    pub fn editor_align_selected_vertical(&mut self) -> bool {
+        self.align_selected(|primary, pos| Vec2i::new(primary.x, pos.y), "ALIGN COLUMN")
+    }
+
+    /// Spaces three or more selected nodes evenly between the outermost two, along whichever
+    /// axis their anchors spread out more on.
+    pub fn editor_distribute_selected(&mut self) -> bool {
+        let mut indices = self.selected_indices();
+        if indices.len() < 3 {
+            self.editor.status = Some("DISTRIBUTE NEEDS 3+ NODES".to_string());
+            return false;
+        }
+        let spread = |axis: fn(Vec2i) -> i32| {
+            let values = indices.iter().map(|&idx| axis(self.def.nodes[idx].pos));
+            values.clone().max().unwrap_or(0) - values.min().unwrap_or(0)
+        };
+        let horizontal = spread(|p| p.x) >= spread(|p| p.y);
+        let axis = |p: Vec2i| if horizontal { p.x } else { p.y };
+        indices.sort_by_key(|&idx| (axis(self.def.nodes[idx].pos), idx));
+        let first = axis(self.def.nodes[indices[0]].pos) as i64;
+        let last = axis(self.def.nodes[indices[indices.len() - 1]].pos) as i64;
+        let gaps = (indices.len() - 1) as i64;
+        let targets: Vec<(usize, Vec2i)> = indices
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| {
+                let pos = self.def.nodes[idx].pos;
+                let v = (first + ((last - first) * i as i64 + gaps / 2).div_euclid(gaps)) as i32;
+                let next = if horizontal {
+                    Vec2i::new(v, pos.y)
+                } else {
+                    Vec2i::new(pos.x, v)
+                };
+                (idx, next)
+            })
+            .collect();
+        self.place_selection(&targets, "DISTRIBUTE")
+    }
+
+    fn align_selected(&mut self, anchor: impl Fn(Vec2i, Vec2i) -> Vec2i, verb: &str) -> bool {
+        let indices = self.selected_indices();
+        let Some(primary) = self
+            .editor
+            .selected
+            .as_deref()
+            .and_then(|id| self.node_index(id))
+        else {
+            return false;
+        };
+        if indices.len() < 2 {
+            self.editor.status = Some(format!("{verb} NEEDS 2+ NODES"));
+            return false;
+        }
+        let primary_pos = self.def.nodes[primary].pos;
+        let targets: Vec<(usize, Vec2i)> = indices
+            .into_iter()
+            .map(|idx| (idx, anchor(primary_pos, self.def.nodes[idx].pos)))
+            .collect();
+        self.place_selection(&targets, verb)
+    }
+
+    fn selected_indices(&self) -> Vec<usize> {
+        self.editor_selected_ids()
+            .into_iter()
+            .filter_map(|id| self.node_index(id))
+            .collect()
+    }
+
+    /// Re-anchors nodes as one undoable edit, refusing placements that overlap an unmoved
+    /// node or another node in `targets`.
+    fn place_selection(&mut self, targets: &[(usize, Vec2i)], verb: &str) -> bool {
+        if targets
+            .iter()
+            .all(|&(idx, pos)| self.def.nodes[idx].pos == pos)
+        {
+            return false;
+        }
+        let moved: Vec<usize> = targets.iter().map(|&(idx, _)| idx).collect();
+        let mut claimed: HashMap<(i32, i32), usize> = HashMap::new();
+        for &(idx, pos) in targets {
+            let blocker = self.node_collision_at(idx, pos, &moved).or_else(|| {
+                self.def.nodes[idx].shape.iter().find_map(|c| {
+                    let cell = (pos.x + c.x, pos.y + c.y);
+                    match claimed.insert(cell, idx) {
+                        Some(other) if other != idx => Some(self.def.nodes[other].id.as_str()),
+                        _ => None,
+                    }
+                })
+            });
+            if let Some(other) = blocker {
+                self.editor.status = Some(format!("{verb} BLOCKED: OVERLAPS {other}"));
+                return false;
+            }
+        }
+        let before = self.def.clone();
+        for &(idx, pos) in targets {
+            self.def.nodes[idx].pos = pos;
+        }
+        let status = match targets {
+            [(idx, _)] => format!("{verb} {}", self.def.nodes[*idx].id),
+            _ => format!("{verb} {} NODES", targets.len()),
+        };
         self.rebuild_caches();
-        self.mark_editor_mutation(before, format!("MOVE {id}"));
+        self.mark_editor_mutation(before, status);
         true
     }
 
@@ -1105,6 +2007,23 @@ fn default_progress_path() -> PathBuf {
         .join("skilltree_progress.json")
 }
 
+/// Where the headful editor exports/imports shareable skilltree layouts.
+pub fn default_skilltree_export_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("target")
+        .join("skilltree_export.json")
+}
+
+fn invalid_def_error(errors: &[SkillTreeError]) -> std::io::Error {
+    let message = errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ");
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
 fn load_progress(path: &Path) -> Result<SkillTreeProgress, std::io::Error> {
     let bytes = fs::read(path)?;
     let p: SkillTreeProgress = serde_json::from_slice(&bytes)
@@ -1226,6 +2145,107 @@ mod tests {
         rt
     }
 
+    fn make_path_runtime(money: u32) -> SkillTreeRuntime {
+        let node = |id: &str, cost: u32, requires: &[&str]| SkillNodeDef {
+            id: id.to_string(),
+            name: id.to_ascii_uppercase(),
+            pos: Vec2i::new(0, 0),
+            shape: vec![Vec2i::new(0, 0)],
+            color: 1,
+            cost,
+            requires: requires.iter().map(|r| r.to_string()).collect(),
+            effect: SkillEffect::None,
+        };
+        let def = SkillTreeDef {
+            version: 1,
+            nodes: vec![
+                node("start", 0, &[]),
+                node("a", 2, &["start"]),
+                node("b", 3, &["start"]),
+                node("c", 5, &["a", "b"]),
+            ],
+        };
+        let progress = SkillTreeProgress {
+            version: 1,
+            money,
+            unlocked: vec!["start".to_string()],
+        };
+        let mut rt = SkillTreeRuntime::from_snapshot(SkillTreeSnapshot {
+            def,
+            progress,
+            camera: SkillTreeCamera::default(),
+            editor: SkillTreeEditorState::default(),
+        });
+        rt.progress_path = std::env::temp_dir().join(format!(
+            "rollout_skilltree_buy_path_{}_{money}.json",
+            std::process::id()
+        ));
+        rt
+    }
+
+    #[test]
+    fn refund_returns_cost_only_for_leaf_purchases() {
+        let mut rt = make_path_runtime(13);
+        assert!(matches!(rt.buy_path("c"), BuyResult::Bought { .. }));
+        let spent = 13 - rt.money();
+
+        assert_eq!(
+            rt.refund("a"),
+            Err(RefundError::RequiredBy(vec!["c".to_string()]))
+        );
+        assert_eq!(rt.refund("start"), Err(RefundError::Root));
+        let c_cost = rt.def.nodes[rt.node_index("c").unwrap()].cost;
+        assert_eq!(rt.refund("c"), Ok(c_cost));
+        assert!(!rt.is_unlocked("c"));
+        assert!(!rt.progress.unlocked.iter().any(|id| id == "c"));
+        assert_eq!(rt.refund("c"), Err(RefundError::NotUnlocked));
+        assert_eq!(rt.money(), 13 - spent + c_cost);
+
+        assert_eq!(rt.respec_all(), spent - c_cost);
+        let _ = std::fs::remove_file(&rt.progress_path);
+        assert_eq!(rt.money(), 13);
+        assert_eq!(rt.progress.unlocked, vec!["start".to_string()]);
+        assert_eq!(rt.run_mods(), SkillTreeRunMods::default());
+    }
+
+    #[test]
+    fn buy_path_buys_prereqs_first_when_affordable() {
+        let mut rt = make_path_runtime(12);
+        let result = rt.buy_path("c");
+        let _ = std::fs::remove_file(&rt.progress_path);
+
+        assert_eq!(
+            result,
+            BuyResult::Bought {
+                nodes: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                cost: 10,
+            }
+        );
+        assert_eq!(rt.money(), 2);
+        assert!(rt.is_unlocked("a") && rt.is_unlocked("b") && rt.is_unlocked("c"));
+        assert_eq!(result.sfx_plays(BuySfxMode::Once), 1);
+        assert_eq!(result.sfx_plays(BuySfxMode::PerNode), 3);
+        assert_eq!(rt.buy_path("c"), BuyResult::AlreadyUnlocked);
+    }
+
+    #[test]
+    fn buy_path_reports_shortfall_and_buys_nothing() {
+        let mut rt = make_path_runtime(9);
+        assert_eq!(
+            rt.buy_path("c"),
+            BuyResult::CannotAfford {
+                cost: 10,
+                shortfall: 1
+            }
+        );
+        assert_eq!(rt.money(), 9);
+        assert!(!rt.is_unlocked("a") && !rt.is_unlocked("b"));
+        assert_eq!(
+            rt.buy_path("missing"),
+            BuyResult::UnknownNode("missing".to_string())
+        );
+    }
+
     #[test]
     fn normalize_shifts_negative_rel_cells_into_shape_origin_and_adjusts_pos() {
         let mut def = SkillTreeDef {
@@ -1312,6 +2332,7 @@ mod tests {
             camera: SkillTreeCamera::default(),
             editor: SkillTreeEditorState::default(),
             editor_history: SkillTreeEditorHistory::default(),
+            editor_clipboard: None,
             id_to_index: HashMap::new(),
             unlocked_set: HashSet::new(),
         };
@@ -1414,6 +2435,62 @@ mod tests {
         assert!(rt.node_index(&id).is_some());
     }
 
+    #[test]
+    fn editor_paste_uses_fresh_ids_and_drops_prereqs() {
+        let mut rt = make_editor_runtime();
+        let id = rt.editor_create_node_at(Vec2i::new(2, 2));
+        assert!(rt.editor_toggle_prereq("start", &id));
+        rt.editor_select(&id, None);
+        assert!(rt.editor_copy_selected());
+
+        // Pasting onto an occupied cell is refused.
+        assert_eq!(rt.editor_paste_at(Vec2i::new(2, 2)), None);
+
+        let first = rt.editor_paste_at(Vec2i::new(5, 2)).expect("paste");
+        assert_eq!(first, format!("{id}_copy"));
+        let second = rt.editor_paste_at(Vec2i::new(7, 2)).expect("paste again");
+        assert_eq!(second, format!("{id}_copy2"));
+
+        let pasted = &rt.def.nodes[rt.node_index(&first).expect("pasted node")];
+        assert_eq!(pasted.pos, Vec2i::new(5, 2));
+        assert!(pasted.requires.is_empty());
+        assert_eq!(rt.editor_selected_id(), Some(second.as_str()));
+        let source = &rt.def.nodes[rt.node_index(&id).expect("source node")];
+        assert_eq!(source.requires, vec!["start".to_string()]);
+
+        assert!(rt.editor_undo());
+        assert!(rt.node_index(&second).is_none());
+    }
+
+    #[test]
+    fn editor_undo_restores_selection_and_saved_state() {
+        let mut rt = make_editor_runtime();
+        let id = rt.editor_create_node_at(Vec2i::new(2, 2));
+        rt.editor_mark_saved();
+        assert!(!rt.editor.dirty);
+
+        rt.editor_select(&id, None);
+        assert!(!rt.editor_request_delete_selected());
+        assert!(rt.editor_request_delete_selected());
+        assert_eq!(rt.editor_selected_id(), None);
+        assert!(rt.editor.dirty);
+
+        assert!(rt.editor_undo());
+        assert!(rt.node_index(&id).is_some());
+        assert_eq!(rt.editor_selected_id(), Some(id.as_str()));
+        assert!(!rt.editor.dirty, "back at the saved revision");
+
+        assert!(rt.editor_undo());
+        assert!(rt.node_index(&id).is_none());
+        assert!(rt.editor.dirty, "older than the last save");
+
+        assert!(rt.editor_redo());
+        assert!(!rt.editor.dirty);
+        assert!(rt.editor_redo());
+        assert_eq!(rt.editor_selected_id(), None);
+        assert!(rt.editor.dirty);
+    }
+
     #[test]
     fn editor_duplicate_selected_uses_stable_copy_suffix() {
         let mut rt = make_editor_runtime();
@@ -1440,6 +2517,282 @@ mod tests {
         assert_eq!(rt.editor.selected.as_deref(), Some(id.as_str()));
     }
 
+    #[test]
+    fn find_nodes_matches_id_or_name_case_insensitively() {
+        let mut rt = make_path_runtime(0);
+        rt.def.nodes[2].name = "Deep Bore".to_string();
+        let ids = |rt: &SkillTreeRuntime, q: &str| -> Vec<String> {
+            rt.find_nodes(q).into_iter().map(|n| n.id.clone()).collect()
+        };
+        assert_eq!(ids(&rt, "BORE"), vec!["b".to_string()]);
+        assert_eq!(ids(&rt, "a"), vec!["start".to_string(), "a".to_string()]);
+        assert!(ids(&rt, "  ").is_empty());
+    }
+
+    #[test]
+    fn editor_focus_node_selects_and_targets_clamped_camera() {
+        let mut rt = make_editor_runtime();
+        let far = rt.editor_create_node_at(Vec2i::new(40, 30));
+        rt.editor_clear_selection();
+
+        assert!(rt.editor_focus_node(&far, 10, 8));
+        assert_eq!(rt.editor_selected_id(), Some(far.as_str()));
+        // Centre of cell (40, 30) lands mid-view: cam_min = (40.5 - 5, 30.5 - 4).
+        assert_eq!(rt.camera.target_pan, Vec2f::new(35.5 + 5.0, 26.5));
+        assert_eq!(rt.camera.pan, Vec2f::new(0.0, 0.0));
+
+        // A wider view would overshoot the far edge, so it stops at the bounds padding:
+        // cam_max = (41 + pad - 20, 31 + pad - 16).
+        assert!(rt.editor_focus_node(&far, 20, 16));
+        let pad = SKILLTREE_CAMERA_BOUNDS_PAD_CELLS;
+        assert_eq!(
+            rt.camera.target_pan,
+            Vec2f::new(21.0 + pad + 10.0, 15.0 + pad)
+        );
+        assert!(!rt.editor_focus_node("missing", 10, 8));
+    }
+
+    #[test]
+    fn editor_snap_rounds_moves_and_scales_nudges() {
+        let mut rt = make_editor_runtime();
+        let id = rt.editor_create_node_at(Vec2i::new(4, 0));
+        rt.editor_toggle_snap();
+        assert!(rt.editor.snap_enabled);
+        assert_eq!(rt.editor.status.as_deref(), Some("SNAP ON (2)"));
+
+        assert!(rt.editor_move_selected_to(Vec2i::new(7, 3)));
+        let idx = rt.node_index(&id).unwrap();
+        assert_eq!(rt.def.nodes[idx].pos, Vec2i::new(8, 4));
+
+        assert!(rt.editor_nudge_selected_by(Vec2i::new(-1, 0)));
+        assert_eq!(rt.def.nodes[idx].pos, Vec2i::new(6, 4));
+
+        rt.editor_toggle_snap();
+        assert_eq!(rt.editor.status.as_deref(), Some("SNAP OFF"));
+        assert!(rt.editor_move_selected_to(Vec2i::new(7, 3)));
+        assert_eq!(rt.def.nodes[idx].pos, Vec2i::new(7, 3));
+    }
+
+    #[test]
+    fn validate_reports_two_node_prereq_cycle_and_save_refuses_it() {
+        let mut rt = make_editor_runtime();
+        let a = rt.editor_create_node_at(Vec2i::new(2, 0));
+        let b = rt.editor_create_node_at(Vec2i::new(4, 0));
+        assert!(rt.editor_toggle_prereq(&a, &b));
+        assert_eq!(rt.def.validate(), Ok(()));
+        assert!(rt.editor_toggle_prereq(&b, &a));
+
+        let cycles = rt.def.find_prereq_cycles();
+        assert_eq!(cycles.len(), 1);
+        let mut cycle = cycles[0].clone();
+        cycle.sort();
+        let mut expected = vec![a.clone(), b.clone()];
+        expected.sort();
+        assert_eq!(cycle, expected);
+        assert!(matches!(
+            rt.def.validate().unwrap_err().as_slice(),
+            [SkillTreeError::PrereqCycle { .. }]
+        ));
+
+        let path = std::env::temp_dir().join(format!(
+            "rollout_skilltree_cycle_save_{}.json",
+            std::process::id()
+        ));
+        rt.def_path = Some(path.clone());
+        let err = rt.save_def().expect_err("cyclic def must not save");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("prereq cycle"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn export_import_round_trips_nodes_shapes_costs_and_prereqs() {
+        let mut source = make_path_runtime(0);
+        source.def.nodes[3].shape = vec![Vec2i::new(0, 0), Vec2i::new(1, 0), Vec2i::new(1, 1)];
+        source.def.nodes[3].pos = Vec2i::new(4, 2);
+        let json = source.export_def_string();
+
+        let mut rt = make_editor_runtime();
+        rt.import_def_string(&json).expect("import");
+        assert_eq!(rt.def, source.def);
+        assert!(rt.node_index("c").is_some());
+        assert!(rt.editor.dirty);
+
+        // Invalid imports leave the current def alone.
+        let mut cyclic = source.def.clone();
+        cyclic.nodes[1].requires.push("c".to_string());
+        let cyclic_json = serde_json::to_string(&cyclic).unwrap();
+        let err = rt.import_def_string(&cyclic_json).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(rt.import_def_string("not json").is_err());
+        assert_eq!(rt.def, source.def);
+
+        assert!(rt.editor_undo());
+        assert_eq!(rt.def.nodes.len(), 1);
+    }
+
+    #[test]
+    fn bundled_def_validates() {
+        assert_eq!(SkillTreeDef::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_dangling_prereqs() {
+        let mut rt = make_editor_runtime();
+        rt.def.nodes[0].requires.push("ghost".to_string());
+        assert!(rt.def.find_prereq_cycles().is_empty());
+        assert_eq!(
+            rt.def.validate(),
+            Err(vec![SkillTreeError::DanglingPrereq {
+                node: "start".to_string(),
+                missing: "ghost".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn editor_snap_step_cycles_through_presets() {
+        let mut rt = make_editor_runtime();
+        assert_eq!(rt.editor.snap_step, DEFAULT_EDITOR_SNAP_STEP);
+        rt.editor_cycle_snap_step();
+        assert_eq!(rt.editor.snap_step, 4);
+        rt.editor_cycle_snap_step();
+        rt.editor_cycle_snap_step();
+        assert_eq!(rt.editor.snap_step, 1);
+        assert_eq!(rt.editor.status.as_deref(), Some("SNAP STEP 1"));
+    }
+
+    #[test]
+    fn editor_box_select_catches_nodes_inside_the_rect() {
+        let mut rt = make_editor_runtime();
+        let a = rt.editor_create_node_at(Vec2i::new(100, 100));
+        let b = rt.editor_create_node_at(Vec2i::new(103, 102));
+        let c = rt.editor_create_node_at(Vec2i::new(120, 100));
+
+        // Drag from bottom-right to top-left; the rect is normalized.
+        rt.editor_begin_box_select(Vec2i::new(104, 104));
+        rt.editor_update_box_select(Vec2i::new(99, 99));
+        assert_eq!(rt.editor_commit_box_select(false), 2);
+        assert!(rt.editor.box_select.is_none());
+        let mut selected = rt.editor_selected_ids();
+        selected.sort_unstable();
+        let mut expected = vec![a.as_str(), b.as_str()];
+        expected.sort_unstable();
+        assert_eq!(selected, expected);
+
+        rt.editor_begin_box_select(Vec2i::new(120, 100));
+        assert_eq!(rt.editor_commit_box_select(true), 1);
+        assert_eq!(rt.editor_selected_ids().len(), 3);
+        assert_eq!(rt.editor_selected_id(), Some(c.as_str()));
+
+        rt.editor_begin_box_select(Vec2i::new(200, 200));
+        assert_eq!(rt.editor_commit_box_select(false), 0);
+        assert!(rt.editor_selected_ids().is_empty());
+    }
+
+    #[test]
+    fn editor_align_and_distribute_selected_nodes() {
+        let mut rt = make_editor_runtime();
+        let a = rt.editor_create_node_at(Vec2i::new(2, 3));
+        let b = rt.editor_create_node_at(Vec2i::new(5, 1));
+        let c = rt.editor_create_node_at(Vec2i::new(11, 6));
+        let pos = |rt: &SkillTreeRuntime, id: &str| rt.def.nodes[rt.node_index(id).unwrap()].pos;
+
+        rt.editor_select(&b, None);
+        rt.editor_toggle_select(&c, None);
+        rt.editor_toggle_select(&a, None);
+        assert!(rt.editor_align_selected_horizontal());
+        assert_eq!(pos(&rt, &b), Vec2i::new(5, 3));
+        assert_eq!(pos(&rt, &c), Vec2i::new(11, 3));
+
+        assert!(rt.editor_distribute_selected());
+        assert_eq!(pos(&rt, &a), Vec2i::new(2, 3));
+        assert_eq!(pos(&rt, &b), Vec2i::new(7, 3));
+        assert_eq!(pos(&rt, &c), Vec2i::new(11, 3));
+
+        // Stacking every node on one column would overlap them; nothing moves.
+        assert!(!rt.editor_align_selected_vertical());
+        assert_eq!(pos(&rt, &b), Vec2i::new(7, 3));
+        assert!(
+            rt.editor
+                .status
+                .as_deref()
+                .unwrap()
+                .starts_with("ALIGN COLUMN BLOCKED")
+        );
+
+        assert!(rt.editor_undo());
+        assert_eq!(pos(&rt, &b), Vec2i::new(5, 3));
+    }
+
+    #[test]
+    fn editor_move_rejects_overlapping_nodes() {
+        let mut rt = make_editor_runtime();
+        let id = rt.editor_create_node_at(Vec2i::new(3, 0));
+        let idx = rt.node_index(&id).unwrap();
+
+        assert!(!rt.editor_move_selected_to(Vec2i::new(0, 0)));
+        assert_eq!(rt.def.nodes[idx].pos, Vec2i::new(3, 0));
+        assert_eq!(
+            rt.editor.status.as_deref(),
+            Some("MOVE BLOCKED: OVERLAPS start")
+        );
+        assert!(rt.editor_move_selected_to(Vec2i::new(1, 0)));
+    }
+
+    #[test]
+    fn editor_multi_select_moves_the_whole_set() {
+        let mut rt = make_editor_runtime();
+        let a = rt.editor_create_node_at(Vec2i::new(3, 0));
+        let b = rt.editor_create_node_at(Vec2i::new(4, 0));
+        let (a_idx, b_idx) = (rt.node_index(&a).unwrap(), rt.node_index(&b).unwrap());
+        assert_eq!(rt.editor_selected_ids(), vec![b.as_str()]);
+
+        rt.editor_select(&a, None);
+        rt.editor_toggle_select(&b, None);
+        assert_eq!(rt.editor_selected_ids(), vec![a.as_str(), b.as_str()]);
+
+        // `b` may slide into the cell `a` vacates; only unselected nodes block.
+        assert!(rt.editor_move_selection_by(Vec2i::new(-1, 0)));
+        assert_eq!(rt.def.nodes[a_idx].pos, Vec2i::new(2, 0));
+        assert_eq!(rt.def.nodes[b_idx].pos, Vec2i::new(3, 0));
+        assert!(!rt.editor_move_selection_by(Vec2i::new(-2, 0)));
+        assert_eq!(
+            rt.editor.status.as_deref(),
+            Some("MOVE BLOCKED: OVERLAPS start")
+        );
+
+        // Dragging the primary with the Move tool carries the rest along.
+        rt.editor_grab(&a, Some(Vec2i::new(0, 0)));
+        assert!(rt.editor_move_selected_to(Vec2i::new(2, 5)));
+        assert_eq!(rt.def.nodes[b_idx].pos, Vec2i::new(3, 5));
+
+        rt.editor_toggle_select(&a, None);
+        assert_eq!(rt.editor_selected_ids(), vec![b.as_str()]);
+        rt.editor_clear_selection();
+        assert!(rt.editor_selected_ids().is_empty());
+        assert!(rt.editor.selection.is_empty());
+    }
+
+    #[test]
+    fn editor_alignment_guides_report_shared_anchor_lines() {
+        let mut rt = make_editor_runtime();
+        rt.editor_create_node_at(Vec2i::new(0, 5));
+        assert_eq!(
+            rt.editor_alignment_guides(),
+            vec![SkillTreeAlignmentGuide::Column(0)]
+        );
+
+        assert!(rt.editor_move_selected_to(Vec2i::new(4, 0)));
+        assert_eq!(
+            rt.editor_alignment_guides(),
+            vec![SkillTreeAlignmentGuide::Row(0)]
+        );
+
+        assert!(rt.editor_move_selected_to(Vec2i::new(4, 4)));
+        assert!(rt.editor_alignment_guides().is_empty());
+    }
+
     #[test]
     fn run_mods_accumulate_bottomwell_effects() {
         let def = SkillTreeDef {
@@ -1,17 +1,38 @@
+use std::fmt;
 use std::time::Duration;
 
-use serde::{Deserialize, Serialize};
+use engine::logical_clock::LogicalClock;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use serde_json::Value;
 
 use crate::round_timer::RoundTimer;
 use crate::skilltree::SkillTreeRuntime;
-use crate::tetris_core::TetrisCore;
+use crate::tetris_core::{LOCK_DELAY_MS_DEFAULT, TetrisCore};
 use crate::view::GameView;
 
 pub const DEFAULT_ROUND_LIMIT: Duration = Duration::from_secs(20);
+/// Pre-round countdown the headful game runs before each round (see `RoundTimer::with_countdown`).
+pub const DEFAULT_ROUND_COUNTDOWN: Duration = Duration::from_secs(3);
 pub const DEFAULT_GRAVITY_INTERVAL: Duration = Duration::from_millis(500);
+pub const DEFAULT_LOCK_DELAY: Duration = Duration::from_millis(LOCK_DELAY_MS_DEFAULT as u64);
+/// Length of one logical step. Gravity and the round timer only ever see whole steps, so the
+/// game's existing millisecond timing is preserved exactly.
+pub const LOGIC_STEP: Duration = Duration::from_millis(1);
 
+/// Version of the serialized `GameState` shape. Bump on breaking changes and add a step to
+/// [`migrate`] so older recordings keep loading.
+///
+/// - 0: recordings made before the version field existed.
+/// - 1: adds `schema_version`.
+pub const STATE_SCHEMA_VERSION: u32 = 1;
+
+// `remote = "Self"` turns the derives into inherent fns so the trait impls below can route
+// deserialization through `migrate`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct GameState {
+    #[serde(default)]
+    pub schema_version: u32,
     pub view: GameView,
     pub tetris: TetrisCore,
     pub skilltree: SkillTreeRuntime,
@@ -20,6 +41,27 @@ pub struct GameState {
     pub gravity_interval: Duration,
     #[serde(with = "crate::serde_duration")]
     pub gravity_elapsed: Duration,
+    /// Logical time advanced by `InputAction::Tick`; never read from the wall clock.
+    #[serde(default = "default_logic_clock")]
+    pub clock: LogicalClock,
+    /// Freezes the round timer during `InputAction::Tick` (debug toggle / profiling runs).
+    #[serde(default)]
+    pub round_timer_disabled: bool,
+    /// Set while `InputAction::Tick` holds the round timer because the view isn't playing, so it
+    /// only resumes a pause it took itself.
+    #[serde(default)]
+    pub round_timer_paused_by_view: bool,
+    /// When false, `InputAction::Hold` is ignored (player setting, copied in per run).
+    #[serde(default = "default_hold_enabled")]
+    pub hold_enabled: bool,
+}
+
+fn default_logic_clock() -> LogicalClock {
+    LogicalClock::new(LOGIC_STEP)
+}
+
+fn default_hold_enabled() -> bool {
+    true
 }
 
 impl GameState {
@@ -39,15 +81,26 @@ impl GameState {
         gravity_interval: Duration,
     ) -> Self {
         Self {
+            schema_version: STATE_SCHEMA_VERSION,
             view: GameView::default(),
             tetris,
             skilltree,
             round_timer: RoundTimer::new(round_limit),
             gravity_interval,
             gravity_elapsed: Duration::ZERO,
+            clock: default_logic_clock(),
+            round_timer_disabled: false,
+            round_timer_paused_by_view: false,
+            hold_enabled: true,
         }
     }
 
+    /// Piece RNG position carried by this state; equal values (with equal bags) draw the same
+    /// upcoming pieces no matter how the state was reached.
+    pub fn piece_rng_state(&self) -> u64 {
+        self.tetris.rng_state()
+    }
+
     pub fn tetris(&self) -> &TetrisCore {
         &self.tetris
     }
@@ -63,4 +116,120 @@ impl GameState {
     pub fn core_mut(&mut self) -> &mut TetrisCore {
         &mut self.tetris
     }
+
+    /// True while the round's pre-start countdown is pending; gameplay input is ignored.
+    pub fn in_countdown(&self) -> bool {
+        !self.round_timer_disabled && !self.round_timer.countdown_remaining().is_zero()
+    }
+}
+
+/// Gameplay invariants for `engine::regression::validate_recording`.
+///
+/// The board never grows past the core's configured width x effective height, and the score never
+/// decreases within a run. A drop back to a fresh run (no lines cleared yet) is a restart, not a
+/// bug.
+pub fn recording_invariants() -> impl FnMut(usize, &GameState) -> Result<(), String> {
+    let mut last_score: Option<u32> = None;
+    move |_frame, state| {
+        let board = state.tetris.board();
+        let max_rows = state.tetris.effective_board_height();
+        if board.len() > max_rows {
+            return Err(format!("board has {} rows (max {max_rows})", board.len()));
+        }
+        let width = state.tetris.board_width();
+        if let Some(y) = board.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "board row {y} has {} cells (expected {width})",
+                board[y].len()
+            ));
+        }
+
+        let score = state.tetris.score();
+        let mid_run = state.tetris.lines_cleared() > 0;
+        if let Some(prev) = last_score.filter(|&prev| score < prev && mid_run) {
+            return Err(format!("score decreased from {prev} to {score}"));
+        }
+        last_score = Some(score);
+        Ok(())
+    }
+}
+
+impl Serialize for GameState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GameState::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let version = value
+            .get("schema_version")
+            .and_then(Value::as_u64)
+            .map_or(0, |v| u32::try_from(v).unwrap_or(u32::MAX));
+        migrate(version, value).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug)]
+pub enum StateMigrationError {
+    /// No migration path exists from `found` to the current schema.
+    TooOld { found: u32, current: u32 },
+    /// The recording was written by a newer build.
+    TooNew { found: u32, current: u32 },
+    /// The migrated value still does not match the current shape.
+    Invalid { found: u32, message: String },
+}
+
+impl fmt::Display for StateMigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooOld { found, current } => write!(
+                f,
+                "recording uses GameState schema v{found}, which is too old to migrate to v{current}; re-record it with this build"
+            ),
+            Self::TooNew { found, current } => write!(
+                f,
+                "recording uses GameState schema v{found}, but this build only understands up to v{current}"
+            ),
+            Self::Invalid { found, message } => write!(
+                f,
+                "recording GameState (schema v{found}) does not match the expected shape: {message}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StateMigrationError {}
+
+/// Upgrades a serialized `GameState` written with schema `old_version` to the current shape.
+pub fn migrate(old_version: u32, mut value: Value) -> Result<GameState, StateMigrationError> {
+    if old_version > STATE_SCHEMA_VERSION {
+        return Err(StateMigrationError::TooNew {
+            found: old_version,
+            current: STATE_SCHEMA_VERSION,
+        });
+    }
+
+    let mut version = old_version;
+    while version < STATE_SCHEMA_VERSION {
+        value = match version {
+            // v0 predates the version field; the rest of the shape is unchanged.
+            0 => value,
+            _ => {
+                return Err(StateMigrationError::TooOld {
+                    found: old_version,
+                    current: STATE_SCHEMA_VERSION,
+                });
+            }
+        };
+        version += 1;
+    }
+
+    let mut state = GameState::deserialize(value).map_err(|e| StateMigrationError::Invalid {
+        found: old_version,
+        message: e.to_string(),
+    })?;
+    state.schema_version = STATE_SCHEMA_VERSION;
+    Ok(state)
 }
@@ -9,9 +9,13 @@ use serde::{Deserialize, Serialize};
 
 pub const BOARD_WIDTH: usize = 10;
 pub const BOARD_HEIGHT: usize = 20;
+/// Smallest width or height accepted by `TetrisCore::set_board_size`; every piece fits in 4x4.
+pub const MIN_BOARD_DIM: usize = 4;
 pub const NEXT_QUEUE_LEN: usize = 5;
 pub const LOCK_DELAY_MS_DEFAULT: u32 = 500;
 pub const LOCK_DELAY_MAX_MS_DEFAULT: u32 = 2_000;
+/// Grounded move/rotate resets allowed per piece before the lock timer stops resetting.
+pub const LOCK_RESET_LIMIT_DEFAULT: u32 = 15;
 pub const LINE_CLEAR_DELAY_MS_DEFAULT: u32 = 180;
 
 const HARD_DROP_POINTS_PER_ROW: u32 = 2;
@@ -156,6 +160,21 @@ impl RotationDir {
     }
 }
 
+/// How `next_queue()` is refilled. Every variant draws from the core's seeded RNG, so the
+/// sequence is reproducible for a given seed and available piece set.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Randomizer {
+    /// Independent draws weighted by each piece's material rarity.
+    #[default]
+    Weighted,
+    /// Shuffled bag holding each available piece once; no repeats within a bag.
+    SevenBag,
+    /// Independent draws with every available piece equally likely.
+    Uniform,
+    /// Shuffled bag holding `n` copies of each available piece.
+    MultiBag(u8),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct DepthWallDef {
@@ -228,9 +247,69 @@ enum LineClearPhase {
     },
 }
 
+/// Per-run play statistics. `Copy` so it costs nothing extra in every recorded frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameStats {
+    pub pieces_placed: u32,
+    /// Successful `hold_piece` swaps.
+    pub holds_used: u32,
+    pub max_combo: u32,
+    /// Logical time with the board in play (after the countdown, before game over).
+    pub play_time_ms: u64,
+}
+
+impl GameStats {
+    /// Pieces placed per second of play time; 0 before any time has passed.
+    pub fn pieces_per_second(&self) -> f32 {
+        if self.play_time_ms == 0 {
+            return 0.0;
+        }
+        self.pieces_placed as f32 * 1000.0 / self.play_time_ms as f32
+    }
+}
+
+/// How long the hard-drop trail takes to fade out.
+pub const HARD_DROP_TRAIL_MS: u32 = 150;
+/// How long a freshly locked piece flashes.
+pub const LOCK_FLASH_MS: u32 = 100;
+
+/// The column span a hard-dropped piece swept through, for the renderer's fading trail.
+/// Rows are board rows (y up) of the piece's lowest cell before and after the drop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardDropTrail {
+    /// Columns `col_start..col_end`.
+    pub col_start: i32,
+    pub col_end: i32,
+    pub from_row: i32,
+    pub to_row: i32,
+    /// Logical time since the drop; advanced by `TetrisCore::advance_effects`.
+    pub age_ms: u32,
+}
+
+/// Cells of the most recently locked piece, flashed briefly by the renderer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockFlash {
+    pub cells: Vec<Vec2i>,
+    pub age_ms: u32,
+}
+
+/// Fraction (0..1) of `duration_ms` that `age_ms` has used up.
+fn effect_progress(age_ms: u32, duration_ms: u32) -> f32 {
+    if duration_ms == 0 {
+        return 1.0;
+    }
+    (age_ms as f32 / duration_ms as f32).clamp(0.0, 1.0)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TetrisCore {
     board: Vec<Vec<u8>>,
+    /// Playfield columns; older recordings without the field are `BOARD_WIDTH` wide.
+    #[serde(default = "default_board_width")]
+    board_width: usize,
+    /// Visible playfield rows above any bottomwell.
+    #[serde(default = "default_board_height")]
+    board_height: usize,
     #[serde(default)]
     board_owner: Vec<Vec<Option<PieceId>>>,
     #[serde(default)]
@@ -244,6 +323,8 @@ pub struct TetrisCore {
     current_piece_pos: Vec2i,
     current_piece_rotation: u8,
     available_pieces: Vec<Piece>,
+    #[serde(default)]
+    randomizer: Randomizer,
     piece_bag: Vec<Piece>,
     #[serde(default)]
     background_seed: u64,
@@ -263,9 +344,29 @@ pub struct TetrisCore {
     grounded_total_lock_ms: u32,
     #[serde(default)]
     grounded_for_lock: bool,
+    #[serde(default = "default_lock_reset_limit")]
+    lock_reset_limit: u32,
+    /// Lock timer resets spent by the active piece; cleared when a new piece takes over.
+    #[serde(default)]
+    lock_resets: u32,
     #[serde(default)]
     line_clear_phase: LineClearPhase,
     last_kick_offset: Vec2i,
+    /// True while the active piece's most recent successful adjustment was a rotation.
+    #[serde(default)]
+    last_move_was_rotation: bool,
+    /// Whether the most recently locked piece locked as a T-spin (3-corner rule).
+    #[serde(default)]
+    last_lock_was_tspin: bool,
+    /// Consecutive line-clearing placements; 0 once a placement clears nothing.
+    #[serde(default)]
+    combo: u32,
+    /// The last line-clearing placement was a tetris or T-spin clear.
+    #[serde(default)]
+    back_to_back: bool,
+    /// Combo/B2B/T-spin score for the locked placement, paid when its rows are committed.
+    #[serde(default)]
+    pending_clear_bonus: u32,
     #[serde(default)]
     bottomwell_enabled: bool,
     #[serde(default = "default_bottomwell_rows")]
@@ -310,10 +411,25 @@ pub struct TetrisCore {
     broken_walls: HashSet<String>,
     #[serde(default)]
     glass_shatter_count: u32,
+    #[serde(default)]
+    stats: GameStats,
+    /// Visual-only; recorded with the state so replays fade the same way.
+    #[serde(default)]
+    last_hard_drop: Option<HardDropTrail>,
+    #[serde(default)]
+    lock_flash: Option<LockFlash>,
     #[serde(skip, default = "default_depth_wall_progress_path")]
     depth_wall_progress_path: PathBuf,
 }
 
+fn default_board_width() -> usize {
+    BOARD_WIDTH
+}
+
+fn default_board_height() -> usize {
+    BOARD_HEIGHT
+}
+
 fn default_bottomwell_rows() -> usize {
     DEFAULT_BOTTOMWELL_ROWS
 }
@@ -334,6 +450,10 @@ fn default_lock_delay_max_ms() -> u32 {
     LOCK_DELAY_MAX_MS_DEFAULT
 }
 
+fn default_lock_reset_limit() -> u32 {
+    LOCK_RESET_LIMIT_DEFAULT
+}
+
 fn default_line_clear_delay_ms() -> u32 {
     LINE_CLEAR_DELAY_MS_DEFAULT
 }
@@ -362,15 +482,18 @@ impl TetrisCore {
         Self {
             board: vec![vec![0; BOARD_WIDTH]; BOARD_HEIGHT],
             board_owner: vec![vec![None; BOARD_WIDTH]; BOARD_HEIGHT],
+            board_width: BOARD_WIDTH,
+            board_height: BOARD_HEIGHT,
             next_piece_id: 1,
             placed_piece_kind: HashMap::new(),
             current_piece: None,
             next_queue: Vec::new(),
             held_piece: None,
             can_hold: true,
-            current_piece_pos: Vec2i::new(4, BOARD_HEIGHT as i32),
+            current_piece_pos: Vec2i::new(spawn_x(BOARD_WIDTH), BOARD_HEIGHT as i32),
             current_piece_rotation: 0,
             available_pieces: vec![Piece::O],
+            randomizer: Randomizer::Weighted,
             piece_bag: Vec::new(),
             background_seed: seed,
             rng: Rng::new(seed),
@@ -383,8 +506,15 @@ impl TetrisCore {
             grounded_lock_ms: 0,
             grounded_total_lock_ms: 0,
             grounded_for_lock: false,
+            lock_reset_limit: LOCK_RESET_LIMIT_DEFAULT,
+            lock_resets: 0,
             line_clear_phase: LineClearPhase::Idle,
             last_kick_offset: Vec2i::ZERO,
+            last_move_was_rotation: false,
+            last_lock_was_tspin: false,
+            combo: 0,
+            back_to_back: false,
+            pending_clear_bonus: 0,
             bottomwell_enabled: false,
             bottomwell_rows: DEFAULT_BOTTOMWELL_ROWS,
             earth_depth: 0,
@@ -407,10 +537,32 @@ impl TetrisCore {
             depth_wall_multi_clear_bonus_percent: DEFAULT_DEPTH_WALL_MULTI_CLEAR_BONUS_PERCENT,
             broken_walls: HashSet::new(),
             glass_shatter_count: 0,
+            stats: GameStats::default(),
+            last_hard_drop: None,
+            lock_flash: None,
             depth_wall_progress_path: default_depth_wall_progress_path(),
         }
     }
 
+    /// Resize the playfield to `cols` x `rows` (each at least `MIN_BOARD_DIM`). Call before
+    /// `initialize_game`; the board is rebuilt empty.
+    pub fn set_board_size(&mut self, cols: usize, rows: usize) {
+        self.board_width = cols.max(MIN_BOARD_DIM);
+        self.board_height = rows.max(MIN_BOARD_DIM);
+        let height = self.effective_board_height();
+        self.board = vec![vec![0; self.board_width]; height];
+        self.board_owner = vec![vec![None; self.board_width]; height];
+        self.current_piece_pos = Vec2i::new(spawn_x(self.board_width), height as i32);
+    }
+
+    pub fn board_width(&self) -> usize {
+        self.board_width
+    }
+
+    pub fn board_height(&self) -> usize {
+        self.board_height
+    }
+
     pub fn set_bottomwell_enabled(&mut self, enabled: bool) {
         self.bottomwell_enabled = enabled;
         self.resize_board_to_effective_height();
@@ -439,11 +591,11 @@ impl TetrisCore {
 
     pub fn effective_board_height(&self) -> usize {
         if self.bottomwell_enabled {
-            BOARD_HEIGHT
+            self.board_height
                 .saturating_add(self.bottomwell_rows)
                 .saturating_add(self.deep_shaft_rows)
         } else {
-            BOARD_HEIGHT
+            self.board_height
         }
     }
 
@@ -529,6 +681,7 @@ impl TetrisCore {
         } else {
             self.available_pieces = pieces;
         }
+        self.piece_bag.clear();
     }
 
     fn initial_bottomwell_fill_rows(&self) -> usize {
@@ -545,23 +698,23 @@ impl TetrisCore {
             self.board_owner.truncate(target);
         } else {
             while self.board.len() < target {
-                self.board.push(vec![0; BOARD_WIDTH]);
-                self.board_owner.push(vec![None; BOARD_WIDTH]);
+                self.board.push(vec![0; self.board_width]);
+                self.board_owner.push(vec![None; self.board_width]);
             }
         }
-        self.current_piece_pos = Vec2i::new(4, self.board.len() as i32);
+        self.current_piece_pos = Vec2i::new(spawn_x(self.board_width), self.board.len() as i32);
     }
 
     pub fn initialize_game(&mut self) {
-        self.board = vec![vec![0; BOARD_WIDTH]; self.effective_board_height()];
-        self.board_owner = vec![vec![None; BOARD_WIDTH]; self.effective_board_height()];
+        self.board = vec![vec![0; self.board_width]; self.effective_board_height()];
+        self.board_owner = vec![vec![None; self.board_width]; self.effective_board_height()];
         self.next_piece_id = 1;
         self.placed_piece_kind.clear();
         self.current_piece = None;
         self.next_queue.clear();
         self.held_piece = None;
         self.can_hold = true;
-        self.current_piece_pos = Vec2i::new(4, self.board.len() as i32);
+        self.current_piece_pos = Vec2i::new(spawn_x(self.board_width), self.board.len() as i32);
         self.current_piece_rotation = 0;
         self.piece_bag.clear();
         self.lines_cleared = 0;
@@ -570,6 +723,11 @@ impl TetrisCore {
         self.clear_lock_delay_state();
         self.line_clear_phase = LineClearPhase::Idle;
         self.last_kick_offset = Vec2i::ZERO;
+        self.last_move_was_rotation = false;
+        self.last_lock_was_tspin = false;
+        self.combo = 0;
+        self.back_to_back = false;
+        self.pending_clear_bonus = 0;
         self.earth_depth = 0;
         self.ore_collected = 0;
         self.coins_collected = 0;
@@ -577,6 +735,9 @@ impl TetrisCore {
         self.active_wall_hp_remaining = 0;
         self.depth_progress_paused = false;
         self.glass_shatter_count = 0;
+        self.stats = GameStats::default();
+        self.last_hard_drop = None;
+        self.lock_flash = None;
         self.reload_depth_wall_progress();
 
         if self.bottomwell_enabled {
@@ -619,7 +780,7 @@ impl TetrisCore {
                 let board_y = self.current_piece_pos.y - gy as i32 + offset;
 
                 if board_x >= 0
-                    && board_x < BOARD_WIDTH as i32
+                    && board_x < self.board_width as i32
                     && board_y >= 0
                     && board_y < self.board.len() as i32
                 {
@@ -700,6 +861,17 @@ impl TetrisCore {
         self.background_seed
     }
 
+    /// Current piece RNG position. It travels with the core (and so with every recorded
+    /// `GameState`), which is what makes re-stepping from a rewound frame reproducible.
+    pub fn rng_state(&self) -> u64 {
+        self.rng.state
+    }
+
+    /// Restarts the piece RNG from `seed`; the background seed (world layout) is unchanged.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
     pub fn score(&self) -> u32 {
         self.score
     }
@@ -733,6 +905,18 @@ impl TetrisCore {
         self.lock_delay_max_ms = lock_delay_max_ms.max(self.lock_delay_ms);
     }
 
+    pub fn lock_reset_limit(&self) -> u32 {
+        self.lock_reset_limit
+    }
+
+    pub fn set_lock_reset_limit(&mut self, lock_reset_limit: u32) {
+        self.lock_reset_limit = lock_reset_limit;
+    }
+
+    pub fn lock_resets(&self) -> u32 {
+        self.lock_resets
+    }
+
     pub fn grounded_lock_ms(&self) -> u32 {
         self.grounded_lock_ms
     }
@@ -777,10 +961,67 @@ impl TetrisCore {
         self.score = self.score.saturating_add(bonus);
     }
 
+    /// Whether the most recently locked piece was a T-spin: a T locked right after a rotation
+    /// with three of its four centre-diagonal cells blocked. Holds until the next lock.
+    pub fn last_action_was_tspin(&self) -> bool {
+        self.last_lock_was_tspin
+    }
+
+    /// Consecutive placements that cleared at least one line (0 = no active combo).
+    pub fn combo(&self) -> u32 {
+        self.combo
+    }
+
+    /// Whether the last line clear was a tetris or T-spin, so the next one earns the B2B bonus.
+    pub fn back_to_back(&self) -> bool {
+        self.back_to_back
+    }
+
     pub fn is_game_over(&self) -> bool {
         self.game_over
     }
 
+    pub fn stats(&self) -> GameStats {
+        self.stats
+    }
+
+    /// Adds logical play time to `stats().play_time_ms`; ignored once the game is over.
+    pub fn add_play_time_ms(&mut self, dt_ms: u32) {
+        if self.game_over {
+            return;
+        }
+        self.stats.play_time_ms = self.stats.play_time_ms.saturating_add(u64::from(dt_ms));
+    }
+
+    /// The last hard drop while its trail is still fading, with its fade progress (0..1).
+    pub fn hard_drop_trail(&self) -> Option<(HardDropTrail, f32)> {
+        self.last_hard_drop
+            .map(|trail| (trail, effect_progress(trail.age_ms, HARD_DROP_TRAIL_MS)))
+    }
+
+    /// The last locked piece while it is still flashing, with its fade progress (0..1).
+    pub fn lock_flash(&self) -> Option<(&LockFlash, f32)> {
+        self.lock_flash
+            .as_ref()
+            .map(|flash| (flash, effect_progress(flash.age_ms, LOCK_FLASH_MS)))
+    }
+
+    /// Ages the hard-drop trail and lock flash by logical time, dropping them once faded.
+    pub fn advance_effects(&mut self, dt_ms: u32) {
+        if let Some(trail) = self.last_hard_drop.as_mut() {
+            trail.age_ms = trail.age_ms.saturating_add(dt_ms);
+            if trail.age_ms >= HARD_DROP_TRAIL_MS {
+                self.last_hard_drop = None;
+            }
+        }
+        if let Some(flash) = self.lock_flash.as_mut() {
+            flash.age_ms = flash.age_ms.saturating_add(dt_ms);
+            if flash.age_ms >= LOCK_FLASH_MS {
+                self.lock_flash = None;
+            }
+        }
+    }
+
     pub fn snapshot(&self) -> TetrisSnapshot {
         TetrisSnapshot {
             board: self.board.clone(),
@@ -806,12 +1047,60 @@ impl TetrisCore {
     }
 
     pub fn set_cell(&mut self, x: usize, y: usize, value: u8) {
-        if y < self.board.len() && x < BOARD_WIDTH {
+        if y < self.board.len() && x < self.board_width {
             self.board[y][x] = value;
             self.board_owner[y][x] = None;
         }
     }
 
+    /// Push `rows` garbage rows (solid except for `hole_col`) in from the bottom, shifting the
+    /// stack up. An active piece that now overlaps the stack is lifted by the same amount. Ends
+    /// the game and returns false if filled cells leave the top or the piece cannot be lifted.
+    pub fn receive_garbage(&mut self, rows: usize, hole_col: usize) -> bool {
+        if self.game_over {
+            return false;
+        }
+        if rows == 0 || self.board.is_empty() {
+            return true;
+        }
+
+        let hole_col = hole_col.min(self.board_width - 1);
+        let mut overflowed = false;
+        for _ in 0..rows {
+            let mut row = vec![CELL_GARBAGE; self.board_width];
+            row[hole_col] = CELL_EMPTY;
+            self.board.insert(0, row);
+            self.board_owner.insert(0, vec![None; self.board_width]);
+            if let Some(top) = self.board.pop() {
+                overflowed |= top.iter().any(|&cell| cell != CELL_EMPTY);
+            }
+            self.board_owner.pop();
+        }
+        self.cleanup_piece_owners();
+
+        if let LineClearPhase::Delay { rows: pending, .. } = &mut self.line_clear_phase {
+            let height = self.board.len();
+            pending.iter_mut().for_each(|y| *y += rows);
+            pending.retain(|&y| y < height);
+        }
+
+        if self.current_piece.is_some()
+            && !self.is_valid_position(self.current_piece_pos, self.current_piece_rotation)
+        {
+            let lifted = self.current_piece_pos + Vec2i::new(0, rows as i32);
+            if self.is_valid_position(lifted, self.current_piece_rotation) {
+                self.current_piece_pos = lifted;
+            } else {
+                overflowed = true;
+            }
+        }
+
+        if overflowed {
+            self.game_over = true;
+        }
+        !overflowed
+    }
+
     /// Advance board-level material simulation once per game turn.
     ///
     /// Each seed grows at most one dirt tile into moss per turn, with growth
@@ -823,7 +1112,7 @@ impl TetrisCore {
 
         let mut seeds = Vec::new();
         for y in 0..self.board.len() {
-            for x in 0..BOARD_WIDTH {
+            for x in 0..self.board_width {
                 if self.board[y][x] == CELL_MOSS_SEED {
                     seeds.push((x, y));
                 }
@@ -837,10 +1126,31 @@ impl TetrisCore {
         let _ = self.start_line_clear_phase_if_needed();
     }
 
+    pub fn set_randomizer(&mut self, randomizer: Randomizer) {
+        self.randomizer = randomizer;
+        self.piece_bag.clear();
+    }
+
+    pub fn randomizer(&self) -> Randomizer {
+        self.randomizer
+    }
+
     pub fn draw_piece(&mut self) -> Piece {
         if self.available_pieces.is_empty() {
             self.available_pieces = vec![Piece::O];
         }
+        match self.randomizer {
+            Randomizer::Weighted => self.draw_weighted_piece(),
+            Randomizer::Uniform => {
+                let index = self.rng.next_u32() as usize % self.available_pieces.len();
+                self.available_pieces[index]
+            }
+            Randomizer::SevenBag => self.draw_from_bag(1),
+            Randomizer::MultiBag(copies) => self.draw_from_bag(copies.max(1) as usize),
+        }
+    }
+
+    fn draw_weighted_piece(&mut self) -> Piece {
         let total_weight = self
             .available_pieces
             .iter()
@@ -858,6 +1168,21 @@ impl TetrisCore {
         Piece::O
     }
 
+    /// Pop from the bag, refilling it with `copies` of each available piece (Fisher-Yates
+    /// shuffled) once empty. The bag is consumed from the back.
+    fn draw_from_bag(&mut self, copies: usize) -> Piece {
+        if self.piece_bag.is_empty() {
+            for _ in 0..copies {
+                self.piece_bag.extend_from_slice(&self.available_pieces);
+            }
+            for i in (1..self.piece_bag.len()).rev() {
+                let j = self.rng.next_u32() as usize % (i + 1);
+                self.piece_bag.swap(i, j);
+            }
+        }
+        self.piece_bag.pop().unwrap_or(Piece::O)
+    }
+
     fn fill_next_queue(&mut self) {
         while self.next_queue.len() < NEXT_QUEUE_LEN {
             let piece = self.draw_piece();
@@ -873,13 +1198,14 @@ impl TetrisCore {
             self.next_queue.remove(0)
         };
         self.current_piece = Some(piece);
-        self.current_piece_pos = Vec2i::new(4, self.board.len() as i32);
+        self.current_piece_pos = Vec2i::new(spawn_x(self.board_width), self.board.len() as i32);
         self.current_piece_rotation = 0;
         self.clear_lock_delay_state();
         self.line_clear_phase = LineClearPhase::Idle;
         self.fill_next_queue();
         self.can_hold = true;
         self.last_kick_offset = Vec2i::ZERO;
+        self.last_move_was_rotation = false;
 
         if !self.is_valid_position(self.current_piece_pos, self.current_piece_rotation) {
             self.game_over = true;
@@ -901,11 +1227,12 @@ impl TetrisCore {
         if let Some(held) = self.held_piece {
             self.held_piece = Some(current);
             self.current_piece = Some(held);
-            self.current_piece_pos = Vec2i::new(4, self.board.len() as i32);
+            self.current_piece_pos = Vec2i::new(spawn_x(self.board_width), self.board.len() as i32);
             self.current_piece_rotation = 0;
             self.clear_lock_delay_state();
             self.last_kick_offset = Vec2i::ZERO;
             self.can_hold = false;
+            self.stats.holds_used = self.stats.holds_used.saturating_add(1);
 
             if !self.is_valid_position(self.current_piece_pos, self.current_piece_rotation) {
                 self.game_over = true;
@@ -917,6 +1244,7 @@ impl TetrisCore {
 
         // Empty hold: store the current piece and consume the next piece from the queue.
         self.held_piece = Some(current);
+        self.stats.holds_used = self.stats.holds_used.saturating_add(1);
         let ok = self.spawn_new_piece();
         self.can_hold = false;
         ok
@@ -939,7 +1267,7 @@ impl TetrisCore {
                 let board_x = pos.x + gx as i32 - offset;
                 let board_y = pos.y - gy as i32 + offset;
 
-                if board_x < 0 || board_x >= BOARD_WIDTH as i32 {
+                if board_x < 0 || board_x >= self.board_width as i32 {
                     return false;
                 }
                 if board_y < 0 {
@@ -965,6 +1293,9 @@ impl TetrisCore {
         let new_pos = self.current_piece_pos + dir;
         if self.is_valid_position(new_pos, self.current_piece_rotation) {
             self.current_piece_pos = new_pos;
+            if new_pos != old_pos {
+                self.last_move_was_rotation = false;
+            }
             self.handle_successful_adjustment(new_pos != old_pos);
             return true;
         }
@@ -1028,6 +1359,7 @@ impl TetrisCore {
         let new_rotation = dir.apply(self.current_piece_rotation, piece_rotation_states(piece));
         if self.try_rotation_with_kicks(new_rotation) {
             self.current_piece_rotation = new_rotation;
+            self.last_move_was_rotation = true;
             let after_cells =
                 Self::occupied_cells(piece, self.current_piece_pos, self.current_piece_rotation);
             self.handle_successful_adjustment(after_cells != before_cells);
@@ -1037,9 +1369,14 @@ impl TetrisCore {
     }
 
     pub fn hard_drop(&mut self) -> i32 {
-        if self.is_line_clear_active() || self.current_piece.is_none() {
+        let Some(piece) = self.current_piece else {
+            return 0;
+        };
+        if self.is_line_clear_active() {
             return 0;
         }
+        let start_cells =
+            Self::occupied_cells(piece, self.current_piece_pos, self.current_piece_rotation);
 
         let mut drop_distance = 0u32;
         loop {
@@ -1047,6 +1384,7 @@ impl TetrisCore {
             if self.is_valid_position(next_pos, self.current_piece_rotation) {
                 self.current_piece_pos = next_pos;
                 drop_distance = drop_distance.saturating_add(1);
+                self.last_move_was_rotation = false;
                 continue;
             }
             if self.apply_hard_drop_impact(next_pos) {
@@ -1059,6 +1397,17 @@ impl TetrisCore {
             .score
             .saturating_add(drop_distance.saturating_mul(HARD_DROP_POINTS_PER_ROW));
 
+        let end_cells =
+            Self::occupied_cells(piece, self.current_piece_pos, self.current_piece_rotation);
+        let lowest = |cells: &[(i32, i32)]| cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        self.last_hard_drop = Some(HardDropTrail {
+            col_start: start_cells.iter().map(|&(x, _)| x).min().unwrap_or(0),
+            col_end: start_cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0),
+            from_row: lowest(&start_cells),
+            to_row: lowest(&end_cells),
+            age_ms: 0,
+        });
+
         self.clear_lock_delay_state();
         self.lock_active_piece();
         drop_distance as i32
@@ -1101,7 +1450,7 @@ impl TetrisCore {
         let target_x = next_pos.x + tip_gx as i32 - offset;
         let target_y = next_pos.y - tip_gy as i32 + offset;
         if target_x < 0
-            || target_x >= BOARD_WIDTH as i32
+            || target_x >= self.board_width as i32
             || target_y < 0
             || target_y >= self.board.len() as i32
         {
@@ -1137,7 +1486,7 @@ impl TetrisCore {
                 let target_x = next_pos.x + gx as i32 - offset;
                 let target_y = next_pos.y - gy as i32 + offset;
                 if target_x < 0
-                    || target_x >= BOARD_WIDTH as i32
+                    || target_x >= self.board_width as i32
                     || target_y < 0
                     || target_y >= self.board.len() as i32
                 {
@@ -1181,7 +1530,7 @@ impl TetrisCore {
         let mut crushed_any = false;
 
         loop {
-            if x < 0 || x >= BOARD_WIDTH as i32 || y < 0 || y >= self.board.len() as i32 {
+            if x < 0 || x >= self.board_width as i32 || y < 0 || y >= self.board.len() as i32 {
                 break;
             }
             let tx = x as usize;
@@ -1240,7 +1589,7 @@ impl TetrisCore {
     }
 
     fn apply_tile_damage(&mut self, x: usize, y: usize, damage: u8) -> bool {
-        if damage == 0 || y >= self.board.len() || x >= BOARD_WIDTH {
+        if damage == 0 || y >= self.board.len() || x >= self.board_width {
             return false;
         }
         let cell = self.board[y][x];
@@ -1280,11 +1629,11 @@ impl TetrisCore {
     fn grow_moss_from_seed(&mut self, seed_x: usize, seed_y: usize) -> bool {
         const MAX_MOSS_BFS_DISTANCE: usize = 3;
 
-        if seed_y >= self.board.len() || seed_x >= BOARD_WIDTH {
+        if seed_y >= self.board.len() || seed_x >= self.board_width {
             return false;
         }
 
-        let mut visited = vec![vec![false; BOARD_WIDTH]; self.board.len()];
+        let mut visited = vec![vec![false; self.board_width]; self.board.len()];
         let mut queue = VecDeque::new();
         visited[seed_y][seed_x] = true;
         queue.push_back((seed_x, seed_y, 0usize));
@@ -1302,7 +1651,7 @@ impl TetrisCore {
             // Stable neighbor ordering keeps growth deterministic.
             let neighbors = [
                 (x.wrapping_sub(1), y, x > 0),
-                (x + 1, y, x + 1 < BOARD_WIDTH),
+                (x + 1, y, x + 1 < self.board_width),
                 (x, y.wrapping_sub(1), y > 0),
                 (x, y + 1, y + 1 < self.board.len()),
             ];
@@ -1329,7 +1678,7 @@ impl TetrisCore {
 
         let mut moved = false;
         for y in 1..self.board.len() {
-            for x in 0..BOARD_WIDTH {
+            for x in 0..self.board_width {
                 if self.board[y][x] != CELL_SAND {
                     continue;
                 }
@@ -1353,7 +1702,7 @@ impl TetrisCore {
 
     fn shatter_piece(&mut self, piece_id: PieceId) {
         for y in 0..self.board.len() {
-            for x in 0..BOARD_WIDTH {
+            for x in 0..self.board_width {
                 if self.board_owner[y][x] == Some(piece_id) {
                     self.board[y][x] = CELL_EMPTY;
                     self.board_owner[y][x] = None;
@@ -1365,7 +1714,7 @@ impl TetrisCore {
     }
 
     fn clear_board_cell(&mut self, x: usize, y: usize) {
-        if y >= self.board.len() || x >= BOARD_WIDTH {
+        if y >= self.board.len() || x >= self.board_width {
             return;
         }
         let owner = self.board_owner[y][x];
@@ -1398,15 +1747,16 @@ impl TetrisCore {
 
     // ── Bottomwell helpers ──────────────────────────────────────────
 
-    /// Generate a deterministic earth row for the given depth.
+    /// Generate a deterministic earth row of `width` cells for the given depth.
     /// Uses `background_seed` + `depth` to seed a local RNG so the
     /// sequence is reproducible across runs with the same seed.
-    pub fn generate_earth_row(seed: u64, depth: u64) -> Vec<u8> {
-        Self::generate_earth_row_with_tuning(seed, depth, 0, 0, 0, 0)
+    pub fn generate_earth_row(width: usize, seed: u64, depth: u64) -> Vec<u8> {
+        Self::generate_earth_row_with_tuning(width, seed, depth, 0, 0, 0, 0)
     }
 
     fn generate_earth_row_for_depth(&self, depth: u64) -> Vec<u8> {
         Self::generate_earth_row_with_tuning(
+            self.board_width,
             self.background_seed,
             depth,
             self.ore_weight_points,
@@ -1417,6 +1767,7 @@ impl TetrisCore {
     }
 
     fn generate_earth_row_with_tuning(
+        width: usize,
         seed: u64,
         depth: u64,
         ore_weight_points: u32,
@@ -1430,17 +1781,17 @@ impl TetrisCore {
         let mut rng = Rng::new(if mixed == 0 { 1 } else { mixed });
 
         // First-biome earth should match the Dirt piece material/appearance.
-        let mut row = vec![CELL_DIRT; BOARD_WIDTH];
+        let mut row = vec![CELL_DIRT; width];
 
         // 1) base holes
-        let mut hole_positions = vec![rng.next_u32() as usize % BOARD_WIDTH];
+        let mut hole_positions = vec![rng.next_u32() as usize % width];
         let second_hole_roll = rng.next_u32() % 100;
         let wants_second_hole =
             (hole_patch_chance_bp > 0 || hole_align_chance_bp > 0) && second_hole_roll < 35;
         if wants_second_hole {
-            let mut second = rng.next_u32() as usize % BOARD_WIDTH;
+            let mut second = rng.next_u32() as usize % width;
             if second == hole_positions[0] {
-                second = (second + 1) % BOARD_WIDTH;
+                second = (second + 1) % width;
             }
             hole_positions.push(second);
         }
@@ -1459,7 +1810,7 @@ impl TetrisCore {
                 .wrapping_mul(0x5851_F42D_4C95_7F2D)
                 .wrapping_add((depth - 1).wrapping_mul(0x14057B7EF767814F));
             let mut prev_rng = Rng::new(if prev_mixed == 0 { 1 } else { prev_mixed });
-            let prev_col = prev_rng.next_u32() as usize % BOARD_WIDTH;
+            let prev_col = prev_rng.next_u32() as usize % width;
             hole_positions[0] = prev_col;
             if hole_positions.len() > 1 && hole_positions[1] == prev_col {
                 hole_positions.remove(1);
@@ -1493,7 +1844,7 @@ impl TetrisCore {
             ore_threshold = 60u32.saturating_sub(coin_threshold.min(60));
         }
 
-        for x in 0..BOARD_WIDTH {
+        for x in 0..width {
             if row[x] == CELL_EMPTY {
                 continue;
             }
@@ -1515,22 +1866,22 @@ impl TetrisCore {
         let count = self.initial_bottomwell_fill_rows().min(self.board.len());
         for i in 0..count {
             let row = if i + 1 == count {
-                Self::generate_grass_surface_row(self.background_seed)
+                Self::generate_grass_surface_row(self.board_width, self.background_seed)
             } else {
                 self.generate_earth_row_for_depth(self.earth_depth)
             };
             self.board[i] = row;
-            self.board_owner[i] = vec![None; BOARD_WIDTH];
+            self.board_owner[i] = vec![None; self.board_width];
             self.earth_depth += 1;
         }
     }
 
     /// Generate the initial top bottomwell row as grass with one hole.
-    fn generate_grass_surface_row(seed: u64) -> Vec<u8> {
+    fn generate_grass_surface_row(width: usize, seed: u64) -> Vec<u8> {
         let mixed = seed ^ 0x9E37_79B9_7F4A_7C15;
         let mut rng = Rng::new(if mixed == 0 { 1 } else { mixed });
-        let mut row = vec![CELL_GRASS; BOARD_WIDTH];
-        let hole_pos = rng.next_u32() as usize % BOARD_WIDTH;
+        let mut row = vec![CELL_GRASS; width];
+        let hole_pos = rng.next_u32() as usize % width;
         row[hole_pos] = CELL_EMPTY;
         row
     }
@@ -1547,7 +1898,7 @@ impl TetrisCore {
             self.earth_depth += 1;
             // Insert at the bottom of the board.
             self.board.insert(0, row);
-            self.board_owner.insert(0, vec![None; BOARD_WIDTH]);
+            self.board_owner.insert(0, vec![None; self.board_width]);
             // Remove from the top to keep height constant.
             if self.board.len() > self.effective_board_height() {
                 self.board.pop();
@@ -1632,7 +1983,7 @@ impl TetrisCore {
             let all_empty = self.board[y].iter().all(|&c| c == CELL_EMPTY);
             if all_empty {
                 self.board[y] = self.generate_earth_row_for_depth(self.earth_depth);
-                self.board_owner[y] = vec![None; BOARD_WIDTH];
+                self.board_owner[y] = vec![None; self.board_width];
                 self.earth_depth += 1;
             }
         }
@@ -1702,7 +2053,7 @@ impl TetrisCore {
                 let board_y = self.current_piece_pos.y - gy as i32 + offset;
 
                 if board_x >= 0
-                    && board_x < BOARD_WIDTH as i32
+                    && board_x < self.board_width as i32
                     && board_y >= 0
                     && board_y < self.board.len() as i32
                 {
@@ -1714,6 +2065,11 @@ impl TetrisCore {
     }
 
     fn clear_lock_delay_state(&mut self) {
+        self.clear_lock_timers();
+        self.lock_resets = 0;
+    }
+
+    fn clear_lock_timers(&mut self) {
         self.grounded_lock_ms = 0;
         self.grounded_total_lock_ms = 0;
         self.grounded_for_lock = false;
@@ -1725,12 +2081,42 @@ impl TetrisCore {
         }
 
         if self.grounded_for_lock && self.is_active_piece_grounded() {
+            // Out of resets: the timer keeps running so the piece locks on schedule.
+            if self.lock_resets >= self.lock_reset_limit {
+                return;
+            }
+            self.lock_resets += 1;
             self.grounded_lock_ms = 0;
             self.grounded_for_lock = false;
             return;
         }
 
-        self.clear_lock_delay_state();
+        // Leaving the ground restarts the timers but not the reset budget.
+        self.clear_lock_timers();
+    }
+
+    /// 3-corner rule: a T whose last move was a rotation, with at least three of the four
+    /// cells diagonal to its centre blocked (walls and floor count as blocked).
+    fn active_piece_is_tspin(&self) -> bool {
+        if self.current_piece != Some(Piece::T) || !self.last_move_was_rotation {
+            return false;
+        }
+        // The T's 3x3 grid is centred on `current_piece_pos`.
+        let center = self.current_piece_pos;
+        let blocked = [(-1, -1), (1, -1), (-1, 1), (1, 1)]
+            .into_iter()
+            .filter(|(dx, dy)| {
+                let x = center.x + dx;
+                let y = center.y + dy;
+                if x < 0 || x >= self.board_width as i32 || y < 0 {
+                    return true;
+                }
+                self.board
+                    .get(y as usize)
+                    .is_some_and(|row| row[x as usize] != 0)
+            })
+            .count();
+        blocked >= 3
     }
 
     fn is_active_piece_grounded(&self) -> bool {
@@ -1804,7 +2190,18 @@ impl TetrisCore {
     }
 
     fn lock_active_piece(&mut self) {
+        self.lock_flash = self.current_piece.map(|piece| LockFlash {
+            cells: Self::occupied_cells(piece, self.current_piece_pos, self.current_piece_rotation)
+                .into_iter()
+                .map(|(x, y)| Vec2i::new(x, y))
+                .collect(),
+            age_ms: 0,
+        });
+        self.last_lock_was_tspin = self.active_piece_is_tspin();
         self.place_piece();
+        self.update_combo_for_placement();
+        self.stats.pieces_placed = self.stats.pieces_placed.saturating_add(1);
+        self.stats.max_combo = self.stats.max_combo.max(self.combo);
         self.clear_lock_delay_state();
         if !self.start_line_clear_phase_if_needed() {
             self.spawn_new_piece();
@@ -1843,6 +2240,36 @@ impl TetrisCore {
         true
     }
 
+    /// Advances the combo/back-to-back state for the piece that just locked and queues the
+    /// score it earns on top of the flat per-line points.
+    fn update_combo_for_placement(&mut self) {
+        let lines = self.detect_full_rows().len() as u32;
+        if lines == 0 {
+            self.combo = 0;
+            return;
+        }
+        self.combo = self.combo.saturating_add(1);
+
+        let tspin = self.last_lock_was_tspin;
+        let base = if tspin {
+            tspin_line_clear_points(lines)
+        } else {
+            line_clear_points(lines)
+        };
+        let difficult = tspin || lines >= 4;
+        let b2b_bonus = if difficult && self.back_to_back {
+            base / 2
+        } else {
+            0
+        };
+        self.back_to_back = difficult;
+
+        self.pending_clear_bonus = base
+            .saturating_sub(line_clear_points(lines))
+            .saturating_add(b2b_bonus)
+            .saturating_add(COMBO_POINTS_PER_STEP.saturating_mul(self.combo - 1));
+    }
+
     fn start_line_clear_phase_if_needed(&mut self) -> bool {
         let lines_to_clear = self.detect_full_rows();
         if lines_to_clear.is_empty() {
@@ -1881,14 +2308,17 @@ impl TetrisCore {
         for line_y in &lines_to_clear {
             self.board.remove(*line_y);
             self.board_owner.remove(*line_y);
-            self.board.push(vec![0; BOARD_WIDTH]);
-            self.board_owner.push(vec![None; BOARD_WIDTH]);
+            self.board.push(vec![0; self.board_width]);
+            self.board_owner.push(vec![None; self.board_width]);
         }
         self.cleanup_piece_owners();
 
         let cleared = lines_to_clear.len() as u32;
         self.lines_cleared = self.lines_cleared.saturating_add(cleared);
         self.score = self.score.saturating_add(line_clear_points(cleared));
+        self.score = self
+            .score
+            .saturating_add(std::mem::take(&mut self.pending_clear_bonus));
 
         // Bottomwell: collect rewards from cleared rows, then only advance
         // depth for clears that actually include bottomwell earth cells.
@@ -1917,11 +2347,11 @@ impl TetrisCore {
     }
 
     fn try_rotation_with_kicks(&mut self, new_rotation: u8) -> bool {
-        if self.current_piece.is_none() {
+        let Some(piece) = self.current_piece else {
             return false;
-        }
+        };
 
-        for (dx, dy) in &GENERIC_KICK_OFFSETS {
+        for (dx, dy) in kick_offsets(piece, self.current_piece_rotation, new_rotation) {
             let test_pos = self.current_piece_pos + Vec2i::new(*dx, *dy);
             if self.is_valid_position(test_pos, new_rotation) {
                 self.last_kick_offset = Vec2i::new(*dx, *dy);
@@ -1935,6 +2365,24 @@ impl TetrisCore {
     }
 }
 
+/// Extra points per combo step after the first clearing placement (guideline 50 x combo).
+const COMBO_POINTS_PER_STEP: u32 = 50;
+
+/// Spawn column for a `width`-wide board: 4 on the standard 10-wide field.
+fn spawn_x(width: usize) -> i32 {
+    (width / 2) as i32 - 1
+}
+
+/// Guideline T-spin clear points (single/double/triple); replaces `line_clear_points`.
+fn tspin_line_clear_points(lines: u32) -> u32 {
+    match lines {
+        0 => 0,
+        1 => 800,
+        2 => 1200,
+        _ => 1600,
+    }
+}
+
 fn line_clear_points(lines: u32) -> u32 {
     // Minimal, deterministic scoring:
     // - 1/2/3/4 line clears: 100/300/500/800
@@ -2011,9 +2459,33 @@ impl Rng {
 
 }
 
+/// Fallback kicks for pieces without an SRS table (O, the 2-state minis, moss seed).
 const GENERIC_KICK_OFFSETS: [(i32, i32); 7] =
     [(0, 0), (-1, 0), (1, 0), (0, 1), (-2, 0), (2, 0), (0, 2)];
 
+/// Kick offsets to try, in order, when rotating `piece` from `from` to `to` (+y is up).
+///
+/// T/J/L/Z use the SRS JLSTZ table, I uses the SRS I table (its two states map to 0 and R), and
+/// 180s use the common SRS+ half-turn table. Everything else falls back to the generic kicks.
+fn kick_offsets(piece: Piece, from: u8, to: u8) -> &'static [(i32, i32)] {
+    match (piece, from, to) {
+        (Piece::T | Piece::J | Piece::L | Piece::Z, _, _) => match (from, to) {
+            (0, 1) | (2, 1) => &[(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+            (1, 0) | (1, 2) => &[(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+            (2, 3) | (0, 3) => &[(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+            (3, 2) | (3, 0) => &[(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+            (0, 2) => &[(0, 0), (0, 1), (1, 1), (-1, 1), (1, 0), (-1, 0)],
+            (2, 0) => &[(0, 0), (0, -1), (-1, -1), (1, -1), (-1, 0), (1, 0)],
+            (1, 3) => &[(0, 0), (1, 0), (1, 2), (1, 1), (0, 2), (0, 1)],
+            (3, 1) => &[(0, 0), (-1, 0), (-1, 2), (-1, 1), (0, 2), (0, 1)],
+            _ => &[(0, 0)],
+        },
+        (Piece::I, 0, 1) => &[(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+        (Piece::I, 1, 0) => &[(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+        _ => &GENERIC_KICK_OFFSETS,
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct PieceGrid {
     size: usize,
@@ -2365,17 +2837,17 @@ mod bottomwell_tests {
 
     #[test]
     fn generation_is_deterministic_by_seed_depth() {
-        let row_a = TetrisCore::generate_earth_row(42, 0);
-        let row_b = TetrisCore::generate_earth_row(42, 0);
+        let row_a = TetrisCore::generate_earth_row(BOARD_WIDTH, 42, 0);
+        let row_b = TetrisCore::generate_earth_row(BOARD_WIDTH, 42, 0);
         assert_eq!(row_a, row_b, "same seed+depth must produce identical rows");
 
-        let row_c = TetrisCore::generate_earth_row(42, 1);
+        let row_c = TetrisCore::generate_earth_row(BOARD_WIDTH, 42, 1);
         assert_ne!(
             row_a, row_c,
             "different depths should (usually) produce different rows"
         );
 
-        let row_d = TetrisCore::generate_earth_row(99, 0);
+        let row_d = TetrisCore::generate_earth_row(BOARD_WIDTH, 99, 0);
         assert_ne!(
             row_a, row_d,
             "different seeds should (usually) produce different rows"
@@ -2589,8 +3061,10 @@ mod bottomwell_tests {
         let mut mod_ore = 0u32;
         let mut mod_coin = 0u32;
         for depth in 20..120 {
-            let base = TetrisCore::generate_earth_row_with_tuning(42, depth, 0, 0, 0, 0);
-            let tuned = TetrisCore::generate_earth_row_with_tuning(42, depth, 6, 3, 0, 0);
+            let base =
+                TetrisCore::generate_earth_row_with_tuning(BOARD_WIDTH, 42, depth, 0, 0, 0, 0);
+            let tuned =
+                TetrisCore::generate_earth_row_with_tuning(BOARD_WIDTH, 42, depth, 6, 3, 0, 0);
             for cell in base {
                 if cell == CELL_ORE {
                     base_ore += 1;
@@ -2612,14 +3086,19 @@ mod bottomwell_tests {
 
     #[test]
     fn generate_earth_row_has_correct_width() {
-        let row = TetrisCore::generate_earth_row(42, 0);
+        let row = TetrisCore::generate_earth_row(BOARD_WIDTH, 42, 0);
         assert_eq!(row.len(), BOARD_WIDTH);
+
+        let mut core = TetrisCore::new(42);
+        core.set_board_size(6, 12);
+        assert_eq!(core.generate_earth_row_for_depth(0).len(), 6);
+        assert_eq!(TetrisCore::generate_earth_row(6, 42, 3).len(), 6);
     }
 
     #[test]
     fn generate_earth_row_contains_only_valid_cell_types() {
         for depth in 0..50 {
-            let row = TetrisCore::generate_earth_row(42, depth);
+            let row = TetrisCore::generate_earth_row(BOARD_WIDTH, 42, depth);
             for &cell in &row {
                 assert!(
                     cell == CELL_EMPTY
@@ -2637,7 +3116,7 @@ mod bottomwell_tests {
     #[test]
     fn generate_earth_row_does_not_emit_grass_cells() {
         for depth in 0..50 {
-            let row = TetrisCore::generate_earth_row(42, depth);
+            let row = TetrisCore::generate_earth_row(BOARD_WIDTH, 42, depth);
             assert!(
                 row.iter().all(|&cell| cell != CELL_GRASS),
                 "earth generation should not include grass at depth {depth}"
@@ -3,32 +3,35 @@ use std::collections::{BinaryHeap, HashMap, HashSet};
 
 use engine::graphics::Renderer2d;
 use engine::render::{
-    CELL_SIZE, clip_rect_to_viewport, color_for_cell, draw_board_cells_in_rect_clipped_with_owners,
+    CELL_SIZE, clip_rect_to_viewport, color_for_cell,
+    draw_board_cells_in_rect_clipped_with_owners_and_palette,
 };
 use engine::ui;
 use engine::ui_tree::UiTree;
 
 use crate::background::draw_tile_background_in_viewport;
 use crate::skilltree::{
-    NodeState, SkillTreeDef, SkillTreeEditorTool, SkillTreeProgress, SkillTreeRuntime,
+    NodeState, SKILLTREE_CAMERA_BOUNDS_PAD_CELLS, SkillTreeAlignmentGuide, SkillTreeDef,
+    SkillTreeEditorTool, SkillTreeProgress, SkillTreeRuntime, SkillTreeWorldBounds,
     skilltree_world_bounds,
 };
 use crate::tetris_core::{Piece, TetrisCore, Vec2i, piece_board_offset, piece_grid, piece_type};
 use crate::ui_ids::*;
 
 mod menus;
+mod theme;
 pub use menus::{
     GameOverMenuLayout, GameOverMenuView, MainMenuLayout, MainMenuView, PauseMenuLayout,
     PauseMenuView, SettingsMenuLayout, SettingsMenuView, draw_game_over_menu,
-    draw_game_over_menu_with_ui, draw_main_menu, draw_main_menu_with_ui, draw_pause_menu,
-    draw_pause_menu_with_ui, draw_settings_menu, draw_settings_menu_with_ui,
+    draw_game_over_menu_with_ui, draw_game_over_menu_with_ui_and_theme, draw_main_menu,
+    draw_main_menu_with_ui, draw_main_menu_with_ui_and_theme, draw_pause_menu,
+    draw_pause_menu_with_ui, draw_pause_menu_with_ui_and_restart_confirm,
+    draw_pause_menu_with_ui_restart_confirm_and_theme, draw_settings_menu,
+    draw_settings_menu_with_ui, draw_settings_menu_with_ui_and_theme, pause_menu_layout,
 };
+pub use theme::Theme;
 
-const COLOR_PANEL_BG: [u8; 4] = [16, 16, 22, 255];
-const COLOR_PANEL_BORDER: [u8; 4] = [40, 40, 55, 255];
-const COLOR_PANEL_BORDER_DISABLED: [u8; 4] = [28, 28, 38, 255];
 const BUTTON_HOVER_BRIGHTEN: f32 = 0.12;
-const COLOR_SKILLTREE_LINK: [u8; 4] = [110, 110, 150, 255];
 const SKILLTREE_LINK_THICKNESS: u32 = 2;
 const SKILLTREE_ARROW_CAP_LENGTH: i32 = 8;
 const SKILLTREE_ARROW_CAP_SPREAD: i32 = 4;
@@ -36,23 +39,14 @@ const SKILLTREE_ROUTE_BOUNDS_PAD_CELLS: i32 = 8;
 const SKILLTREE_ROUTE_STEP_COST: i32 = 10;
 const SKILLTREE_ROUTE_TURN_PENALTY: i32 = 8;
 const SKILLTREE_ROUTE_OVERLAP_PENALTY: i32 = 2;
-const COLOR_SKILLTREE_CURSOR: [u8; 4] = [255, 220, 120, 255];
 
 pub const MAIN_MENU_TITLE: &str = "UNTITLED";
 
 const PAUSE_BUTTON_SIZE: u32 = 44;
 const PAUSE_BUTTON_MARGIN: u32 = 12;
-const COLOR_PAUSE_ICON: [u8; 4] = [235, 235, 245, 255];
-const COLOR_DEPTH_LOCKED: [u8; 4] = [255, 188, 112, 255];
-const COLOR_DEPTH_WALL_FILL: [u8; 4] = [24, 20, 16, 255];
-const COLOR_DEPTH_WALL_BORDER: [u8; 4] = [120, 92, 62, 255];
 const DEPTH_WALL_OVERLAY_ROWS: u32 = 2;
 
-const COLOR_PAUSE_MENU_TEXT: [u8; 4] = [235, 235, 245, 255];
-const COLOR_PAUSE_MENU_DIM: [u8; 4] = [0, 0, 0, 255];
 const PAUSE_MENU_DIM_ALPHA: u8 = 170;
-const COLOR_PAUSE_MENU_BG: [u8; 4] = [10, 10, 14, 255];
-const COLOR_PAUSE_MENU_BORDER: [u8; 4] = [40, 40, 55, 255];
 
 const PANEL_MARGIN: u32 = 16;
 const PANEL_PADDING: u32 = 12;
@@ -63,8 +57,6 @@ const PREVIEW_SIZE: u32 = PREVIEW_GRID * PREVIEW_CELL;
 const PREVIEW_GAP_Y: u32 = 10;
 
 const GHOST_ALPHA: u8 = 80;
-const LINE_CLEAR_FLASH_COLOR: [u8; 4] = [255, 255, 255, 255];
-const COLOR_TIP_MARKER: [u8; 4] = [245, 235, 170, 255];
 
 pub type Rect = ui::Rect;
 
@@ -95,6 +87,89 @@ pub struct SkillTreeLayout {
     pub grid_rows: u32,
     pub grid_cam_min_x: i32,
     pub grid_cam_min_y: i32,
+
+    /// Overview panel in the grid's bottom-right corner (`None` when there are no nodes).
+    pub minimap: Option<SkillTreeMinimap>,
+}
+
+const SKILLTREE_MINIMAP_MAX_W: u32 = 168;
+const SKILLTREE_MINIMAP_MAX_H: u32 = 120;
+
+/// Overview of the whole skilltree: node bounds plus the camera pad, scaled uniformly into
+/// `rect`. World y increases upward, screen y downward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillTreeMinimap {
+    pub rect: Rect,
+    /// World cell at the panel's bottom-left corner.
+    pub world_min: Vec2i,
+    /// World cells covered (always at least 1x1).
+    pub world_size: Vec2i,
+}
+
+impl SkillTreeMinimap {
+    /// Fits `bounds` (padded like the camera clamp) into the bottom-right corner of `area`.
+    pub fn fit(bounds: SkillTreeWorldBounds, area: Rect) -> Option<Self> {
+        let pad = SKILLTREE_CAMERA_BOUNDS_PAD_CELLS.ceil() as i32;
+        let world_min = Vec2i::new(bounds.min.x - pad, bounds.min.y - pad);
+        let world_size = Vec2i::new(
+            (bounds.max.x + 1 + pad - world_min.x).max(1),
+            (bounds.max.y + 1 + pad - world_min.y).max(1),
+        );
+        let px_per_cell = (SKILLTREE_MINIMAP_MAX_W as f32 / world_size.x as f32)
+            .min(SKILLTREE_MINIMAP_MAX_H as f32 / world_size.y as f32);
+        let w = ((world_size.x as f32 * px_per_cell).round() as u32).min(area.w);
+        let h = ((world_size.y as f32 * px_per_cell).round() as u32).min(area.h);
+        if w == 0 || h == 0 {
+            return None;
+        }
+        let rect = Rect::new(
+            area.x.saturating_add(area.w - w),
+            area.y.saturating_add(area.h - h),
+            w,
+            h,
+        );
+        Some(Self {
+            rect,
+            world_min,
+            world_size,
+        })
+    }
+
+    fn px_per_cell(&self) -> f32 {
+        (self.rect.w as f32 / self.world_size.x as f32)
+            .min(self.rect.h as f32 / self.world_size.y as f32)
+    }
+
+    /// Screen rect for the world-cell span `[min, max_exclusive)`, clipped to the panel.
+    pub fn screen_rect(&self, min: Vec2i, max_exclusive: Vec2i) -> Option<Rect> {
+        let scale = self.px_per_cell();
+        let panel_right = self.rect.x.saturating_add(self.rect.w) as f32;
+        let panel_bottom = self.rect.y.saturating_add(self.rect.h) as f32;
+        let left = self.rect.x as f32 + (min.x - self.world_min.x) as f32 * scale;
+        let right = self.rect.x as f32 + (max_exclusive.x - self.world_min.x) as f32 * scale;
+        let bottom = panel_bottom - (min.y - self.world_min.y) as f32 * scale;
+        let top = panel_bottom - (max_exclusive.y - self.world_min.y) as f32 * scale;
+        let x0 = left.floor().max(self.rect.x as f32) as u32;
+        let y0 = top.floor().max(self.rect.y as f32) as u32;
+        let x1 = right.ceil().min(panel_right).max(0.0) as u32;
+        let y1 = bottom.ceil().min(panel_bottom).max(0.0) as u32;
+        (x1 > x0 && y1 > y0).then(|| Rect::new(x0, y0, x1 - x0, y1 - y0))
+    }
+
+    /// World cell under screen pixel (`sx`, `sy`), or `None` outside the panel.
+    pub fn world_at_screen(&self, sx: u32, sy: u32) -> Option<Vec2i> {
+        if !self.rect.contains(sx, sy) {
+            return None;
+        }
+        let scale = self.px_per_cell();
+        let wx = (sx as f32 + 0.5 - self.rect.x as f32) / scale;
+        let panel_bottom = self.rect.y.saturating_add(self.rect.h) as f32;
+        let wy = (panel_bottom - (sy as f32 + 0.5)) / scale;
+        Some(Vec2i::new(
+            self.world_min.x + wx.floor() as i32,
+            self.world_min.y + wy.floor() as i32,
+        ))
+    }
 }
 
 pub fn compute_layout(
@@ -201,6 +276,45 @@ pub fn draw_tetris_world_with_camera_offset(
     height: u32,
     state: &TetrisCore,
     world_offset_y_px: i32,
+) -> UiLayout {
+    draw_tetris_world_with_camera_offset_and_ghost(
+        frame,
+        width,
+        height,
+        state,
+        world_offset_y_px,
+        true,
+    )
+}
+
+/// Like `draw_tetris_world_with_camera_offset`, with the ghost piece drawn only if `show_ghost`.
+pub fn draw_tetris_world_with_camera_offset_and_ghost(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    state: &TetrisCore,
+    world_offset_y_px: i32,
+    show_ghost: bool,
+) -> UiLayout {
+    draw_tetris_world_with_camera_offset_ghost_and_theme(
+        frame,
+        width,
+        height,
+        state,
+        world_offset_y_px,
+        show_ghost,
+        &Theme::default(),
+    )
+}
+
+pub fn draw_tetris_world_with_camera_offset_ghost_and_theme(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    state: &TetrisCore,
+    world_offset_y_px: i32,
+    show_ghost: bool,
+    theme: &Theme,
 ) -> UiLayout {
     let board = state.board();
     let board_h = board.len() as u32;
@@ -221,12 +335,13 @@ pub fn draw_tetris_world_with_camera_offset(
     );
 
     // --- Layer 2: board cells ---
-    draw_board_cells_in_rect_clipped_with_owners(
+    draw_board_cells_in_rect_clipped_with_owners_and_palette(
         frame,
         board,
         Some(state.board_piece_ids()),
         world_board_rect,
         layout.board,
+        &theme.cells,
     );
 
     draw_line_clear_overlay(
@@ -238,6 +353,19 @@ pub fn draw_tetris_world_with_camera_offset(
         board_w,
         board_h,
         state,
+        theme,
+    );
+
+    draw_hard_drop_effects(
+        frame,
+        width,
+        height,
+        world_board_rect,
+        layout.board,
+        board_w,
+        board_h,
+        state,
+        theme,
     );
 
     draw_depth_wall_overlay(
@@ -249,6 +377,7 @@ pub fn draw_tetris_world_with_camera_offset(
         board_w,
         board_h,
         state,
+        theme,
     );
 
     // --- Layer 4: active piece + ghost ---
@@ -261,6 +390,8 @@ pub fn draw_tetris_world_with_camera_offset(
         board_w,
         board_h,
         state,
+        show_ghost,
+        theme,
     );
 
     layout
@@ -275,6 +406,7 @@ fn draw_line_clear_overlay(
     board_w: u32,
     board_h: u32,
     state: &TetrisCore,
+    theme: &Theme,
 ) {
     if !state.is_line_clear_active() || board_w == 0 || board_h == 0 {
         return;
@@ -309,13 +441,79 @@ fn draw_line_clear_overlay(
                 clipped.y,
                 clipped.w,
                 clipped.h,
-                LINE_CLEAR_FLASH_COLOR,
+                theme.line_clear_flash,
                 alpha,
             );
         }
     }
 }
 
+fn draw_hard_drop_effects(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    board_rect: Rect,
+    viewport_rect: Rect,
+    board_w: u32,
+    board_h: u32,
+    state: &TetrisCore,
+    theme: &Theme,
+) {
+    if board_w == 0 || board_h == 0 {
+        return;
+    }
+    let mut blend_cell = |x: i32, row: i32, alpha: u8| {
+        if x < 0 || row < 0 || x >= board_w as i32 || row >= board_h as i32 {
+            return;
+        }
+        let inverted_y = board_h - 1 - row as u32;
+        let cell_rect = Rect::new(
+            board_rect.x + x as u32 * CELL_SIZE,
+            board_rect.y + inverted_y * CELL_SIZE,
+            CELL_SIZE,
+            CELL_SIZE,
+        );
+        let Some(clipped) = clip_rect_to_viewport(cell_rect, viewport_rect) else {
+            return;
+        };
+        blend_rect(
+            frame,
+            width,
+            height,
+            clipped.x,
+            clipped.y,
+            clipped.w,
+            clipped.h,
+            theme.line_clear_flash,
+            alpha,
+        );
+    };
+
+    if let Some((trail, progress)) = state.hard_drop_trail() {
+        // Fade from the piece upward so the streak reads as motion.
+        let span = (trail.from_row - trail.to_row).max(1) as f32;
+        for row in trail.to_row..=trail.from_row {
+            let tail = (row - trail.to_row) as f32 / span;
+            let alpha = ((1.0 - progress) * (1.0 - tail) * 90.0).round() as u8;
+            if alpha == 0 {
+                continue;
+            }
+            for x in trail.col_start..trail.col_end {
+                blend_cell(x, row, alpha);
+            }
+        }
+    }
+
+    if let Some((flash, progress)) = state.lock_flash() {
+        let alpha = ((1.0 - progress) * 160.0).round() as u8;
+        if alpha > 0 {
+            for cell in &flash.cells {
+                blend_cell(cell.x, cell.y, alpha);
+            }
+        }
+    }
+}
+
 fn draw_depth_wall_overlay(
     frame: &mut dyn Renderer2d,
     width: u32,
@@ -325,6 +523,7 @@ fn draw_depth_wall_overlay(
     board_w: u32,
     board_h: u32,
     state: &TetrisCore,
+    theme: &Theme,
 ) {
     if !state.depth_progress_paused() || board_w == 0 || board_h == 0 {
         return;
@@ -339,7 +538,12 @@ fn draw_depth_wall_overlay(
     let wall_y = board_rect
         .y
         .saturating_add(board_h.saturating_sub(wall_rows).saturating_mul(CELL_SIZE));
-    let wall_rect = Rect::new(board_rect.x, wall_y, board_w.saturating_mul(CELL_SIZE), wall_h);
+    let wall_rect = Rect::new(
+        board_rect.x,
+        wall_y,
+        board_w.saturating_mul(CELL_SIZE),
+        wall_h,
+    );
     let Some(clipped) = clip_rect_to_viewport(wall_rect, viewport_rect) else {
         return;
     };
@@ -352,7 +556,7 @@ fn draw_depth_wall_overlay(
         clipped.y,
         clipped.w,
         clipped.h,
-        COLOR_DEPTH_WALL_FILL,
+        theme.depth_wall_fill,
         230,
     );
     draw_rect_outline(
@@ -363,14 +567,26 @@ fn draw_depth_wall_overlay(
         clipped.y,
         clipped.w,
         clipped.h,
-        COLOR_DEPTH_WALL_BORDER,
+        theme.depth_wall_border,
     );
 
     let hp_text = format!("WALL HP {}", state.active_wall_hp_remaining());
-    let text_w = (hp_text.len() as u32).saturating_mul(8);
-    let text_x = clipped.x.saturating_add(clipped.w.saturating_sub(text_w) / 2);
-    let text_y = clipped.y.saturating_add(clipped.h.saturating_sub(8) / 2);
-    draw_text(frame, width, height, text_x, text_y, &hp_text, COLOR_DEPTH_LOCKED);
+    let (text_w, text_h) = frame.measure_text(&hp_text);
+    let text_x = clipped
+        .x
+        .saturating_add(clipped.w.saturating_sub(text_w) / 2);
+    let text_y = clipped
+        .y
+        .saturating_add(clipped.h.saturating_sub(text_h) / 2);
+    draw_text(
+        frame,
+        width,
+        height,
+        text_x,
+        text_y,
+        &hp_text,
+        theme.depth_locked,
+    );
 }
 
 pub fn draw_tetris_hud(
@@ -415,6 +631,7 @@ pub fn draw_tetris_hud_with_ui_and_pause_hover(
     ui_tree: &mut UiTree,
     pause_hovered: bool,
 ) {
+    let theme = &Theme::default();
     draw_hold_panel(
         frame,
         width,
@@ -422,8 +639,16 @@ pub fn draw_tetris_hud_with_ui_and_pause_hover(
         layout.hold_panel,
         state.held_piece(),
         state.can_hold(),
+        theme,
+    );
+    draw_next_panel(
+        frame,
+        width,
+        height,
+        layout.next_panel,
+        state.next_queue(),
+        theme,
     );
-    draw_next_panel(frame, width, height, layout.next_panel, state.next_queue());
 
     ui_tree.ensure_container(UI_TETRIS_HUD_CONTAINER, ui::Rect::from_size(width, height));
     ui_tree.add_child(UI_CANVAS, UI_TETRIS_HUD_CONTAINER);
@@ -436,9 +661,16 @@ pub fn draw_tetris_hud_with_ui_and_pause_hover(
     ui_tree.ensure_button(UI_TETRIS_HOLD, layout.hold_panel, Some(ACTION_TETRIS_HOLD));
     ui_tree.add_child(UI_TETRIS_HUD_CONTAINER, UI_TETRIS_HOLD);
 
-    draw_pause_button(frame, width, height, layout.pause_button, pause_hovered);
+    draw_pause_button(
+        frame,
+        width,
+        height,
+        layout.pause_button,
+        pause_hovered,
+        theme,
+    );
 
-    draw_tetris_status_text(frame, width, height, state, layout);
+    draw_tetris_status_text(frame, width, height, state, layout, theme);
 }
 
 pub fn draw_tetris_hud_view(
@@ -448,6 +680,26 @@ pub fn draw_tetris_hud_view(
     state: &TetrisCore,
     layout: UiLayout,
     mouse_pos: Option<(u32, u32)>,
+) {
+    draw_tetris_hud_view_with_theme(
+        frame,
+        width,
+        height,
+        state,
+        layout,
+        mouse_pos,
+        &Theme::default(),
+    );
+}
+
+pub fn draw_tetris_hud_view_with_theme(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    state: &TetrisCore,
+    layout: UiLayout,
+    mouse_pos: Option<(u32, u32)>,
+    theme: &Theme,
 ) {
     let pause_hovered = mouse_pos
         .map(|(mx, my)| layout.pause_button.contains(mx, my))
@@ -459,11 +711,26 @@ pub fn draw_tetris_hud_view(
         layout.hold_panel,
         state.held_piece(),
         state.can_hold(),
+        theme,
+    );
+    draw_next_panel(
+        frame,
+        width,
+        height,
+        layout.next_panel,
+        state.next_queue(),
+        theme,
+    );
+    draw_pause_button(
+        frame,
+        width,
+        height,
+        layout.pause_button,
+        pause_hovered,
+        theme,
     );
-    draw_next_panel(frame, width, height, layout.next_panel, state.next_queue());
-    draw_pause_button(frame, width, height, layout.pause_button, pause_hovered);
 
-    draw_tetris_status_text(frame, width, height, state, layout);
+    draw_tetris_status_text(frame, width, height, state, layout, theme);
 }
 
 pub fn draw_tetris(
@@ -483,6 +750,7 @@ fn draw_tetris_status_text(
     height: u32,
     state: &TetrisCore,
     layout: UiLayout,
+    theme: &Theme,
 ) {
     let hud_x = layout.pause_button.x.saturating_sub(220);
     let mut y = layout.pause_button.y.saturating_add(6);
@@ -490,35 +758,22 @@ fn draw_tetris_status_text(
     let lines_text = format!("LINES {}", state.lines_cleared());
     let depth_text = format!("DEPTH {}", state.background_depth_rows());
 
-    draw_text(
-        frame,
-        width,
-        height,
-        hud_x,
-        y,
-        &score_text,
-        COLOR_PAUSE_ICON,
-    );
+    draw_text(frame, width, height, hud_x, y, &score_text, theme.hud_text);
     y = y.saturating_add(14);
-    draw_text(
-        frame,
-        width,
-        height,
-        hud_x,
-        y,
-        &lines_text,
-        COLOR_PAUSE_ICON,
-    );
+    draw_text(frame, width, height, hud_x, y, &lines_text, theme.hud_text);
     y = y.saturating_add(14);
-    draw_text(
-        frame,
-        width,
-        height,
-        hud_x,
-        y,
-        &depth_text,
-        COLOR_PAUSE_ICON,
-    );
+    draw_text(frame, width, height, hud_x, y, &depth_text, theme.hud_text);
+
+    let streak_text = match (state.combo(), state.back_to_back()) {
+        (0 | 1, false) => None,
+        (0 | 1, true) => Some("B2B".to_string()),
+        (combo, false) => Some(format!("COMBO {combo}")),
+        (combo, true) => Some(format!("COMBO {combo} B2B")),
+    };
+    if let Some(streak_text) = streak_text {
+        y = y.saturating_add(14);
+        draw_text(frame, width, height, hud_x, y, &streak_text, theme.hud_text);
+    }
 
     if state.depth_progress_paused() {
         let lock_text = "DEPTH LOCKED";
@@ -534,7 +789,7 @@ fn draw_tetris_status_text(
             hud_x,
             y,
             lock_text,
-            COLOR_DEPTH_LOCKED,
+            theme.depth_locked,
         );
         y = y.saturating_add(14);
         draw_text(
@@ -544,10 +799,10 @@ fn draw_tetris_status_text(
             hud_x,
             y,
             &wall_text,
-            COLOR_DEPTH_LOCKED,
+            theme.depth_locked,
         );
         y = y.saturating_add(14);
-        draw_text(frame, width, height, hud_x, y, &hp_text, COLOR_DEPTH_LOCKED);
+        draw_text(frame, width, height, hud_x, y, &hp_text, theme.depth_locked);
     }
 }
 
@@ -574,6 +829,7 @@ fn draw_pause_button(
     height: u32,
     rect: Rect,
     hovered: bool,
+    theme: &Theme,
 ) {
     if rect.w == 0 || rect.h == 0 {
         return;
@@ -582,7 +838,7 @@ fn draw_pause_button(
         return;
     }
 
-    let (fill, border) = button_colors(hovered);
+    let (fill, border) = button_colors(hovered, theme);
     fill_rect(frame, width, height, rect.x, rect.y, rect.w, rect.h, fill);
     draw_rect_outline(frame, width, height, rect.x, rect.y, rect.w, rect.h, border);
 
@@ -603,7 +859,7 @@ fn draw_pause_button(
         icon_y0,
         bar_w,
         bar_h,
-        COLOR_PAUSE_ICON,
+        theme.hud_text,
     );
     fill_rect(
         frame,
@@ -613,7 +869,7 @@ fn draw_pause_button(
         icon_y0,
         bar_w,
         bar_h,
-        COLOR_PAUSE_ICON,
+        theme.hud_text,
     );
 }
 
@@ -641,6 +897,26 @@ pub fn draw_skilltree_runtime_with_ui_and_mouse(
     ui_tree: &mut UiTree,
     runtime: &SkillTreeRuntime,
     mouse_pos: Option<(u32, u32)>,
+) -> SkillTreeLayout {
+    draw_skilltree_runtime_with_ui_mouse_and_theme(
+        frame,
+        width,
+        height,
+        ui_tree,
+        runtime,
+        mouse_pos,
+        &Theme::default(),
+    )
+}
+
+pub fn draw_skilltree_runtime_with_ui_mouse_and_theme(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    ui_tree: &mut UiTree,
+    runtime: &SkillTreeRuntime,
+    mouse_pos: Option<(u32, u32)>,
+    theme: &Theme,
 ) -> SkillTreeLayout {
     draw_skilltree_impl(
         frame,
@@ -651,6 +927,7 @@ pub fn draw_skilltree_runtime_with_ui_and_mouse(
         &runtime.def,
         &runtime.progress,
         mouse_pos,
+        theme,
     )
 }
 
@@ -664,7 +941,17 @@ pub fn draw_skilltree_with_ui(
     // `draw_skilltree_runtime_with_ui` instead.
     let def = SkillTreeDef::default();
     let progress = SkillTreeProgress::default();
-    draw_skilltree_impl(frame, width, height, ui_tree, None, &def, &progress, None)
+    draw_skilltree_impl(
+        frame,
+        width,
+        height,
+        ui_tree,
+        None,
+        &def,
+        &progress,
+        None,
+        &Theme::default(),
+    )
 }
 
 fn draw_skilltree_impl(
@@ -676,9 +963,19 @@ fn draw_skilltree_impl(
     def: &SkillTreeDef,
     progress: &SkillTreeProgress,
     mouse_pos: Option<(u32, u32)>,
+    theme: &Theme,
 ) -> SkillTreeLayout {
     // Skilltree is its own scene: clear the frame so the Tetris board is not visible.
-    fill_rect(frame, width, height, 0, 0, width, height, color_for_cell(0));
+    fill_rect(
+        frame,
+        width,
+        height,
+        0,
+        0,
+        width,
+        height,
+        color_for_cell(&theme.cells, 0),
+    );
 
     let margin = 0u32;
     let pad = 18u32;
@@ -712,7 +1009,7 @@ fn draw_skilltree_impl(
         safe.x.saturating_add(pad),
         safe.y.saturating_add(pad),
         "SKILL TREE",
-        COLOR_PAUSE_MENU_TEXT,
+        theme.menu_text,
     );
 
     let money_text = format!("MONEY {}", progress.money);
@@ -723,7 +1020,7 @@ fn draw_skilltree_impl(
         safe.x.saturating_add(pad),
         safe.y.saturating_add(pad + 24),
         &money_text,
-        COLOR_PAUSE_MENU_TEXT,
+        theme.menu_text,
     );
 
     let editor_enabled = runtime.map(|rt| rt.editor.enabled).unwrap_or(false);
@@ -749,10 +1046,14 @@ fn draw_skilltree_impl(
                 .selected
                 .as_deref()
                 .map(|id| {
-                    if let Some(idx) = rt.node_index(id) {
+                    let label = if let Some(idx) = rt.node_index(id) {
                         format!("{id}/{}", rt.def.nodes[idx].name)
                     } else {
                         id.to_string()
+                    };
+                    match rt.editor_selected_ids().len() {
+                        0 | 1 => label,
+                        n => format!("{label} +{}", n - 1),
                     }
                 })
                 .unwrap_or_else(|| "NONE".to_string());
@@ -765,6 +1066,9 @@ fn draw_skilltree_impl(
                 dirty,
                 link
             );
+            if rt.editor.snap_enabled {
+                context.push_str(&format!("  SNAP {}", rt.editor.snap_step.max(1)));
+            }
             if let Some(pending) = rt.editor.pending_delete_id.as_deref() {
                 context.push_str(&format!("  DEL? {pending}"));
             }
@@ -780,7 +1084,7 @@ fn draw_skilltree_impl(
             safe.x.saturating_add(pad),
             safe.y.saturating_add(pad + 48),
             &context,
-            COLOR_PAUSE_MENU_TEXT,
+            theme.menu_text,
         );
         draw_text(
             frame,
@@ -789,7 +1093,7 @@ fn draw_skilltree_impl(
             safe.x.saturating_add(pad),
             safe.y.saturating_add(pad + 72),
             "1..5 TOOL TAB CYCLE ? HELP / SEARCH | ARROWS PAN SHIFT+ARROWS FAST 0 RESET F FOCUS",
-            COLOR_PAUSE_MENU_TEXT,
+            theme.menu_text,
         );
         if help_expanded {
             draw_text(
@@ -799,7 +1103,7 @@ fn draw_skilltree_impl(
                 safe.x.saturating_add(pad),
                 safe.y.saturating_add(pad + 96),
                 "IJKL CURSOR ENTER APPLY | N NEW DEL(confirm) CTRL+Z/Y UNDO/REDO CTRL+D DUP",
-                COLOR_PAUSE_MENU_TEXT,
+                theme.menu_text,
             );
             draw_text(
                 frame,
@@ -807,10 +1111,19 @@ fn draw_skilltree_impl(
                 height,
                 safe.x.saturating_add(pad),
                 safe.y.saturating_add(pad + 120),
-                "SHIFT+IJKL NUDGE SELECTED | S SAVE R RELOAD ESC EXIT",
-                COLOR_PAUSE_MENU_TEXT,
+                "SHIFT+IJKL NUDGE SELECTED G SNAP SHIFT+G STEP | S SAVE R RELOAD ESC EXIT",
+                theme.menu_text,
+            );
+            draw_text(
+                frame,
+                width,
+                height,
+                safe.x.saturating_add(pad),
+                safe.y.saturating_add(pad + 144),
+                "SHIFT+CLICK MULTI CTRL+DRAG BOX | H/SHIFT+H ALIGN B DISTRIBUTE | CTRL+E/O EXPORT/IMPORT",
+                theme.menu_text,
             );
-            tip_y = safe.y.saturating_add(pad + 144);
+            tip_y = safe.y.saturating_add(pad + 168);
         } else {
             draw_text(
                 frame,
@@ -819,7 +1132,7 @@ fn draw_skilltree_impl(
                 safe.x.saturating_add(pad),
                 safe.y.saturating_add(pad + 96),
                 "IJKL CURSOR ENTER APPLY | N NEW DEL(confirm) CTRL+Z/Y UNDO/REDO CTRL+D DUP",
-                COLOR_PAUSE_MENU_TEXT,
+                theme.menu_text,
             );
             tip_y = safe.y.saturating_add(pad + 120);
         }
@@ -831,7 +1144,7 @@ fn draw_skilltree_impl(
                 safe.x.saturating_add(pad),
                 tip_y,
                 search_line,
-                COLOR_PAUSE_MENU_TEXT,
+                theme.menu_text,
             );
             tip_y = tip_y.saturating_add(24);
         }
@@ -885,7 +1198,8 @@ fn draw_skilltree_impl(
                     }
                 }
             };
-            draw_tool_button(frame, width, height, rect, label, hovered, active);
+            let colors = tool_button_colors(hovered, active, theme);
+            draw_tool_button(frame, width, height, rect, label, colors, theme);
 
             match tool_kind {
                 SkillTreeEditorTool::Select => tool_select_button = rect,
@@ -939,7 +1253,7 @@ fn draw_skilltree_impl(
             safe.x.saturating_add(pad),
             safe.y.saturating_add(pad + 48),
             "CLICK: BUY  (F4: TOGGLE EDITOR)",
-            COLOR_PAUSE_MENU_TEXT,
+            theme.menu_text,
         );
         draw_text(
             frame,
@@ -948,7 +1262,7 @@ fn draw_skilltree_impl(
             safe.x.saturating_add(pad),
             safe.y.saturating_add(pad + 72),
             "ENTER: START NEW RUN   ESC: MAIN MENU",
-            COLOR_PAUSE_MENU_TEXT,
+            theme.menu_text,
         );
         tip_y = safe.y.saturating_add(pad + 96);
     }
@@ -959,7 +1273,7 @@ fn draw_skilltree_impl(
         safe.x.saturating_add(pad),
         tip_y,
         "TIP: EDITOR CHANGES SAVE TO game/assets/skilltree.json",
-        COLOR_PAUSE_MENU_TEXT,
+        theme.menu_text,
     );
 
     // Grid rendering (world coords; y increases upward).
@@ -1066,13 +1380,20 @@ fn draw_skilltree_impl(
         grid_pan_px_x,
         grid_pan_px_y,
     };
-    draw_skilltree_links(frame, width, height, def, link_transform);
+    draw_skilltree_links(frame, width, height, def, link_transform, theme);
 
     // Draw nodes as polyblocks.
     let unlocked: std::collections::HashSet<&str> =
         progress.unlocked.iter().map(|s| s.as_str()).collect();
-    let selected = runtime.and_then(|rt| rt.editor.selected.as_deref());
+    let selected = runtime
+        .map(|rt| rt.editor_selected_ids())
+        .unwrap_or_default();
     let connect_from = runtime.and_then(|rt| rt.editor.connect_from.as_deref());
+    // Editor-only: outline nodes caught in a prereq cycle so they can be unlinked before saving.
+    let cycle_ids: HashSet<String> = runtime
+        .filter(|rt| rt.editor.enabled)
+        .map(|rt| rt.def.find_prereq_cycles().into_iter().flatten().collect())
+        .unwrap_or_default();
     for node in &def.nodes {
         let (state, can_buy) = if let Some(rt) = runtime {
             (rt.node_state(node), rt.can_buy(node))
@@ -1084,13 +1405,13 @@ fn draw_skilltree_impl(
             (NodeState::Locked, false)
         };
 
-        let mut fill = color_for_cell(node.color);
-        let mut border = COLOR_PANEL_BORDER;
-        let is_selected = selected == Some(node.id.as_str());
+        let mut fill = color_for_cell(&theme.cells, node.color);
+        let mut border = theme.panel_border;
+        let is_selected = selected.contains(&node.id.as_str());
         let is_connect_from = connect_from == Some(node.id.as_str());
         match state {
             NodeState::Unlocked => {
-                border = COLOR_PANEL_BORDER;
+                border = theme.panel_border;
             }
             NodeState::Available => {
                 if can_buy {
@@ -1109,6 +1430,9 @@ fn draw_skilltree_impl(
         if is_connect_from {
             border = brighten_color(border, 0.22);
         }
+        if cycle_ids.contains(&node.id) {
+            border = theme.skilltree_cycle;
+        }
         if is_selected {
             border = [245, 245, 255, 255];
         }
@@ -1161,7 +1485,7 @@ fn draw_skilltree_impl(
                 label_x,
                 label_y,
                 &node.name,
-                COLOR_PAUSE_MENU_TEXT,
+                theme.menu_text,
             );
             if node.cost > 0 {
                 let cost = format!("${}", node.cost);
@@ -1172,12 +1496,85 @@ fn draw_skilltree_impl(
                     label_x,
                     label_y.saturating_add(18),
                     &cost,
-                    COLOR_PAUSE_MENU_TEXT,
+                    theme.menu_text,
                 );
             }
         }
     }
 
+    // Alignment guides for the node being moved (visual only; placement is not affected).
+    if let Some(rt) =
+        runtime.filter(|rt| rt.editor.enabled && rt.editor.tool == SkillTreeEditorTool::Move)
+    {
+        for guide in rt.editor_alignment_guides() {
+            match guide {
+                SkillTreeAlignmentGuide::Column(wx) => {
+                    let x = grid_view_x0
+                        .saturating_add((wx - grid_cam_min_x).saturating_mul(grid_cell_i32))
+                        .saturating_add(grid_pan_px_x)
+                        .saturating_add(grid_cell_i32 / 2);
+                    if x >= grid_view_x0 && x < grid_view_x1 {
+                        fill_rect_i32(
+                            frame,
+                            width,
+                            height,
+                            x,
+                            grid_view_y0,
+                            1,
+                            grid_pixel_h,
+                            theme.skilltree_guide,
+                        );
+                    }
+                }
+                SkillTreeAlignmentGuide::Row(wy) => {
+                    let row_from_top = grid_rows as i32 - 1 - (wy - grid_cam_min_y);
+                    let y = grid_view_y0
+                        .saturating_add(row_from_top.saturating_mul(grid_cell_i32))
+                        .saturating_add(grid_pan_px_y)
+                        .saturating_add(grid_cell_i32 / 2);
+                    if y >= grid_view_y0 && y < grid_view_y1 {
+                        fill_rect_i32(
+                            frame,
+                            width,
+                            height,
+                            grid_view_x0,
+                            y,
+                            grid_pixel_w,
+                            1,
+                            theme.skilltree_guide,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(rect) = runtime
+        .filter(|rt| rt.editor.enabled)
+        .and_then(|rt| rt.editor.box_select)
+    {
+        let (min, max) = (rect.min(), rect.max());
+        let x0 = grid_view_x0
+            .saturating_add((min.x - grid_cam_min_x).saturating_mul(grid_cell_i32))
+            .saturating_add(grid_pan_px_x);
+        let y0 = grid_view_y0
+            .saturating_add(
+                (grid_rows as i32 - 1 - (max.y - grid_cam_min_y)).saturating_mul(grid_cell_i32),
+            )
+            .saturating_add(grid_pan_px_y);
+        let x1 = x0.saturating_add((max.x - min.x + 1).saturating_mul(grid_cell_i32));
+        let y1 = y0.saturating_add((max.y - min.y + 1).saturating_mul(grid_cell_i32));
+        let x0 = x0.clamp(grid_view_x0, grid_view_x1);
+        let y0 = y0.clamp(grid_view_y0, grid_view_y1);
+        let x1 = x1.clamp(grid_view_x0, grid_view_x1);
+        let y1 = y1.clamp(grid_view_y0, grid_view_y1);
+        if x1 > x0 && y1 > y0 && x0 >= 0 && y0 >= 0 {
+            let (x, y, w, h) = (x0 as u32, y0 as u32, (x1 - x0) as u32, (y1 - y0) as u32);
+            blend_rect(frame, width, height, x, y, w, h, theme.skilltree_cursor, 48);
+            draw_rect_outline(frame, width, height, x, y, w, h, theme.skilltree_cursor);
+        }
+    }
+
     if let Some(rt) = runtime {
         if rt.editor.enabled {
             let wx = rt.editor.cursor_world.x;
@@ -1211,7 +1608,7 @@ fn draw_skilltree_impl(
                         py as u32,
                         grid_cell,
                         grid_cell,
-                        COLOR_SKILLTREE_CURSOR,
+                        theme.skilltree_cursor,
                     );
                 }
                 let center_x = px.saturating_add(grid_cell_i32 / 2);
@@ -1224,12 +1621,81 @@ fn draw_skilltree_impl(
                     center_y.saturating_sub(2),
                     4,
                     4,
-                    COLOR_SKILLTREE_CURSOR,
+                    theme.skilltree_cursor,
                 );
             }
         }
     }
 
+    // Outside the editor, keep the overview clear of the START NEW RUN button row.
+    let minimap_reserve_bottom = if editor_enabled { 0 } else { 52 };
+    let minimap = skilltree_world_bounds(def).and_then(|bounds| {
+        let area = Rect::new(
+            content.x,
+            content.y,
+            content.w,
+            content.h.saturating_sub(minimap_reserve_bottom),
+        );
+        SkillTreeMinimap::fit(bounds, area)
+    });
+    if let Some(map) = minimap {
+        let panel_rect = map.rect;
+        blend_rect(
+            frame,
+            width,
+            height,
+            panel_rect.x,
+            panel_rect.y,
+            panel_rect.w,
+            panel_rect.h,
+            theme.panel_bg,
+            200,
+        );
+        for node in &def.nodes {
+            let xs = node.shape.iter().map(|rel| node.pos.x + rel.x);
+            let ys = node.shape.iter().map(|rel| node.pos.y + rel.y);
+            let (Some(min_x), Some(max_x)) = (xs.clone().min(), xs.max()) else {
+                continue;
+            };
+            let (Some(min_y), Some(max_y)) = (ys.clone().min(), ys.max()) else {
+                continue;
+            };
+            let min = Vec2i::new(min_x, min_y);
+            let max = Vec2i::new(max_x + 1, max_y + 1);
+            if let Some(r) = map.screen_rect(min, max) {
+                let fill = color_for_cell(&theme.cells, node.color);
+                fill_rect(frame, width, height, r.x, r.y, r.w, r.h, fill);
+            }
+        }
+        let view_min = Vec2i::new(grid_cam_min_x, grid_cam_min_y);
+        let view_max = Vec2i::new(
+            grid_cam_min_x + grid_cols as i32,
+            grid_cam_min_y + grid_rows as i32,
+        );
+        if let Some(r) = map.screen_rect(view_min, view_max) {
+            draw_rect_outline(
+                frame,
+                width,
+                height,
+                r.x,
+                r.y,
+                r.w,
+                r.h,
+                theme.skilltree_cursor,
+            );
+        }
+        draw_rect_outline(
+            frame,
+            width,
+            height,
+            panel_rect.x,
+            panel_rect.y,
+            panel_rect.w,
+            panel_rect.h,
+            theme.panel_border,
+        );
+    }
+
     if let Some(rt) = runtime {
         if let Some(status) = rt.editor.status.as_deref() {
             draw_text(
@@ -1239,7 +1705,7 @@ fn draw_skilltree_impl(
                 safe.x.saturating_add(pad),
                 safe.y.saturating_add(safe.h.saturating_sub(pad + 16)),
                 status,
-                COLOR_PAUSE_MENU_TEXT,
+                theme.menu_text,
             );
         }
     }
@@ -1271,6 +1737,7 @@ fn draw_skilltree_impl(
             start_new_game_button,
             "START NEW RUN",
             hovered,
+            theme,
         );
         start_new_game_button
     };
@@ -1291,6 +1758,7 @@ fn draw_skilltree_impl(
         grid_rows,
         grid_cam_min_x,
         grid_cam_min_y,
+        minimap,
     }
 }
 
@@ -1315,6 +1783,8 @@ fn draw_ghost_and_active_piece(
     board_w: u32,
     board_h: u32,
     state: &TetrisCore,
+    show_ghost: bool,
+    theme: &Theme,
 ) {
     let Some(piece) = state.current_piece() else {
         return;
@@ -1323,7 +1793,7 @@ fn draw_ghost_and_active_piece(
     let rotation = state.current_piece_rotation();
 
     // Ghost should render behind the active piece.
-    if let Some(ghost_pos) = state.ghost_piece_pos() {
+    if let Some(ghost_pos) = state.ghost_piece_pos().filter(|_| show_ghost) {
         draw_piece_on_board(
             frame,
             width,
@@ -1336,6 +1806,7 @@ fn draw_ghost_and_active_piece(
             ghost_pos,
             rotation,
             PieceDrawStyle::Ghost,
+            theme,
         );
     }
 
@@ -1351,6 +1822,7 @@ fn draw_ghost_and_active_piece(
         state.current_piece_pos(),
         rotation,
         PieceDrawStyle::Solid,
+        theme,
     );
 }
 
@@ -1366,6 +1838,7 @@ fn draw_piece_on_board(
     pos: Vec2i,
     rotation: u8,
     style: PieceDrawStyle,
+    theme: &Theme,
 ) {
     if board_w == 0 || board_h == 0 {
         return;
@@ -1373,7 +1846,7 @@ fn draw_piece_on_board(
 
     let grid = piece_grid(piece, rotation);
     let offset = piece_board_offset(piece);
-    let color = color_for_cell(piece_type(piece));
+    let color = color_for_cell(&theme.cells, piece_type(piece));
 
     for gy in 0..grid.size() {
         for gx in 0..grid.size() {
@@ -1418,7 +1891,7 @@ fn draw_piece_on_board(
                             height,
                             clipped_cell_rect,
                             direction,
-                            COLOR_TIP_MARKER,
+                            theme.tip_marker,
                         );
                     }
                 }
@@ -1441,7 +1914,7 @@ fn draw_piece_on_board(
                             height,
                             clipped_cell_rect,
                             direction,
-                            dim_color(COLOR_TIP_MARKER, 0.6),
+                            dim_color(theme.tip_marker, 0.6),
                         );
                     }
                 }
@@ -1457,6 +1930,7 @@ fn draw_hold_panel(
     rect: Rect,
     held_piece: Option<Piece>,
     can_hold: bool,
+    theme: &Theme,
 ) {
     if rect.w == 0 || rect.h == 0 {
         return;
@@ -1470,20 +1944,19 @@ fn draw_hold_panel(
         rect.y,
         rect.w,
         rect.h,
-        COLOR_PANEL_BG,
+        theme.panel_bg,
     );
     let border = if can_hold {
-        COLOR_PANEL_BORDER
+        theme.panel_border
     } else {
-        COLOR_PANEL_BORDER_DISABLED
+        theme.panel_border_disabled
     };
     draw_rect_outline(frame, width, height, rect.x, rect.y, rect.w, rect.h, border);
 
     let preview_x = rect.x + PANEL_PADDING;
     let preview_y = rect.y + PANEL_PADDING;
-    draw_piece_preview(
-        frame, width, height, preview_x, preview_y, held_piece, can_hold,
-    );
+    let preview = Rect::new(preview_x, preview_y, PREVIEW_SIZE, PREVIEW_SIZE);
+    draw_piece_preview(frame, width, height, preview, held_piece, can_hold, theme);
 }
 
 fn draw_next_panel(
@@ -1492,6 +1965,7 @@ fn draw_next_panel(
     height: u32,
     rect: Rect,
     next_queue: &[Piece],
+    theme: &Theme,
 ) {
     if rect.w == 0 || rect.h == 0 {
         return;
@@ -1505,7 +1979,7 @@ fn draw_next_panel(
         rect.y,
         rect.w,
         rect.h,
-        COLOR_PANEL_BG,
+        theme.panel_bg,
     );
     draw_rect_outline(
         frame,
@@ -1515,7 +1989,7 @@ fn draw_next_panel(
         rect.y,
         rect.w,
         rect.h,
-        COLOR_PANEL_BORDER,
+        theme.panel_border,
     );
 
     let mut y = rect.y + PANEL_PADDING;
@@ -1525,7 +1999,8 @@ fn draw_next_panel(
         if y.saturating_add(PREVIEW_SIZE) > rect.y.saturating_add(rect.h) {
             break;
         }
-        draw_piece_preview(frame, width, height, x, y, Some(piece), true);
+        let preview = Rect::new(x, y, PREVIEW_SIZE, PREVIEW_SIZE);
+        draw_piece_preview(frame, width, height, preview, Some(piece), true, theme);
         y = y.saturating_add(PREVIEW_SIZE + PREVIEW_GAP_Y);
     }
 }
@@ -1534,11 +2009,12 @@ fn draw_piece_preview(
     frame: &mut dyn Renderer2d,
     width: u32,
     height: u32,
-    x: u32,
-    y: u32,
+    preview: Rect,
     piece: Option<Piece>,
     enabled: bool,
+    theme: &Theme,
 ) {
+    let (x, y) = (preview.x, preview.y);
     if x >= width || y >= height {
         return;
     }
@@ -1550,8 +2026,8 @@ fn draw_piece_preview(
         height,
         x,
         y,
-        PREVIEW_SIZE,
-        PREVIEW_SIZE,
+        preview.w,
+        preview.h,
         [10, 10, 14, 255],
     );
 
@@ -1566,7 +2042,7 @@ fn draw_piece_preview(
     let offset_x = (PREVIEW_GRID.saturating_sub(grid_w)) / 2;
     let offset_y = (PREVIEW_GRID.saturating_sub(grid_h)) / 2;
 
-    let mut color = color_for_cell(piece_type(piece));
+    let mut color = color_for_cell(&theme.cells, piece_type(piece));
     if !enabled {
         color = dim_color(color, 0.55);
     }
@@ -1591,7 +2067,7 @@ fn draw_piece_preview(
             );
             if let Some(direction) = tip_direction(piece, 0, gx, gy) {
                 let tip_rect = Rect::new(px, py, PREVIEW_CELL, PREVIEW_CELL);
-                draw_tip_marker(frame, width, height, tip_rect, direction, COLOR_TIP_MARKER);
+                draw_tip_marker(frame, width, height, tip_rect, direction, theme.tip_marker);
             }
         }
     }
@@ -1639,12 +2115,20 @@ fn draw_tip_marker(
     let marker_h = (cell_rect.h / 3).max(2).min(cell_rect.h);
     let (x, y) = match direction {
         TipDirection::Right => (
-            cell_rect.x.saturating_add(cell_rect.w.saturating_sub(marker_w)),
-            cell_rect.y.saturating_add((cell_rect.h.saturating_sub(marker_h)) / 2),
+            cell_rect
+                .x
+                .saturating_add(cell_rect.w.saturating_sub(marker_w)),
+            cell_rect
+                .y
+                .saturating_add((cell_rect.h.saturating_sub(marker_h)) / 2),
         ),
         TipDirection::Down => (
-            cell_rect.x.saturating_add((cell_rect.w.saturating_sub(marker_w)) / 2),
-            cell_rect.y.saturating_add(cell_rect.h.saturating_sub(marker_h)),
+            cell_rect
+                .x
+                .saturating_add((cell_rect.w.saturating_sub(marker_w)) / 2),
+            cell_rect
+                .y
+                .saturating_add(cell_rect.h.saturating_sub(marker_h)),
         ),
     };
     fill_rect(frame, width, height, x, y, marker_w, marker_h, color);
@@ -1667,14 +2151,14 @@ fn brighten_color(mut c: [u8; 4], amount: f32) -> [u8; 4] {
     c
 }
 
-fn button_colors(hovered: bool) -> ([u8; 4], [u8; 4]) {
+fn button_colors(hovered: bool, theme: &Theme) -> ([u8; 4], [u8; 4]) {
     if hovered {
         (
-            brighten_color(COLOR_PANEL_BG, BUTTON_HOVER_BRIGHTEN),
-            brighten_color(COLOR_PANEL_BORDER, BUTTON_HOVER_BRIGHTEN),
+            brighten_color(theme.panel_bg, BUTTON_HOVER_BRIGHTEN),
+            brighten_color(theme.panel_border, BUTTON_HOVER_BRIGHTEN),
         )
     } else {
-        (COLOR_PANEL_BG, COLOR_PANEL_BORDER)
+        (theme.panel_bg, theme.panel_border)
     }
 }
 
@@ -1685,8 +2169,9 @@ fn draw_button(
     rect: Rect,
     label: &str,
     hovered: bool,
+    theme: &Theme,
 ) {
-    let (fill, border) = button_colors(hovered);
+    let (fill, border) = button_colors(hovered, theme);
     fill_rect(frame, width, height, rect.x, rect.y, rect.w, rect.h, fill);
     draw_rect_outline(frame, width, height, rect.x, rect.y, rect.w, rect.h, border);
     draw_text(
@@ -1696,24 +2181,28 @@ fn draw_button(
         rect.x.saturating_add(16),
         rect.y.saturating_add(rect.h / 2).saturating_sub(6),
         label,
-        COLOR_PAUSE_MENU_TEXT,
+        theme.menu_text,
     );
 }
 
+fn tool_button_colors(hovered: bool, active: bool, theme: &Theme) -> ([u8; 4], [u8; 4]) {
+    let (fill, border) = button_colors(hovered, theme);
+    if active {
+        (brighten_color(fill, 0.18), [245, 245, 255, 255])
+    } else {
+        (fill, border)
+    }
+}
+
 fn draw_tool_button(
     frame: &mut dyn Renderer2d,
     width: u32,
     height: u32,
     rect: Rect,
     label: &str,
-    hovered: bool,
-    active: bool,
+    (fill, border): ([u8; 4], [u8; 4]),
+    theme: &Theme,
 ) {
-    let (mut fill, mut border) = button_colors(hovered);
-    if active {
-        fill = brighten_color(fill, 0.18);
-        border = [245, 245, 255, 255];
-    }
     fill_rect(frame, width, height, rect.x, rect.y, rect.w, rect.h, fill);
     draw_rect_outline(frame, width, height, rect.x, rect.y, rect.w, rect.h, border);
     draw_text(
@@ -1723,7 +2212,7 @@ fn draw_tool_button(
         rect.x.saturating_add(12),
         rect.y.saturating_add(rect.h / 2).saturating_sub(6),
         label,
-        COLOR_PAUSE_MENU_TEXT,
+        theme.menu_text,
     );
 }
 
@@ -2754,6 +3243,7 @@ fn draw_skilltree_links(
     height: u32,
     def: &SkillTreeDef,
     transform: SkilltreeGridTransform,
+    theme: &Theme,
 ) {
     let geometry = build_skilltree_link_geometry(def);
     let route_bounds = skilltree_route_bounds(def);
@@ -2788,7 +3278,7 @@ fn draw_skilltree_links(
             source_port,
             target_port,
             transform,
-            COLOR_SKILLTREE_LINK,
+            theme.skilltree_link,
             SKILLTREE_LINK_THICKNESS,
         );
         for cell in route {
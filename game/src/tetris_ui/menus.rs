@@ -1,15 +1,16 @@
-use engine::graphics::Renderer2d;
+use engine::graphics::{Renderer2d, text_scale_for_height};
+use engine::localization::Localizer;
 use engine::render::color_for_cell;
 use engine::slider::Slider;
 use engine::ui;
 use engine::ui_tree::UiTree;
 
-use crate::settings::PlayerSettings;
+use crate::localization;
+use crate::settings::{PiecePalette, PlayerSettings, ThemeId};
 use crate::ui_ids::*;
 
 use super::{
-    COLOR_PAUSE_MENU_BG, COLOR_PAUSE_MENU_BORDER, COLOR_PAUSE_MENU_DIM, COLOR_PAUSE_MENU_TEXT,
-    MAIN_MENU_TITLE, PAUSE_MENU_DIM_ALPHA, Rect, blend_rect, draw_button, draw_rect_outline,
+    MAIN_MENU_TITLE, PAUSE_MENU_DIM_ALPHA, Rect, Theme, blend_rect, draw_button, draw_rect_outline,
     draw_text, draw_text_scaled, fill_rect,
 };
 
@@ -19,6 +20,7 @@ pub struct PauseMenuLayout {
     pub resume_button: Rect,
     pub end_run_button: Rect,
     pub settings_button: Rect,
+    pub restart_button: Rect,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -43,6 +45,11 @@ pub struct SettingsMenuLayout {
     pub auto_pause_toggle: Rect,
     pub high_contrast_toggle: Rect,
     pub reduce_motion_toggle: Rect,
+    pub das_meter_toggle: Rect,
+    pub ghost_toggle: Rect,
+    pub hold_toggle: Rect,
+    pub theme_button: Rect,
+    pub piece_palette_button: Rect,
     pub back_button: Rect,
     pub reset_button: Rect,
 }
@@ -57,12 +64,64 @@ pub struct GameOverMenuLayout {
 
 pub struct PauseMenuView;
 
+/// Height reserved above the pause-menu buttons for the "PAUSED" / "ESC TO RESUME" lines.
+const PAUSE_MENU_HEADER_H: u32 = 40;
+
+/// Pause-menu geometry, shared by drawing and the view tree so hit-tests match what is drawn.
+///
+/// Buttons stack upward from the panel bottom (resume, settings, restart, end run) and shrink
+/// together when the panel is too short, so they never overlap each other or the header.
+pub fn pause_menu_layout(width: u32, height: u32) -> PauseMenuLayout {
+    let margin = 32u32;
+    let pad = 18u32;
+
+    let screen = ui::Rect::from_size(width, height);
+    let safe = screen.inset(ui::Insets::all(margin));
+    if safe.w == 0 || safe.h == 0 {
+        return PauseMenuLayout::default();
+    }
+
+    let panel_size = ui::Size::new(380, 320).clamp_max(safe.size());
+    if panel_size.w == 0 || panel_size.h == 0 {
+        return PauseMenuLayout::default();
+    }
+    let panel = safe.place(panel_size, ui::Anchor::Center);
+
+    let content = panel.inset(ui::Insets {
+        left: pad,
+        top: pad.saturating_add(PAUSE_MENU_HEADER_H),
+        right: pad,
+        bottom: pad,
+    });
+    let rows = 4u32;
+    let gap = 12u32.min(content.h / (rows * 4));
+    let button_h = (content.h.saturating_sub(gap * (rows - 1)) / rows).min(44);
+    let base = content.place(
+        ui::Size::new(240, button_h).clamp_max(content.size()),
+        ui::Anchor::BottomCenter,
+    );
+    let stacked = |row: u32| Rect {
+        x: base.x,
+        y: base.y.saturating_sub(row * button_h.saturating_add(gap)),
+        w: base.w,
+        h: base.h,
+    };
+
+    PauseMenuLayout {
+        panel,
+        resume_button: stacked(0),
+        settings_button: stacked(1),
+        restart_button: stacked(2),
+        end_run_button: stacked(3),
+    }
+}
+
 impl PauseMenuView {
     pub fn render(frame: &mut dyn Renderer2d, width: u32, height: u32) -> PauseMenuLayout {
         let mut ui_tree = UiTree::new();
         ui_tree.ensure_canvas(UI_CANVAS, ui::Rect::from_size(width, height));
         ui_tree.add_root(UI_CANVAS);
-        Self::render_with_ui(frame, width, height, &mut ui_tree)
+        Self::render_with_ui(frame, width, height, &mut ui_tree, &localization::english())
     }
 
     pub fn render_with_ui(
@@ -70,6 +129,39 @@ impl PauseMenuView {
         width: u32,
         height: u32,
         ui_tree: &mut UiTree,
+        labels: &dyn Localizer,
+    ) -> PauseMenuLayout {
+        Self::render_with_ui_and_restart_confirm(frame, width, height, ui_tree, false, labels)
+    }
+
+    /// `restart_armed` relabels the restart button while it waits for a confirming click.
+    pub fn render_with_ui_and_restart_confirm(
+        frame: &mut dyn Renderer2d,
+        width: u32,
+        height: u32,
+        ui_tree: &mut UiTree,
+        restart_armed: bool,
+        labels: &dyn Localizer,
+    ) -> PauseMenuLayout {
+        Self::render_with_ui_restart_confirm_and_theme(
+            frame,
+            width,
+            height,
+            ui_tree,
+            restart_armed,
+            labels,
+            &Theme::default(),
+        )
+    }
+
+    pub fn render_with_ui_restart_confirm_and_theme(
+        frame: &mut dyn Renderer2d,
+        width: u32,
+        height: u32,
+        ui_tree: &mut UiTree,
+        restart_armed: bool,
+        labels: &dyn Localizer,
+        theme: &Theme,
     ) -> PauseMenuLayout {
         // Dim the entire game view.
         blend_rect(
@@ -80,33 +172,22 @@ impl PauseMenuView {
             0,
             width,
             height,
-            COLOR_PAUSE_MENU_DIM,
+            theme.menu_dim,
             PAUSE_MENU_DIM_ALPHA,
         );
 
-        let margin = 32u32;
-        let pad = 18u32;
-
-        // Layout is expressed via the engine UI layout helpers, then converted to our local `Rect`
-        // for hit-testing and drawing.
-        let screen = ui::Rect::from_size(width, height);
-        let safe = screen.inset(ui::Insets::all(margin));
-        if safe.w == 0 || safe.h == 0 {
-            return PauseMenuLayout::default();
-        }
-
-        let panel_size = ui::Size::new(380, 320).clamp_max(safe.size());
-        if panel_size.w == 0 || panel_size.h == 0 {
-            return PauseMenuLayout::default();
+        let layout = pause_menu_layout(width, height);
+        let PauseMenuLayout {
+            panel,
+            resume_button,
+            end_run_button,
+            settings_button,
+            restart_button,
+        } = layout;
+        if panel.w == 0 || panel.h == 0 {
+            return layout;
         }
-
-        let panel_ui = safe.place(panel_size, ui::Anchor::Center);
-        let panel = Rect {
-            x: panel_ui.x,
-            y: panel_ui.y,
-            w: panel_ui.w,
-            h: panel_ui.h,
-        };
+        let pad = 18u32;
 
         fill_rect(
             frame,
@@ -116,7 +197,7 @@ impl PauseMenuView {
             panel.y,
             panel.w,
             panel.h,
-            COLOR_PAUSE_MENU_BG,
+            theme.menu_bg,
         );
         draw_rect_outline(
             frame,
@@ -126,7 +207,7 @@ impl PauseMenuView {
             panel.y,
             panel.w,
             panel.h,
-            COLOR_PAUSE_MENU_BORDER,
+            theme.menu_border,
         );
 
         draw_text(
@@ -135,8 +216,8 @@ impl PauseMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad),
-            "PAUSED",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("menu.paused"),
+            theme.menu_text,
         );
         draw_text(
             frame,
@@ -144,77 +225,67 @@ impl PauseMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad + 24),
-            "ESC TO RESUME",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("menu.escToResume"),
+            theme.menu_text,
         );
 
-        let content = panel_ui.inset(ui::Insets::all(pad));
-        let gap = 12u32;
-        let button_size = ui::Size::new(240, 44).clamp_max(content.size());
-        let base_ui = content.place(button_size, ui::Anchor::BottomCenter);
-        let resume_button = Rect {
-            x: base_ui.x,
-            y: base_ui.y,
-            w: base_ui.w,
-            h: base_ui.h,
-        };
-        let settings_button = Rect {
-            x: resume_button.x,
-            y: resume_button
-                .y
-                .saturating_sub(resume_button.h.saturating_add(gap)),
-            w: resume_button.w,
-            h: resume_button.h,
-        };
-        let end_run_button = Rect {
-            x: resume_button.x,
-            y: settings_button
-                .y
-                .saturating_sub(settings_button.h.saturating_add(gap)),
-            w: resume_button.w,
-            h: resume_button.h,
-        };
-
         ui_tree.ensure_container(UI_PAUSE_MENU_CONTAINER, panel);
         ui_tree.add_child(UI_CANVAS, UI_PAUSE_MENU_CONTAINER);
         ui_tree.ensure_button(UI_PAUSE_RESUME, resume_button, Some(ACTION_PAUSE_RESUME));
         ui_tree.add_child(UI_PAUSE_MENU_CONTAINER, UI_PAUSE_RESUME);
         ui_tree.ensure_button(UI_PAUSE_END_RUN, end_run_button, Some(ACTION_PAUSE_END_RUN));
         ui_tree.add_child(UI_PAUSE_MENU_CONTAINER, UI_PAUSE_END_RUN);
-        ui_tree.ensure_button(UI_PAUSE_SETTINGS, settings_button, None);
+        ui_tree.ensure_button(
+            UI_PAUSE_SETTINGS,
+            settings_button,
+            Some(ACTION_PAUSE_SETTINGS),
+        );
         ui_tree.add_child(UI_PAUSE_MENU_CONTAINER, UI_PAUSE_SETTINGS);
+        ui_tree.ensure_button(UI_PAUSE_RESTART, restart_button, Some(ACTION_PAUSE_RESTART));
+        ui_tree.add_child(UI_PAUSE_MENU_CONTAINER, UI_PAUSE_RESTART);
 
         draw_button(
             frame,
             width,
             height,
             resume_button,
-            "RESUME",
+            &labels.label("menu.resume"),
             ui_tree.is_hovered(UI_PAUSE_RESUME),
+            theme,
         );
         draw_button(
             frame,
             width,
             height,
             settings_button,
-            "SETTINGS",
+            &labels.label("menu.settings"),
             ui_tree.is_hovered(UI_PAUSE_SETTINGS),
+            theme,
+        );
+        draw_button(
+            frame,
+            width,
+            height,
+            restart_button,
+            &labels.label(if restart_armed {
+                "menu.confirmRestart"
+            } else {
+                "menu.restart"
+            }),
+            ui_tree.is_hovered(UI_PAUSE_RESTART),
+            theme,
         );
         draw_button(
             frame,
             width,
             height,
             end_run_button,
-            "END RUN",
+            &labels.label("menu.endRun"),
             ui_tree.is_hovered(UI_PAUSE_END_RUN),
+            theme,
         );
 
-        PauseMenuLayout {
-            panel,
-            resume_button,
-            end_run_button,
-            settings_button,
-        }
+        layout
     }
 }
 
@@ -225,7 +296,7 @@ impl MainMenuView {
         let mut ui_tree = UiTree::new();
         ui_tree.ensure_canvas(UI_CANVAS, ui::Rect::from_size(width, height));
         ui_tree.add_root(UI_CANVAS);
-        Self::render_with_ui(frame, width, height, &mut ui_tree)
+        Self::render_with_ui(frame, width, height, &mut ui_tree, &localization::english())
     }
 
     pub fn render_with_ui(
@@ -233,9 +304,30 @@ impl MainMenuView {
         width: u32,
         height: u32,
         ui_tree: &mut UiTree,
+        labels: &dyn Localizer,
+    ) -> MainMenuLayout {
+        Self::render_with_ui_and_theme(frame, width, height, ui_tree, labels, &Theme::default())
+    }
+
+    pub fn render_with_ui_and_theme(
+        frame: &mut dyn Renderer2d,
+        width: u32,
+        height: u32,
+        ui_tree: &mut UiTree,
+        labels: &dyn Localizer,
+        theme: &Theme,
     ) -> MainMenuLayout {
         // Main menu is its own scene: clear the frame so the Tetris board is not visible underneath.
-        fill_rect(frame, width, height, 0, 0, width, height, color_for_cell(0));
+        fill_rect(
+            frame,
+            width,
+            height,
+            0,
+            0,
+            width,
+            height,
+            color_for_cell(&theme.cells, 0),
+        );
 
         let margin = 32u32;
         let pad = 18u32;
@@ -258,13 +350,13 @@ impl MainMenuView {
 
         // Layout: a vertical stack (title, start, skilltree editor, settings, quit), centered.
         let title = MAIN_MENU_TITLE;
-        let title_chars = title.chars().count() as u32;
-        let glyph_cols = 4u32; // 3 glyph columns + 1 column spacing (matches `draw_text` advances).
-        let denom = title_chars.saturating_mul(glyph_cols).max(1);
+        let denom = frame.measure_text_scaled(title, 1).0.max(1);
         let max_scale = 12u32;
-        let title_scale = (safe.w / denom).clamp(2, max_scale);
-        let title_w = denom.saturating_mul(title_scale).min(safe.w);
-        let title_h = (5u32).saturating_mul(title_scale).min(safe.h);
+        let min_scale = text_scale_for_height(height).min(max_scale);
+        let title_scale = (safe.w / denom).clamp(min_scale, max_scale);
+        let (title_w, title_h) = frame.measure_text_scaled(title, title_scale);
+        let title_w = title_w.min(safe.w);
+        let title_h = title_h.min(safe.h);
 
         let content = safe.inset(ui::Insets::all(pad));
         let button_size = ui::Size::new(240, 44).clamp_max(content.size());
@@ -289,7 +381,7 @@ impl MainMenuView {
             title_x,
             title_y,
             title,
-            COLOR_PAUSE_MENU_TEXT,
+            theme.menu_text,
             title_scale,
         );
 
@@ -352,17 +444,25 @@ impl MainMenuView {
         ui_tree.add_child(UI_MAIN_MENU_CONTAINER, UI_MAIN_MENU_QUIT);
 
         for (id, rect, label) in [
-            (UI_MAIN_MENU_START, start_button, "START"),
+            (UI_MAIN_MENU_START, start_button, "menu.start"),
             (
                 UI_MAIN_MENU_SKILLTREE_EDITOR,
                 skilltree_editor_button,
-                "SKILLTREE EDITOR",
+                "menu.skilltreeEditor",
             ),
-            (UI_MAIN_MENU_SETTINGS, settings_button, "SETTINGS"),
-            (UI_MAIN_MENU_QUIT, quit_button, "QUIT"),
+            (UI_MAIN_MENU_SETTINGS, settings_button, "menu.settings"),
+            (UI_MAIN_MENU_QUIT, quit_button, "menu.quit"),
         ] {
             let hovered = ui_tree.is_hovered(id);
-            draw_button(frame, width, height, rect, label, hovered);
+            draw_button(
+                frame,
+                width,
+                height,
+                rect,
+                &labels.label(label),
+                hovered,
+                theme,
+            );
         }
 
         MainMenuLayout {
@@ -382,7 +482,7 @@ impl GameOverMenuView {
         let mut ui_tree = UiTree::new();
         ui_tree.ensure_canvas(UI_CANVAS, ui::Rect::from_size(width, height));
         ui_tree.add_root(UI_CANVAS);
-        Self::render_with_ui(frame, width, height, &mut ui_tree)
+        Self::render_with_ui(frame, width, height, &mut ui_tree, &localization::english())
     }
 
     pub fn render_with_ui(
@@ -390,6 +490,18 @@ impl GameOverMenuView {
         width: u32,
         height: u32,
         ui_tree: &mut UiTree,
+        labels: &dyn Localizer,
+    ) -> GameOverMenuLayout {
+        Self::render_with_ui_and_theme(frame, width, height, ui_tree, labels, &Theme::default())
+    }
+
+    pub fn render_with_ui_and_theme(
+        frame: &mut dyn Renderer2d,
+        width: u32,
+        height: u32,
+        ui_tree: &mut UiTree,
+        labels: &dyn Localizer,
+        theme: &Theme,
     ) -> GameOverMenuLayout {
         // Dim the entire game view.
         blend_rect(
@@ -400,7 +512,7 @@ impl GameOverMenuView {
             0,
             width,
             height,
-            COLOR_PAUSE_MENU_DIM,
+            theme.menu_dim,
             PAUSE_MENU_DIM_ALPHA,
         );
 
@@ -428,7 +540,7 @@ impl GameOverMenuView {
             panel.y,
             panel.w,
             panel.h,
-            COLOR_PAUSE_MENU_BG,
+            theme.menu_bg,
         );
         draw_rect_outline(
             frame,
@@ -438,7 +550,7 @@ impl GameOverMenuView {
             panel.y,
             panel.w,
             panel.h,
-            COLOR_PAUSE_MENU_BORDER,
+            theme.menu_border,
         );
 
         draw_text(
@@ -447,8 +559,8 @@ impl GameOverMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad),
-            "GAME OVER",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("menu.gameOver"),
+            theme.menu_text,
         );
         draw_text(
             frame,
@@ -456,8 +568,8 @@ impl GameOverMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad + 24),
-            "RUN ENDED",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("menu.runEnded"),
+            theme.menu_text,
         );
         draw_text(
             frame,
@@ -465,8 +577,8 @@ impl GameOverMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad + 48),
-            "ENTER TO RESTART",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("menu.enterToRestart"),
+            theme.menu_text,
         );
         draw_text(
             frame,
@@ -474,8 +586,8 @@ impl GameOverMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad + 72),
-            "K: SKILL TREE",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("menu.skillTreeHint"),
+            theme.menu_text,
         );
         draw_text(
             frame,
@@ -483,8 +595,8 @@ impl GameOverMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad + 96),
-            "ESC: MAIN MENU",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("menu.mainMenuHint"),
+            theme.menu_text,
         );
 
         let button_h = 44u32.min(panel.h.saturating_sub(pad.saturating_mul(2)));
@@ -534,12 +646,20 @@ impl GameOverMenuView {
         ui_tree.add_child(UI_GAME_OVER_CONTAINER, UI_GAME_OVER_QUIT);
 
         for (id, rect, label) in [
-            (UI_GAME_OVER_RESTART, restart_button, "RESTART"),
-            (UI_GAME_OVER_SKILLTREE, skilltree_button, "SKILL TREE"),
-            (UI_GAME_OVER_QUIT, quit_button, "QUIT"),
+            (UI_GAME_OVER_RESTART, restart_button, "menu.restart"),
+            (UI_GAME_OVER_SKILLTREE, skilltree_button, "menu.skillTree"),
+            (UI_GAME_OVER_QUIT, quit_button, "menu.quit"),
         ] {
             let hovered = ui_tree.is_hovered(id);
-            draw_button(frame, width, height, rect, label, hovered);
+            draw_button(
+                frame,
+                width,
+                height,
+                rect,
+                &labels.label(label),
+                hovered,
+                theme,
+            );
         }
 
         GameOverMenuLayout {
@@ -563,7 +683,14 @@ impl SettingsMenuView {
         let mut ui_tree = UiTree::new();
         ui_tree.ensure_canvas(UI_CANVAS, ui::Rect::from_size(width, height));
         ui_tree.add_root(UI_CANVAS);
-        Self::render_with_ui(frame, width, height, &mut ui_tree, settings)
+        Self::render_with_ui(
+            frame,
+            width,
+            height,
+            &mut ui_tree,
+            settings,
+            &localization::english(),
+        )
     }
 
     pub fn render_with_ui(
@@ -572,6 +699,27 @@ impl SettingsMenuView {
         height: u32,
         ui_tree: &mut UiTree,
         settings: &PlayerSettings,
+        labels: &dyn Localizer,
+    ) -> SettingsMenuLayout {
+        Self::render_with_ui_and_theme(
+            frame,
+            width,
+            height,
+            ui_tree,
+            settings,
+            labels,
+            &Theme::default(),
+        )
+    }
+
+    pub fn render_with_ui_and_theme(
+        frame: &mut dyn Renderer2d,
+        width: u32,
+        height: u32,
+        ui_tree: &mut UiTree,
+        settings: &PlayerSettings,
+        labels: &dyn Localizer,
+        theme: &Theme,
     ) -> SettingsMenuLayout {
         blend_rect(
             frame,
@@ -581,7 +729,7 @@ impl SettingsMenuView {
             0,
             width,
             height,
-            COLOR_PAUSE_MENU_DIM,
+            theme.menu_dim,
             PAUSE_MENU_DIM_ALPHA,
         );
 
@@ -608,7 +756,7 @@ impl SettingsMenuView {
             panel.y,
             panel.w,
             panel.h,
-            COLOR_PAUSE_MENU_BG,
+            theme.menu_bg,
         );
         draw_rect_outline(
             frame,
@@ -618,7 +766,7 @@ impl SettingsMenuView {
             panel.y,
             panel.w,
             panel.h,
-            COLOR_PAUSE_MENU_BORDER,
+            theme.menu_border,
         );
 
         draw_text(
@@ -627,8 +775,8 @@ impl SettingsMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad),
-            "SETTINGS",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("menu.settings"),
+            theme.menu_text,
         );
         draw_text(
             frame,
@@ -636,8 +784,8 @@ impl SettingsMenuView {
             height,
             panel.x.saturating_add(pad),
             panel.y.saturating_add(pad + 24),
-            "ESC: BACK  DRAG SLIDERS TO APPLY",
-            COLOR_PAUSE_MENU_TEXT,
+            &labels.label("settings.hint"),
+            theme.menu_text,
         );
 
         ui_tree.ensure_container(UI_SETTINGS_MENU_CONTAINER, panel);
@@ -679,47 +827,83 @@ impl SettingsMenuView {
             frame,
             width,
             height,
-            slider_label_x,
-            row0_y,
-            "MASTER VOLUME",
+            (slider_label_x, row0_y),
+            &labels.label("settings.masterVolume"),
             Slider::new(master_track, 0.0, 1.0, settings.audio.master_volume),
-            true,
+            theme,
         );
         draw_slider_row(
             frame,
             width,
             height,
-            slider_label_x,
-            row0_y.saturating_add(slider_row_h),
-            "MUSIC VOLUME",
+            (slider_label_x, row0_y.saturating_add(slider_row_h)),
+            &labels.label("settings.musicVolume"),
             Slider::new(music_track, 0.0, 1.0, settings.audio.music_volume),
-            true,
+            theme,
         );
         draw_slider_row(
             frame,
             width,
             height,
-            slider_label_x,
-            row0_y.saturating_add(slider_row_h.saturating_mul(2)),
-            "SFX VOLUME",
+            (
+                slider_label_x,
+                row0_y.saturating_add(slider_row_h.saturating_mul(2)),
+            ),
+            &labels.label("settings.sfxVolume"),
             Slider::new(sfx_track, 0.0, 1.0, settings.audio.sfx_volume),
-            true,
+            theme,
         );
         draw_slider_row(
             frame,
             width,
             height,
-            slider_label_x,
-            row0_y.saturating_add(slider_row_h.saturating_mul(3)),
-            "SCREEN SHAKE",
+            (
+                slider_label_x,
+                row0_y.saturating_add(slider_row_h.saturating_mul(3)),
+            ),
+            &labels.label("settings.screenShake"),
             Slider::new(
                 shake_track,
                 0.0,
                 100.0,
                 settings.video.clamped_screen_shake() as f32,
             ),
-            false,
+            theme,
         );
+        for (id, track, value, max, step) in [
+            (
+                UI_SETTINGS_SLIDER_MASTER,
+                master_track,
+                settings.audio.master_volume,
+                1.0,
+                None,
+            ),
+            (
+                UI_SETTINGS_SLIDER_MUSIC,
+                music_track,
+                settings.audio.music_volume,
+                1.0,
+                None,
+            ),
+            (
+                UI_SETTINGS_SLIDER_SFX,
+                sfx_track,
+                settings.audio.sfx_volume,
+                1.0,
+                None,
+            ),
+            (
+                UI_SETTINGS_SLIDER_SHAKE,
+                shake_track,
+                settings.video.clamped_screen_shake() as f32,
+                100.0,
+                Some(1.0),
+            ),
+        ] {
+            ui_tree.ensure_slider(id, track, value, 0.0..=max);
+            ui_tree.set_slider_step(id, step);
+            ui_tree.add_child(UI_SETTINGS_MENU_CONTAINER, id);
+        }
 
         let toggle_y0 = row0_y
             .saturating_add(slider_row_h.saturating_mul(4))
@@ -758,52 +942,143 @@ impl SettingsMenuView {
             toggle_w,
             toggle_h,
         );
+        let das_meter_toggle = Rect::new(
+            left_x,
+            toggle_y0 + (toggle_h + toggle_gap) * 3,
+            toggle_w,
+            toggle_h,
+        );
+        let ghost_toggle = Rect::new(
+            right_x,
+            toggle_y0 + (toggle_h + toggle_gap) * 3,
+            toggle_w,
+            toggle_h,
+        );
+        let hold_toggle = Rect::new(
+            left_x,
+            toggle_y0 + (toggle_h + toggle_gap) * 4,
+            toggle_w,
+            toggle_h,
+        );
+        let theme_button = Rect::new(
+            right_x,
+            toggle_y0 + (toggle_h + toggle_gap) * 4,
+            toggle_w,
+            toggle_h,
+        );
+        // Left column only: the right column's sixth row is taken by the back button.
+        let piece_palette_button = Rect::new(
+            left_x,
+            toggle_y0 + (toggle_h + toggle_gap) * 5,
+            toggle_w,
+            toggle_h,
+        );
 
         for (id, rect, label, on) in [
             (
                 UI_SETTINGS_TOGGLE_MUTE,
                 mute_toggle,
-                "MUTE ALL",
+                "settings.muteAll",
                 settings.audio.mute_all,
             ),
             (
                 UI_SETTINGS_TOGGLE_MUSIC,
                 music_toggle,
-                "MUSIC ENABLED",
+                "settings.musicEnabled",
                 settings.audio.music_enabled,
             ),
             (
                 UI_SETTINGS_TOGGLE_TIMER,
                 show_timer_toggle,
-                "SHOW TIMER",
+                "settings.showTimer",
                 settings.gameplay.show_round_timer,
             ),
             (
                 UI_SETTINGS_TOGGLE_AUTO_PAUSE,
                 auto_pause_toggle,
-                "AUTO PAUSE (FOCUS)",
+                "settings.autoPause",
                 settings.gameplay.auto_pause_on_focus_loss,
             ),
             (
                 UI_SETTINGS_TOGGLE_HIGH_CONTRAST,
                 high_contrast_toggle,
-                "HIGH CONTRAST UI",
+                "settings.highContrast",
                 settings.accessibility.high_contrast_ui,
             ),
             (
                 UI_SETTINGS_TOGGLE_REDUCE_MOTION,
                 reduce_motion_toggle,
-                "REDUCE MOTION",
+                "settings.reduceMotion",
                 settings.accessibility.reduce_motion,
             ),
+            (
+                UI_SETTINGS_TOGGLE_DAS_METER,
+                das_meter_toggle,
+                "settings.dasMeter",
+                settings.gameplay.show_das_meter,
+            ),
+            (
+                UI_SETTINGS_TOGGLE_GHOST,
+                ghost_toggle,
+                "settings.showGhost",
+                settings.gameplay.show_ghost,
+            ),
+            (
+                UI_SETTINGS_TOGGLE_HOLD,
+                hold_toggle,
+                "settings.holdEnabled",
+                settings.gameplay.hold_enabled,
+            ),
         ] {
-            ui_tree.ensure_button(id, rect, None);
+            ui_tree.ensure_checkbox(id, rect, on);
             ui_tree.add_child(UI_SETTINGS_MENU_CONTAINER, id);
-            let state = if on { "ON" } else { "OFF" };
-            let line = format!("{label}: {state}");
-            draw_button(frame, width, height, rect, &line, ui_tree.is_hovered(id));
+            let state = labels.label(if on { "settings.on" } else { "settings.off" });
+            let line = format!("{}: {state}", labels.label(label));
+            draw_button(
+                frame,
+                width,
+                height,
+                rect,
+                &line,
+                ui_tree.is_hovered(id),
+                theme,
+            );
         }
 
+        ui_tree.ensure_button(UI_SETTINGS_CYCLE_THEME, theme_button, None);
+        ui_tree.add_child(UI_SETTINGS_MENU_CONTAINER, UI_SETTINGS_CYCLE_THEME);
+        let theme_name = labels.label(match settings.video.theme {
+            ThemeId::Classic => "settings.themeClassic",
+            ThemeId::Mono => "settings.themeMono",
+        });
+        let theme_line = format!("{}: {theme_name}", labels.label("settings.theme"));
+        draw_button(
+            frame,
+            width,
+            height,
+            theme_button,
+            &theme_line,
+            ui_tree.is_hovered(UI_SETTINGS_CYCLE_THEME),
+            theme,
+        );
+
+        ui_tree.ensure_button(UI_SETTINGS_CYCLE_PIECE_PALETTE, piece_palette_button, None);
+        ui_tree.add_child(UI_SETTINGS_MENU_CONTAINER, UI_SETTINGS_CYCLE_PIECE_PALETTE);
+        let palette_name = labels.label(match settings.accessibility.piece_palette {
+            PiecePalette::Classic => "settings.piecePaletteClassic",
+            PiecePalette::Colorblind => "settings.piecePaletteColorblind",
+        });
+        let palette_line = format!("{}: {palette_name}", labels.label("settings.piecePalette"));
+        draw_button(
+            frame,
+            width,
+            height,
+            piece_palette_button,
+            &palette_line,
+            ui_tree.is_hovered(UI_SETTINGS_CYCLE_PIECE_PALETTE),
+            theme,
+        );
+
         let button_size = ui::Size::new(220, 42).clamp_max(content.size());
         let back_button_ui = content.place(button_size, ui::Anchor::BottomRight);
         let back_button = Rect::new(
@@ -830,16 +1105,18 @@ impl SettingsMenuView {
             width,
             height,
             back_button,
-            "BACK",
+            &labels.label("settings.back"),
             ui_tree.is_hovered(UI_SETTINGS_BACK),
+            theme,
         );
         draw_button(
             frame,
             width,
             height,
             reset_button,
-            "RESET DEFAULTS",
+            &labels.label("settings.resetDefaults"),
             ui_tree.is_hovered(UI_SETTINGS_RESET),
+            theme,
         );
 
         SettingsMenuLayout {
@@ -854,6 +1131,11 @@ impl SettingsMenuView {
             auto_pause_toggle,
             high_contrast_toggle,
             reduce_motion_toggle,
+            das_meter_toggle,
+            ghost_toggle,
+            hold_toggle,
+            theme_button,
+            piece_palette_button,
             back_button,
             reset_button,
         }
@@ -864,11 +1146,10 @@ fn draw_slider_row(
     frame: &mut dyn Renderer2d,
     width: u32,
     height: u32,
-    label_x: u32,
-    label_y: u32,
+    (label_x, label_y): (u32, u32),
     label: &str,
     slider: Slider,
-    unit_percent: bool,
+    theme: &Theme,
 ) {
     draw_text(
         frame,
@@ -877,7 +1158,7 @@ fn draw_slider_row(
         label_x,
         label_y,
         label,
-        COLOR_PAUSE_MENU_TEXT,
+        theme.menu_text,
     );
 
     let track = slider.track;
@@ -899,7 +1180,7 @@ fn draw_slider_row(
         track.y,
         track.w,
         track.h,
-        COLOR_PAUSE_MENU_BORDER,
+        theme.menu_border,
     );
 
     let t = slider.normalized_value();
@@ -937,11 +1218,7 @@ fn draw_slider_row(
         [16, 16, 24, 255],
     );
 
-    let value = if unit_percent {
-        format!("{:>3}%", (slider.value * 100.0).round() as i32)
-    } else {
-        format!("{:>3}%", slider.value.round() as i32)
-    };
+    let value = slider.percent_label();
     draw_text(
         frame,
         width,
@@ -949,7 +1226,7 @@ fn draw_slider_row(
         track.x.saturating_add(track.w).saturating_sub(54),
         label_y,
         &value,
-        COLOR_PAUSE_MENU_TEXT,
+        theme.menu_text,
     );
 }
 
@@ -962,8 +1239,47 @@ pub fn draw_pause_menu_with_ui(
     width: u32,
     height: u32,
     ui_tree: &mut UiTree,
+    labels: &dyn Localizer,
+) -> PauseMenuLayout {
+    PauseMenuView::render_with_ui(frame, width, height, ui_tree, labels)
+}
+
+pub fn draw_pause_menu_with_ui_and_restart_confirm(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    ui_tree: &mut UiTree,
+    restart_armed: bool,
+    labels: &dyn Localizer,
 ) -> PauseMenuLayout {
-    PauseMenuView::render_with_ui(frame, width, height, ui_tree)
+    PauseMenuView::render_with_ui_and_restart_confirm(
+        frame,
+        width,
+        height,
+        ui_tree,
+        restart_armed,
+        labels,
+    )
+}
+
+pub fn draw_pause_menu_with_ui_restart_confirm_and_theme(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    ui_tree: &mut UiTree,
+    restart_armed: bool,
+    labels: &dyn Localizer,
+    theme: &Theme,
+) -> PauseMenuLayout {
+    PauseMenuView::render_with_ui_restart_confirm_and_theme(
+        frame,
+        width,
+        height,
+        ui_tree,
+        restart_armed,
+        labels,
+        theme,
+    )
 }
 
 pub fn draw_main_menu(frame: &mut dyn Renderer2d, width: u32, height: u32) -> MainMenuLayout {
@@ -975,8 +1291,20 @@ pub fn draw_main_menu_with_ui(
     width: u32,
     height: u32,
     ui_tree: &mut UiTree,
+    labels: &dyn Localizer,
 ) -> MainMenuLayout {
-    MainMenuView::render_with_ui(frame, width, height, ui_tree)
+    MainMenuView::render_with_ui(frame, width, height, ui_tree, labels)
+}
+
+pub fn draw_main_menu_with_ui_and_theme(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    ui_tree: &mut UiTree,
+    labels: &dyn Localizer,
+    theme: &Theme,
+) -> MainMenuLayout {
+    MainMenuView::render_with_ui_and_theme(frame, width, height, ui_tree, labels, theme)
 }
 
 pub fn draw_game_over_menu(
@@ -992,8 +1320,20 @@ pub fn draw_game_over_menu_with_ui(
     width: u32,
     height: u32,
     ui_tree: &mut UiTree,
+    labels: &dyn Localizer,
 ) -> GameOverMenuLayout {
-    GameOverMenuView::render_with_ui(frame, width, height, ui_tree)
+    GameOverMenuView::render_with_ui(frame, width, height, ui_tree, labels)
+}
+
+pub fn draw_game_over_menu_with_ui_and_theme(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    ui_tree: &mut UiTree,
+    labels: &dyn Localizer,
+    theme: &Theme,
+) -> GameOverMenuLayout {
+    GameOverMenuView::render_with_ui_and_theme(frame, width, height, ui_tree, labels, theme)
 }
 
 pub fn draw_settings_menu(
@@ -1011,6 +1351,21 @@ pub fn draw_settings_menu_with_ui(
     height: u32,
     ui_tree: &mut UiTree,
     settings: &PlayerSettings,
+    labels: &dyn Localizer,
+) -> SettingsMenuLayout {
+    SettingsMenuView::render_with_ui(frame, width, height, ui_tree, settings, labels)
+}
+
+pub fn draw_settings_menu_with_ui_and_theme(
+    frame: &mut dyn Renderer2d,
+    width: u32,
+    height: u32,
+    ui_tree: &mut UiTree,
+    settings: &PlayerSettings,
+    labels: &dyn Localizer,
+    theme: &Theme,
 ) -> SettingsMenuLayout {
-    SettingsMenuView::render_with_ui(frame, width, height, ui_tree, settings)
+    SettingsMenuView::render_with_ui_and_theme(
+        frame, width, height, ui_tree, settings, labels, theme,
+    )
 }
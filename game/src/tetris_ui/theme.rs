@@ -0,0 +1,105 @@
+use engine::render::CellPalette;
+
+use crate::settings::{AccessibilitySettings, PiecePalette, ThemeId};
+
+/// Colors used by the `tetris_ui` draw functions. `Theme::default()` is the classic palette the
+/// UI has always drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub name: &'static str,
+    /// Board, piece, and preview fills (also feeds skilltree node colors).
+    pub cells: CellPalette,
+    pub panel_bg: [u8; 4],
+    pub panel_border: [u8; 4],
+    pub panel_border_disabled: [u8; 4],
+    /// HUD text and the pause icon.
+    pub hud_text: [u8; 4],
+    pub depth_locked: [u8; 4],
+    pub depth_wall_fill: [u8; 4],
+    pub depth_wall_border: [u8; 4],
+    pub menu_text: [u8; 4],
+    pub menu_dim: [u8; 4],
+    pub menu_bg: [u8; 4],
+    pub menu_border: [u8; 4],
+    pub tip_marker: [u8; 4],
+    pub line_clear_flash: [u8; 4],
+    pub skilltree_link: [u8; 4],
+    pub skilltree_cursor: [u8; 4],
+    pub skilltree_guide: [u8; 4],
+    pub skilltree_cycle: [u8; 4],
+}
+
+impl Theme {
+    pub const CLASSIC: Theme = Theme {
+        name: "classic",
+        cells: CellPalette::CLASSIC,
+        panel_bg: [16, 16, 22, 255],
+        panel_border: [40, 40, 55, 255],
+        panel_border_disabled: [28, 28, 38, 255],
+        hud_text: [235, 235, 245, 255],
+        depth_locked: [255, 188, 112, 255],
+        depth_wall_fill: [24, 20, 16, 255],
+        depth_wall_border: [120, 92, 62, 255],
+        menu_text: [235, 235, 245, 255],
+        menu_dim: [0, 0, 0, 255],
+        menu_bg: [10, 10, 14, 255],
+        menu_border: [40, 40, 55, 255],
+        tip_marker: [245, 235, 170, 255],
+        line_clear_flash: [255, 255, 255, 255],
+        skilltree_link: [110, 110, 150, 255],
+        skilltree_cursor: [255, 220, 120, 255],
+        skilltree_guide: [96, 170, 235, 255],
+        skilltree_cycle: [235, 84, 84, 255],
+    };
+
+    /// Grayscale pieces and neutral chrome; warnings keep a little brightness contrast.
+    pub const MONO: Theme = Theme {
+        name: "mono",
+        cells: CellPalette::CLASSIC.grayscale(),
+        panel_bg: [14, 14, 14, 255],
+        panel_border: [60, 60, 60, 255],
+        panel_border_disabled: [32, 32, 32, 255],
+        hud_text: [230, 230, 230, 255],
+        depth_locked: [255, 255, 255, 255],
+        depth_wall_fill: [20, 20, 20, 255],
+        depth_wall_border: [110, 110, 110, 255],
+        menu_text: [230, 230, 230, 255],
+        menu_dim: [0, 0, 0, 255],
+        menu_bg: [8, 8, 8, 255],
+        menu_border: [60, 60, 60, 255],
+        tip_marker: [250, 250, 250, 255],
+        line_clear_flash: [255, 255, 255, 255],
+        skilltree_link: [120, 120, 120, 255],
+        skilltree_cursor: [240, 240, 240, 255],
+        skilltree_guide: [170, 170, 170, 255],
+        skilltree_cycle: [255, 255, 255, 255],
+    };
+
+    pub fn for_id(id: ThemeId) -> Theme {
+        match id {
+            ThemeId::Classic => Self::CLASSIC,
+            ThemeId::Mono => Self::MONO,
+        }
+    }
+
+    /// `for_id` with the accessibility piece palette applied and, with `high_contrast_ui`, a
+    /// brighter board grid. `Mono` keeps its grayscale look over the colorblind luminance steps.
+    pub fn for_settings(id: ThemeId, accessibility: &AccessibilitySettings) -> Theme {
+        let theme = Self::for_id(id);
+        let mut cells = match (accessibility.piece_palette, id) {
+            (PiecePalette::Classic, _) => theme.cells,
+            (PiecePalette::Colorblind, ThemeId::Classic) => CellPalette::COLORBLIND,
+            (PiecePalette::Colorblind, ThemeId::Mono) => CellPalette::COLORBLIND.grayscale(),
+        };
+        if accessibility.high_contrast_ui {
+            cells = cells.with_high_contrast_grid();
+        }
+        Theme { cells, ..theme }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
@@ -16,6 +16,7 @@ pub const UI_PAUSE_MENU_CONTAINER: UiId = UiId(300);
 pub const UI_PAUSE_RESUME: UiId = UiId(301);
 pub const UI_PAUSE_END_RUN: UiId = UiId(302);
 pub const UI_PAUSE_SETTINGS: UiId = UiId(303);
+pub const UI_PAUSE_RESTART: UiId = UiId(304);
 
 pub const UI_GAME_OVER_CONTAINER: UiId = UiId(400);
 pub const UI_GAME_OVER_RESTART: UiId = UiId(401);
@@ -40,6 +41,15 @@ pub const UI_SETTINGS_TOGGLE_TIMER: UiId = UiId(605);
 pub const UI_SETTINGS_TOGGLE_AUTO_PAUSE: UiId = UiId(606);
 pub const UI_SETTINGS_TOGGLE_HIGH_CONTRAST: UiId = UiId(607);
 pub const UI_SETTINGS_TOGGLE_REDUCE_MOTION: UiId = UiId(608);
+pub const UI_SETTINGS_TOGGLE_DAS_METER: UiId = UiId(609);
+pub const UI_SETTINGS_SLIDER_MASTER: UiId = UiId(610);
+pub const UI_SETTINGS_SLIDER_MUSIC: UiId = UiId(611);
+pub const UI_SETTINGS_SLIDER_SFX: UiId = UiId(612);
+pub const UI_SETTINGS_SLIDER_SHAKE: UiId = UiId(613);
+pub const UI_SETTINGS_TOGGLE_GHOST: UiId = UiId(614);
+pub const UI_SETTINGS_TOGGLE_HOLD: UiId = UiId(615);
+pub const UI_SETTINGS_CYCLE_THEME: UiId = UiId(616);
+pub const UI_SETTINGS_CYCLE_PIECE_PALETTE: UiId = UiId(617);
 
 pub const ACTION_MAIN_MENU_START: UiAction = UiAction(1);
 pub const ACTION_MAIN_MENU_SKILLTREE_EDITOR: UiAction = UiAction(2);
@@ -57,3 +67,5 @@ pub const ACTION_SKILLTREE_TOOL_MOVE: UiAction = UiAction(13);
 pub const ACTION_SKILLTREE_TOOL_ADD_CELL: UiAction = UiAction(14);
 pub const ACTION_SKILLTREE_TOOL_REMOVE_CELL: UiAction = UiAction(15);
 pub const ACTION_SKILLTREE_TOOL_LINK: UiAction = UiAction(16);
+pub const ACTION_PAUSE_RESTART: UiAction = UiAction(17);
+pub const ACTION_PAUSE_SETTINGS: UiAction = UiAction(18);
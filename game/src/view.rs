@@ -22,6 +22,8 @@ pub enum GameViewEvent {
     Back,
     TogglePause,
     GameOver,
+    /// Abandon the paused run and start a fresh one.
+    Restart,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -59,6 +61,10 @@ impl GameView {
             (GameView::Tetris { .. }, GameViewEvent::GameOver) => {
                 (GameView::GameOver, GameViewEffect::None)
             }
+            (GameView::Tetris { paused: true }, GameViewEvent::Restart) => (
+                GameView::Tetris { paused: false },
+                GameViewEffect::ResetTetris,
+            ),
 
             (GameView::GameOver, GameViewEvent::StartGame) => (
                 GameView::Tetris { paused: false },
@@ -96,6 +102,21 @@ mod tests {
         assert_eq!(GameView::default(), GameView::MainMenu);
     }
 
+    #[test]
+    fn restart_only_applies_to_a_paused_run() {
+        assert_eq!(
+            GameView::Tetris { paused: true }.handle(GameViewEvent::Restart),
+            (
+                GameView::Tetris { paused: false },
+                GameViewEffect::ResetTetris
+            )
+        );
+        assert_eq!(
+            GameView::Tetris { paused: false }.handle(GameViewEvent::Restart),
+            (GameView::Tetris { paused: false }, GameViewEffect::None)
+        );
+    }
+
     #[test]
     fn start_game_from_main_menu_enters_tetris_and_requests_reset() {
         assert_eq!(
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::state::GameState;
 use crate::tetris_ui::MAIN_MENU_TITLE;
 use crate::tetris_ui::compute_layout;
+use crate::tetris_ui::pause_menu_layout;
 use crate::view::GameView;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -23,6 +24,8 @@ pub enum GameUiAction {
     Resume,
     EndRun,
     Restart,
+    RestartRun,
+    OpenSettings,
 }
 
 pub fn build_menu_view_tree(view: GameView, width: u32, height: u32) -> ViewTree<GameUiAction> {
@@ -43,16 +46,40 @@ pub fn build_menu_view_tree(view: GameView, width: u32, height: u32) -> ViewTree
             }
         }
         GameView::Tetris { paused: true } => {
-            if let Some((resume, end_run)) = pause_menu_button_rects(width, height) {
-                push_button(&mut tree, 10, resume, "RESUME", GameUiAction::Resume, true);
+            let layout = pause_menu_layout(width, height);
+            if layout.panel.w > 0 && layout.panel.h > 0 {
+                push_button(
+                    &mut tree,
+                    10,
+                    layout.resume_button,
+                    "RESUME",
+                    GameUiAction::Resume,
+                    true,
+                );
                 push_button(
                     &mut tree,
                     11,
-                    end_run,
+                    layout.end_run_button,
                     "END RUN",
                     GameUiAction::EndRun,
                     true,
                 );
+                push_button(
+                    &mut tree,
+                    12,
+                    layout.settings_button,
+                    "SETTINGS",
+                    GameUiAction::OpenSettings,
+                    true,
+                );
+                push_button(
+                    &mut tree,
+                    13,
+                    layout.restart_button,
+                    "RESTART",
+                    GameUiAction::RestartRun,
+                    true,
+                );
             }
         }
         GameView::GameOver => {
@@ -244,51 +271,6 @@ fn main_menu_button_rects(width: u32, height: u32) -> Option<(ui::Rect, ui::Rect
     Some((start_button, skilltree_editor_button, quit_button))
 }
 
-fn pause_menu_button_rects(width: u32, height: u32) -> Option<(ui::Rect, ui::Rect)> {
-    let margin = 32u32;
-    let pad = 18u32;
-
-    let screen = ui::Rect::from_size(width, height);
-    let safe = screen.inset(ui::Insets::all(margin));
-    if safe.w == 0 || safe.h == 0 {
-        return None;
-    }
-
-    let panel_size = ui::Size::new(380, 320).clamp_max(safe.size());
-    if panel_size.w == 0 || panel_size.h == 0 {
-        return None;
-    }
-
-    let panel_ui = safe.place(panel_size, ui::Anchor::Center);
-    let content = panel_ui.inset(ui::Insets::all(pad));
-    let gap = 12u32;
-    let button_size = ui::Size::new(240, 44).clamp_max(content.size());
-    let resume_ui = content.place(button_size, ui::Anchor::BottomCenter);
-    let resume_button = ui::Rect {
-        x: resume_ui.x,
-        y: resume_ui.y,
-        w: resume_ui.w,
-        h: resume_ui.h,
-    };
-    let settings_button = ui::Rect {
-        x: resume_button.x,
-        y: resume_button
-            .y
-            .saturating_sub(resume_button.h.saturating_add(gap)),
-        w: resume_button.w,
-        h: resume_button.h,
-    };
-    let end_run_button = ui::Rect {
-        x: resume_button.x,
-        y: settings_button
-            .y
-            .saturating_sub(settings_button.h.saturating_add(gap)),
-        w: resume_button.w,
-        h: resume_button.h,
-    };
-    Some((resume_button, end_run_button))
-}
-
 fn game_over_button_rects(width: u32, height: u32) -> Option<(ui::Rect, ui::Rect, ui::Rect)> {
     let margin = 32u32;
     let pad = 18u32;
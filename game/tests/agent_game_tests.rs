@@ -1,5 +1,9 @@
 use engine::GameLogic;
-use game::agent::{TetrisAction, TetrisGame};
+use game::agent::{
+    BoardFeatures, HeuristicBot, HeuristicWeights, PlayPolicy, TetrisAction, TetrisGame,
+    run_policy_games,
+};
+use game::playtest::{InputAction, TetrisLogic};
 use game::tetris_core::{BOARD_HEIGHT, Piece, Vec2i};
 
 #[test]
@@ -35,3 +39,53 @@ fn tetris_game_rotate_updates_rotation() {
     let rotated = game.step(&state, TetrisAction::RotateCw);
     assert_eq!(rotated.tetris.current_piece_rotation(), 1);
 }
+
+#[test]
+fn board_features_ignore_complete_rows() {
+    let board = vec![
+        vec![1, 1, 1, 1],
+        vec![1, 0, 1, 0],
+        vec![0, 0, 1, 0],
+        vec![1, 0, 0, 0],
+    ];
+    let features = BoardFeatures::from_board(&board);
+    assert_eq!(features.complete_lines, 1);
+    // Without the full row: heights 3,0,2,0 with a hole under column 0's top cell.
+    assert_eq!(features.aggregate_height, 5);
+    assert_eq!(features.holes, 1);
+    assert_eq!(features.bumpiness, 3 + 2 + 2);
+}
+
+#[test]
+fn heuristic_bot_plan_ends_with_hard_drop() {
+    let state = TetrisLogic::new(1, Piece::all()).initial_state();
+    let plan = HeuristicBot::default().plan(&state.tetris);
+    assert_eq!(plan.last(), Some(&InputAction::HardDrop));
+}
+
+#[test]
+fn heuristic_bot_clears_lines_deterministically() {
+    let logic = TetrisLogic::new(0, Piece::all());
+    let bot = HeuristicBot::default();
+    let report = run_policy_games(&logic, &bot, &[3, 11], 80);
+    assert_eq!(report.games(), 2);
+    assert!(
+        report.average_lines() > 0.0,
+        "bot cleared no lines: {report:?}"
+    );
+    assert_eq!(report, run_policy_games(&logic, &bot, &[3, 11], 80));
+}
+
+#[test]
+fn heuristic_weights_are_configurable() {
+    let state = TetrisLogic::new(5, Piece::all()).initial_state();
+    let flat = HeuristicBot::new(HeuristicWeights {
+        aggregate_height: 0.0,
+        complete_lines: 0.0,
+        holes: 0.0,
+        bumpiness: 0.0,
+    });
+    // With every weight zeroed all placements tie, so the first candidate (no rotation, no
+    // shift) wins.
+    assert_eq!(flat.plan(&state.tetris), vec![InputAction::HardDrop]);
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use engine::graphics::{CpuRenderer, Renderer2d};
+use engine::profiling::{FrameTimings, Profiler};
 use engine::surface::SurfaceSize;
 use game::debug::DebugHud;
 
@@ -91,14 +92,15 @@ fn debug_hud_emits_lines_with_expected_labels() {
     let mut hud = DebugHud::new();
     hud.record_input(Duration::from_micros(250));
     hud.record_gravity(Duration::from_micros(500));
-    hud.on_frame(
-        Duration::from_millis(16),
-        Duration::from_millis(1),
-        Duration::from_millis(2),
-        Duration::from_millis(1),
-        Duration::from_millis(3),
-        Duration::from_millis(7),
-    );
+    hud.on_frame(FrameTimings {
+        frame_dt: Duration::from_millis(16),
+        board: Some(Duration::from_millis(1)),
+        draw: Some(Duration::from_millis(2)),
+        overlay: Some(Duration::from_millis(1)),
+        present: Some(Duration::from_millis(3)),
+        total: Duration::from_millis(7),
+        ..FrameTimings::default()
+    });
 
     let lines = hud.lines();
     assert!(
@@ -193,3 +195,19 @@ fn clicking_timer_line_toggles_round_timer_override() {
     ));
     assert!(!hud.round_timer_disabled());
 }
+
+#[test]
+fn overlay_text_scales_up_on_hidpi_surfaces() {
+    let mut hud = DebugHud::new();
+    let small = hud
+        .timer_toggle_rect(1920, 1080)
+        .expect("timer toggle rect at 1080p");
+    let large = hud
+        .timer_toggle_rect(3840, 2160)
+        .expect("timer toggle rect at 4K");
+    assert_eq!(large.h, small.h * 2, "4K should double the line height");
+
+    // Hit-testing uses the same scale as drawing.
+    assert!(hud.handle_click(large.x + 1, large.y + large.h - 1, 3840, 2160));
+    assert!(hud.round_timer_disabled());
+}
@@ -1,7 +1,9 @@
 use std::time::Duration;
 
-use game::state::GameState;
-use game::tetris_core::{Piece, TetrisCore};
+use engine::TimeMachine;
+use engine::regression::validate_recording;
+use game::state::{GameState, recording_invariants};
+use game::tetris_core::{BOARD_WIDTH, Piece, TetrisCore};
 use game::view::GameView;
 
 #[test]
@@ -33,3 +35,41 @@ fn game_state_round_trip_preserves_state() {
     );
     assert!(restored.skilltree.is_unlocked("start"));
 }
+
+#[test]
+fn recording_invariants_flag_score_drops_mid_run() {
+    let mut core = TetrisCore::new(0);
+    core.set_available_pieces(vec![Piece::O]);
+    core.initialize_game();
+    let fresh = GameState::new(core);
+
+    let mut scored = fresh.clone();
+    for x in 0..BOARD_WIDTH {
+        scored.tetris.set_cell(x, 0, 1);
+    }
+    assert_eq!(scored.tetris.clear_lines(), 1);
+    scored.tetris.add_score(500);
+
+    let mut higher = scored.clone();
+    higher.tetris.add_score(100);
+    let high = higher.tetris.score();
+    let mut lowered = scored.clone();
+    lowered.tetris.add_score(50);
+
+    // fresh -> scored -> restart (fresh) is a clean recording.
+    let mut tm = TimeMachine::new(fresh.clone());
+    tm.record(scored.clone());
+    tm.record(fresh.clone());
+    assert_eq!(validate_recording(&tm, recording_invariants()), Ok(()));
+
+    let mut tm = TimeMachine::new(fresh);
+    tm.record(scored);
+    tm.record(higher);
+    tm.record(lowered.clone());
+    let err = validate_recording(&tm, recording_invariants()).expect_err("score went down");
+    assert_eq!(err.frame, 3);
+    assert_eq!(
+        err.message,
+        format!("score decreased from {high} to {}", lowered.tetris.score())
+    );
+}
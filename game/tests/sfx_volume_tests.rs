@@ -1,4 +1,7 @@
-use game::sfx::{ACTION_SFX_VOLUME, LINE_CLEAR_SFX_VOLUME, MOVE_PIECE_SFX_VOLUME, MUSIC_VOLUME};
+use game::sfx::{
+    ACTION_SFX_VOLUME, LINE_CLEAR_SFX_VOLUME, MOVE_PIECE_SFX_VOLUME, MUSIC_VOLUME, SFX_HARD_DROP,
+    SFX_LINE_CLEAR, find_sound_file,
+};
 
 #[test]
 fn move_piece_sfx_is_softer_than_other_actions() {
@@ -28,3 +31,23 @@ fn sfx_volumes_are_in_valid_range() {
         assert!(v <= 1.0, "{name} volume must be <= 1.0, got {v}");
     }
 }
+
+#[test]
+fn find_sound_file_prefers_supported_extensions_and_skips_missing() {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("rollout_game_test_sfx_{nanos}"));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("hard_drop.ogg"), b"").unwrap();
+    std::fs::write(dir.join("line_clear.mp3"), b"").unwrap();
+
+    assert_eq!(
+        find_sound_file(&dir, SFX_HARD_DROP),
+        Some(dir.join("hard_drop.ogg"))
+    );
+    assert_eq!(find_sound_file(&dir, SFX_LINE_CLEAR), None);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
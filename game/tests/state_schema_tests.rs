@@ -0,0 +1,71 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use engine::TimeMachine;
+use game::{
+    state::{GameState, STATE_SCHEMA_VERSION, StateMigrationError, migrate},
+    tetris_core::TetrisCore,
+};
+
+fn unique_temp_json_path() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    std::env::temp_dir().join(format!("rollout_game_test_state_schema_{nanos}.json"))
+}
+
+fn state_value_with_version(version: Option<u32>) -> serde_json::Value {
+    let state = GameState::new(TetrisCore::new(7));
+    let mut value = serde_json::to_value(&state).expect("serialize game state");
+    let obj = value
+        .as_object_mut()
+        .expect("game state serializes as an object");
+    match version {
+        Some(v) => obj.insert("schema_version".to_string(), v.into()),
+        None => obj.remove("schema_version"),
+    };
+    value
+}
+
+#[test]
+fn unversioned_recording_migrates_to_current_schema() {
+    let out = unique_temp_json_path();
+    let recording = serde_json::json!({
+        "states": [state_value_with_version(None)],
+        "frame": 0,
+    });
+    fs::write(&out, recording.to_string()).expect("write recording json");
+
+    let tm = TimeMachine::<GameState>::load_json_file(&out).expect("load old recording");
+    assert_eq!(tm.state().schema_version, STATE_SCHEMA_VERSION);
+
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn newer_schema_reports_a_clear_error() {
+    let out = unique_temp_json_path();
+    let recording = serde_json::json!({
+        "states": [state_value_with_version(Some(STATE_SCHEMA_VERSION + 1))],
+        "frame": 0,
+    });
+    fs::write(&out, recording.to_string()).expect("write recording json");
+
+    let err = TimeMachine::<GameState>::load_json_file(&out).unwrap_err();
+    assert!(
+        err.to_string().contains("only understands up to"),
+        "unexpected error: {err}"
+    );
+
+    let _ = fs::remove_file(out);
+}
+
+#[test]
+fn migrate_reports_shape_errors_with_version() {
+    let err = migrate(0, serde_json::json!({ "view": "nonsense" })).unwrap_err();
+    assert!(matches!(err, StateMigrationError::Invalid { found: 0, .. }));
+}
@@ -2,10 +2,9 @@ use std::collections::HashSet;
 
 use game::tetris_core::{
     BOARD_HEIGHT, BOARD_WIDTH, CELL_DIRT, CELL_EMPTY, CELL_GARBAGE, CELL_GLASS, CELL_MOSS,
-    CELL_MOSS_SEED, CELL_SAND, CELL_STONE,
-    DEFAULT_BOTTOMWELL_ROWS, GravityAdvanceResult, LINE_CLEAR_DELAY_MS_DEFAULT,
-    LOCK_DELAY_MAX_MS_DEFAULT, LOCK_DELAY_MS_DEFAULT, NEXT_QUEUE_LEN, Piece, RotationDir,
-    TetrisCore, Vec2i,
+    CELL_MOSS_SEED, CELL_SAND, CELL_STONE, DEFAULT_BOTTOMWELL_ROWS, GravityAdvanceResult,
+    LINE_CLEAR_DELAY_MS_DEFAULT, LOCK_DELAY_MAX_MS_DEFAULT, LOCK_DELAY_MS_DEFAULT, NEXT_QUEUE_LEN,
+    Piece, Randomizer, RotationDir, TetrisCore, Vec2i,
 };
 
 fn grounded_o_piece_core() -> TetrisCore {
@@ -539,6 +538,38 @@ fn successful_ground_moves_reset_delay() {
     );
 }
 
+#[test]
+fn exceeding_lock_reset_cap_forces_lock() {
+    let mut core = grounded_o_piece_core();
+    core.set_lock_reset_limit(2);
+    core.set_lock_delay_max_ms(10_000);
+
+    for dx in [1, -1] {
+        assert_eq!(
+            core.advance_with_gravity(400),
+            GravityAdvanceResult::Grounded
+        );
+        assert!(core.move_piece(Vec2i::new(dx, 0)));
+        assert_eq!(core.grounded_lock_ms(), 0);
+    }
+    assert_eq!(core.lock_resets(), 2);
+
+    assert_eq!(
+        core.advance_with_gravity(250),
+        GravityAdvanceResult::Grounded
+    );
+    assert_eq!(
+        core.advance_with_gravity(250),
+        GravityAdvanceResult::Grounded
+    );
+    // Cap reached: this move does not restart the timer, so the next tick locks.
+    assert!(core.move_piece(Vec2i::new(1, 0)));
+    assert_eq!(core.grounded_lock_ms(), 250);
+    assert_eq!(core.lock_resets(), 2);
+    assert_eq!(core.advance_with_gravity(250), GravityAdvanceResult::Locked);
+    assert_eq!(core.lock_resets(), 0);
+}
+
 #[test]
 fn o_piece_spin_without_relocation_does_not_reset_delay() {
     let mut core = grounded_o_piece_core();
@@ -761,3 +792,158 @@ fn inputs_are_ignored_during_line_clear_delay() {
     );
     assert!(core.is_line_clear_active());
 }
+
+/// Fills `core` from `rows` (top row first, `X` = filled) starting at y=0 for the last row.
+fn set_rows(core: &mut TetrisCore, rows: &[&str]) {
+    for (y, row) in rows.iter().rev().enumerate() {
+        for (x, ch) in row.chars().enumerate() {
+            if ch == 'X' {
+                core.set_cell(x, y, CELL_STONE);
+            }
+        }
+    }
+}
+
+#[test]
+fn srs_kick_lands_classic_t_spin_triple() {
+    let mut core = TetrisCore::new(0);
+    core.set_available_pieces(vec![Piece::O]);
+    core.initialize_game();
+    core.set_line_clear_delay_ms(0);
+    set_rows(
+        &mut core,
+        &[
+            "XX........",
+            "X.........",
+            "X.XXXXXXXX",
+            "X..XXXXXXX",
+            "X.XXXXXXXX",
+        ],
+    );
+
+    // Slide the flat T under the overhang, then rotate CW: SRS 0->R test 5 (-1, -2) drops it
+    // into the slot.
+    core.set_current_piece_for_test(Piece::T, Vec2i::new(3, 3), 0);
+    assert!(core.move_piece(Vec2i::new(-1, 0)));
+    assert!(core.rotate_piece(RotationDir::Cw));
+    assert_eq!(core.current_piece_pos(), Vec2i::new(1, 1));
+    assert_eq!(core.current_piece_rotation(), 1);
+
+    core.hard_drop();
+    assert!(core.last_action_was_tspin());
+    core.advance_with_gravity(0);
+    assert_eq!(core.lines_cleared(), 3);
+}
+
+#[test]
+fn t_spin_requires_rotation_as_last_move() {
+    let mut core = TetrisCore::new(0);
+    core.set_available_pieces(vec![Piece::O]);
+    core.initialize_game();
+    set_rows(&mut core, &["X.X.......", "...X......", "X.X......."]);
+
+    // Same 3-corner pocket, but the T slides in instead of rotating in.
+    core.set_current_piece_for_test(Piece::T, Vec2i::new(1, 4), 1);
+    core.hard_drop();
+    assert!(!core.last_action_was_tspin());
+}
+
+#[test]
+fn srs_wall_kick_rotates_t_off_the_left_wall() {
+    let mut core = TetrisCore::new(0);
+    core.set_available_pieces(vec![Piece::O]);
+    core.initialize_game();
+
+    // Vertical T hugging the wall with its nub pointing right; rotating CCW to spawn would put
+    // a cell at x = -1, so SRS R->0 test 2 (+1, 0) shifts it right.
+    core.set_current_piece_for_test(Piece::T, Vec2i::new(0, 5), 1);
+    assert!(core.rotate_piece(RotationDir::Ccw));
+    assert_eq!(core.current_piece_pos(), Vec2i::new(1, 5));
+    assert_eq!(core.current_piece_rotation(), 0);
+}
+
+/// Locks `piece` at rest at `pos` (no drop points) and commits any resulting line clear.
+fn lock_in_place(core: &mut TetrisCore, piece: Piece, pos: Vec2i, rotation: u8) -> u32 {
+    let before = core.score();
+    core.set_current_piece_for_test(piece, pos, rotation);
+    assert_eq!(core.hard_drop(), 0);
+    core.advance_with_gravity(0);
+    core.score() - before
+}
+
+#[test]
+fn combo_grows_on_consecutive_clears_and_breaks_on_a_dry_placement() {
+    let mut core = TetrisCore::new(0);
+    core.set_available_pieces(vec![Piece::O]);
+    core.initialize_game();
+    core.set_line_clear_delay_ms(0);
+    for y in 0..4 {
+        for x in 0..BOARD_WIDTH {
+            if x != 4 && x != 5 {
+                core.set_cell(x, y, CELL_STONE);
+            }
+        }
+    }
+
+    assert_eq!(lock_in_place(&mut core, Piece::O, Vec2i::new(4, 1), 0), 300);
+    assert_eq!(core.combo(), 1);
+    // Second consecutive clear earns the 50-point combo step on top of the double.
+    assert_eq!(lock_in_place(&mut core, Piece::O, Vec2i::new(4, 1), 0), 350);
+    assert_eq!(core.combo(), 2);
+    assert!(!core.back_to_back());
+
+    assert_eq!(lock_in_place(&mut core, Piece::O, Vec2i::new(0, 1), 0), 0);
+    assert_eq!(core.combo(), 0);
+}
+
+#[test]
+fn back_to_back_tetrises_earn_half_again() {
+    let mut core = TetrisCore::new(0);
+    core.set_available_pieces(vec![Piece::O]);
+    core.initialize_game();
+    core.set_line_clear_delay_ms(0);
+    for y in 0..8 {
+        for x in 0..BOARD_WIDTH - 1 {
+            core.set_cell(x, y, CELL_STONE);
+        }
+    }
+
+    // Vertical I in the right-hand well (rotation 1 puts its cells at x = pos.x + 1).
+    assert_eq!(lock_in_place(&mut core, Piece::I, Vec2i::new(8, 2), 1), 800);
+    assert!(core.back_to_back());
+    assert_eq!(
+        lock_in_place(&mut core, Piece::I, Vec2i::new(8, 2), 1),
+        800 + 400 + 50
+    );
+    assert!(core.back_to_back());
+}
+
+fn draw_n(randomizer: Randomizer, seed: u64, n: usize) -> Vec<Piece> {
+    let mut core = TetrisCore::new(seed);
+    core.set_available_pieces(Piece::all());
+    core.set_randomizer(randomizer);
+    (0..n).map(|_| core.draw_piece()).collect()
+}
+
+#[test]
+fn seven_bag_never_repeats_a_piece_within_a_bag() {
+    let bag_len = Piece::ALL.len();
+    let pieces = draw_n(Randomizer::SevenBag, 17, bag_len * 20);
+    for bag in pieces.chunks(bag_len) {
+        let unique: HashSet<Piece> = bag.iter().copied().collect();
+        assert_eq!(unique.len(), bag_len, "repeat within bag {bag:?}");
+    }
+    assert_eq!(pieces, draw_n(Randomizer::SevenBag, 17, bag_len * 20));
+    assert_ne!(pieces, draw_n(Randomizer::SevenBag, 18, bag_len * 20));
+}
+
+#[test]
+fn multi_bag_deals_each_piece_n_times_per_bag() {
+    let bag_len = Piece::ALL.len() * 3;
+    let pieces = draw_n(Randomizer::MultiBag(3), 4, bag_len * 5);
+    for bag in pieces.chunks(bag_len) {
+        for piece in Piece::ALL {
+            assert_eq!(bag.iter().filter(|&&p| p == piece).count(), 3);
+        }
+    }
+}
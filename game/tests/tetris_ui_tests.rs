@@ -1,9 +1,10 @@
 use engine::graphics::CpuRenderer;
-use engine::render::{CELL_SIZE, color_for_cell};
+use engine::render::{CELL_SIZE, CellPalette, color_for_cell};
 use engine::surface::SurfaceSize;
 use engine::ui;
 use engine::ui_tree::{UiInput, UiTree};
 
+use game::localization::english;
 use game::skilltree::{
     SkillEffect, SkillNodeDef, SkillTreeDef, SkillTreeProgress, SkillTreeRuntime,
 };
@@ -12,9 +13,10 @@ use game::tetris_core::{
     TetrisCore, Vec2i,
 };
 use game::tetris_ui::{
-    MAIN_MENU_TITLE, SkillTreeLayout, draw_game_over_menu, draw_main_menu, draw_main_menu_with_ui,
-    draw_pause_menu, draw_skilltree, draw_skilltree_runtime_with_ui, draw_tetris,
-    draw_tetris_hud_with_ui, draw_tetris_world, draw_tetris_world_with_camera_offset,
+    MAIN_MENU_TITLE, SkillTreeLayout, Theme, draw_game_over_menu, draw_main_menu,
+    draw_main_menu_with_ui, draw_pause_menu, draw_skilltree, draw_skilltree_runtime_with_ui,
+    draw_tetris, draw_tetris_hud_with_ui, draw_tetris_world, draw_tetris_world_with_camera_offset,
+    draw_tetris_world_with_camera_offset_ghost_and_theme, pause_menu_layout,
 };
 use game::ui_ids::{UI_CANVAS, UI_TETRIS_PAUSE};
 
@@ -190,7 +192,7 @@ fn draw_tetris_renders_hold_panel_outside_board_area() {
     let mut pixel = [0u8; 4];
     pixel.copy_from_slice(&frame[idx..idx + 4]);
 
-    let bg = color_for_cell(0);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
     assert_ne!(
         pixel, bg,
         "expected hold panel border to differ from background"
@@ -318,7 +320,7 @@ fn draw_tetris_world_nonzero_camera_offset_shifts_world_pixels() {
     let mut gfx_shifted = CpuRenderer::new(&mut frame_shifted, SurfaceSize::new(width, height));
     let _ = draw_tetris_world_with_camera_offset(&mut gfx_shifted, width, height, &core, offset_y);
 
-    let piece = color_for_cell(1);
+    let piece = color_for_cell(&CellPalette::CLASSIC, 1);
     let sample_x = layout.board.x + CELL_SIZE / 2;
     let sample_y_base = layout.board.y + 1;
     let sample_y_shifted = sample_y_base + CELL_SIZE;
@@ -365,7 +367,7 @@ fn draw_tetris_world_large_positive_offset_clamps_to_bottom_margin() {
     let mut gfx_shifted = CpuRenderer::new(&mut frame_shifted, SurfaceSize::new(width, height));
     let _ = draw_tetris_world_with_camera_offset(&mut gfx_shifted, width, height, &core, 10_000);
 
-    let piece = color_for_cell(1);
+    let piece = color_for_cell(&CellPalette::CLASSIC, 1);
     let sample_x = layout.board.x + 1;
     let sample_y_base = layout.board.y + 1;
     let sample_y_clamped = sample_y_base + max_down;
@@ -398,7 +400,7 @@ fn draw_tetris_world_large_negative_offset_clamps_to_top_margin() {
     let mut gfx_shifted = CpuRenderer::new(&mut frame_shifted, SurfaceSize::new(width, height));
     let _ = draw_tetris_world_with_camera_offset(&mut gfx_shifted, width, height, &core, -10_000);
 
-    let piece = color_for_cell(1);
+    let piece = color_for_cell(&CellPalette::CLASSIC, 1);
     let sample_x = layout.board.x + 1;
     let sample_y_base = layout.board.y + (BOARD_HEIGHT as u32 - 1) * CELL_SIZE + 1;
     let sample_y_clamped = sample_y_base.saturating_sub(max_up);
@@ -438,7 +440,7 @@ fn draw_tetris_world_camera_offset_clips_world_layers_to_board_viewport() {
         CELL_SIZE as i32 * 2,
     );
 
-    let piece = color_for_cell(1);
+    let piece = color_for_cell(&CellPalette::CLASSIC, 1);
     let sample_x = layout.board.x + 1;
     let inside_y = layout.board.y + (layout.board.h / 2);
     let inside_px = pixel_at(&frame_shifted, width, sample_x, inside_y);
@@ -503,7 +505,7 @@ fn draw_tetris_world_camera_offset_keeps_top_edge_background_persistent() {
     );
     assert_ne!(
         sample,
-        color_for_cell(0),
+        color_for_cell(&CellPalette::CLASSIC, 0),
         "top edge should remain background-filled under partial downward camera offsets"
     );
 }
@@ -551,7 +553,7 @@ fn draw_tetris_world_grassline_stays_behind_bottomwell_cells() {
     let sample_y = layout.board.y + inverted_y * CELL_SIZE + CELL_SIZE / 2;
     let sample = pixel_at(&frame, width, sample_x, sample_y);
 
-    let filled_cell = color_for_cell(1);
+    let filled_cell = color_for_cell(&CellPalette::CLASSIC, 1);
     let filled_edge = piece_edge_color(filled_cell);
     let grassline = [94, 152, 72, 255];
     assert!(
@@ -613,7 +615,7 @@ fn draw_tetris_world_depth_wall_overlay_obscures_bottom_rows_when_locked() {
     let y_unlocked =
         layout_unlocked.board.y + (BOARD_HEIGHT as u32 - 1) * CELL_SIZE + CELL_SIZE / 2;
 
-    let piece = color_for_cell(1);
+    let piece = color_for_cell(&CellPalette::CLASSIC, 1);
     let locked_px = pixel_at(&frame_locked, width, x_locked, y_locked);
     let unlocked_px = pixel_at(&frame_unlocked, width, x_unlocked, y_unlocked);
     assert!(
@@ -684,6 +686,7 @@ fn world_camera_offset_keeps_hud_layout_and_pause_hit_target_stable() {
         mouse_pos: Some((pause_x, pause_y)),
         mouse_down: false,
         mouse_up: false,
+        ..UiInput::default()
     });
     assert!(
         ui_base.is_hovered(UI_TETRIS_PAUSE),
@@ -706,6 +709,7 @@ fn world_camera_offset_keeps_hud_layout_and_pause_hit_target_stable() {
         mouse_pos: Some((pause_x, pause_y)),
         mouse_down: false,
         mouse_up: false,
+        ..UiInput::default()
     });
     assert!(
         ui_shifted.is_hovered(UI_TETRIS_PAUSE),
@@ -729,8 +733,8 @@ fn draw_tetris_renders_ghost_piece_at_hard_drop_position() {
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     let layout = draw_tetris(&mut gfx, width, height, &core);
 
-    let bg = color_for_cell(0);
-    let piece_color = color_for_cell(2);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
+    let piece_color = color_for_cell(&CellPalette::CLASSIC, 2);
 
     // Sample a pixel inside the bottom-left ghost cell at board coords (4, 0).
     let ghost_cell_x = 4u32;
@@ -764,6 +768,39 @@ fn draw_tetris_renders_ghost_piece_at_hard_drop_position() {
     );
 }
 
+#[test]
+fn draw_tetris_world_uses_theme_cell_colors_for_the_active_piece() {
+    let width = 800u32;
+    let height = 600u32;
+    let mut core = TetrisCore::new(0);
+    core.set_available_pieces(vec![Piece::O]);
+    core.initialize_game();
+    core.set_current_piece_for_test(Piece::O, Vec2i::new(4, 10), 0);
+
+    let sample = |theme: &Theme| {
+        let mut frame = vec![0u8; (width * height * 4) as usize];
+        let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+        let layout = draw_tetris_world_with_camera_offset_ghost_and_theme(
+            &mut gfx, width, height, &core, 0, true, theme,
+        );
+        let inverted_y = (BOARD_HEIGHT as u32 - 1) - 10;
+        let px = layout.board.x + 4 * CELL_SIZE + 1;
+        let py = layout.board.y + inverted_y * CELL_SIZE + 1;
+        pixel_at(&frame, width, px, py)
+    };
+
+    assert_eq!(
+        sample(&Theme::default()),
+        color_for_cell(&CellPalette::CLASSIC, 2)
+    );
+    let mono = sample(&Theme::MONO);
+    assert_eq!(mono, color_for_cell(&Theme::MONO.cells, 2));
+    assert!(
+        mono[0] == mono[1] && mono[1] == mono[2],
+        "mono theme should draw grayscale pieces, got {mono:?}"
+    );
+}
+
 #[test]
 fn draw_tetris_renders_pause_button_in_bounds() {
     let width = 800u32;
@@ -783,7 +820,7 @@ fn draw_tetris_renders_pause_button_in_bounds() {
     let mut pixel = [0u8; 4];
     pixel.copy_from_slice(&frame[idx..idx + 4]);
 
-    let bg = color_for_cell(0);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
     assert_ne!(
         pixel, bg,
         "expected pause button border to differ from background"
@@ -820,12 +857,12 @@ fn draw_tetris_world_flashes_pending_line_clear_rows() {
     let sample = pixel_at(&frame, width, sample_x, sample_y);
     assert_ne!(
         sample,
-        color_for_cell(1),
+        color_for_cell(&CellPalette::CLASSIC, 1),
         "pending clear row should be flash-blended above base block color"
     );
     assert_ne!(
         sample,
-        color_for_cell(0),
+        color_for_cell(&CellPalette::CLASSIC, 0),
         "pending clear row should still render as a non-background filled row"
     );
 }
@@ -835,7 +872,7 @@ fn draw_pause_menu_draws_a_panel_and_resume_button() {
     let width = 800u32;
     let height = 600u32;
 
-    let bg = color_for_cell(0);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
     let mut frame = vec![0u8; (width * height * 4) as usize];
     for px in frame.chunks_exact_mut(4) {
         px.copy_from_slice(&bg);
@@ -855,12 +892,48 @@ fn draw_pause_menu_draws_a_panel_and_resume_button() {
     );
 }
 
+#[test]
+fn pause_menu_buttons_do_not_overlap_at_supported_resolutions() {
+    fn overlaps(a: ui::Rect, b: ui::Rect) -> bool {
+        a.x < b.x + b.w && b.x < a.x + a.w && a.y < b.y + b.h && b.y < a.y + a.h
+    }
+
+    for (width, height) in [
+        (640, 360),
+        (800, 600),
+        (1280, 720),
+        (1920, 1080),
+        (480, 320),
+    ] {
+        let layout = pause_menu_layout(width, height);
+        let buttons = [
+            layout.resume_button,
+            layout.settings_button,
+            layout.restart_button,
+            layout.end_run_button,
+        ];
+        for (i, a) in buttons.iter().enumerate() {
+            assert!(a.w > 0 && a.h > 0, "{width}x{height}: button {i} is empty");
+            assert!(
+                a.y >= layout.panel.y + 18 + 40 && a.y + a.h <= layout.panel.y + layout.panel.h,
+                "{width}x{height}: button {i} leaves the panel body"
+            );
+            for (j, b) in buttons.iter().enumerate().skip(i + 1) {
+                assert!(
+                    !overlaps(*a, *b),
+                    "{width}x{height}: buttons {i} and {j} overlap"
+                );
+            }
+        }
+    }
+}
+
 #[test]
 fn draw_main_menu_draws_a_panel_and_buttons() {
     let width = 800u32;
     let height = 600u32;
 
-    let bg = color_for_cell(0);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
     let mut frame = vec![0u8; (width * height * 4) as usize];
     for px in frame.chunks_exact_mut(4) {
         px.copy_from_slice(&bg);
@@ -914,7 +987,7 @@ fn draw_main_menu_brightens_start_button_on_hover() {
     let width = 800u32;
     let height = 600u32;
 
-    let bg = color_for_cell(0);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
 
     let mut frame_normal = vec![0u8; (width * height * 4) as usize];
     for px in frame_normal.chunks_exact_mut(4) {
@@ -937,16 +1010,18 @@ fn draw_main_menu_brightens_start_button_on_hover() {
     let mut ui_tree = UiTree::new();
     ui_tree.ensure_canvas(UI_CANVAS, ui::Rect::from_size(width, height));
     ui_tree.add_root(UI_CANVAS);
-    let _ = draw_main_menu_with_ui(&mut gfx_hover, width, height, &mut ui_tree);
+    let _ = draw_main_menu_with_ui(&mut gfx_hover, width, height, &mut ui_tree, &english());
     let _ = ui_tree.process_input(UiInput {
         mouse_pos: Some((hover_x, hover_y)),
         mouse_down: false,
         mouse_up: false,
+        ..UiInput::default()
     });
     ui_tree.begin_frame();
     ui_tree.ensure_canvas(UI_CANVAS, ui::Rect::from_size(width, height));
     ui_tree.add_root(UI_CANVAS);
-    let _layout_hover = draw_main_menu_with_ui(&mut gfx_hover, width, height, &mut ui_tree);
+    let _layout_hover =
+        draw_main_menu_with_ui(&mut gfx_hover, width, height, &mut ui_tree, &english());
     let mut hover_px = [0u8; 4];
     hover_px.copy_from_slice(&frame_hover[idx..idx + 4]);
 
@@ -967,8 +1042,8 @@ fn draw_main_menu_clears_the_tetris_board_underneath() {
     let height = 600u32;
     let mut frame = vec![0u8; (width * height * 4) as usize];
 
-    let bg = color_for_cell(0);
-    let piece_color = color_for_cell(2);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
+    let piece_color = color_for_cell(&CellPalette::CLASSIC, 2);
 
     let mut core = TetrisCore::new(0);
     core.set_available_pieces(vec![Piece::O]);
@@ -1010,7 +1085,7 @@ fn draw_game_over_menu_draws_a_panel_and_buttons() {
     let width = 800u32;
     let height = 600u32;
 
-    let bg = color_for_cell(0);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
     let mut frame = vec![0u8; (width * height * 4) as usize];
     for px in frame.chunks_exact_mut(4) {
         px.copy_from_slice(&bg);
@@ -1036,7 +1111,7 @@ fn draw_skilltree_draws_a_panel_and_start_new_game_button() {
     let width = 800u32;
     let height = 600u32;
 
-    let bg = color_for_cell(0);
+    let bg = color_for_cell(&CellPalette::CLASSIC, 0);
     let mut frame = vec![0u8; (width * height * 4) as usize];
 
     // Draw a known Tetris piece first so we can assert the skilltree scene clears it.
@@ -1047,7 +1122,7 @@ fn draw_skilltree_draws_a_panel_and_start_new_game_button() {
     let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
     let tetris_layout = draw_tetris(&mut gfx, width, height, &core);
 
-    let piece_color = color_for_cell(2);
+    let piece_color = color_for_cell(&CellPalette::CLASSIC, 2);
     let cell_x = 4u32;
     let cell_y_from_bottom = 10u32;
     let inverted_y = (BOARD_HEIGHT as u32 - 1) - cell_y_from_bottom;
@@ -1100,6 +1175,39 @@ fn draw_skilltree_editor_hides_start_new_game_button() {
     assert_eq!(layout.start_new_game_button.h, 0);
 }
 
+#[test]
+fn draw_skilltree_minimap_maps_nodes_in_the_bottom_right_corner() {
+    let width = 800u32;
+    let height = 600u32;
+    let mut frame = vec![0u8; (width * height * 4) as usize];
+
+    let runtime = SkillTreeRuntime::load_default();
+    let mut gfx = CpuRenderer::new(&mut frame, SurfaceSize::new(width, height));
+    let mut ui_tree = UiTree::new();
+    ui_tree.ensure_canvas(UI_CANVAS, ui::Rect::from_size(width, height));
+    ui_tree.add_root(UI_CANVAS);
+    let layout = draw_skilltree_runtime_with_ui(&mut gfx, width, height, &mut ui_tree, &runtime);
+
+    let map = layout.minimap.expect("default skilltree has nodes");
+    assert!(map.rect.w > 0 && map.rect.h > 0);
+    assert!(map.rect.x + map.rect.w <= width && map.rect.y + map.rect.h <= height);
+    assert!(map.rect.x > width / 2 && map.rect.y > height / 2);
+
+    let node = &runtime.def.nodes[0];
+    let cell = Vec2i::new(node.pos.x + node.shape[0].x, node.pos.y + node.shape[0].y);
+    let r = map
+        .screen_rect(cell, Vec2i::new(cell.x + 1, cell.y + 1))
+        .expect("node cell inside the minimap");
+    assert_eq!(
+        map.world_at_screen(r.x + r.w / 2, r.y + r.h / 2),
+        Some(cell)
+    );
+    assert_eq!(
+        map.world_at_screen(map.rect.x.saturating_sub(1), map.rect.y),
+        None
+    );
+}
+
 #[test]
 fn draw_skilltree_draws_dependency_arrows() {
     let width = 800u32;
@@ -1,22 +1,22 @@
 mod plans;
 mod state;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::process::Stdio;
 use std::sync::mpsc;
 use std::thread;
-use std::time::{Duration as StdDuration, Instant};
+use std::time::{Duration as StdDuration, Instant, SystemTime};
 
 use anyhow::{Context, Result, bail};
 use chrono::Utc;
 use clap::{Parser, Subcommand};
 use plans::{PlanGraph, Task, load_plans};
 use serde_json::Value;
-use state::{ClaimStore, mark_task_done};
+use state::{ClaimStore, default_claim_ttl, mark_task_done};
 
 #[derive(Debug, Parser)]
 #[command(name = "plantool")]
@@ -24,6 +24,13 @@ use state::{ClaimStore, mark_task_done};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Move completed plans here instead of plans/done (relative paths are under the workspace
+    /// root). Plans archived outside plans/done are no longer loaded for dependency resolution.
+    #[arg(long, global = true, conflicts_with = "no_archive")]
+    archive_dir: Option<PathBuf>,
+    /// Leave completed plans in place instead of archiving them.
+    #[arg(long, global = true, default_value_t = false)]
+    no_archive: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -32,18 +39,48 @@ enum Commands {
     List {
         #[arg(long, default_value_t = false)]
         ready: bool,
+        /// Only show plans whose file changed within this window (e.g. 30m, 2h, 1d).
+        #[arg(long, value_parser = parse_since)]
+        since: Option<StdDuration>,
+        /// Only show plans with this `Tags:` entry.
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Print the plan dependency graph, colored by plan status.
+    Graph {
+        /// Emit a Graphviz DOT digraph (e.g. `plan graph --dot | dot -Tsvg > plans.svg`).
+        #[arg(long, default_value_t = false)]
+        dot: bool,
+    },
+    /// Print open plans in dependency order, grouped into waves that could run in parallel.
+    Schedule {
+        #[arg(long, default_value = "agent:cursor-agent")]
+        owner: String,
     },
     Claim {
         task_id: String,
         #[arg(long)]
         owner: String,
+        /// How long the claim holds before it expires (e.g. 30m, 2h); defaults to 30m.
+        #[arg(long, value_parser = parse_since)]
+        ttl: Option<StdDuration>,
+    },
+    /// List claims and when they expire.
+    Claims {
+        /// Only show claims whose TTL has elapsed without being released.
+        #[arg(long, default_value_t = false)]
+        expired: bool,
     },
     Complete {
-        task_id: String,
+        #[arg(required = true, num_args = 1..)]
+        task_ids: Vec<String>,
         #[arg(long)]
         owner: Option<String>,
         #[arg(long)]
         note: Option<String>,
+        /// Complete nothing if any task fails validation, and stop at the first write failure.
+        #[arg(long, default_value_t = false)]
+        atomic: bool,
     },
     Run {
         #[arg(long, default_value = "agent:cursor-agent")]
@@ -65,22 +102,62 @@ enum Commands {
         exec: String,
         #[arg(long, default_value_t = false)]
         auto_complete_on_success: bool,
+        /// Only pick plans whose file changed within this window (e.g. 30m, 2h, 1d).
+        #[arg(long, value_parser = parse_since)]
+        since: Option<StdDuration>,
+        /// Only pick plans with this `Tags:` entry; untagged plans still count as dependencies.
+        #[arg(long)]
+        tag: Option<String>,
+        /// TTL for each plan claim (e.g. 2h); defaults to 30m.
+        #[arg(long, value_parser = parse_since)]
+        claim_ttl: Option<StdDuration>,
+        /// Run up to this many independent ready plans at once; output is prefixed by plan id.
+        #[arg(long, default_value_t = 1)]
+        jobs: usize,
+        /// Also write each plan run's output to `<dir>/<plan_id>-<timestamp>.log` (relative paths
+        /// are under the workspace root).
+        #[arg(long)]
+        log_dir: Option<PathBuf>,
+        /// Re-claim and re-run a failed plan up to this many times before it counts toward the
+        /// 3-consecutive-failure circuit breaker.
+        #[arg(long, default_value_t = 0)]
+        max_retries: usize,
+        /// Sleep before the first retry; doubles for each further retry of the same plan.
+        #[arg(long, default_value_t = 30)]
+        retry_backoff_seconds: u64,
     },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
     let root = discover_workspace_root()?;
+    let archive_dir = resolve_archive_dir(&root, cli.archive_dir.as_deref(), cli.no_archive);
+    let archive_dir = archive_dir.as_deref();
 
     match cli.command {
         Commands::Validate => cmd_validate(&root),
-        Commands::List { ready } => cmd_list(&root, ready),
-        Commands::Claim { task_id, owner } => cmd_claim(&root, &task_id, &owner),
-        Commands::Complete {
+        Commands::List { ready, since, tag } => cmd_list(&root, ready, &PlanFilter { since, tag }),
+        Commands::Graph { dot } => cmd_graph(&root, dot),
+        Commands::Schedule { owner } => cmd_schedule(&root, &owner),
+        Commands::Claim {
             task_id,
             owner,
+            ttl,
+        } => cmd_claim(&root, &task_id, &owner, claim_ttl(ttl)?),
+        Commands::Claims { expired } => cmd_claims(&root, expired),
+        Commands::Complete {
+            task_ids,
+            owner,
             note,
-        } => cmd_complete(&root, &task_id, owner.as_deref(), note.as_deref()),
+            atomic,
+        } => cmd_complete(
+            &root,
+            &task_ids,
+            owner.as_deref(),
+            note.as_deref(),
+            atomic,
+            archive_dir,
+        ),
         Commands::Run {
             owner,
             watch,
@@ -90,6 +167,13 @@ fn main() -> Result<()> {
             idle_timeout_seconds,
             exec,
             auto_complete_on_success,
+            since,
+            tag,
+            claim_ttl: ttl,
+            jobs,
+            log_dir,
+            max_retries,
+            retry_backoff_seconds,
         } => cmd_run(
             &root,
             &owner,
@@ -100,10 +184,24 @@ fn main() -> Result<()> {
             idle_timeout_seconds,
             &exec,
             auto_complete_on_success,
+            archive_dir,
+            &PlanFilter { since, tag },
+            claim_ttl(ttl)?.unwrap_or_else(default_claim_ttl),
+            jobs,
+            log_dir.map(|dir| root.join(dir)).as_deref(),
+            RetryPolicy {
+                max_retries,
+                backoff_seconds: retry_backoff_seconds,
+            },
         ),
     }
 }
 
+fn claim_ttl(ttl: Option<StdDuration>) -> Result<Option<chrono::Duration>> {
+    ttl.map(|ttl| chrono::Duration::from_std(ttl).context("Claim TTL is too large"))
+        .transpose()
+}
+
 fn cmd_validate(root: &Path) -> Result<()> {
     let graph = load_plans(root)?;
     if assert_graph_valid(&graph).is_err() {
@@ -119,13 +217,32 @@ fn cmd_validate(root: &Path) -> Result<()> {
     Ok(())
 }
 
-fn cmd_list(root: &Path, ready_only: bool) -> Result<()> {
+/// Narrows which plans `list` shows and `run` picks. Dependency checks still see every plan.
+#[derive(Debug, Clone, Default)]
+struct PlanFilter {
+    since: Option<StdDuration>,
+    tag: Option<String>,
+}
+
+impl PlanFilter {
+    fn matches(&self, plan: &plans::Plan, now: SystemTime) -> bool {
+        plan_is_recent(&plan.path, self.since, now)
+            && self.tag.as_deref().is_none_or(|tag| plan.has_tag(tag))
+    }
+}
+
+fn cmd_list(root: &Path, ready_only: bool, filter: &PlanFilter) -> Result<()> {
     let (graph, excluded_plan_ids) = load_actionable_graph(root)?;
     warn_excluded_plans(&excluded_plan_ids);
     let claims = ClaimStore::load(root)?;
     let now = Utc::now();
+    let now_sys = SystemTime::now();
 
     for plan in &graph.plans {
+        // Only hides plans; dependency checks below still see the whole graph.
+        if !filter.matches(plan, now_sys) {
+            continue;
+        }
         let plan_claimed = claims
             .active_claim(&plan_claim_key(&plan.id), now)
             .is_some();
@@ -153,13 +270,326 @@ fn cmd_list(root: &Path, ready_only: bool) -> Result<()> {
             } else {
                 "blocked".to_string()
             };
-            println!("[{}] {}  {}", status, task.id, display_task_text(task));
+            println!(
+                "[{}] {}  {}{}",
+                status,
+                task.id,
+                plan_list_metadata(plan),
+                display_task_text(task)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Priority and tags shown ahead of the task text in `list`, e.g. `(priority 5) {frontend}  `.
+fn plan_list_metadata(plan: &plans::Plan) -> String {
+    let mut parts = Vec::new();
+    if let Some(priority) = plan.priority {
+        parts.push(format!("(priority {priority})"));
+    }
+    if !plan.tags.is_empty() {
+        parts.push(format!("{{{}}}", plan.tags.join(", ")));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{}  ", parts.join(" "))
+    }
+}
+
+fn cmd_schedule(root: &Path, owner: &str) -> Result<()> {
+    let graph = load_plans(root)?;
+    if assert_graph_valid(&graph).is_err() {
+        bail!("Refusing to schedule an invalid plan graph; run `plan validate` for details");
+    }
+    let claims = ClaimStore::load(root)?;
+    let waves = compute_schedule(&graph, &claims, Utc::now(), owner);
+    if waves.is_empty() {
+        println!("All checklist items are complete in plans/*.txt and plans/*.md.");
+        return Ok(());
+    }
+
+    for (idx, wave) in waves.iter().enumerate() {
+        if idx == 0 {
+            println!("Wave 1 (ready now):");
+        } else {
+            println!("Wave {}:", idx + 1);
+        }
+        for entry in wave {
+            let mut line = format!("  {}  ({} open task(s))", entry.plan_id, entry.open_tasks);
+            if !entry.blocked_by.is_empty() {
+                line.push_str(&format!("  blocked by: {}", entry.blocked_by.join(", ")));
+            }
+            if let Some(skip) = &entry.skip_reason {
+                line.push_str(&format!("  [skipped for {owner}: {skip}]"));
+            }
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScheduledPlan {
+    plan_id: String,
+    open_tasks: usize,
+    /// Dependencies that are not complete yet.
+    blocked_by: Vec<String>,
+    /// Why `select_next_ready_plan` would pass over this plan for the owner even once unblocked.
+    skip_reason: Option<String>,
+}
+
+/// Groups open plans into waves: wave 0 is ready now, and every later plan lands one wave after
+/// its last open dependency. Within a wave plans are ordered by priority, then id (see
+/// `Plan::selection_cmp`), the same order `select_next_ready_plan` uses, so the first unskipped
+/// wave-0 entry is the plan it would pick.
+///
+/// Expects a graph without cycles or missing dependencies (see `assert_graph_valid`).
+fn compute_schedule(
+    graph: &PlanGraph,
+    claims: &ClaimStore,
+    now: chrono::DateTime<Utc>,
+    owner: &str,
+) -> Vec<Vec<ScheduledPlan>> {
+    let mut remaining: Vec<&plans::Plan> = graph
+        .plans
+        .iter()
+        .filter(|plan| !graph.plan_completed(&plan.id))
+        .collect();
+    remaining.sort_by(|a, b| a.selection_cmp(b));
+
+    let mut scheduled: HashSet<&str> = HashSet::new();
+    let mut waves = Vec::new();
+    while !remaining.is_empty() {
+        let (ready, rest): (Vec<&plans::Plan>, Vec<&plans::Plan>) =
+            remaining.into_iter().partition(|plan| {
+                plan.depends_on
+                    .iter()
+                    .all(|dep| graph.plan_completed(dep) || scheduled.contains(dep.as_str()))
+            });
+        if ready.is_empty() {
+            // Only reachable with a cycle, which callers reject up front.
+            break;
+        }
+        let wave = ready
+            .iter()
+            .map(|plan| ScheduledPlan {
+                plan_id: plan.id.clone(),
+                open_tasks: plan.tasks.iter().filter(|t| !t.done).count(),
+                blocked_by: plan
+                    .depends_on
+                    .iter()
+                    .filter(|dep| !graph.plan_completed(dep))
+                    .cloned()
+                    .collect(),
+                skip_reason: schedule_skip_reason(plan, claims, now, owner),
+            })
+            .collect();
+        scheduled.extend(ready.iter().map(|plan| plan.id.as_str()));
+        waves.push(wave);
+        remaining = rest;
+    }
+    waves
+}
+
+fn schedule_skip_reason(
+    plan: &plans::Plan,
+    claims: &ClaimStore,
+    now: chrono::DateTime<Utc>,
+    owner: &str,
+) -> Option<String> {
+    match claims.active_claim(&plan_claim_key(&plan.id), now) {
+        Some(claim) if claim.owner != owner => {
+            return Some(format!("claimed by {}", claim.owner));
+        }
+        _ => {}
+    }
+    let actionable = plan
+        .tasks
+        .iter()
+        .any(|t| !t.done && task_executable_by_owner(t, owner));
+    (!actionable).then(|| "human-only".to_string())
+}
+
+fn cmd_graph(root: &Path, dot: bool) -> Result<()> {
+    let full = load_plans(root)?;
+    let (graph, excluded_plan_ids) = prune_invalid_plans(full.clone());
+    let claims = ClaimStore::load(root)?;
+    let nodes = compute_graph_nodes(&full, &graph, &excluded_plan_ids, &claims, Utc::now());
+    if dot {
+        print!("{}", render_plan_dot(&full, &nodes));
+        return Ok(());
+    }
+
+    warn_excluded_plans(&excluded_plan_ids);
+    for node in &nodes {
+        let deps = &full.plans_by_id[&node.plan_id].depends_on;
+        if deps.is_empty() {
+            println!("[{}] {}", node.status.label(), node.plan_id);
+        } else {
+            println!(
+                "[{}] {}  depends on: {}",
+                node.status.label(),
+                node.plan_id,
+                deps.join(", ")
+            );
         }
     }
     Ok(())
 }
 
-fn cmd_claim(root: &Path, task_id: &str, owner: &str) -> Result<()> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlanNodeStatus {
+    Done,
+    Ready,
+    Blocked,
+    Claimed,
+    /// Pruned by `load_actionable_graph` (missing dependency or cycle).
+    Excluded,
+}
+
+impl PlanNodeStatus {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::Ready => "ready",
+            Self::Blocked => "blocked",
+            Self::Claimed => "claimed",
+            Self::Excluded => "excluded",
+        }
+    }
+
+    fn dot_fill_color(self) -> &'static str {
+        match self {
+            Self::Done => "palegreen",
+            Self::Ready => "lightskyblue",
+            Self::Blocked => "khaki",
+            Self::Claimed => "plum",
+            Self::Excluded => "lightgray",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlanGraphNode {
+    plan_id: String,
+    status: PlanNodeStatus,
+    open_tasks: usize,
+}
+
+/// One node per plan in `full`, ordered by id. Statuses for plans that survive pruning are
+/// computed on the pruned `graph`, matching what `list` and `run` see.
+fn compute_graph_nodes(
+    full: &PlanGraph,
+    graph: &PlanGraph,
+    excluded_plan_ids: &[String],
+    claims: &ClaimStore,
+    now: chrono::DateTime<Utc>,
+) -> Vec<PlanGraphNode> {
+    let mut nodes: Vec<PlanGraphNode> = full
+        .plans
+        .iter()
+        .map(|plan| {
+            let open_tasks = plan.tasks.iter().filter(|t| !t.done).count();
+            let claimed = claims
+                .active_claim(&plan_claim_key(&plan.id), now)
+                .is_some()
+                || plan
+                    .tasks
+                    .iter()
+                    .any(|t| !t.done && claims.active_claim(&t.id, now).is_some());
+            let status = if excluded_plan_ids.contains(&plan.id) {
+                PlanNodeStatus::Excluded
+            } else if graph.plan_completed(&plan.id) {
+                PlanNodeStatus::Done
+            } else if claimed {
+                PlanNodeStatus::Claimed
+            } else if graph.dependencies_completed(&plan.id) {
+                PlanNodeStatus::Ready
+            } else {
+                PlanNodeStatus::Blocked
+            };
+            PlanGraphNode {
+                plan_id: plan.id.clone(),
+                status,
+                open_tasks,
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.plan_id.cmp(&b.plan_id));
+    nodes
+}
+
+/// Renders a DOT digraph with edges pointing from each dependency to the plan that needs it.
+/// Dependencies that do not resolve to a plan are drawn as red dashed placeholder nodes.
+fn render_plan_dot(full: &PlanGraph, nodes: &[PlanGraphNode]) -> String {
+    let mut out = String::from("digraph plans {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=filled, fontname=\"Helvetica\"];\n");
+
+    for node in nodes {
+        let mut label = format!("{}\\n{}", dot_escape(&node.plan_id), node.status.label());
+        if node.open_tasks > 0 {
+            label.push_str(&format!(" ({} open)", node.open_tasks));
+        }
+        let style = if node.status == PlanNodeStatus::Excluded {
+            ", style=\"filled,dashed\""
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\", fillcolor={}{}];\n",
+            dot_escape(&node.plan_id),
+            label,
+            node.status.dot_fill_color(),
+            style
+        ));
+    }
+
+    let mut missing: Vec<&str> = full
+        .plans
+        .iter()
+        .flat_map(|plan| plan.depends_on.iter())
+        .filter(|dep| !full.plans_by_id.contains_key(*dep))
+        .map(String::as_str)
+        .collect();
+    missing.sort();
+    missing.dedup();
+    for dep in missing {
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\\nmissing\", style=dashed, color=red, fontcolor=red];\n",
+            dot_escape(dep),
+            dot_escape(dep)
+        ));
+    }
+
+    for node in nodes {
+        let plan = &full.plans_by_id[&node.plan_id];
+        for dep in &plan.depends_on {
+            let style = if node.status == PlanNodeStatus::Excluded {
+                " [style=dashed]"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\"{};\n",
+                dot_escape(dep),
+                dot_escape(&node.plan_id),
+                style
+            ));
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn cmd_claim(root: &Path, task_id: &str, owner: &str, ttl: Option<chrono::Duration>) -> Result<()> {
     let (graph, excluded_plan_ids) = load_actionable_graph(root)?;
     warn_excluded_plans(&excluded_plan_ids);
     let task = graph
@@ -181,22 +611,159 @@ fn cmd_claim(root: &Path, task_id: &str, owner: &str) -> Result<()> {
     }
 
     let mut claims = ClaimStore::load(root)?;
-    claims.claim(task_id, owner, Utc::now())?;
+    match ttl {
+        Some(ttl) => claims.claim_with_ttl(task_id, owner, Utc::now(), ttl)?,
+        None => claims.claim(task_id, owner, Utc::now())?,
+    }
     claims.save(root)?;
     println!("Claimed {} for {}", task_id, owner);
     Ok(())
 }
 
-fn cmd_complete(root: &Path, task_id: &str, owner: Option<&str>, note: Option<&str>) -> Result<()> {
+fn cmd_claims(root: &Path, expired_only: bool) -> Result<()> {
+    let claims = ClaimStore::load(root)?;
+    let now = Utc::now();
+    let lines = format_claims(&claims, now, expired_only);
+    if lines.is_empty() {
+        if expired_only {
+            println!("No expired claims.");
+        } else {
+            println!("No claims.");
+        }
+    }
+    for line in lines {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn format_claims(
+    claims: &ClaimStore,
+    now: chrono::DateTime<Utc>,
+    expired_only: bool,
+) -> Vec<String> {
+    let mut entries: Vec<&state::Claim> = if expired_only {
+        claims.expired_claims(now)
+    } else {
+        claims.claims.values().collect()
+    };
+    entries.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+    entries
+        .into_iter()
+        .map(|claim| {
+            let status = if claim.is_expired(now) {
+                "expired"
+            } else {
+                "active"
+            };
+            format!(
+                "[{}] {}  {}  claimed {}  lease until {}",
+                status,
+                claim.task_id,
+                claim.owner,
+                claim.claimed_at.to_rfc3339(),
+                claim.lease_until.to_rfc3339()
+            )
+        })
+        .collect()
+}
+
+fn cmd_complete(
+    root: &Path,
+    task_ids: &[String],
+    owner: Option<&str>,
+    note: Option<&str>,
+    atomic: bool,
+    archive_dir: Option<&Path>,
+) -> Result<()> {
     let (graph, excluded_plan_ids) = load_actionable_graph(root)?;
     warn_excluded_plans(&excluded_plan_ids);
+    let mut claims = ClaimStore::load(root)?;
+    let now = Utc::now();
+
+    // Validate every task against one graph/claims snapshot before touching any plan file.
+    let mut seen = HashSet::new();
+    let mut pending = Vec::new();
+    let mut failed = Vec::new();
+    for task_id in task_ids {
+        if !seen.insert(task_id.as_str()) {
+            continue;
+        }
+        match validate_completion(&graph, &claims, now, task_id, owner) {
+            Ok(Some(task)) => pending.push(task),
+            Ok(None) => println!("Task {} already complete", task_id),
+            Err(err) => {
+                eprintln!("Failed {}: {:#}", task_id, err);
+                failed.push(task_id.as_str());
+            }
+        }
+    }
+    if atomic && !failed.is_empty() {
+        bail!(
+            "Aborted (--atomic): {} task(s) failed validation, nothing completed: {}",
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+
+    let mut noted_plans = HashSet::new();
+    let mut touched_plans = Vec::new();
+    for task in pending {
+        // The note is appended to the plan file, so write it once per plan.
+        let task_note = note.filter(|_| noted_plans.insert(task.plan_id.as_str()));
+        if let Err(err) = mark_task_done(task, task_note) {
+            if atomic {
+                claims.save(root)?;
+                return Err(err.context(format!("Aborted (--atomic) while completing {}", task.id)));
+            }
+            eprintln!("Failed {}: {:#}", task.id, err);
+            failed.push(task.id.as_str());
+            continue;
+        }
+        claims.release(&task.id);
+        println!("Completed {}", task.id);
+        if !touched_plans.contains(&task.plan_id) {
+            touched_plans.push(task.plan_id.clone());
+        }
+    }
+    claims.save(root)?;
+
+    // Archive only after the whole batch so a plan finished by several ids moves exactly once.
+    for plan_id in &touched_plans {
+        if let Some(archived_path) = maybe_archive_completed_plan(root, plan_id, archive_dir)? {
+            println!(
+                "Archived completed plan {} to {}",
+                plan_id,
+                archived_path.display()
+            );
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} task(s) failed: {}",
+            failed.len(),
+            seen.len(),
+            failed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Returns the task if `owner` may complete it now, `None` if it is already done.
+fn validate_completion<'a>(
+    graph: &'a PlanGraph,
+    claims: &ClaimStore,
+    now: chrono::DateTime<Utc>,
+    task_id: &str,
+    owner: Option<&str>,
+) -> Result<Option<&'a Task>> {
     let task = graph
         .tasks_by_id
         .get(task_id)
         .with_context(|| format!("Unknown task id {}", task_id))?;
     if task.done {
-        println!("Task {} already complete", task_id);
-        return Ok(());
+        return Ok(None);
     }
     if task.human_only {
         if let Some(owner_name) = owner {
@@ -209,9 +776,7 @@ fn cmd_complete(root: &Path, task_id: &str, owner: Option<&str>, note: Option<&s
             }
         }
     }
-
-    let mut claims = ClaimStore::load(root)?;
-    if let Some(active) = claims.active_claim(task_id, Utc::now()) {
+    if let Some(active) = claims.active_claim(task_id, now) {
         if let Some(owner_name) = owner {
             if active.owner != owner_name {
                 bail!(
@@ -223,19 +788,28 @@ fn cmd_complete(root: &Path, task_id: &str, owner: Option<&str>, note: Option<&s
             }
         }
     }
+    Ok(Some(task))
+}
 
-    mark_task_done(task, note)?;
-    claims.release(task_id);
-    claims.save(root)?;
-    println!("Completed {}", task_id);
-    if let Some(archived_path) = maybe_archive_completed_plan(root, &task.plan_id)? {
-        println!(
-            "Archived completed plan {} to {}",
-            task.plan_id,
-            archived_path.display()
-        );
+/// How `cmd_run` retries a failed plan before counting it toward the circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RetryPolicy {
+    max_retries: usize,
+    backoff_seconds: u64,
+}
+
+impl RetryPolicy {
+    /// Sleep before retry number `retry` (1-based): the backoff doubles with every retry.
+    fn delay_for(self, retry: usize) -> StdDuration {
+        let factor = 1u64 << retry.saturating_sub(1).min(16);
+        StdDuration::from_secs(self.backoff_seconds.saturating_mul(factor))
     }
-    Ok(())
+}
+
+/// Result of one plan execution, reported by a worker thread back to `cmd_run`.
+struct PlanRunOutcome {
+    plan_id: String,
+    result: Result<ExecResult>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -249,99 +823,235 @@ fn cmd_run(
     idle_timeout_seconds: u64,
     exec: &str,
     auto_complete_on_success: bool,
+    archive_dir: Option<&Path>,
+    filter: &PlanFilter,
+    claim_ttl: chrono::Duration,
+    jobs: usize,
+    log_dir: Option<&Path>,
+    retry: RetryPolicy,
 ) -> Result<()> {
+    let jobs = jobs.max(1);
+    if let Some(dir) = log_dir {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create log directory {}", dir.display()))?;
+    }
     let started = Instant::now();
     let mut steps = 0usize;
     let mut consecutive_failures = 0usize;
     let mut last_excluded_signature = String::new();
+    let mut stopping = false;
+    // Workers only run the exec command. Claiming, releasing, failure counting and archiving all
+    // happen on this thread, reloading the claim store each time, so concurrent runs never race
+    // on claims.json.
+    let (outcome_tx, outcome_rx) = mpsc::channel::<PlanRunOutcome>();
+    let mut in_flight: HashSet<String> = HashSet::new();
+    // Retries used per plan in this run, and failed plans waiting out their backoff.
+    let mut retries_used: HashMap<String, usize> = HashMap::new();
+    let mut pending_retries: Vec<(String, Instant)> = Vec::new();
 
     loop {
-        if steps >= max_steps {
-            println!("Stopping: reached max steps ({max_steps})");
-            break;
+        if !stopping && in_flight.len() < jobs {
+            if steps >= max_steps {
+                println!("Stopping: reached max steps ({max_steps})");
+                stopping = true;
+            } else if started.elapsed() > StdDuration::from_secs(max_minutes * 60) {
+                println!("Stopping: reached max runtime ({max_minutes} minutes)");
+                stopping = true;
+            }
         }
-        if started.elapsed() > StdDuration::from_secs(max_minutes * 60) {
-            println!("Stopping: reached max runtime ({max_minutes} minutes)");
-            break;
+
+        if !stopping && in_flight.len() < jobs {
+            let (graph, excluded_plan_ids) = load_actionable_graph(root)?;
+            let excluded_signature = excluded_plan_ids.join(",");
+            if !excluded_plan_ids.is_empty() && excluded_signature != last_excluded_signature {
+                warn_excluded_plans(&excluded_plan_ids);
+                last_excluded_signature = excluded_signature;
+            }
+            let mut claims = ClaimStore::load(root)?;
+            let now = Utc::now();
+            let due_retry = pending_retries
+                .iter()
+                .position(|(_, due)| *due <= Instant::now())
+                .map(|idx| pending_retries.remove(idx).0);
+            let next = if let Some(plan_id) = due_retry {
+                let work = graph
+                    .plans_by_id
+                    .get(&plan_id)
+                    .filter(|plan| plan_ready_for_owner(&graph, plan, &claims, now, owner))
+                    .and_then(|plan| plan_work_item(plan, owner));
+                if work.is_none() {
+                    println!("Plan {} is no longer ready; dropping its retry", plan_id);
+                    retries_used.remove(&plan_id);
+                    continue;
+                }
+                work
+            } else {
+                let mut busy = in_flight.clone();
+                busy.extend(pending_retries.iter().map(|(id, _)| id.clone()));
+                select_next_ready_plan(&graph, &claims, now, owner, filter, &busy)
+            };
+            if let Some(plan_work) = next {
+                let claim_id = plan_claim_key(&plan_work.plan_id);
+                claims.claim_with_ttl(&claim_id, owner, now, claim_ttl)?;
+                claims.save(root)?;
+                steps += 1;
+                println!(
+                    "Step {}: claimed plan {} ({} open items)",
+                    steps, plan_work.plan_id, plan_work.pending_count
+                );
+
+                let cmd = render_exec_command(exec, &plan_work);
+                println!("Executing: {}", cmd);
+                println!("==============================");
+                println!("Plan Output: {}", plan_work.plan_id);
+                println!("==============================");
+                let prefix = if jobs > 1 {
+                    format!("[{}] ", plan_work.plan_id)
+                } else {
+                    String::new()
+                };
+                let log = match log_dir {
+                    Some(dir) => {
+                        let path = plan_log_path(dir, &plan_work.plan_id, Utc::now());
+                        let file = fs::File::create(&path).with_context(|| {
+                            format!("Failed to create log file {}", path.display())
+                        })?;
+                        println!("Logging {} to {}", plan_work.plan_id, path.display());
+                        Some(file)
+                    }
+                    None => None,
+                };
+                let mut output = RunOutput { prefix, log };
+                output.write_log(&format!("Executing: {}", cmd));
+                let plan_id = plan_work.plan_id.clone();
+                let tx = outcome_tx.clone();
+                thread::spawn(move || {
+                    let result = run_shell(&cmd, idle_timeout_seconds, &mut output);
+                    if let Ok(exec_result) = &result {
+                        output.write_log(&format!("Exit code: {}", exec_result.exit_code));
+                        output.write_log(&format!(
+                            "Execution succeeded: {}",
+                            execution_succeeded(exec_result)
+                        ));
+                    }
+                    let _ = tx.send(PlanRunOutcome { plan_id, result });
+                });
+                in_flight.insert(plan_work.plan_id);
+                continue;
+            }
+            if in_flight.is_empty() {
+                if let Some(due) = pending_retries.iter().map(|(_, due)| *due).min() {
+                    thread::sleep(due.saturating_duration_since(Instant::now()));
+                    continue;
+                }
+                if watch {
+                    println!("No ready tasks. Sleeping {}s...", sleep_seconds);
+                    thread::sleep(StdDuration::from_secs(sleep_seconds));
+                    continue;
+                }
+                let diagnostics = compute_ready_diagnostics(&graph, &claims, now, owner);
+                println!("No ready tasks. Exiting.");
+                print_no_ready_guidance(&diagnostics, owner);
+                break;
+            }
         }
 
-        let (graph, excluded_plan_ids) = load_actionable_graph(root)?;
-        let excluded_signature = excluded_plan_ids.join(",");
-        if !excluded_plan_ids.is_empty() && excluded_signature != last_excluded_signature {
-            warn_excluded_plans(&excluded_plan_ids);
-            last_excluded_signature = excluded_signature;
+        if in_flight.is_empty() {
+            break;
         }
-        let mut claims = ClaimStore::load(root)?;
-        let now = Utc::now();
-        let Some(plan_work) = select_next_ready_plan(&graph, &claims, now, owner) else {
+        // With free slots, wake up for due retries and (in watch mode) periodically so newly
+        // ready plans get picked up even while other plans are still running.
+        let mut wake_after = None;
+        if !stopping && in_flight.len() < jobs {
             if watch {
-                println!("No ready tasks. Sleeping {}s...", sleep_seconds);
-                thread::sleep(StdDuration::from_secs(sleep_seconds));
-                continue;
+                wake_after = Some(StdDuration::from_secs(sleep_seconds));
             }
-            let diagnostics = compute_ready_diagnostics(&graph, &claims, now, owner);
-            println!("No ready tasks. Exiting.");
-            print_no_ready_guidance(&diagnostics, owner);
-            break;
+            if let Some(due) = pending_retries.iter().map(|(_, due)| *due).min() {
+                let until_due = due.saturating_duration_since(Instant::now());
+                wake_after = Some(wake_after.map_or(until_due, |w: StdDuration| w.min(until_due)));
+            }
+        }
+        let outcome = if let Some(wake_after) = wake_after {
+            match outcome_rx.recv_timeout(wake_after) {
+                Ok(outcome) => outcome,
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    bail!("Plan workers disconnected unexpectedly")
+                }
+            }
+        } else {
+            outcome_rx
+                .recv()
+                .with_context(|| "Plan workers disconnected unexpectedly")?
         };
-
-        let claim_id = plan_claim_key(&plan_work.plan_id);
-        claims.claim(&claim_id, owner, now)?;
-        claims.save(root)?;
-        steps += 1;
-        println!(
-            "Step {}: claimed plan {} ({} open items)",
-            steps, plan_work.plan_id, plan_work.pending_count
-        );
-
-        let cmd = render_exec_command(exec, &plan_work);
-        println!("Executing: {}", cmd);
+        in_flight.remove(&outcome.plan_id);
+        let plan_id = outcome.plan_id;
         println!("==============================");
-        println!("Plan Output: {}", plan_work.plan_id);
-        println!("==============================");
-        let exec_result = run_shell(&cmd, idle_timeout_seconds)?;
-        println!("==============================");
-        let ok = execution_succeeded(&exec_result);
-        println!("Command exit code: {}", exec_result.exit_code);
+        // A command that could not run is a failed plan, not a reason to abandon the others
+        // still in flight (and their claims).
+        let exec_result = match outcome.result {
+            Ok(exec_result) => {
+                println!(
+                    "Command exit code for {}: {}",
+                    plan_id, exec_result.exit_code
+                );
+                Some(exec_result)
+            }
+            Err(err) => {
+                eprintln!("Failed to run command for {}: {:#}", plan_id, err);
+                None
+            }
+        };
+        let ok = exec_result.as_ref().is_some_and(execution_succeeded);
 
+        let claim_id = plan_claim_key(&plan_id);
+        let mut claims = ClaimStore::load(root)?;
+        claims.release(&claim_id);
+        claims.save(root)?;
+        if ok {
+            retries_used.remove(&plan_id);
+        }
         if ok && auto_complete_on_success {
-            claims.release(&claim_id);
-            claims.save(root)?;
-            println!("Run succeeded for {}", plan_work.plan_id);
+            println!("Run succeeded for {}", plan_id);
             consecutive_failures = 0;
         } else if ok {
-            claims.release(&claim_id);
-            claims.save(root)?;
-            println!("Execution finished for {}", plan_work.plan_id);
+            println!("Execution finished for {}", plan_id);
             consecutive_failures = 0;
+        } else if exec_result.is_some()
+            && retries_used.get(&plan_id).copied().unwrap_or(0) < retry.max_retries
+            && !stopping
+        {
+            let used = retries_used.entry(plan_id.clone()).or_insert(0);
+            *used += 1;
+            let delay = retry.delay_for(*used);
+            println!(
+                "Plan {} failed; retry {}/{} in {}s",
+                plan_id,
+                used,
+                retry.max_retries,
+                delay.as_secs()
+            );
+            pending_retries.push((plan_id, Instant::now() + delay));
+            continue;
         } else {
-            claims.release(&claim_id);
-            claims.save(root)?;
+            retries_used.remove(&plan_id);
             consecutive_failures += 1;
             println!(
                 "Plan {} failed (failure count: {})",
-                plan_work.plan_id, consecutive_failures
+                plan_id, consecutive_failures
             );
-            if let Some(archived_path) = maybe_archive_completed_plan(root, &plan_work.plan_id)? {
-                println!(
-                    "Archived completed plan {} to {}",
-                    plan_work.plan_id,
-                    archived_path.display()
-                );
-            }
-            if consecutive_failures >= 3 {
-                println!("Circuit breaker: 3 consecutive failures.");
-                break;
-            }
-            continue;
         }
-        if let Some(archived_path) = maybe_archive_completed_plan(root, &plan_work.plan_id)? {
+        if let Some(archived_path) = maybe_archive_completed_plan(root, &plan_id, archive_dir)? {
             println!(
                 "Archived completed plan {} to {}",
-                plan_work.plan_id,
+                plan_id,
                 archived_path.display()
             );
         }
+        if consecutive_failures >= 3 && !stopping {
+            println!("Circuit breaker: 3 consecutive failures.");
+            stopping = true;
+        }
     }
 
     Ok(())
@@ -526,7 +1236,58 @@ impl StreamFormatter {
     }
 }
 
-fn run_shell(command: &str, idle_timeout_seconds: u64) -> Result<ExecResult> {
+/// Where `run_shell` sends formatted output: the console (with `prefix` on every line) and, with
+/// `--log-dir`, a per-plan log file.
+struct RunOutput {
+    prefix: String,
+    log: Option<fs::File>,
+}
+
+impl RunOutput {
+    fn line(&mut self, text: &str) {
+        println!("{}{}", self.prefix, text);
+        self.write_log(text);
+    }
+
+    fn stderr_line(&mut self, text: &str) {
+        eprintln!("{}[stderr] {}", self.prefix, text);
+        self.write_log(&format!("[stderr] {text}"));
+    }
+
+    fn write_log(&mut self, text: &str) {
+        let Some(file) = self.log.as_mut() else {
+            return;
+        };
+        if let Err(err) = writeln!(file, "{text}") {
+            eprintln!("{}WARNING: stopped writing plan log: {err}", self.prefix);
+            self.log = None;
+        }
+    }
+}
+
+fn plan_log_path(log_dir: &Path, plan_id: &str, now: chrono::DateTime<Utc>) -> PathBuf {
+    let safe_plan_id: String = plan_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    log_dir.join(format!(
+        "{}-{}.log",
+        safe_plan_id,
+        now.format("%Y%m%dT%H%M%S%.3fZ")
+    ))
+}
+
+fn run_shell(
+    command: &str,
+    idle_timeout_seconds: u64,
+    output: &mut RunOutput,
+) -> Result<ExecResult> {
     let mut stream_success = false;
     let mut stream_error = false;
     let mut attempt = 0usize;
@@ -535,10 +1296,10 @@ fn run_shell(command: &str, idle_timeout_seconds: u64) -> Result<ExecResult> {
     loop {
         attempt += 1;
         if attempt > 1 {
-            println!(
+            output.line(&format!(
                 "... idle timeout reached; restarting command with --continue (attempt {})",
                 attempt
-            );
+            ));
         }
 
         // Execute plan commands through bash so WSL runs natively without PowerShell routing.
@@ -593,10 +1354,10 @@ fn run_shell(command: &str, idle_timeout_seconds: u64) -> Result<ExecResult> {
             while let Ok(stream_line) = rx.try_recv() {
                 last_output_at = Instant::now();
                 if stream_line.is_stderr {
-                    eprintln!("[stderr] {}", stream_line.line);
+                    output.stderr_line(&stream_line.line);
                 } else {
                     for rendered in formatter.render_line(&stream_line.line) {
-                        println!("{}", rendered);
+                        output.line(&rendered);
                     }
                     update_stream_result_flags(
                         &stream_line.line,
@@ -615,10 +1376,10 @@ fn run_shell(command: &str, idle_timeout_seconds: u64) -> Result<ExecResult> {
 
             if last_output_at.elapsed() >= StdDuration::from_secs(idle_timeout_seconds) {
                 idled_out = true;
-                println!(
+                output.line(&format!(
                     "... idle timeout reached (no output for {}s)",
                     idle_timeout_seconds
-                );
+                ));
                 terminate_process_tree(child.id());
                 let status = child
                     .wait()
@@ -629,7 +1390,10 @@ fn run_shell(command: &str, idle_timeout_seconds: u64) -> Result<ExecResult> {
             thread::sleep(StdDuration::from_millis(200));
             let elapsed_secs = started.elapsed().as_secs();
             if elapsed_secs >= next_heartbeat_secs {
-                println!("... task command still running ({}s elapsed)", elapsed_secs);
+                output.line(&format!(
+                    "... task command still running ({}s elapsed)",
+                    elapsed_secs
+                ));
                 next_heartbeat_secs += 10;
             }
         };
@@ -638,10 +1402,10 @@ fn run_shell(command: &str, idle_timeout_seconds: u64) -> Result<ExecResult> {
         let _ = stderr_handle.join();
         while let Ok(stream_line) = rx.try_recv() {
             if stream_line.is_stderr {
-                eprintln!("[stderr] {}", stream_line.line);
+                output.stderr_line(&stream_line.line);
             } else {
                 for rendered in formatter.render_line(&stream_line.line) {
-                    println!("{}", rendered);
+                    output.line(&rendered);
                 }
                 update_stream_result_flags(
                     &stream_line.line,
@@ -651,7 +1415,7 @@ fn run_shell(command: &str, idle_timeout_seconds: u64) -> Result<ExecResult> {
             }
         }
         for rendered in formatter.flush() {
-            println!("{}", rendered);
+            output.line(&rendered);
         }
 
         if idled_out && current_command.contains("cursor-agent") {
@@ -659,7 +1423,7 @@ fn run_shell(command: &str, idle_timeout_seconds: u64) -> Result<ExecResult> {
                 with_continue_diagnostic_prompt(&current_command, idle_timeout_seconds);
             continue;
         } else if idled_out {
-            println!("... command idled out and was terminated");
+            output.line("... command idled out and was terminated");
         }
 
         let exit_code = status.code().unwrap_or(1);
@@ -781,7 +1545,36 @@ fn display_task_text(task: &Task) -> String {
     }
 }
 
-fn maybe_archive_completed_plan(root: &Path, plan_id: &str) -> Result<Option<PathBuf>> {
+fn default_archive_dir(root: &Path) -> PathBuf {
+    root.join("plans").join("done")
+}
+
+/// Where completed plans go: `None` when archiving is disabled, otherwise `--archive-dir`
+/// (resolved against `root`) or plans/done.
+fn resolve_archive_dir(
+    root: &Path,
+    archive_dir: Option<&Path>,
+    no_archive: bool,
+) -> Option<PathBuf> {
+    if no_archive {
+        return None;
+    }
+    Some(match archive_dir {
+        Some(dir) => root.join(dir),
+        None => default_archive_dir(root),
+    })
+}
+
+/// Moves `plan_id` into `archive_dir` once every task is done. With `archive_dir` `None` the plan
+/// stays in place (its tasks are still marked done by the caller).
+fn maybe_archive_completed_plan(
+    root: &Path,
+    plan_id: &str,
+    archive_dir: Option<&Path>,
+) -> Result<Option<PathBuf>> {
+    let Some(archive_dir) = archive_dir else {
+        return Ok(None);
+    };
     let graph = load_plans(root)?;
     let Some(plan) = graph.plans_by_id.get(plan_id) else {
         return Ok(None);
@@ -790,21 +1583,20 @@ fn maybe_archive_completed_plan(root: &Path, plan_id: &str) -> Result<Option<Pat
         return Ok(None);
     }
 
-    let done_dir = root.join("plans").join("done");
-    if plan.path.starts_with(&done_dir) {
+    // Plans already under plans/done stay put even when archiving elsewhere.
+    if plan.path.starts_with(archive_dir) || plan.path.starts_with(default_archive_dir(root)) {
         return Ok(None);
     }
     if !plan.path.exists() {
         return Ok(None);
     }
 
-    let archived_path = archive_plan_file(root, &plan.path)?;
+    let archived_path = archive_plan_file(archive_dir, &plan.path)?;
     Ok(Some(archived_path))
 }
 
-fn archive_plan_file(root: &Path, plan_path: &Path) -> Result<PathBuf> {
-    let done_dir = root.join("plans").join("done");
-    fs::create_dir_all(&done_dir)
+fn archive_plan_file(done_dir: &Path, plan_path: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(done_dir)
         .with_context(|| format!("Failed to create {}", done_dir.display()))?;
 
     let file_name = plan_path
@@ -857,6 +1649,46 @@ fn copy_then_remove(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Parses a `--since` window such as `90s`, `30m`, `2h`, `1d` or `1w`; a bare number is seconds.
+fn parse_since(value: &str) -> std::result::Result<StdDuration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid duration `{value}` (expected e.g. 30m, 2h, 1d)"))?;
+    let unit_secs = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "unknown duration unit `{unit}` (use s, m, h, d or w)"
+            ));
+        }
+    };
+    Ok(StdDuration::from_secs(amount.saturating_mul(unit_secs)))
+}
+
+/// Whether the plan file at `path` passes the `--since` filter. Fails open: with no filter, or
+/// when the file's mtime can't be read or lies in the future, the plan counts as recent.
+fn plan_is_recent(path: &Path, since: Option<StdDuration>, now: SystemTime) -> bool {
+    let Some(window) = since else {
+        return true;
+    };
+    let Ok(modified) = fs::metadata(path).and_then(|meta| meta.modified()) else {
+        return true;
+    };
+    match now.duration_since(modified) {
+        Ok(age) => age <= window,
+        Err(_) => true,
+    }
+}
+
 fn sanitize_prompt_fragment(value: &str) -> String {
     value.replace('\r', "").replace('\'', "''")
 }
@@ -975,33 +1807,49 @@ fn terminate_process_tree(pid: u32) {
     }
 }
 
+/// Never picks a plan in `running`, which `cmd_run` uses to keep concurrent workers on distinct
+/// plans (the owner's own claims would not stop a repeat pick).
 fn select_next_ready_plan(
     graph: &PlanGraph,
     claims: &ClaimStore,
     now: chrono::DateTime<Utc>,
     owner: &str,
+    filter: &PlanFilter,
+    running: &HashSet<String>,
 ) -> Option<PlanWorkItem> {
+    let now_sys = SystemTime::now();
     let mut plans: Vec<&plans::Plan> = graph
         .plans
         .iter()
-        .filter(|plan| graph.dependencies_completed(&plan.id))
-        .filter(|plan| {
-            let claim_id = plan_claim_key(&plan.id);
-            match claims.active_claim(&claim_id, now) {
-                None => true,
-                Some(claim) => claim.owner == owner,
-            }
-        })
-        .filter(|plan| {
-            plan.tasks
-                .iter()
-                .any(|t| !t.done && task_executable_by_owner(t, owner))
-        })
+        .filter(|plan| !running.contains(&plan.id))
+        .filter(|plan| filter.matches(plan, now_sys))
+        .filter(|plan| plan_ready_for_owner(graph, plan, claims, now, owner))
         .collect();
 
-    plans.sort_by(|a, b| a.id.cmp(&b.id));
-    let plan = plans.into_iter().next()?;
+    plans.sort_by(|a, b| a.selection_cmp(b));
+    plan_work_item(plans.into_iter().next()?, owner)
+}
 
+fn plan_ready_for_owner(
+    graph: &PlanGraph,
+    plan: &plans::Plan,
+    claims: &ClaimStore,
+    now: chrono::DateTime<Utc>,
+    owner: &str,
+) -> bool {
+    let claim_ok = match claims.active_claim(&plan_claim_key(&plan.id), now) {
+        None => true,
+        Some(claim) => claim.owner == owner,
+    };
+    claim_ok
+        && graph.dependencies_completed(&plan.id)
+        && plan
+            .tasks
+            .iter()
+            .any(|t| !t.done && task_executable_by_owner(t, owner))
+}
+
+fn plan_work_item(plan: &plans::Plan, owner: &str) -> Option<PlanWorkItem> {
     let pending_tasks: Vec<&Task> = plan
         .tasks
         .iter()
@@ -1151,6 +1999,8 @@ mod tests {
             id: id.to_string(),
             path,
             depends_on: depends_on.iter().map(|dep| dep.to_string()).collect(),
+            priority: None,
+            tags: Vec::new(),
             tasks,
         }
     }
@@ -1178,6 +2028,8 @@ mod tests {
             id: id.to_string(),
             path,
             depends_on: depends_on.iter().map(|dep| dep.to_string()).collect(),
+            priority: None,
+            tags: Vec::new(),
             tasks,
         }
     }
@@ -1229,6 +2081,23 @@ mod tests {
         assert_eq!(idle_timeout_seconds, 600);
     }
 
+    #[test]
+    fn retry_policy_defaults_to_no_retries_and_doubles_backoff() {
+        let cli = Cli::try_parse_from(["plantool", "run"]).expect("run args should parse");
+        let Commands::Run { max_retries, .. } = cli.command else {
+            panic!("expected run subcommand");
+        };
+        assert_eq!(max_retries, 0);
+
+        let policy = RetryPolicy {
+            max_retries: 3,
+            backoff_seconds: 5,
+        };
+        assert_eq!(policy.delay_for(1), StdDuration::from_secs(5));
+        assert_eq!(policy.delay_for(2), StdDuration::from_secs(10));
+        assert_eq!(policy.delay_for(3), StdDuration::from_secs(20));
+    }
+
     #[test]
     fn diagnostics_explain_why_no_plan_is_ready() {
         let graph = make_graph(vec![
@@ -1258,6 +2127,163 @@ mod tests {
         );
     }
 
+    #[test]
+    fn schedule_groups_waves_and_matches_select_next_ready_plan() {
+        let graph = make_graph(vec![
+            make_plan("A", &[], &[true]),
+            make_plan("B", &["A"], &[false, false]),
+            make_plan_with_specs("AA", &[], &[(false, true)]),
+            make_plan("C", &["B"], &[false]),
+            make_plan("D", &["B", "AA"], &[false]),
+            make_plan("E", &["C", "D"], &[false]),
+        ]);
+        let claims = ClaimStore::default();
+        let now = Utc::now();
+        let owner = "agent:self";
+
+        let waves = compute_schedule(&graph, &claims, now, owner);
+        let ids: Vec<Vec<&str>> = waves
+            .iter()
+            .map(|w| w.iter().map(|p| p.plan_id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["AA", "B"], vec!["C", "D"], vec!["E"]]);
+        assert_eq!(waves[0][0].skip_reason.as_deref(), Some("human-only"));
+        assert_eq!(waves[0][1].open_tasks, 2);
+        assert!(waves[0][1].blocked_by.is_empty());
+        assert_eq!(waves[1][1].blocked_by, vec!["B", "AA"]);
+
+        let first_pick = waves[0]
+            .iter()
+            .find(|p| p.skip_reason.is_none())
+            .expect("a pickable plan");
+        let selected = select_next_ready_plan(
+            &graph,
+            &claims,
+            now,
+            owner,
+            &PlanFilter::default(),
+            &HashSet::new(),
+        )
+        .expect("expected ready plan");
+        assert_eq!(first_pick.plan_id, selected.plan_id);
+    }
+
+    #[test]
+    fn plan_dot_colors_by_status_and_marks_pruned_nodes() {
+        let full = make_graph(vec![
+            make_plan("A", &[], &[true]),
+            make_plan("B", &["A"], &[false]),
+            make_plan("C", &["B"], &[false]),
+            make_plan("D", &[], &[false]),
+            make_plan("E", &["MISSING"], &[false]),
+        ]);
+        let (graph, excluded) = prune_invalid_plans(full.clone());
+        let now = Utc::now();
+        let mut claims = ClaimStore::default();
+        claims
+            .claim("D#1", "agent:other", now)
+            .expect("claim should succeed");
+
+        let nodes = compute_graph_nodes(&full, &graph, &excluded, &claims, now);
+        let statuses: Vec<(&str, PlanNodeStatus)> = nodes
+            .iter()
+            .map(|n| (n.plan_id.as_str(), n.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("A", PlanNodeStatus::Done),
+                ("B", PlanNodeStatus::Ready),
+                ("C", PlanNodeStatus::Blocked),
+                ("D", PlanNodeStatus::Claimed),
+                ("E", PlanNodeStatus::Excluded),
+            ]
+        );
+
+        let dot = render_plan_dot(&full, &nodes);
+        assert!(dot.starts_with("digraph plans {"));
+        assert!(dot.contains("\"A\" -> \"B\";"));
+        assert!(dot.contains("\"B\" -> \"C\";"));
+        assert!(dot.contains("\"MISSING\" -> \"E\" [style=dashed];"));
+        assert!(dot.contains(
+            "\"E\" [label=\"E\\nexcluded (1 open)\", fillcolor=lightgray, style=\"filled,dashed\"];"
+        ));
+        assert!(dot.contains("\"MISSING\" [label=\"MISSING\\nmissing\""));
+    }
+
+    #[test]
+    fn claims_expire_after_ttl_and_are_listed_as_stale() {
+        let now = Utc::now();
+        let mut claims = ClaimStore::default();
+        claims
+            .claim_with_ttl(
+                "PLAN::A",
+                "agent:crashed",
+                now,
+                chrono::Duration::minutes(5),
+            )
+            .expect("claim should succeed");
+        claims
+            .claim("PLAN::B", "agent:alive", now)
+            .expect("claim should succeed");
+
+        let later = now + chrono::Duration::minutes(10);
+        assert!(claims.active_claim("PLAN::A", later).is_none());
+        assert!(claims.active_claim("PLAN::B", later).is_some());
+        claims
+            .claim("PLAN::A", "agent:other", later)
+            .expect("expired claim should not block a new owner");
+        assert!(
+            claims
+                .claim_with_ttl("PLAN::C", "agent:x", now, chrono::Duration::zero())
+                .is_err()
+        );
+
+        let mut stale = ClaimStore::default();
+        stale
+            .claim_with_ttl(
+                "PLAN::A",
+                "agent:crashed",
+                now,
+                chrono::Duration::minutes(5),
+            )
+            .expect("claim should succeed");
+        stale
+            .claim("PLAN::B", "agent:alive", now)
+            .expect("claim should succeed");
+        let expired = format_claims(&stale, later, true);
+        assert_eq!(expired.len(), 1);
+        assert!(expired[0].starts_with("[expired] PLAN::A  agent:crashed"));
+        let all = format_claims(&stale, later, false);
+        assert_eq!(all.len(), 2);
+        assert!(all[1].starts_with("[active] PLAN::B  agent:alive"));
+    }
+
+    #[test]
+    fn run_output_tees_lines_into_plan_log() {
+        let ws = TempWorkspace::new();
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-01T12:34:56Z")
+            .expect("valid timestamp")
+            .with_timezone(&Utc);
+        let path = plan_log_path(&ws.root, "MY PLAN/1", now);
+        assert_eq!(path, ws.root.join("MY_PLAN_1-20260301T123456.000Z.log"));
+
+        let mut output = RunOutput {
+            prefix: "[MY PLAN/1] ".to_string(),
+            log: Some(fs::File::create(&path).expect("create log")),
+        };
+        output.line("hello");
+        output.stderr_line("oops");
+        drop(output);
+        let text = fs::read_to_string(&path).expect("read log");
+        assert_eq!(text, "hello\n[stderr] oops\n");
+    }
+
+    #[test]
+    fn dot_escape_quotes_and_backslashes() {
+        assert_eq!(dot_escape(r#"A"B\C"#), r#"A\"B\\C"#);
+    }
+
     #[test]
     fn select_next_ready_plan_skips_human_only_plans_for_agent_owner() {
         let graph = make_graph(vec![
@@ -1267,25 +2293,207 @@ mod tests {
         let claims = ClaimStore::default();
         let now = Utc::now();
 
-        let selected = select_next_ready_plan(&graph, &claims, now, "agent:cursor-agent")
-            .expect("expected actionable plan");
+        let selected = select_next_ready_plan(
+            &graph,
+            &claims,
+            now,
+            "agent:cursor-agent",
+            &PlanFilter::default(),
+            &HashSet::new(),
+        )
+        .expect("expected actionable plan");
         assert_eq!(selected.plan_id, "B");
         assert_eq!(selected.pending_count, 1);
     }
 
+    #[test]
+    fn select_next_ready_plan_prefers_higher_priority_then_id() {
+        let mut urgent = make_plan("Z_URGENT", &[], &[false]);
+        urgent.priority = Some(5);
+        let mut low = make_plan("A_LOW", &[], &[false]);
+        low.priority = Some(-1);
+        let graph = make_graph(vec![make_plan("A_DEFAULT", &[], &[false]), low, urgent]);
+        let claims = ClaimStore::default();
+        let now = Utc::now();
+        let mut running = HashSet::new();
+
+        let mut order = Vec::new();
+        while let Some(selected) = select_next_ready_plan(
+            &graph,
+            &claims,
+            now,
+            "agent:self",
+            &PlanFilter::default(),
+            &running,
+        ) {
+            running.insert(selected.plan_id.clone());
+            order.push(selected.plan_id);
+        }
+        assert_eq!(order, vec!["Z_URGENT", "A_LOW", "A_DEFAULT"]);
+
+        let waves = compute_schedule(&graph, &claims, now, "agent:self");
+        let wave_ids: Vec<&str> = waves[0].iter().map(|p| p.plan_id.as_str()).collect();
+        assert_eq!(wave_ids, order);
+    }
+
+    #[test]
+    fn tag_filter_only_selects_tagged_plans_but_respects_untagged_dependencies() {
+        let mut ui = make_plan("UI", &["CORE"], &[false]);
+        ui.tags = vec!["frontend".to_string()];
+        let claims = ClaimStore::default();
+        let now = Utc::now();
+        let filter = PlanFilter {
+            tag: Some("frontend".to_string()),
+            ..PlanFilter::default()
+        };
+
+        let graph = make_graph(vec![make_plan("CORE", &[], &[false]), ui.clone()]);
+        assert!(
+            select_next_ready_plan(&graph, &claims, now, "agent:self", &filter, &HashSet::new())
+                .is_none(),
+            "untagged CORE is ready but must not be picked, and UI still waits on it"
+        );
+
+        let graph = make_graph(vec![make_plan("CORE", &[], &[true]), ui]);
+        let selected =
+            select_next_ready_plan(&graph, &claims, now, "agent:self", &filter, &HashSet::new())
+                .expect("UI should be ready once CORE is done");
+        assert_eq!(selected.plan_id, "UI");
+        assert_eq!(
+            plan_list_metadata(&graph.plans_by_id["UI"]),
+            "{frontend}  ".to_string()
+        );
+    }
+
+    #[test]
+    fn select_next_ready_plan_skips_plans_already_running() {
+        let graph = make_graph(vec![
+            make_plan("A", &[], &[false]),
+            make_plan("B", &[], &[false]),
+            make_plan("C", &["A"], &[false]),
+        ]);
+        let claims = ClaimStore::default();
+        let now = Utc::now();
+        let running: HashSet<String> = ["A".to_string()].into_iter().collect();
+
+        let selected = select_next_ready_plan(
+            &graph,
+            &claims,
+            now,
+            "agent:self",
+            &PlanFilter::default(),
+            &running,
+        )
+        .expect("expected another ready plan");
+        assert_eq!(selected.plan_id, "B");
+
+        let running: HashSet<String> = ["A".to_string(), "B".to_string()].into_iter().collect();
+        assert!(
+            select_next_ready_plan(
+                &graph,
+                &claims,
+                now,
+                "agent:self",
+                &PlanFilter::default(),
+                &running
+            )
+            .is_none()
+        );
+    }
+
     #[test]
     fn select_next_ready_plan_allows_human_owner_to_pick_human_only_task() {
         let graph = make_graph(vec![make_plan_with_specs("A", &[], &[(false, true)])]);
         let claims = ClaimStore::default();
         let now = Utc::now();
 
-        let selected =
-            select_next_ready_plan(&graph, &claims, now, "human:hanzen").expect("expected plan");
+        let selected = select_next_ready_plan(
+            &graph,
+            &claims,
+            now,
+            "human:hanzen",
+            &PlanFilter::default(),
+            &HashSet::new(),
+        )
+        .expect("expected plan");
         assert_eq!(selected.plan_id, "A");
         assert_eq!(selected.pending_count, 1);
         assert!(selected.open_tasks.contains("[human]"));
     }
 
+    #[test]
+    fn since_parses_units_and_rejects_garbage() {
+        assert_eq!(parse_since("45"), Ok(StdDuration::from_secs(45)));
+        assert_eq!(parse_since("30m"), Ok(StdDuration::from_secs(30 * 60)));
+        assert_eq!(parse_since("2h"), Ok(StdDuration::from_secs(2 * 60 * 60)));
+        assert_eq!(parse_since("1d"), Ok(StdDuration::from_secs(24 * 60 * 60)));
+        assert!(parse_since("h").is_err());
+        assert!(parse_since("3y").is_err());
+
+        let cli = Cli::try_parse_from(["plantool", "list", "--ready", "--since", "2h"])
+            .expect("list args should parse");
+        let Commands::List { ready, since, .. } = cli.command else {
+            panic!("expected list subcommand");
+        };
+        assert!(ready);
+        assert_eq!(since, Some(StdDuration::from_secs(2 * 60 * 60)));
+    }
+
+    #[test]
+    fn since_filter_fails_open_and_keeps_old_dependencies_blocking() {
+        let ws = TempWorkspace::new();
+        let plans_dir = ws.root.join("plans");
+        let mut old = make_plan("OLD", &[], &[false]);
+        let mut fresh = make_plan("FRESH", &["OLD"], &[false]);
+        let mut unreadable = make_plan("UNREADABLE", &[], &[false]);
+        old.path = plans_dir.join("old.txt");
+        fresh.path = plans_dir.join("fresh.txt");
+        unreadable.path = plans_dir.join("missing.txt");
+        fs::write(&old.path, "old").expect("write old plan");
+        fs::write(&fresh.path, "fresh").expect("write fresh plan");
+        let week_ago = SystemTime::now() - StdDuration::from_secs(7 * 24 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(&old.path)
+            .and_then(|file| file.set_modified(week_ago))
+            .expect("backdate old plan");
+
+        let since = Some(StdDuration::from_secs(60 * 60));
+        let now_sys = SystemTime::now();
+        assert!(!plan_is_recent(&old.path, since, now_sys));
+        assert!(plan_is_recent(&fresh.path, since, now_sys));
+        assert!(plan_is_recent(&unreadable.path, since, now_sys));
+        assert!(plan_is_recent(&old.path, None, now_sys));
+
+        // FRESH is recent but still waits on OLD; the unreadable plan is kept (fail-open).
+        let graph = make_graph(vec![old, fresh, unreadable]);
+        let claims = ClaimStore::default();
+        let selected = select_next_ready_plan(
+            &graph,
+            &claims,
+            Utc::now(),
+            "agent:self",
+            &PlanFilter {
+                since,
+                ..PlanFilter::default()
+            },
+            &HashSet::new(),
+        )
+        .expect("expected fail-open plan");
+        assert_eq!(selected.plan_id, "UNREADABLE");
+
+        let unfiltered = select_next_ready_plan(
+            &graph,
+            &claims,
+            Utc::now(),
+            "agent:self",
+            &PlanFilter::default(),
+            &HashSet::new(),
+        )
+        .expect("expected plan without filter");
+        assert_eq!(unfiltered.plan_id, "OLD");
+    }
+
     #[test]
     fn maybe_archive_completed_plan_moves_file_to_done() {
         let ws = TempWorkspace::new();
@@ -1294,8 +2502,12 @@ mod tests {
         fs::write(&active_path, "Plan-ID: ARCHIVE_ME_PLAN\n- [x] done\n")
             .expect("write active plan");
 
-        let archived_path =
-            maybe_archive_completed_plan(&ws.root, "ARCHIVE_ME_PLAN").expect("archive result");
+        let archived_path = maybe_archive_completed_plan(
+            &ws.root,
+            "ARCHIVE_ME_PLAN",
+            Some(&plans_dir.join("done")),
+        )
+        .expect("archive result");
         let archived_path = archived_path.expect("completed plan should be archived");
 
         assert!(
@@ -1317,8 +2529,12 @@ mod tests {
         )
         .expect("write active plan");
 
-        let archived_path =
-            maybe_archive_completed_plan(&ws.root, "STILL_OPEN_PLAN").expect("archive result");
+        let archived_path = maybe_archive_completed_plan(
+            &ws.root,
+            "STILL_OPEN_PLAN",
+            Some(&plans_dir.join("done")),
+        )
+        .expect("archive result");
         assert!(
             archived_path.is_none(),
             "incomplete plan should not be archived"
@@ -1326,6 +2542,128 @@ mod tests {
         assert!(active_path.exists(), "open plan should remain active");
     }
 
+    #[test]
+    fn complete_command_accepts_multiple_task_ids() {
+        let cli = Cli::try_parse_from(["plantool", "complete", "A#1", "A#2", "--atomic"])
+            .expect("complete args should parse");
+        let Commands::Complete {
+            task_ids, atomic, ..
+        } = cli.command
+        else {
+            panic!("expected complete subcommand");
+        };
+        assert_eq!(task_ids, vec!["A#1", "A#2"]);
+        assert!(atomic);
+    }
+
+    #[test]
+    fn cmd_complete_batch_archives_plan_once_all_tasks_are_done() {
+        let ws = TempWorkspace::new();
+        let plans_dir = ws.root.join("plans");
+        let active_path = plans_dir.join("batch_plan.txt");
+        fs::write(
+            &active_path,
+            "Plan-ID: BATCH_PLAN\n- [ ] first\n- [ ] second\n",
+        )
+        .expect("write active plan");
+
+        let ids = vec!["BATCH_PLAN#1".to_string(), "BATCH_PLAN#2".to_string()];
+        let done_dir = default_archive_dir(&ws.root);
+        cmd_complete(&ws.root, &ids, None, Some("batch"), false, Some(&done_dir))
+            .expect("complete batch");
+
+        assert!(!active_path.exists(), "completed plan should be archived");
+        let archived = fs::read_to_string(plans_dir.join("done").join("batch_plan.txt"))
+            .expect("read archived plan");
+        assert!(archived.contains("- [x] first"));
+        assert!(archived.contains("- [x] second"));
+        assert_eq!(archived.matches("Completion Note: batch").count(), 1);
+    }
+
+    #[test]
+    fn cmd_complete_with_no_archive_marks_done_in_place() {
+        let ws = TempWorkspace::new();
+        let active_path = ws.root.join("plans").join("keep_plan.txt");
+        fs::write(&active_path, "Plan-ID: KEEP_PLAN\n- [ ] only\n").expect("write active plan");
+
+        let cli = Cli::try_parse_from(["plantool", "complete", "KEEP_PLAN#1", "--no-archive"])
+            .expect("complete args should parse");
+        let archive_dir = resolve_archive_dir(&ws.root, cli.archive_dir.as_deref(), cli.no_archive);
+        assert_eq!(archive_dir, None);
+
+        let ids = vec!["KEEP_PLAN#1".to_string()];
+        cmd_complete(&ws.root, &ids, None, None, false, archive_dir.as_deref())
+            .expect("complete task");
+
+        let text = fs::read_to_string(&active_path).expect("plan should stay in place");
+        assert!(text.contains("- [x] only"));
+        assert!(!ws.root.join("plans").join("done").exists());
+    }
+
+    #[test]
+    fn custom_archive_dir_is_created_and_suffixes_duplicates() {
+        let ws = TempWorkspace::new();
+        let cli = Cli::try_parse_from(["plantool", "complete", "X#1", "--archive-dir", "archive"])
+            .expect("complete args should parse");
+        let archive_dir = resolve_archive_dir(&ws.root, cli.archive_dir.as_deref(), cli.no_archive)
+            .expect("archiving enabled");
+        assert_eq!(archive_dir, ws.root.join("archive"));
+
+        for (round, plan_id) in ["FIRST_PLAN", "SECOND_PLAN"].into_iter().enumerate() {
+            let active_path = ws.root.join("plans").join("same_name.txt");
+            fs::write(&active_path, format!("Plan-ID: {plan_id}\n- [x] done\n"))
+                .expect("write active plan");
+            let archived = maybe_archive_completed_plan(&ws.root, plan_id, Some(&archive_dir))
+                .expect("archive result")
+                .expect("completed plan should be archived");
+            let expected = if round == 0 {
+                "same_name.txt"
+            } else {
+                "same_name_1.txt"
+            };
+            assert_eq!(archived, archive_dir.join(expected));
+            assert!(!active_path.exists());
+        }
+    }
+
+    #[test]
+    fn cmd_complete_checks_claims_per_task_and_respects_atomic() {
+        let ws = TempWorkspace::new();
+        let active_path = ws.root.join("plans").join("claimed_plan.txt");
+        fs::write(
+            &active_path,
+            "Plan-ID: CLAIMED_PLAN\n- [ ] mine\n- [ ] theirs\n- [ ] open\n",
+        )
+        .expect("write active plan");
+        let mut claims = ClaimStore::default();
+        claims
+            .claim("CLAIMED_PLAN#2", "agent:other", Utc::now())
+            .expect("claim should succeed");
+        claims.save(&ws.root).expect("save claims");
+
+        let ids = vec![
+            "CLAIMED_PLAN#1".to_string(),
+            "CLAIMED_PLAN#2".to_string(),
+            "CLAIMED_PLAN#3".to_string(),
+        ];
+        let err = cmd_complete(&ws.root, &ids, Some("agent:self"), None, true, None)
+            .expect_err("atomic batch should fail");
+        assert!(err.to_string().contains("CLAIMED_PLAN#2"));
+        let text = fs::read_to_string(&active_path).expect("read plan");
+        assert!(
+            !text.contains("[x]"),
+            "atomic failure should complete nothing"
+        );
+
+        let err = cmd_complete(&ws.root, &ids, Some("agent:self"), None, false, None)
+            .expect_err("batch should report the failed task");
+        assert!(err.to_string().contains("1 of 3"));
+        let text = fs::read_to_string(&active_path).expect("read plan");
+        assert!(text.contains("- [x] mine"));
+        assert!(text.contains("- [ ] theirs"));
+        assert!(text.contains("- [x] open"));
+    }
+
     #[test]
     fn prune_invalid_plans_removes_missing_dependency_chains() {
         let graph = make_graph(vec![
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -20,10 +21,32 @@ pub struct Plan {
     pub id: String,
     pub path: PathBuf,
     pub depends_on: Vec<String>,
+    /// From an optional `Priority: N` header; higher runs first.
+    pub priority: Option<i32>,
+    /// From an optional `Tags: a, b` header.
+    pub tags: Vec<String>,
     pub tasks: Vec<Task>,
 }
 
-#[derive(Debug)]
+impl Plan {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Order in which ready plans are picked: explicit priorities (highest first), then plans
+    /// without one, with the plan id as the tie-break.
+    pub fn selection_cmp(&self, other: &Plan) -> Ordering {
+        match (self.priority, other.priority) {
+            (Some(a), Some(b)) => b.cmp(&a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+        .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct PlanGraph {
     pub plans: Vec<Plan>,
     pub plans_by_id: HashMap<String, Plan>,
@@ -293,6 +316,8 @@ fn parse_plan_file(path: &Path) -> Result<Plan> {
 
     let mut plan_id = None;
     let mut depends_on: Vec<String> = Vec::new();
+    let mut priority = None;
+    let mut tags: Vec<String> = Vec::new();
     let mut tasks = Vec::new();
 
     for (idx, line) in lines.iter().enumerate() {
@@ -309,6 +334,22 @@ fn parse_plan_file(path: &Path) -> Result<Plan> {
                 .filter(|s| !s.is_empty() && !is_no_dependency_marker(s))
                 .map(|s| s.to_string())
                 .collect();
+        } else if let Some(value) = trimmed.strip_prefix("Tags:") {
+            tags = value
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect();
+        } else if let Some(value) = trimmed.strip_prefix("Priority:") {
+            let value = value.trim();
+            priority = Some(value.parse::<i32>().with_context(|| {
+                format!(
+                    "Invalid Priority {:?} in plan file {}",
+                    value,
+                    path.display()
+                )
+            })?);
         } else if let Some(task) = parse_task_line(trimmed) {
             let task_id = format!(
                 "{}#{}",
@@ -339,6 +380,8 @@ fn parse_plan_file(path: &Path) -> Result<Plan> {
         id,
         path: path.to_path_buf(),
         depends_on,
+        priority,
+        tags,
         tasks,
     })
 }
@@ -532,6 +575,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_plans_parses_priority_header() {
+        let ws = TempWorkspace::new();
+        let plans = ws.root.join("plans");
+        fs::write(
+            plans.join("urgent_plan.txt"),
+            "Plan-ID: URGENT_PLAN\nPriority: 10\n- [ ] fix\n",
+        )
+        .expect("write urgent plan");
+        fs::write(
+            plans.join("later_plan.txt"),
+            "Plan-ID: LATER_PLAN\n- [ ] later\n",
+        )
+        .expect("write later plan");
+
+        let graph = load_plans(&ws.root).expect("load plans");
+        assert_eq!(graph.plans_by_id["URGENT_PLAN"].priority, Some(10));
+        assert!(graph.plans_by_id["URGENT_PLAN"].tags.is_empty());
+        assert_eq!(graph.plans_by_id["LATER_PLAN"].priority, None);
+
+        fs::write(
+            plans.join("broken_plan.txt"),
+            "Plan-ID: BROKEN_PLAN\nPriority: soon\n- [ ] x\n",
+        )
+        .expect("write broken plan");
+        let err = load_plans(&ws.root).expect_err("non-numeric priority should fail");
+        assert!(format!("{err:#}").contains("Invalid Priority"));
+    }
+
+    #[test]
+    fn load_plans_parses_tags_header() {
+        let ws = TempWorkspace::new();
+        fs::write(
+            ws.root.join("plans").join("ui_plan.txt"),
+            "Plan-ID: UI_PLAN\nTags: frontend, ui ,\n- [ ] polish\n",
+        )
+        .expect("write plan");
+
+        let graph = load_plans(&ws.root).expect("load plans");
+        let plan = &graph.plans_by_id["UI_PLAN"];
+        assert_eq!(plan.tags, vec!["frontend", "ui"]);
+        assert!(plan.has_tag("Frontend"));
+        assert!(!plan.has_tag("backend"));
+    }
+
     #[test]
     fn parse_task_line_extracts_human_labels() {
         let parsed = parse_task_line("- [ ] [human] run feel tuning").expect("expected task");
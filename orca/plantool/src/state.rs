@@ -10,6 +10,10 @@ use crate::plans::Task;
 
 const DEFAULT_LEASE_MINUTES: i64 = 30;
 
+pub fn default_claim_ttl() -> Duration {
+    Duration::minutes(DEFAULT_LEASE_MINUTES)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claim {
     pub task_id: String,
@@ -18,6 +22,14 @@ pub struct Claim {
     pub lease_until: DateTime<Utc>,
 }
 
+impl Claim {
+    /// A claim lapses once its TTL (`lease_until - claimed_at`) has elapsed, so a crashed owner
+    /// cannot hold a task or plan forever.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.lease_until
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ClaimStore {
     pub claims: HashMap<String, Claim>,
@@ -46,12 +58,32 @@ impl ClaimStore {
     }
 
     pub fn active_claim<'a>(&'a self, task_id: &str, now: DateTime<Utc>) -> Option<&'a Claim> {
-        self.claims.get(task_id).filter(|c| c.lease_until > now)
+        self.claims.get(task_id).filter(|c| !c.is_expired(now))
+    }
+
+    /// Claims whose TTL has elapsed but that were never released, ordered by task id.
+    pub fn expired_claims(&self, now: DateTime<Utc>) -> Vec<&Claim> {
+        let mut expired: Vec<&Claim> = self.claims.values().filter(|c| c.is_expired(now)).collect();
+        expired.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+        expired
     }
 
     pub fn claim(&mut self, task_id: &str, owner: &str, now: DateTime<Utc>) -> Result<()> {
-        if let Some(existing) = self.claims.get(task_id) {
-            if existing.lease_until > now && existing.owner != owner {
+        self.claim_with_ttl(task_id, owner, now, default_claim_ttl())
+    }
+
+    pub fn claim_with_ttl(
+        &mut self,
+        task_id: &str,
+        owner: &str,
+        now: DateTime<Utc>,
+        ttl: Duration,
+    ) -> Result<()> {
+        if ttl <= Duration::zero() {
+            bail!("Claim TTL for {} must be positive", task_id);
+        }
+        if let Some(existing) = self.active_claim(task_id, now) {
+            if existing.owner != owner {
                 bail!(
                     "Task {} is already claimed by {} until {}",
                     task_id,
@@ -64,7 +96,7 @@ impl ClaimStore {
             task_id: task_id.to_string(),
             owner: owner.to_string(),
             claimed_at: now,
-            lease_until: now + Duration::minutes(DEFAULT_LEASE_MINUTES),
+            lease_until: now + ttl,
         };
         self.claims.insert(task_id.to_string(), claim);
         Ok(())